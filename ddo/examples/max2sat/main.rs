@@ -86,7 +86,7 @@ fn max_width<P: Problem>(p: &P, w: Option<usize>) -> Box<dyn WidthHeuristic<P::S
     }
 }
 
-fn solution_cost(pb: &Max2Sat, solution: &Option<Vec<Decision>>) -> isize {
+fn solution_cost(pb: &Max2Sat, solution: &Option<Solution>) -> isize {
     if let Some(sol) = solution {
         let n = pb.nb_vars;
         let mut model = vec![0; n];
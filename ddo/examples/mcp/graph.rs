@@ -17,13 +17,9 @@
 // IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
 // CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
-use std::fs::File;
-use std::io::{BufRead, BufReader, Lines, Read};
 use std::ops::{Index, IndexMut};
 
-use regex::Regex;
-
-use ddo::Variable;
+use ddo::{io::dimacs, Variable};
 
 /// The graph is represented by its adjacency matrix
 #[derive(Debug, Clone)]
@@ -47,38 +43,6 @@ impl Graph {
         self[(y, x)] = w;
     }
 
-    pub fn from_lines<B: BufRead>(lines: Lines<B>) -> Graph {
-        let graph   = Regex::new(r"^(?P<vars>\d+)\s+(?P<edges>\d+)$").unwrap();
-        let edge    = Regex::new(r"^(?P<src>\d+)\s+(?P<dst>\d+)\s+(?P<w>-?\d+)$").unwrap();
-
-        let mut result = Graph::new(0);
-        for line in lines {
-            let line = line.unwrap();
-            let line = line.trim();
-
-            if line.is_empty() {
-                continue;
-            }
-            // skip comments
-            if line.starts_with("c ") {
-                continue;
-            }
-
-            if let Some(caps) = graph.captures(line) {
-                result = Graph::new(caps["vars"].to_string().parse::<usize>().unwrap());
-                continue;
-            }
-
-            if let Some(caps) = edge.captures(line) {
-                let x = caps["src"].to_string().parse::<usize>().unwrap() - 1;
-                let y = caps["dst"].to_string().parse::<usize>().unwrap() - 1;
-                let w = caps["w"].to_string().parse::<isize>().unwrap();
-                result.add_bidir_edge(x, y, w);
-            }
-        }
-        result
-    }
-
     fn offset(&self, x: usize, y: usize) -> usize {
         x * self.nb_vertices + y
     }
@@ -107,18 +71,15 @@ impl IndexMut<(usize, usize)> for Graph {
         &mut self.adj_matrix[off]
     }
 }
-impl From<File> for Graph {
-    fn from(file: File) -> Graph {
-        BufReader::new(file).into()
-    }
-}
-impl <S: Read> From<BufReader<S>> for Graph {
-    fn from(buf: BufReader<S>) -> Graph {
-        buf.lines().into()
-    }
-}
-impl <B: BufRead> From<Lines<B>> for Graph {
-    fn from(lines: Lines<B>) -> Graph {
-        Graph::from_lines(lines)
+/// The parsing of the instance files is delegated to `ddo::io::dimacs`
+/// (`read_edge_list`): this conversion only recasts the parsed edge list
+/// into the adjacency matrix which the DP model works with.
+impl From<dimacs::Graph> for Graph {
+    fn from(graph: dimacs::Graph) -> Graph {
+        let mut result = Graph::new(graph.nb_vertices);
+        for (src, dst, weight) in graph.edges {
+            result.add_bidir_edge(src, dst, weight);
+        }
+        result
     }
 }
@@ -1,4 +1,4 @@
-use std::{time::{Duration, Instant}, fs::File};
+use std::time::{Duration, Instant};
 
 use clap::Parser;
 use ddo::*;
@@ -28,7 +28,7 @@ struct Params {
 
 fn main() {
     let Params{file, width, timeout} = Params::parse();
-    let graph = Graph::from(File::open(file).expect("could not open file"));
+    let graph = Graph::from(io::dimacs::read_edge_list(file).expect("could not parse the instance"));
     let problem = Mcp::from(graph);
     let relax = McpRelax::new(&problem);
     let rank = McpRanking;
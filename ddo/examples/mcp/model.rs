@@ -18,8 +18,6 @@
 // CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
 use std::cmp::{max, min};
-use std::fs::File;
-use std::io::{BufRead, BufReader, Lines, Read};
 
 use ddo::*;
 
@@ -134,19 +132,9 @@ impl From<Graph> for Mcp {
         Mcp::new(g)
     }
 }
-impl From<File> for Mcp {
-    fn from(f: File) -> Self {
-        Mcp::new(f.into())
-    }
-}
-impl <S: Read> From<BufReader<S>> for Mcp {
-    fn from(buf: BufReader<S>) -> Mcp {
-        Mcp::new(buf.into())
-    }
-}
-impl <B: BufRead> From<Lines<B>> for Mcp {
-    fn from(lines: Lines<B>) -> Mcp {
-        Mcp::new(lines.into())
+impl From<io::dimacs::Graph> for Mcp {
+    fn from(g: io::dimacs::Graph) -> Self {
+        Mcp::new(g.into())
     }
 }
 
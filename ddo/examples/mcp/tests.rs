@@ -19,7 +19,7 @@
 
 //! This module is meant to tests the correctness of our maxcut problem example
 
-use std::{path::PathBuf, fs::File};
+use std::path::PathBuf;
 
 use ddo::*;
 
@@ -38,7 +38,7 @@ pub fn solve_id(id: &str) -> isize {
     let fname = fname.to_str();
     let fname = fname.unwrap();
     
-    let graph = Graph::from(File::open(fname).expect("could not open file"));
+    let graph = Graph::from(io::dimacs::read_edge_list(fname).expect("could not parse the instance"));
     let problem = Mcp::from(graph);
     let relaxation = McpRelax::new(&problem);
     let ranking = McpRanking;
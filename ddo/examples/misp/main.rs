@@ -20,12 +20,11 @@
 //! This example show how to implement a solver for the maximum independent set problem 
 //! using ddo. It is a fairly simple example but it features most of the aspects you will
 //! want to copy when implementing your own solver.
-use std::{cell::RefCell, path::Path, fs::File, io::{BufReader, BufRead}, num::ParseIntError, time::{Duration, Instant}};
+use std::{cell::RefCell, path::Path, time::{Duration, Instant}};
 
 use bit_set::BitSet;
 use clap::Parser;
 use ddo::*;
-use regex::Regex;
 
 #[cfg(test)]
 mod tests;
@@ -145,6 +144,14 @@ impl Problem for Misp {
     fn is_impacted_by(&self, var: Variable, state: &Self::State) -> bool {
         state.contains(var.id())
     }
+
+    /// When the bitset is empty, no vertex can be added to the independent set
+    /// anymore: branching further would only append zero-cost 'no' decisions.
+    /// Flagging these states as leaves lets the compiler terminate the paths
+    /// reaching them early instead of unrolling them until the last variable.
+    fn is_leaf(&self, state: &Self::State) -> bool {
+        state.is_empty()
+    }
 }
 
 /// In addition to a dynamic programming (DP) model of the problem you want to solve, 
@@ -236,83 +243,35 @@ struct Args {
 }
 
 /// This enumeration simply groups the kind of errors that might occur when parsing a
-/// misp instance from file. There can be io errors (file unavailable ?), format error
-/// (e.g. the file is not an instance but contains the text of your next paper), 
-/// or parse int errors (which are actually a variant of the format error since it tells 
-/// you that the parser expected an integer number but got ... something else).
+/// misp instance from file. Since the actual parsing is delegated to `ddo::io::dimacs`,
+/// both the io errors (file unavailable ?) and the format errors (e.g. the file is not
+/// an instance but contains the text of your next paper) surface as io errors.
 #[derive(Debug, thiserror::Error)]
 enum Error {
     /// There was an io related error
     #[error("io error {0}")]
     Io(#[from] std::io::Error),
-    /// The parser expected to read something that was an integer but got some garbage
-    #[error("parse int {0}")]
-    ParseInt(#[from] ParseIntError),
-    /// The file was not properly formatted.
-    #[error("ill formed instance")]
-    Format,
 }
 
 /// This function is used to read a misp instance from file. It returns either a
 /// misp instance if everything went on well or an error describing the problem.
+/// The heavy lifting (DIMACS parsing) is delegated to `ddo::io::dimacs`: this
+/// function only recasts the parsed graph into the complement-of-adjacency
+/// bitsets which the DP model works with.
 fn read_instance<P: AsRef<Path>>(fname: P) -> Result<Misp, Error> {
-    let f = File::open(fname)?;
-    let f = BufReader::new(f);
-    
-    let comment   = Regex::new(r"^c\s.*$").unwrap();
-    let pb_decl   = Regex::new(r"^p\s+edge\s+(?P<vars>\d+)\s+(?P<edges>\d+)$").unwrap();
-    let node_decl = Regex::new(r"^n\s+(?P<node>\d+)\s+(?P<weight>-?\d+)").unwrap();
-    let edge_decl = Regex::new(r"^e\s+(?P<src>\d+)\s+(?P<dst>\d+)").unwrap();
-
-    let mut g = Misp{nb_vars: 0, neighbors: vec![], weight: vec![]};
-    for line in f.lines() {
-        let line = line?;
-        let line = line.trim();
-
-        if line.is_empty() {
-            continue;
-        }
-
-        if comment.is_match(line) {
-            continue;
-        }
-
-        if let Some(caps) = pb_decl.captures(line) {
-            let n = caps["vars"].to_string().parse::<usize>()?;
-            let full = (0..n).collect(); 
-
-            g.nb_vars    = n;
-            g.neighbors  = vec![full; n];
-            g.weight     = vec![1; n];
-            continue;
-        }
-
-        if let Some(caps) = node_decl.captures(line) {
-            let n = caps["node"].to_string().parse::<usize>()?;
-            let w = caps["weight"].to_string().parse::<isize>()?;
-
-            let n = n - 1;
-            g.weight[n] = w;
-            continue;
-        }
-
-        if let Some(caps) = edge_decl.captures(line) {
-            let src = caps["src"].to_string().parse::<usize>()?;
-            let dst = caps["dst"].to_string().parse::<usize>()?;
-
-            let src = src-1;
-            let dst = dst-1;
-
-            g.neighbors[src].remove(dst);
-            g.neighbors[dst].remove(src);
-
-            continue;
-        }
-
-        // skip
-        return Err(Error::Format)
+    let graph = io::dimacs::read_weighted_clq(fname)?;
+
+    let n    = graph.nb_vertices;
+    let full = (0..n).collect::<BitSet>();
+    let mut g = Misp {
+        nb_vars:   n,
+        neighbors: vec![full; n],
+        weight:    graph.vertex_weights,
+    };
+    for (src, dst, _) in graph.edges {
+        g.neighbors[src].remove(dst);
+        g.neighbors[dst].remove(src);
     }
-
     Ok(g)
 }
 
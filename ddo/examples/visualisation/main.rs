@@ -279,6 +279,8 @@ fn main() {
         ranking: &ranking,
         cutoff: &NoCutoff,
         max_width: 5,
+        max_in_degree: None,
+        max_out_degree: usize::MAX,
         residual: &residual,
         best_lb: isize::MIN,
         cache: &cache,
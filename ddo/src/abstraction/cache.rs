@@ -45,6 +45,12 @@ pub trait Cache {
     fn get_threshold(&self, state: &Self::State, depth: usize) -> Option<Threshold>;
 
     /// Updates the threshold associated with the given state, only if it is increased.
+    ///
+    /// The value recorded here is a bound proven while exploring a subproblem
+    /// rooted in the given state: any node reaching that same state later on
+    /// with a longest-path value which does not exceed the threshold cannot
+    /// improve on what has already been (or will provably be) explored, and
+    /// `must_explore` will hence tell the solver to prune it.
     fn update_threshold(&self, state: Arc<Self::State>, depth: usize, value: isize, explored: bool);
 
     /// Removes all thresholds associated with states at the given depth.
@@ -52,5 +58,32 @@ pub trait Cache {
 
     /// Clears the data structure.
     fn clear(&self);
-    
+
+    /// This method is a variant of `must_explore` which additionally gives
+    /// the cache access to the problem, e.g. to derive a compact fingerprint
+    /// of the state (see `Problem::state_fingerprint`). The default simply
+    /// ignores the problem; caches which do not key their thresholds on the
+    /// full state may override the `_with_problem` family instead.
+    fn must_explore_with_problem(&self, problem: &dyn Problem<State = Self::State>, subproblem: &SubProblem<Self::State>) -> bool {
+        let threshold = self.get_threshold_with_problem(problem, subproblem.state.as_ref(), subproblem.depth);
+        if let Some(threshold) = threshold {
+            subproblem.value > threshold.value || (subproblem.value == threshold.value && !threshold.explored)
+        } else {
+            true
+        }
+    }
+
+    /// This method is a variant of `get_threshold` which additionally gives
+    /// the cache access to the problem. The default ignores the problem and
+    /// delegates to `get_threshold`.
+    fn get_threshold_with_problem(&self, _problem: &dyn Problem<State = Self::State>, state: &Self::State, depth: usize) -> Option<Threshold> {
+        self.get_threshold(state, depth)
+    }
+
+    /// This method is a variant of `update_threshold` which additionally
+    /// gives the cache access to the problem. The default ignores the
+    /// problem and delegates to `update_threshold`.
+    fn update_threshold_with_problem(&self, _problem: &dyn Problem<State = Self::State>, state: Arc<Self::State>, depth: usize, value: isize, explored: bool) {
+        self.update_threshold(state, depth, value, explored)
+    }
 }
\ No newline at end of file
@@ -116,6 +116,11 @@ pub trait DominanceChecker {
     /// Removes all entries associated with states at the given depth.
     fn clear_layer(&self, depth: usize);
 
+    /// Removes all stored entries, at every depth. This is meant for solvers
+    /// which are reused across several instances in a single process: the
+    /// next search must not be pruned against the fronts of the previous one.
+    fn clear(&self);
+
     /// Returns true if the state is dominated by a stored one, and a potential
     /// pruning threshold, and inserts the (key, value) pair otherwise
     fn is_dominated_or_insert(&self, state: Arc<Self::State>, depth: usize, value: isize) -> DominanceCheckResult;
@@ -24,7 +24,9 @@
 //! The most important abstractions that should be provided by a client are
 //! `Problem` and `Relaxation`.
 
-use crate::{Variable, Decision};
+use std::hash::{Hash, Hasher};
+
+use crate::{Variable, Decision, CompilationType};
 
 /// This trait defines the "contract" of what defines an optimization problem
 /// solvable with the branch-and-bound with DD paradigm. An implementation of
@@ -45,6 +47,18 @@ pub trait Problem {
     /// This method is an implementation of the transition function mentioned
     /// in the mathematical model of a DP formulation for some problem.
     fn transition(&self, state: &Self::State, decision: Decision) -> Self::State;
+    /// This method is a fallible variant of `transition`: returning `None`
+    /// rejects the decision altogether, in which case the compilation of a
+    /// DD creates neither the child node nor its incoming edge. It is meant
+    /// for the formulations in which the infeasibility of a decision only
+    /// becomes apparent while computing the successor state (think of a time
+    /// window violation in TSPTW): rejecting it here spares the contortions
+    /// of pre-filtering in `for_each_in_domain` and hence of computing the
+    /// successor state twice. The default accepts every decision and simply
+    /// delegates to `transition`.
+    fn transition_checked(&self, state: &Self::State, decision: Decision) -> Option<Self::State> {
+        Some(self.transition(state, decision))
+    }
     /// This method is an implementation of the transition cost function mentioned
     /// in the mathematical model of a DP formulation for some problem.
     fn transition_cost(&self, source: &Self::State, dest: &Self::State, decision: Decision) -> isize;
@@ -56,18 +70,198 @@ pub trait Problem {
     /// method as an iterator.
     fn next_variable(&self, depth: usize, next_layer: &mut dyn Iterator<Item = &Self::State>)
         -> Option<Variable>;
-    /// This method calls the function `f` for any value in the domain of 
+    /// This method optionally returns the complete static branching order of
+    /// the problem; that is, the variable to branch on at each depth of the
+    /// diagram. When the order is fixed upfront (e.g. branch on variable
+    /// $x_i$ at depth $i$, as in knapsack), returning it here spares the
+    /// repeated calls to `next_variable` during the compilation: the
+    /// compiler simply indexes into the precomputed order. Beware that when
+    /// this method returns `Some` order, `next_variable` is never consulted.
+    /// The default returns `None`, which means "use `next_variable`".
+    fn static_order(&self) -> Option<Vec<Variable>> {
+        None
+    }
+    /// This method calls the function `f` for any value in the domain of
     /// variable `var` when in state `state`.  The function `f` is a function
     /// (callback, closure, ..) that accepts one decision.
     fn for_each_in_domain(&self, var: Variable, state: &Self::State, f: &mut dyn DecisionCallback);
+    /// This method is a variant of `for_each_in_domain` which additionally
+    /// receives the partial assignment (`path`) leading to `state`: the
+    /// decisions of the subproblem being explored followed by the decisions
+    /// made inside the current diagram. It is meant for the formulations
+    /// whose domains genuinely depend on the previously made decisions and
+    /// for which encoding the entire path into the state would bloat it. The
+    /// default ignores `path` and delegates to `for_each_in_domain`; it is
+    /// only ever called when `has_path_dependent_domains` returns true.
+    ///
+    /// # Warning
+    /// When several paths lead to the same state, only one of them (the best
+    /// one) is handed down: a domain depending on anything but the *set* of
+    /// decisions that characterizes the state would make the DP formulation
+    /// unsound to begin with.
+    fn for_each_in_domain_with_path(&self, var: Variable, state: &Self::State, _path: &[Decision], f: &mut dyn DecisionCallback) {
+        self.for_each_in_domain(var, state, f)
+    }
+    /// This method returns true iff the domains of this problem depend on the
+    /// partial assignment and must hence be enumerated through
+    /// `for_each_in_domain_with_path`. The default is false. Beware that
+    /// opting in has a cost: the compilation of a DD must then materialize
+    /// the path from the root down to every single node it expands, which
+    /// costs O(depth) time and memory per expanded node. Only opt in when the
+    /// path cannot reasonably be folded into the state.
+    fn has_path_dependent_domains(&self) -> bool {
+        false
+    }
+    /// This method returns an iterator over the values in the domain of the
+    /// variable `var` when in state `state`. It gives access to the very same
+    /// values as `for_each_in_domain`, but it lets the compilation of a DD
+    /// draw the values lazily and stop early when expanding more decisions is
+    /// known to be useless (e.g. when the next layer of a restricted DD is
+    /// already full). The default implementation builds on top of
+    /// `for_each_in_domain` and hence materializes the complete domain in a
+    /// vector; problems whose domains are large dense ranges (and which care
+    /// about the cost of materializing them) should override it with a
+    /// genuinely lazy iterator -- and say so through `has_lazy_domain_iter`.
+    fn domain_iter<'a>(&'a self, var: Variable, state: &'a Self::State) -> Box<dyn Iterator<Item = isize> + 'a> {
+        let mut domain = vec![];
+        self.for_each_in_domain(var, state, &mut |d: Decision| domain.push(d.value));
+        Box::new(domain.into_iter())
+    }
+    /// This method returns true iff `domain_iter` has been overridden with a
+    /// genuinely lazy iterator. When that is the case, the compilation of a
+    /// restricted DD draws the domain values through `domain_iter` and stops
+    /// expanding a node as soon as the next layer is full: the surplus of a
+    /// (possibly huge) domain would be dropped by the very next restriction
+    /// anyway, so there is no point in materializing it. Beware that opting
+    /// in trades the quality of the restrictions (the nodes that survive are
+    /// no longer the best ranked ones but the first generated ones once the
+    /// layer is full) for the guarantee that no more than `max_width` nodes
+    /// are ever created per layer.
+    fn has_lazy_domain_iter(&self) -> bool {
+        false
+    }
+    /// This method returns true iff the barrier-based caching of thresholds
+    /// may soundly be used when solving this problem. It is a hint consumed
+    /// by `AutoSolver` to decide between the caching and the non-caching
+    /// default engines; the default is the conservative `false`.
+    ///
+    /// # Warning
+    /// Returning `true` is only sound when the DP formulation has the
+    /// overlapping-subproblems property: whenever two decision paths lead to
+    /// a state comparing equal (an exact, hashable key), the subtrees hanging
+    /// below them must be the very same problem. If two equal states could
+    /// denote different residual problems, the cached thresholds would prune
+    /// subproblems that still had to be explored.
+    fn supports_caching(&self) -> bool {
+        false
+    }
+    /// This optional method lets a problem summarize a state into a compact
+    /// 64-bit fingerprint. When it returns `Some`, a fingerprint-aware cache
+    /// (see `FingerprintCache`) keys its thresholds on the fingerprint
+    /// *instead of* the full state, which can dramatically reduce the memory
+    /// footprint of wide searches over large states (think e.g. of a TSPTW
+    /// state made of a bitset and time information). The default returns
+    /// `None`, meaning that no problem-specific fingerprint is available.
+    ///
+    /// # Warning
+    /// Two distinct states mapping to the same fingerprint will share their
+    /// cached thresholds, which may prune a subproblem that still had to be
+    /// explored. Only implement this method if you accept that tiny
+    /// collision risk (use all 64 bits, e.g. with a good hash of the full
+    /// state) and verify any optimality claim that matters, for instance by
+    /// re-running the search with an exact cache such as `SimpleCache`.
+    fn state_fingerprint(&self, _state: &Self::State) -> Option<u64> {
+        None
+    }
+    /// This method feeds the identity of the given state into the provided
+    /// hasher. Together with `state_eq`, it defines the notion of state
+    /// identity used by the *threshold cache*: the problem-aware maps
+    /// consulted through the `_with_problem` entry points of `Cache`, which
+    /// are the ones the solvers use. The default delegates to the `Hash`
+    /// implementation of the state; override it -- consistently with
+    /// `state_eq` -- when the derived hashing is not the identity you want,
+    /// e.g. to ignore a bookkeeping field of the state without wrapping the
+    /// state in a newtype.
+    ///
+    /// # Warning
+    /// The scope of this custom identity is the cache *only*. The layer maps
+    /// of the DD compilers and the dominance store keep using the state's own
+    /// `Hash` / `Eq` implementations: two states which only compare equal
+    /// under `state_eq` are still distinct nodes within a single DD. If the
+    /// custom identity must also drive the in-DD deduplication, bake it into
+    /// the `Hash` / `Eq` implementations of the state type itself.
+    fn state_hash(&self, state: &Self::State, mut hasher: &mut dyn Hasher)
+    where Self::State: Hash {
+        state.hash(&mut hasher)
+    }
+    /// This method decides whether two states denote the very same subproblem
+    /// in the eyes of the threshold cache (see `state_hash`, with which it
+    /// must be consistent: equal states must hash alike -- and which narrows
+    /// the scope of this custom identity to the cache only). The default
+    /// delegates to the `Eq` implementation of the state.
+    fn state_eq(&self, a: &Self::State, b: &Self::State) -> bool
+    where Self::State: Eq {
+        a == b
+    }
     /// This method returns false iff this node can be moved forward to the next
     /// layer without making any decision about the variable `_var`.
-    /// When that is the case, a default decision is to be assumed about the 
-    /// variable. Implementing this method is only ever useful if you intend to 
+    /// When that is the case, a default decision is to be assumed about the
+    /// variable. Implementing this method is only ever useful if you intend to
     /// compile a decision diagram that comprises long arcs.
     fn is_impacted_by(&self, _var: Variable, _state: &Self::State) -> bool {
         true
     }
+    /// This method returns true iff the given state is a leaf of the dynamic
+    /// program: a state whose value is final even though some variables may
+    /// remain unassigned (think e.g. of the empty bitset in MISP -- no vertex
+    /// can be selected anymore, so branching further only appends zero-cost
+    /// 'no' decisions). The compiler then terminates the path early: it
+    /// records the value of the node without branching on the remaining
+    /// variables. The default is `false`, which leaves the usual termination
+    /// criterion -- `next_variable` returning `None` -- as the sole one.
+    ///
+    /// # Note
+    /// A solution extracted from an early-terminated path only assigns the
+    /// variables it branched on. This is a hint: a compiler which ignores it
+    /// (and keeps branching until `next_variable` returns `None`) remains
+    /// correct, provided the domains of leaf states are well defined.
+    fn is_leaf(&self, _state: &Self::State) -> bool {
+        false
+    }
+    /// This method returns true iff *every* state of the problem admits a
+    /// feasible completion -- think of knapsack, where leaving all the
+    /// remaining items out is always allowed, so the value accumulated at any
+    /// state is the value of an actual solution. When this hint is set, the
+    /// compiler no longer discards a node left without any successor (empty
+    /// domain, every transition rejected, ...) as an infeasible dead end:
+    /// the node becomes a terminal and its current value a valid incumbent
+    /// candidate. The default is `false`, under which only the paths reaching
+    /// the terminal layer (or an `is_leaf` state) count as solutions.
+    ///
+    /// # Warning
+    /// Returning true for a problem which *can* reach infeasible states is
+    /// unsound: the value accumulated along a path without any feasible
+    /// completion would then be reported as a valid `best_value`. Solutions
+    /// derived from such early terminals only assign the variables that were
+    /// branched on, exactly like the ones of `is_leaf` states.
+    fn always_feasible(&self) -> bool {
+        false
+    }
+}
+
+/// This trait is a variant of `Problem` meant for robust optimization: it
+/// describes a DP model whose transition costs are uncertain and only known
+/// to lie within an interval. Such a problem cannot be solved directly;
+/// instead, one wraps it in one of the `WorstCase` / `BestCase` adapters
+/// (which implement `Problem` by picking one end of each interval) and solves
+/// those. Maximizing the `WorstCase` yields the robust optimum (the best
+/// guaranteed value) while maximizing the `BestCase` yields the optimistic
+/// bound on what could possibly be attained.
+pub trait RobustProblem: Problem {
+    /// This method returns the interval within which the actual cost of the
+    /// given transition is known to lie (the first member of the pair is the
+    /// lowest possible cost, the second one is the highest possible cost)
+    fn transition_cost_interval(&self, source: &Self::State, dest: &Self::State, decision: Decision) -> (isize, isize);
 }
 
 /// A relaxation encapsulates the relaxation $\Gamma$ and $\oplus$ which are
@@ -84,7 +278,32 @@ pub trait Relaxation {
     /// merged states. In the mathematical model, this operation was denoted
     /// with the $\oplus$ operator.
     fn merge(&self, states: &mut dyn Iterator<Item = &Self::State>) -> Self::State;
-    
+
+    /// This method is a variant of `merge` which may additionally signal that
+    /// nothing feasible remains reachable from the merged node: when it
+    /// returns `None`, the compiler drops the merged node entirely (along
+    /// with the arcs that would have been redirected towards it) instead of
+    /// keeping a dead node in the layer. The default implementation simply
+    /// delegates to `merge`, which must always produce a state. Beware: it is
+    /// the modeler's responsibility to only return `None` when *no* feasible
+    /// completion exists from *any* of the merged states; otherwise, the
+    /// relaxed DD ceases to be a valid over-approximation and the solver may
+    /// prune the optimum away.
+    fn merge_opt(&self, states: &mut dyn Iterator<Item = &Self::State>) -> Option<Self::State> {
+        Some(self.merge(states))
+    }
+
+    /// This method is a variant of `merge_opt` which additionally receives
+    /// the type of the compilation being performed. This is the method which
+    /// the compilation of a DD actually invokes: overriding it lets a
+    /// relaxation tailor its merge behavior to the kind of diagram being
+    /// compiled (e.g. use a cheaper merged state when only an approximate
+    /// resolution is at stake). The default ignores the compilation type and
+    /// delegates to `merge_opt`, which preserves the usual behavior exactly.
+    fn merge_opt_with_type(&self, _comp_type: CompilationType, states: &mut dyn Iterator<Item = &Self::State>) -> Option<Self::State> {
+        self.merge_opt(states)
+    }
+
     /// This method relaxes the cost associated to a particular decision. It
     /// is called for any arc labeled `decision` whose weight needs to be 
     /// adjusted because it is redirected from connecting `src` with `dst` to 
@@ -99,11 +318,107 @@ pub trait Relaxation {
         cost: isize,
     ) -> isize;
 
-    /// Returns a very rough estimation (upper bound) of the optimal value that 
+    /// This method is a variant of `relax` which additionally receives the
+    /// type of the compilation being performed; it is the method which the
+    /// compilation of a DD actually invokes when redirecting an arc towards
+    /// a merged node. The default ignores the compilation type and delegates
+    /// to `relax`, which preserves the usual behavior exactly.
+    #[allow(clippy::too_many_arguments)]
+    fn relax_with_type(
+        &self,
+        _comp_type: CompilationType,
+        source: &Self::State,
+        dest: &Self::State,
+        new: &Self::State,
+        decision: Decision,
+        cost: isize,
+    ) -> isize {
+        self.relax(source, dest, new, decision, cost)
+    }
+
+    /// Returns a very rough estimation (upper bound) of the optimal value that
     /// could be reached if state were the initial state
     fn fast_upper_bound(&self, _state: &Self::State) -> isize {
         isize::MAX
     }
+
+    /// Returns a rough estimation (upper bound) of the total objective value
+    /// reachable when passing through `state`, knowing that `value` has been
+    /// accumulated along the path leading to it and that `depth` variables
+    /// have been decided upon. Because the arrival context is known, this
+    /// method can sometimes bound the total more tightly than the sum of the
+    /// path value and the state-only completion bound (think e.g. of
+    /// time-dependent transition costs). The default simply delegates to
+    /// `fast_upper_bound`, which preserves the usual behavior exactly.
+    fn fast_upper_bound_with_value(&self, state: &Self::State, value: isize, _depth: usize) -> isize {
+        value.saturating_add(self.fast_upper_bound(state))
+    }
+
+    /// This method is a variant of `fast_upper_bound` which may additionally
+    /// suggest the feasible completion that the bound computation stumbled
+    /// upon (think e.g. of the integral part of the fractional knapsack
+    /// bound, which is a feasible solution usually thrown away). When a
+    /// completion is returned, the solver validates it -- the decisions are
+    /// replayed against the model -- and, if it checks out, uses it to raise
+    /// the incumbent lower bound right away. The default delegates to
+    /// `fast_upper_bound` and suggests nothing, which preserves the usual
+    /// behavior exactly.
+    fn fast_upper_bound_with_solution(&self, state: &Self::State) -> (isize, Option<Vec<Decision>>) {
+        (self.fast_upper_bound(state), None)
+    }
+
+    /// Returns a very rough estimation (lower bound) of the optimal value that
+    /// could be reached if state were the initial state. Unlike the upper
+    /// bound counterpart, this estimate must be *achievable*: it is the value
+    /// of some feasible completion from the given state (think e.g. of a
+    /// greedy completion for the knapsack). The solvers exploit it to tighten
+    /// their incumbent lower bound -- and hence prune more aggressively --
+    /// before even compiling a DD for the subproblem rooted in that state.
+    /// The default returns `isize::MIN`, which means "no information".
+    fn fast_lower_bound(&self, _state: &Self::State) -> isize {
+        isize::MIN
+    }
+
+    /// When a layer of a relaxed DD exceeds the maximum width, this method
+    /// decides which nodes get merged together. It receives the states of the
+    /// whole layer, sorted from the most promising to the least promising one
+    /// (as per the state ranking), and it returns the groups of indices that
+    /// must each be merged into one single node (groups of less than two
+    /// indices are left untouched). The default merges all the surplus states
+    /// (the indices from `width - 1` onwards) into one single group, which is
+    /// the usual relaxation scheme. Overriding this method lets you cluster
+    /// the states by similarity instead (e.g. grouping knapsack states with
+    /// nearby capacities), which typically yields tighter relaxed bounds. Do
+    /// make sure that the returned groups shrink the layer to at most `width`
+    /// nodes, otherwise the maximum width will not be honored.
+    fn select_merge_groups(&self, states: &[&Self::State], width: usize) -> Vec<Vec<usize>> {
+        vec![(width.saturating_sub(1)..states.len()).collect()]
+    }
+
+    /// When a group of nodes is merged during the compilation of a relaxed
+    /// DD, this method selects which node of the group acts as the
+    /// *representative* of the merged node: the merged node inherits the
+    /// best incoming arc -- and hence the best parent -- of its
+    /// representative, which conditions the paths reported through the
+    /// merged node and the local bounds derived from them. It receives the
+    /// states of the merged group along with their longest-path value and
+    /// must return the index of the representative within that group. The
+    /// default picks the node with the maximum value, which is the usual
+    /// scheme; problems like TSPTW may prefer another criterion (e.g. the
+    /// state with the minimum elapsed time) to keep the representative
+    /// meaningful. Note that this choice never affects the value of the
+    /// merged node itself: the relaxed bounds stay valid regardless.
+    fn select_representative(&self, states: &mut dyn Iterator<Item = (&Self::State, isize)>) -> usize {
+        let mut best = 0;
+        let mut best_value = isize::MIN;
+        for (i, (_, value)) in states.enumerate() {
+            if value > best_value {
+                best = i;
+                best_value = value;
+            }
+        }
+        best
+    }
 }
 
 /// This trait basically defines a callback which is passed on to the problem
@@ -121,9 +436,66 @@ impl <X: FnMut(Decision)> DecisionCallback for X {
     }
 }
 
+/// A callback which simply collects the enumerated decisions into a vector.
+/// This spares the `&mut |d| vec.push(d)` boilerplate when materializing a
+/// domain, typically in tests:
+/// `problem.for_each_in_domain(var, &state, &mut CollectCallback(&mut vec))`.
+pub struct CollectCallback<'a>(pub &'a mut Vec<Decision>);
+impl DecisionCallback for CollectCallback<'_> {
+    fn apply(&mut self, decision: Decision) {
+        self.0.push(decision)
+    }
+}
+
+/// This function replays the given sequence of `decisions` through the DP
+/// model of `pb`: starting from the initial state, it branches on the
+/// variables in the order dictated by the model and accumulates the
+/// transition costs along the way. It returns the total objective value of
+/// the path (including `initial_value`), or `None` when the sequence is
+/// infeasible -- that is, when some variable to branch on has no matching
+/// decision in the sequence, or when the prescribed value does not belong to
+/// the domain of that variable in the current state.
+///
+/// This is typically useful to double-check a solution returned by a solver
+/// or to evaluate an externally provided assignment against the model.
+pub fn evaluate<P: Problem>(pb: &P, decisions: &[Decision]) -> Option<isize> {
+    let static_order = pb.static_order();
+
+    let mut state = pb.initial_state();
+    let mut total = pb.initial_value();
+    let mut taken = vec![];
+    for depth in 0..pb.nb_variables() {
+        let var = match static_order.as_ref() {
+            Some(order) => order.get(depth).copied(),
+            None => pb.next_variable(depth, &mut std::iter::once(&state)),
+        };
+        let Some(var) = var else { break };
+
+        // the replay must exercise the very hooks the compilers do: the
+        // domain is enumerated with the path taken so far (in case the
+        // domains are path dependent) and the transition is the checked one
+        // (in case the domain over-approximates and the model rejects at
+        // transition time)
+        let decision = decisions.iter().copied().find(|d| d.variable == var)?;
+        let mut feasible = false;
+        pb.for_each_in_domain_with_path(var, &state, &taken, &mut |d: Decision| {
+            feasible |= d.value == decision.value;
+        });
+        if !feasible {
+            return None;
+        }
+
+        let next = pb.transition_checked(&state, decision)?;
+        total = total.saturating_add(pb.transition_cost(&state, &next, decision));
+        state = next;
+        taken.push(decision);
+    }
+    Some(total)
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{Relaxation, DecisionCallback, Decision, Problem};
+    use crate::{Relaxation, CollectCallback, DecisionCallback, Decision, Problem};
     
     #[test]
     fn by_default_fast_upperbound_yields_positive_max() {
@@ -131,10 +503,154 @@ mod tests {
         assert_eq!(isize::MAX, rlx.fast_upper_bound(&'x'));
     }
     #[test]
+    fn by_default_fast_upperbound_with_value_adds_the_path_value() {
+        let rlx = DummyRelax;
+        // the default saturates instead of overflowing
+        assert_eq!(isize::MAX, rlx.fast_upper_bound_with_value(&'x', 42, 0));
+
+        let rlx = BoundedRelax;
+        assert_eq!(142, rlx.fast_upper_bound_with_value(&'x', 42, 0));
+    }
+    #[test]
+    fn by_default_fast_lowerbound_yields_negative_min() {
+        let rlx = DummyRelax;
+        assert_eq!(isize::MIN, rlx.fast_lower_bound(&'x'));
+    }
+    #[test]
+    fn the_collect_callback_materializes_the_domain_into_a_vec() {
+        let pb = Knapsack;
+        let state = pb.initial_state();
+        let mut domain = vec![];
+        pb.for_each_in_domain(crate::Variable(0), &state, &mut CollectCallback(&mut domain));
+        assert_eq!(vec![
+            Decision { variable: crate::Variable(0), value: TAKE_IT },
+            Decision { variable: crate::Variable(0), value: LEAVE_IT_OUT },
+        ], domain);
+    }
+    #[test]
     fn by_default_all_states_are_impacted_by_all_vars() {
         let pb = DummyProblem;
         assert!(pb.is_impacted_by(crate::Variable(10), &'x'));
     }
+    #[test]
+    fn by_default_the_domain_iter_is_not_lazy() {
+        let pb = DummyProblem;
+        assert!(!pb.has_lazy_domain_iter());
+    }
+    #[test]
+    fn by_default_there_is_no_static_order() {
+        let pb = DummyProblem;
+        assert!(pb.static_order().is_none());
+    }
+    #[test]
+    fn by_default_the_typed_variants_ignore_the_compilation_type() {
+        let rlx = CharRelax;
+        let states = ['a', 'b'];
+        assert_eq!(Some('a'), rlx.merge_opt_with_type(crate::CompilationType::Relaxed, &mut states.iter()));
+        assert_eq!(Some('a'), rlx.merge_opt_with_type(crate::CompilationType::Restricted, &mut states.iter()));
+
+        let dec = Decision{variable: crate::Variable(0), value: 1};
+        assert_eq!(5, rlx.relax_with_type(crate::CompilationType::Relaxed, &'a', &'b', &'a', dec, 5));
+        assert_eq!(5, rlx.relax_with_type(crate::CompilationType::Restricted, &'a', &'b', &'a', dec, 5));
+    }
+    #[test]
+    fn by_default_every_decision_is_accepted() {
+        let pb = Knapsack;
+        let state = pb.initial_state();
+        let dec = Decision{variable: crate::Variable(0), value: TAKE_IT};
+        assert_eq!(Some(pb.transition(&state, dec)), pb.transition_checked(&state, dec));
+    }
+    #[test]
+    fn by_default_domains_do_not_depend_on_the_path() {
+        let pb = Knapsack;
+        assert!(!pb.has_path_dependent_domains());
+
+        let state = pb.initial_state();
+        let mut eager = vec![];
+        let mut with_path = vec![];
+        pb.for_each_in_domain(crate::Variable(0), &state, &mut |d: Decision| eager.push(d.value));
+        pb.for_each_in_domain_with_path(crate::Variable(0), &state, &[], &mut |d: Decision| with_path.push(d.value));
+        assert_eq!(eager, with_path);
+    }
+
+    #[test]
+    fn evaluating_a_feasible_path_yields_its_total_value() {
+        let pb = Knapsack;
+        let decisions = vec![
+            Decision{variable: crate::Variable(0), value: 0},
+            Decision{variable: crate::Variable(1), value: 1},
+            Decision{variable: crate::Variable(2), value: 1},
+        ];
+        assert_eq!(Some(220), crate::evaluate(&pb, &decisions));
+    }
+    #[test]
+    fn the_decisions_may_be_listed_in_any_order() {
+        let pb = Knapsack;
+        let decisions = vec![
+            Decision{variable: crate::Variable(2), value: 1},
+            Decision{variable: crate::Variable(0), value: 0},
+            Decision{variable: crate::Variable(1), value: 1},
+        ];
+        assert_eq!(Some(220), crate::evaluate(&pb, &decisions));
+    }
+    #[test]
+    fn a_decision_out_of_the_domain_is_infeasible() {
+        let pb = Knapsack;
+        // taking all three items exceeds the capacity: by the time variable 2
+        // is branched on, TAKE_IT is no longer in the domain
+        let decisions = vec![
+            Decision{variable: crate::Variable(0), value: 1},
+            Decision{variable: crate::Variable(1), value: 1},
+            Decision{variable: crate::Variable(2), value: 1},
+        ];
+        assert_eq!(None, crate::evaluate(&pb, &decisions));
+    }
+    #[test]
+    fn a_missing_decision_is_infeasible() {
+        let pb = Knapsack;
+        let decisions = vec![
+            Decision{variable: crate::Variable(0), value: 1},
+            Decision{variable: crate::Variable(2), value: 1},
+        ];
+        assert_eq!(None, crate::evaluate(&pb, &decisions));
+    }
+    #[test]
+    fn a_decision_rejected_at_transition_time_is_infeasible() {
+        let pb = VetoedKnapsack;
+        // the over-approximated domain accepts taking every item but the
+        // checked transition rejects the take which busts the capacity: the
+        // replay must exercise the checked transition to notice it
+        let decisions = vec![
+            Decision{variable: crate::Variable(0), value: 1},
+            Decision{variable: crate::Variable(1), value: 1},
+            Decision{variable: crate::Variable(2), value: 1},
+        ];
+        assert_eq!(None, crate::evaluate(&pb, &decisions));
+
+        let decisions = vec![
+            Decision{variable: crate::Variable(0), value: 0},
+            Decision{variable: crate::Variable(1), value: 1},
+            Decision{variable: crate::Variable(2), value: 1},
+        ];
+        assert_eq!(Some(220), crate::evaluate(&pb, &decisions));
+    }
+    #[test]
+    fn path_dependent_domains_are_replayed_with_the_path() {
+        let pb = AtMostOneTake;
+        // taking twice is only ruled out by the path-aware domain: the replay
+        // must hand the decisions taken so far to the domain enumeration
+        let decisions = vec![
+            Decision{variable: crate::Variable(0), value: 1},
+            Decision{variable: crate::Variable(1), value: 1},
+        ];
+        assert_eq!(None, crate::evaluate(&pb, &decisions));
+
+        let decisions = vec![
+            Decision{variable: crate::Variable(0), value: 1},
+            Decision{variable: crate::Variable(1), value: 0},
+        ];
+        assert_eq!(Some(1), crate::evaluate(&pb, &decisions));
+    }
 
     #[test]
     fn any_closure_is_a_decision_callback() {
@@ -176,6 +692,166 @@ mod tests {
             todo!()
         }
     }
+    const TAKE_IT: isize = 1;
+    const LEAVE_IT_OUT: isize = 0;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    struct KnapsackState {
+        depth: usize,
+        capacity: usize,
+    }
+    /// A tiny knapsack instance: capacity 50, profits [60, 100, 120] and
+    /// weights [10, 20, 30]. The optimum (220) leaves the first item out.
+    struct Knapsack;
+    impl Problem for Knapsack {
+        type State = KnapsackState;
+
+        fn nb_variables(&self) -> usize {
+            3
+        }
+        fn initial_state(&self) -> Self::State {
+            KnapsackState{depth: 0, capacity: 50}
+        }
+        fn initial_value(&self) -> isize {
+            0
+        }
+        fn transition(&self, state: &Self::State, dec: Decision) -> Self::State {
+            let mut ret = *state;
+            ret.depth += 1;
+            if dec.value == TAKE_IT {
+                ret.capacity -= Self::WEIGHT[dec.variable.id()]
+            }
+            ret
+        }
+        fn transition_cost(&self, _: &Self::State, _: &Self::State, dec: Decision) -> isize {
+            Self::PROFIT[dec.variable.id()] * dec.value
+        }
+        fn next_variable(&self, depth: usize, _: &mut dyn Iterator<Item = &Self::State>)
+            -> Option<crate::Variable> {
+            if depth < self.nb_variables() {
+                Some(crate::Variable(depth))
+            } else {
+                None
+            }
+        }
+        fn for_each_in_domain(&self, var: crate::Variable, state: &Self::State, f: &mut dyn DecisionCallback) {
+            if state.capacity >= Self::WEIGHT[var.id()] {
+                f.apply(Decision{variable: var, value: TAKE_IT});
+            }
+            f.apply(Decision{variable: var, value: LEAVE_IT_OUT});
+        }
+    }
+    impl Knapsack {
+        const PROFIT: [isize; 3] = [60, 100, 120];
+        const WEIGHT: [usize; 3] = [10, 20, 30];
+    }
+
+    /// The same knapsack instance, except that its domain over-approximates
+    /// (taking an item is always offered) and the capacity check is deferred
+    /// to `transition_checked` -- the contract which lets a model reject a
+    /// decision at transition time
+    struct VetoedKnapsack;
+    impl Problem for VetoedKnapsack {
+        type State = KnapsackState;
+
+        fn nb_variables(&self) -> usize {
+            Knapsack.nb_variables()
+        }
+        fn initial_state(&self) -> Self::State {
+            Knapsack.initial_state()
+        }
+        fn initial_value(&self) -> isize {
+            Knapsack.initial_value()
+        }
+        fn transition(&self, state: &Self::State, dec: Decision) -> Self::State {
+            Knapsack.transition(state, dec)
+        }
+        fn transition_checked(&self, state: &Self::State, dec: Decision) -> Option<Self::State> {
+            if dec.value == TAKE_IT && state.capacity < Knapsack::WEIGHT[dec.variable.id()] {
+                None
+            } else {
+                Some(self.transition(state, dec))
+            }
+        }
+        fn transition_cost(&self, source: &Self::State, dest: &Self::State, dec: Decision) -> isize {
+            Knapsack.transition_cost(source, dest, dec)
+        }
+        fn next_variable(&self, depth: usize, states: &mut dyn Iterator<Item = &Self::State>)
+            -> Option<crate::Variable> {
+            Knapsack.next_variable(depth, states)
+        }
+        fn for_each_in_domain(&self, var: crate::Variable, _: &Self::State, f: &mut dyn DecisionCallback) {
+            f.apply(Decision{variable: var, value: TAKE_IT});
+            f.apply(Decision{variable: var, value: LEAVE_IT_OUT});
+        }
+    }
+
+    /// A two-variable problem whose sole constraint -- the value 1 may be
+    /// chosen at most once -- only lives in the path-aware domain enumeration
+    struct AtMostOneTake;
+    impl Problem for AtMostOneTake {
+        type State = usize;
+
+        fn nb_variables(&self) -> usize {
+            2
+        }
+        fn initial_state(&self) -> Self::State {
+            0
+        }
+        fn initial_value(&self) -> isize {
+            0
+        }
+        fn transition(&self, state: &Self::State, _: Decision) -> Self::State {
+            state + 1
+        }
+        fn transition_cost(&self, _: &Self::State, _: &Self::State, dec: Decision) -> isize {
+            dec.value
+        }
+        fn next_variable(&self, depth: usize, _: &mut dyn Iterator<Item = &Self::State>)
+            -> Option<crate::Variable> {
+            if depth < self.nb_variables() {
+                Some(crate::Variable(depth))
+            } else {
+                None
+            }
+        }
+        fn for_each_in_domain(&self, var: crate::Variable, _: &Self::State, f: &mut dyn DecisionCallback) {
+            f.apply(Decision{variable: var, value: 1});
+            f.apply(Decision{variable: var, value: 0});
+        }
+        fn for_each_in_domain_with_path(&self, var: crate::Variable, _: &Self::State, path: &[Decision], f: &mut dyn DecisionCallback) {
+            if !path.iter().any(|d| d.value == 1) {
+                f.apply(Decision{variable: var, value: 1});
+            }
+            f.apply(Decision{variable: var, value: 0});
+        }
+        fn has_path_dependent_domains(&self) -> bool {
+            true
+        }
+    }
+
+    /// A relaxation with an actual (trivial) behavior: it keeps the first of
+    /// the merged states and leaves the arc costs untouched
+    struct CharRelax;
+    impl Relaxation for CharRelax {
+        type State = char;
+
+        fn merge(&self, states: &mut dyn Iterator<Item = &Self::State>) -> Self::State {
+            *states.next().unwrap()
+        }
+
+        fn relax(
+            &self,
+            _source: &Self::State,
+            _dest: &Self::State,
+            _new: &Self::State,
+            _decision: crate::Decision,
+            cost: isize,
+        ) -> isize {
+            cost
+        }
+    }
+
     struct DummyRelax;
     impl Relaxation for DummyRelax {
         type State = char;
@@ -195,4 +871,28 @@ mod tests {
             todo!()
         }
     }
+
+    struct BoundedRelax;
+    impl Relaxation for BoundedRelax {
+        type State = char;
+
+        fn merge(&self, _states: &mut dyn Iterator<Item = &Self::State>) -> Self::State {
+            todo!()
+        }
+
+        fn relax(
+            &self,
+            _source: &Self::State,
+            _dest: &Self::State,
+            _new: &Self::State,
+            _decision: crate::Decision,
+            _cost: isize,
+        ) -> isize {
+            todo!()
+        }
+
+        fn fast_upper_bound(&self, _state: &Self::State) -> isize {
+            100
+        }
+    }
 }
\ No newline at end of file
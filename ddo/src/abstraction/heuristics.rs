@@ -61,6 +61,37 @@ use crate::SubProblem;
 pub trait WidthHeuristic<State> {
     /// Estimates a good maximum width for an MDD rooted in the given state
     fn max_width(&self, state: &SubProblem<State>) -> usize;
+
+    /// Estimates a good maximum width for an MDD rooted in the given state,
+    /// knowing the current best lower and upper bounds on the optimal value.
+    /// This is the method which is actually called by the solvers: it allows
+    /// an heuristic to adapt the width to the progress of the search (e.g.
+    /// compile wider DDs as long as the optimality gap remains large, as the
+    /// `GapScaledWidth` heuristic does). By default, the bounds are simply
+    /// ignored and this method delegates to `max_width`, so the heuristics
+    /// that do not care about the bounds (`FixedWidth`, `NbUnassignedWidth`,
+    /// ...) need only implement the latter. Note that the combinators
+    /// (`Times`, `DivBy`) forward the bounds to the heuristic they decorate.
+    fn max_width_with_bounds(&self, state: &SubProblem<State>, _best_lb: isize, _best_ub: isize) -> usize {
+        self.max_width(state)
+    }
+
+    /// Estimates a good maximum number of branching decisions which may be
+    /// enumerated from any single node of an MDD rooted in the given state.
+    /// When a node's domain is larger than this limit, the compiler stops
+    /// pulling decisions from it and only keeps the ones leading to the
+    /// best successor states (according to the `StateRanking`). By default,
+    /// the out-degree is unbounded.
+    ///
+    /// # Warning
+    /// Capping the out-degree of nodes drops arcs from the diagram, which
+    /// makes the compiled DD a *restriction* of the subproblem: it is no
+    /// longer exact, and a relaxed DD compiled with such a cap yields bounds
+    /// which may be invalid. This knob is only meant to speed up restricted
+    /// compilations on problems with huge domains.
+    fn max_degree(&self, _state: &SubProblem<State>) -> usize {
+        usize::MAX
+    }
 }
 
 /// A state ranking is an heuristic that imposes a partial order on states.
@@ -76,9 +107,19 @@ pub trait StateRanking {
     /// tell the kind of states it is able to operate on.
     type State;
 
-    /// This method compares two states and determines which is the most 
-    /// desirable to keep. In this ordering, 
+    /// This method compares two states and determines which is the most
+    /// desirable to keep. In this ordering,
     fn compare(&self, a: &Self::State, b: &Self::State) -> Ordering;
+
+    /// This secondary criterion is consulted when `compare` considers two
+    /// states equally desirable. Overriding it makes the merge/delete choice
+    /// of the layer squashing reproducible instead of depending on the whims
+    /// of the sort; when this one returns `Equal` too, the framework falls
+    /// back to a stable insertion-order rule. The default implementation
+    /// expresses no preference.
+    fn tie_break(&self, _a: &Self::State, _b: &Self::State) -> Ordering {
+        Ordering::Equal
+    }
 }
 
 /// A subproblem ranking is an heuristic that imposes a partial order on
@@ -102,4 +143,35 @@ pub trait SubProblemRanking {
 pub trait Cutoff {
     /// Returns true iff the criterion is met and the search must stop.
     fn must_stop(&self) -> bool;
+
+    /// Notifies the criterion that `nb_nodes` additional nodes have been
+    /// developed during the compilation of a decision diagram. The default
+    /// implementation does nothing; it is only meant to be overridden by the
+    /// criteria whose budget is expressed in a number of nodes (e.g.
+    /// `NodeBudget`).
+    fn add_expanded(&self, _nb_nodes: usize) {}
+
+    /// Notifies the criterion of the current number of subproblems queued in
+    /// the solver fringe. The default implementation does nothing; it is only
+    /// meant to be overridden by the criteria which watch the memory consumed
+    /// by the fringe (e.g. `FringeSizeLimit`).
+    fn set_fringe_size(&self, _nb_subproblems: usize) {}
+
+    /// Notifies the criterion of the best lower and upper bounds which are
+    /// currently known on the optimal value. The default implementation does
+    /// nothing; it is only meant to be overridden by the criteria which watch
+    /// the optimality gap (e.g. `GapLimit`).
+    fn set_bounds(&self, _lb: isize, _ub: isize) {}
+
+    /// Reports the fraction of the budget which is still available, as a
+    /// value between 1.0 (nothing consumed yet) and 0.0 (budget exhausted),
+    /// or `None` when the criterion has no measurable budget (e.g. `NoCutoff`
+    /// or `GapLimit`). The default implementation returns `None`; it is only
+    /// meant to be overridden by the criteria which grant a finite budget
+    /// (e.g. `TimeBudget`, `NodeBudget`). A width heuristic may consult this
+    /// information to adapt the width of the compiled DDs to the remaining
+    /// budget, as `BudgetScaledWidth` does.
+    fn remaining_budget_ratio(&self) -> Option<f64> {
+        None
+    }
 }
\ No newline at end of file
@@ -17,7 +17,7 @@
 // IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
 // CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
-use crate::{SubProblem, Completion, Reason, Problem, Relaxation, StateRanking, Solution, Cutoff, Cache, DominanceChecker};
+use crate::{SubProblem, Completion, Reason, Problem, Relaxation, StateRanking, Solution, Cutoff, Cache, Decision, DominanceChecker};
 
 // FIXME: Replace that with the following enum definition when const generics allow enum types
 /// What type of cut-set are we using for relaxed DDs ?
@@ -61,6 +61,27 @@ pub struct CompilationInput<'a, State> {
     pub cutoff: &'a dyn Cutoff,
     /// What is the maximum width of the mdd ?
     pub max_width: usize,
+    /// If set, the maximum number of incoming edges which may be retained on
+    /// a merged node of a relaxed dd (keeping the edges with the best value).
+    ///
+    /// # Warning
+    /// Capping the in-degree of merged nodes keeps relaxed DDs sparse but it
+    /// drops paths from the diagram: the compiled DD then no longer
+    /// over-approximates the subproblem and the bounds it yields may be
+    /// invalid. Only use this option when an approximate resolution is
+    /// acceptable.
+    pub max_in_degree: Option<usize>,
+    /// The maximum number of branching decisions which may be enumerated from
+    /// any single node of the dd (keeping only the decisions leading to the
+    /// best-ranked successor states). Use `usize::MAX` to leave the
+    /// out-degree unbounded.
+    ///
+    /// # Warning
+    /// Capping the out-degree drops arcs from the diagram: the compiled DD
+    /// is then a restriction of the subproblem, hence it is never exact and
+    /// the bounds derived from a relaxed DD compiled with this cap may be
+    /// invalid. Only use this option for restricted compilations.
+    pub max_out_degree: usize,
     /// The subproblem whose state space must be explored
     pub residual: &'a SubProblem<State>,
     /// The best known lower bound at the time when the dd is being compiled
@@ -101,6 +122,61 @@ pub trait DecisionDiagram {
     /// maximizing the objective value. When no feasible solution exists in the
     /// approximate DD, it returns the value None instead.
     fn best_exact_solution(&self) -> Option<Solution>;
+    /// Returns the average out-degree (total edges / total non-leaf nodes)
+    /// which has been observed over all the compilations performed with this
+    /// structure. A high branching factor explains wide DDs and may hint that
+    /// domain reduction would help. Implementations which do not track the
+    /// required counters may stick to the default (which returns `NaN`).
+    fn avg_branching_factor(&self) -> f64 {
+        f64::NAN
+    }
+    /// Iteratively applies the given function `func` to the decision labeling
+    /// each edge of the diagram resulting from the last compilation. This is
+    /// the raw material from which a solver can derive per-variable assignment
+    /// statistics over the explored search space. Implementations which do not
+    /// keep their edges around may stick to the default (which visits nothing).
+    fn for_each_edge_decision(&self, _func: &mut dyn FnMut(Decision)) {}
+    /// Returns (up to) the `k` best root-to-terminal paths of this decision
+    /// diagram along with their values, sorted by decreasing value. The
+    /// default implementation only knows about the single best path and
+    /// hence returns at most one entry.
+    ///
+    /// # Warning
+    /// The returned paths are only guaranteed to be feasible solutions when
+    /// the diagram comprises no merged node -- that is, for exact and
+    /// restricted DDs. On an inexact relaxed DD, some of the paths may cross
+    /// a merged node and correspond to no feasible solution at all.
+    fn best_k_solutions(&self, k: usize) -> Vec<(isize, Solution)> {
+        self.best_value().zip(self.best_solution()).into_iter().take(k).collect()
+    }
+    /// Returns the number of distinct root-to-terminal paths of this decision
+    /// diagram which achieve `best_value`, saturating at `u128::MAX` for the
+    /// (astronomically) large counts. The default implementation only knows
+    /// about the single best path and hence reports at most one.
+    ///
+    /// # Warning
+    /// This count is only meaningful when the compilation was exact: on a
+    /// restricted DD some tied-optimal paths may have been deleted, and on a
+    /// relaxed DD some of the counted paths may cross a merged node and
+    /// correspond to no feasible solution at all.
+    fn count_best_paths(&self) -> u128 {
+        u128::from(self.best_value().is_some())
+    }
+    /// Returns all the root-to-terminal paths of this decision diagram whose
+    /// value is at least `threshold`, along with their values, sorted by
+    /// decreasing value. The default implementation only knows about the
+    /// single best path and hence returns at most one entry.
+    ///
+    /// # Warning
+    /// The returned paths are only guaranteed to be feasible solutions when
+    /// the diagram comprises no merged node -- that is, for exact and
+    /// restricted DDs. Also beware that the number of qualifying paths may
+    /// blow up combinatorially as the threshold moves away from the optimum.
+    fn solutions_above(&self, threshold: isize) -> Vec<(isize, Solution)> {
+        self.best_value().zip(self.best_solution()).into_iter()
+            .filter(|(value, _)| *value >= threshold)
+            .collect()
+    }
     /// Iteratively applies the given function `func` to each element of the
     /// exact cut-set that was computed during DD compilation.
     ///
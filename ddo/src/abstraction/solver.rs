@@ -19,11 +19,100 @@
 
 //! This module defines the `Solver` trait.
 
-use crate::{Decision, Completion};
+use std::ops::{Deref, DerefMut};
 
-/// A decision is nothing but a sequence of decision covering all problem
-/// variables.
-pub type Solution = Vec<Decision>;
+use crate::{Decision, Completion, Variable};
+
+/// A solution is nothing but a sequence of decision covering all problem
+/// variables. It wraps the vector of decisions so that the decisions can be
+/// lent out (`decisions`), iterated, or consumed (`into_sorted_by_variable`)
+/// without incurring a spurious clone of the underlying vector. For backwards
+/// compatibility, it dereferences to the plain vector of decisions.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Solution(Vec<Decision>);
+
+impl Solution {
+    /// Creates a solution from the given sequence of decisions.
+    pub fn new(decisions: Vec<Decision>) -> Self {
+        Self(decisions)
+    }
+    /// Lends an iterator over the decisions of this solution. On the contrary
+    /// to cloning the solution, this borrows the decisions in place: only the
+    /// (Copy) decisions that are actually consumed are ever copied.
+    pub fn decisions(&self) -> impl Iterator<Item = Decision> + '_ {
+        self.0.iter().copied()
+    }
+    /// Consumes this solution and returns the decisions it comprises, sorted
+    /// by increasing variable identifier.
+    pub fn into_sorted_by_variable(mut self) -> Vec<Decision> {
+        self.0.sort_unstable_by_key(|d| d.variable.0);
+        self.0
+    }
+    /// Returns the number of decisions comprised in this solution. For
+    /// variable-length problems (think e.g. of MISP, where not every
+    /// variable needs to be assigned), this may be less than the number of
+    /// variables of the problem.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+    /// Returns true iff this solution comprises no decision at all.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    /// Lends an iterator over the variables that are effectively assigned a
+    /// value by this solution (in the order in which the decisions were
+    /// made).
+    pub fn assigned_variables(&self) -> impl Iterator<Item = Variable> + '_ {
+        self.0.iter().map(|d| d.variable)
+    }
+}
+
+impl Deref for Solution {
+    type Target = Vec<Decision>;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl DerefMut for Solution {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+impl From<Vec<Decision>> for Solution {
+    fn from(decisions: Vec<Decision>) -> Self {
+        Self(decisions)
+    }
+}
+impl From<Solution> for Vec<Decision> {
+    fn from(solution: Solution) -> Self {
+        solution.0
+    }
+}
+impl IntoIterator for Solution {
+    type Item = Decision;
+    type IntoIter = std::vec::IntoIter<Decision>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+impl<'a> IntoIterator for &'a Solution {
+    type Item = &'a Decision;
+    type IntoIter = std::slice::Iter<'a, Decision>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+impl PartialEq<Vec<Decision>> for Solution {
+    fn eq(&self, other: &Vec<Decision>) -> bool {
+        self.0.eq(other)
+    }
+}
+impl PartialEq<Solution> for Vec<Decision> {
+    fn eq(&self, other: &Solution) -> bool {
+        self.eq(&other.0)
+    }
+}
 
 /// This is the solver abstraction. It is implemented by a structure that 
 /// implements the branch-and-bound with MDD paradigm (or possibly an other
@@ -95,3 +184,85 @@ pub trait Solver {
     /// Returns the number of nodes that have been explored during the search.
     fn explored(&self) -> usize;
 }
+
+/// This trait lets you observe the progress of a resolution programmatically:
+/// an implementation can forward the events to a logging facade, a file, a
+/// GUI, or anything else. The solvers notify their reporter (registered with
+/// `with_reporter`) of the salient events of the search. All the methods have
+/// a default (no-op) implementation so you only implement the notifications
+/// you care about.
+pub trait SearchReporter {
+    /// Called once, when the resolution begins.
+    fn on_start(&mut self) {}
+    /// Called whenever the incumbent bounds improve; `lb` and `ub` are the
+    /// best known lower and upper bounds at the time of the notification.
+    fn on_new_bound(&mut self, _lb: isize, _ub: isize) {}
+    /// Called when the search is done with all the subproblems rooted at the
+    /// given depth (layer) of the problem: from that point on, every open
+    /// subproblem is rooted strictly deeper. This gives a coarse notion of
+    /// the progress of the search.
+    fn on_layer(&mut self, _layer: usize) {}
+    /// Called once, when the resolution ends (regardless of whether it went
+    /// all the way to an optimality proof or was cut off).
+    fn on_finish(&mut self, _completion: &Completion) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn decisions_lends_the_decisions_without_consuming_the_solution() {
+        let solution = Solution::new(vec![
+            Decision { variable: Variable(1), value: 7 },
+            Decision { variable: Variable(0), value: 2 },
+        ]);
+        let values = solution.decisions().map(|d| d.value).collect::<Vec<_>>();
+        assert_eq!(values, vec![7, 2]);
+        // the solution is untouched: it can still be used afterwards
+        assert_eq!(solution.len(), 2);
+    }
+
+    #[test]
+    fn into_sorted_by_variable_orders_the_decisions_by_variable_id() {
+        let solution = Solution::new(vec![
+            Decision { variable: Variable(2), value: 30 },
+            Decision { variable: Variable(0), value: 10 },
+            Decision { variable: Variable(1), value: 20 },
+        ]);
+        assert_eq!(solution.into_sorted_by_variable(), vec![
+            Decision { variable: Variable(0), value: 10 },
+            Decision { variable: Variable(1), value: 20 },
+            Decision { variable: Variable(2), value: 30 },
+        ]);
+    }
+
+    #[test]
+    fn len_and_assigned_variables_describe_the_solution_without_iterating() {
+        let solution = Solution::new(vec![
+            Decision { variable: Variable(2), value: 1 },
+            Decision { variable: Variable(0), value: 1 },
+        ]);
+        // a variable-length solution may assign less variables than the
+        // problem counts
+        assert_eq!(solution.len(), 2);
+        assert!(!solution.is_empty());
+        assert_eq!(solution.assigned_variables().collect::<Vec<_>>(),
+                   vec![Variable(2), Variable(0)]);
+
+        assert!(Solution::default().is_empty());
+    }
+
+    #[test]
+    fn a_solution_derefs_to_the_plain_vector_of_decisions() {
+        let mut solution = Solution::new(vec![
+            Decision { variable: Variable(1), value: 7 },
+            Decision { variable: Variable(0), value: 2 },
+        ]);
+        solution.sort_unstable_by_key(|d| d.variable.0);
+        assert_eq!(solution, vec![
+            Decision { variable: Variable(0), value: 2 },
+            Decision { variable: Variable(1), value: 7 },
+        ]);
+    }
+}
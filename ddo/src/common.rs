@@ -30,6 +30,7 @@ use std::sync::Arc;
 /// In this case, each variable is assumed to be identified with an integer
 /// ranging from 0 until `problem.nb_vars()`
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Variable(pub usize);
 impl Variable {
     #[inline]
@@ -54,7 +55,8 @@ impl Variable {
 /// This denotes a decision that was made during the search. It affects a given
 /// `value` to the specified `variable`. Any given `Decision` should be
 /// understood as ```[[ variable = value ]]````
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Decision {
     pub variable : Variable,
     pub value    : isize
@@ -68,10 +70,16 @@ pub struct Decision {
 /// resolution of the original problem which had been defined. 
 /// 
 /// # Note:
-/// Sub-problems are automatically instantiated from nodes in the exact cut-sets 
-/// of relaxed decision diagrams. If you are only discovering the API, rest 
+/// Sub-problems are automatically instantiated from nodes in the exact cut-sets
+/// of relaxed decision diagrams. If you are only discovering the API, rest
 /// assured.. you don't need to implement any subproblem yourself.
+///
+/// When the `serde` feature is enabled, a subproblem can be (de)serialized
+/// -- e.g. to ship fringe subproblems to other machines in a distributed
+/// setting -- provided the state type itself implements `Serialize` and
+/// `Deserialize`.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SubProblem<T> {
     /// The root state of this sub problem
     pub state: Arc<T>,
@@ -86,6 +94,34 @@ pub struct SubProblem<T> {
     pub depth: usize,
 }
 
+/// A state-less summary of a subproblem: its depth, value, upper bound and
+/// the decision path leading to it. This is what the solvers hand out when
+/// asked to describe the subproblems left open by an interrupted search (see
+/// `fringe_snapshot`): enough to decide whether resuming is worthwhile,
+/// without tying the caller to the state type of the model.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubProblemSummary {
+    /// The depth of the subproblem with respect to the root problem
+    pub depth: usize,
+    /// The root value of the subproblem
+    pub value: isize,
+    /// An upper bound on the objective reachable in the subproblem
+    pub ub: isize,
+    /// The path to traverse to reach the subproblem from the root of the
+    /// original problem
+    pub path: Vec<Decision>,
+}
+impl<T> From<&SubProblem<T>> for SubProblemSummary {
+    fn from(subproblem: &SubProblem<T>) -> Self {
+        SubProblemSummary {
+            depth: subproblem.depth,
+            value: subproblem.value,
+            ub: subproblem.ub,
+            path: subproblem.path.clone(),
+        }
+    }
+}
+
 // ----------------------------------------------------------------------------
 // --- THRESHOLD --------------------------------------------------------------
 // ----------------------------------------------------------------------------
@@ -110,8 +146,179 @@ pub enum Reason {
     CutoffOccurred
 }
 
+/// The reason why a subproblem popped from the solver fringe was closed
+/// without spawning any further subproblem.
+///
+/// # Note
+/// Dominance relations are exploited *inside* the compilation of the DDs
+/// (a dominated node is simply never added to a layer). Hence, dominance
+/// pruning does not appear at the granularity of the fringe subproblems
+/// which this enum describes.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum PruningReason {
+    /// The upper bound of the subproblem was no better than the best known
+    /// lower bound when it was popped from the fringe
+    BoundPruned,
+    /// A past compilation recorded a threshold proving that the subproblem
+    /// need not be explored again
+    CachePruned,
+    /// The subproblem was solved exactly; the payload carries the value of
+    /// the best solution rooted in that subproblem (if it admits one)
+    ExactlySolved(Option<isize>),
+}
+
+/// One entry of the proof log: it keeps a trace of one subproblem which the
+/// solver closed, along with the reason why it was eliminated. A complete
+/// proof log constitutes an auditable trace of why each region of the search
+/// space was discarded.
+#[derive(Debug, Clone)]
+pub struct ProofEntry<T> {
+    /// The state of the subproblem which was closed
+    pub state: Arc<T>,
+    /// The length of the longest path between the root and that state
+    pub value: isize,
+    /// The upper bound of the subproblem at the time when it was closed
+    pub ub: isize,
+    /// The depth of the subproblem in the problem (number of decisions taken)
+    pub depth: usize,
+    /// The reason why the subproblem was closed
+    pub reason: PruningReason,
+}
+
+/// One entry of the search trace: it keeps the essential information about
+/// one subproblem which the solver popped off its fringe (see the solver's
+/// `with_search_trace`).
+#[derive(Debug, Clone)]
+pub struct TraceEntry<T> {
+    /// The root state of the subproblem which was popped
+    pub state: Arc<T>,
+    /// The length of the longest path between the root and that state
+    pub value: isize,
+    /// The upper bound of the subproblem at the time when it was popped
+    pub ub: isize,
+    /// The best known lower bound at the time when the subproblem was popped
+    pub lb: isize,
+    /// The depth of the subproblem in the problem (number of decisions taken)
+    pub depth: usize,
+    /// The path to traverse to reach this subproblem from the root of the
+    /// original problem
+    pub path: Vec<Decision>,
+}
+
+/// The trace of a branch-and-bound exploration: it records every subproblem
+/// which the solver popped off its fringe, in the order in which they were
+/// popped. Unlike the DD visualization (which shows one single compiled
+/// diagram), rendering this trace with `as_graphviz` depicts the overall
+/// exploration tree -- which subproblems were dived into, with what bounds,
+/// and how they branch off one another. This is a precious tool to understand
+/// why the solver dives where it does.
+#[derive(Debug, Clone)]
+pub struct SearchTrace<T> {
+    /// The entries of the trace, in exploration (pop) order
+    pub entries: Vec<TraceEntry<T>>,
+}
+// the derived implementation would needlessly require `T: Default`
+impl<T> Default for SearchTrace<T> {
+    fn default() -> Self {
+        Self { entries: vec![] }
+    }
+}
+impl<T> SearchTrace<T> {
+    /// Renders the exploration tree in the 'dot' language. Each node of the
+    /// output tree is one explored subproblem (identified by its exploration
+    /// rank and annotated with its depth, value and bounds) and each edge is
+    /// labeled with the decisions that separate a subproblem from the
+    /// deepest previously explored subproblem it descends from.
+    pub fn as_graphviz(&self) -> String {
+        let mut out = String::new();
+        out.push_str("digraph {\n");
+        for (id, entry) in self.entries.iter().enumerate() {
+            let label = format!("rank {id}\\ndepth {}\\nvalue {}\\nub {}\\nlb {}",
+                entry.depth, entry.value, entry.ub, entry.lb);
+            out.push_str(&format!("\t{id} [shape=\"record\",label=\"{label}\"];\n"));
+            if let Some(parent) = self.parent_of(id) {
+                let label = Self::edge_label(&self.entries[parent], entry);
+                out.push_str(&format!("\t{parent} -> {id} [label=\"{label}\"];\n"));
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+    /// Returns the parent of the given entry in the exploration tree; that
+    /// is, the deepest previously explored subproblem whose path is a prefix
+    /// of the path of the given entry (the root has no parent).
+    fn parent_of(&self, id: usize) -> Option<usize> {
+        let child = &self.entries[id];
+        self.entries.iter().enumerate().take(id)
+            .filter(|(_, p)| p.depth < child.depth
+                && p.path.iter().all(|d| child.path.contains(d)))
+            .max_by_key(|(_, p)| p.depth)
+            .map(|(i, _)| i)
+    }
+    /// Creates the label of the edge connecting a parent to one of its
+    /// children: the decisions on the child's path that do not belong to the
+    /// parent's path, ordered by variable
+    fn edge_label(parent: &TraceEntry<T>, child: &TraceEntry<T>) -> String {
+        let mut diff = child.path.iter()
+            .filter(|d| !parent.path.contains(d))
+            .copied()
+            .collect::<Vec<_>>();
+        diff.sort_unstable_by_key(|d| d.variable.id());
+        diff.iter()
+            .map(|d| format!("x{} = {}", d.variable.id(), d.value))
+            .collect::<Vec<_>>()
+            .join("\\n")
+    }
+}
+
+/// A breakdown of where the solving time went. All the durations are
+/// cumulative over the whole resolution. Note that the dominance checks are
+/// performed *during* the compilation of the DDs: the dominance time is thus
+/// a subset of (and not in addition to) the compilation times.
+///
+/// # Note
+/// The instrumentation producing this data is compiled away when the
+/// `profiling` feature (on by default) is disabled, in which case all the
+/// durations are zero.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TimeBreakdown {
+    /// The total time spent compiling restricted DDs
+    pub restricted: std::time::Duration,
+    /// The total time spent compiling relaxed DDs
+    pub relaxed: std::time::Duration,
+    /// The total time spent pushing to and popping from the solver fringe
+    pub fringe: std::time::Duration,
+    /// The total time spent performing dominance checks
+    pub dominance: std::time::Duration,
+}
+
+/// A summary of the work a solver actually performed during `maximize`. All
+/// the counters are cumulative over the whole resolution, which makes them a
+/// convenient yardstick to compare solver configurations (say, two width
+/// heuristics, or a caching against a non-caching solver) on one instance.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SolverStats {
+    /// The number of restricted DDs which have been compiled
+    pub nb_restricted_dds: usize,
+    /// The number of relaxed DDs which have been compiled
+    pub nb_relaxed_dds: usize,
+    /// The total number of nodes developed over all the DD compilations
+    pub nb_nodes_expanded: usize,
+    /// The number of subproblems which have been popped off the fringe
+    pub nb_fringe_pops: usize,
+    /// The largest size the fringe has ever reached
+    pub peak_fringe_size: usize,
+    /// The number of states discarded by a dominance check during the DD
+    /// compilations
+    pub nb_dominance_prunes: usize,
+    /// The number of subproblems discarded because of a cache (barrier)
+    /// threshold before any DD was compiled for them
+    pub nb_cache_prunes: usize,
+}
+
 /// The outcome of an mdd development
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Completion {
     /// is the given solution exact (proved optimal for the given [sub-]problem)?
     /// or is it an approximation ?
@@ -119,12 +326,149 @@ pub struct Completion {
     /// if present the value of the best solution derived from this mdd
     pub best_value: Option<isize>,
 }
+impl Completion {
+    /// Combines the outcomes of two independent resolutions of the *same*
+    /// problem instance (e.g. two portfolio runs using different widths or
+    /// rankings): the combined outcome retains the best of both objective
+    /// values and is exact as soon as either run proved optimality.
+    ///
+    /// # Example
+    /// ```
+    /// # use ddo::*;
+    /// let timed_out = Completion { is_exact: false, best_value: Some( 95) };
+    /// let proved    = Completion { is_exact: true,  best_value: Some(100) };
+    /// let combined  = timed_out.combine(proved);
+    /// assert!(combined.is_exact);
+    /// assert_eq!(Some(100), combined.best_value);
+    /// ```
+    pub fn combine(self, other: Completion) -> Completion {
+        Completion {
+            is_exact: self.is_exact || other.is_exact,
+            best_value: match (self.best_value, other.best_value) {
+                (Some(a), Some(b)) => Some(a.max(b)),
+                (a, b) => a.or(b),
+            },
+        }
+    }
+}
+
+/// Combines the outcomes of several independent resolutions of the *same*
+/// problem instance -- each given as the completion of one run along with the
+/// best solution that run found -- and returns the best objective value with
+/// its solution. The returned completion is exact iff at least one of the
+/// runs proved optimality (see `Completion::combine`). This is the typical
+/// post-processing of a portfolio: solve one instance with several solver
+/// configurations and keep the best of all the results. An empty portfolio
+/// yields an inexact completion without any value or solution.
+pub fn best_of(runs: impl IntoIterator<Item = (Completion, Option<Vec<Decision>>)>) -> (Completion, Option<Vec<Decision>>) {
+    let mut best = Completion { is_exact: false, best_value: None };
+    let mut solution = None;
+
+    for (completion, run_solution) in runs {
+        // the solution follows the objective value: it is replaced only when
+        // the run strictly improves on the best value known so far
+        let improved = match (best.best_value, completion.best_value) {
+            (None, Some(_))    => true,
+            (Some(a), Some(b)) => b > a,
+            _                  => false,
+        };
+        if improved {
+            solution = run_solution;
+        }
+        best = best.combine(completion);
+    }
+
+    (best, solution)
+}
 
 
 // ############################################################################
 // #### TESTS #################################################################
 // ############################################################################
 
+#[cfg(all(test, feature = "serde"))]
+mod test_serde {
+    use std::sync::Arc;
+
+    use crate::{Decision, SubProblem, Variable};
+
+    #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    struct KnapsackState {
+        depth: usize,
+        capacity: usize,
+    }
+
+    #[test]
+    fn a_subproblem_roundtrips_through_serialization() {
+        let subproblem = SubProblem {
+            state: Arc::new(KnapsackState { depth: 1, capacity: 40 }),
+            value: 60,
+            path: vec![Decision { variable: Variable(0), value: 1 }],
+            ub: 220,
+            depth: 1,
+        };
+
+        let json = serde_json::to_string(&subproblem).unwrap();
+        let back: SubProblem<KnapsackState> = serde_json::from_str(&json).unwrap();
+        assert_eq!(subproblem, back);
+    }
+}
+
+#[cfg(test)]
+mod test_best_of {
+    use crate::{best_of, Completion, Decision, Variable};
+
+    fn solution(value: isize) -> Option<Vec<Decision>> {
+        Some(vec![Decision { variable: Variable(0), value }])
+    }
+
+    #[test]
+    fn it_keeps_the_best_value_along_with_its_solution() {
+        let runs = vec![
+            (Completion { is_exact: false, best_value: Some( 95) }, solution(1)),
+            (Completion { is_exact: false, best_value: Some(100) }, solution(2)),
+            (Completion { is_exact: false, best_value: Some( 90) }, solution(3)),
+        ];
+        let (best, sol) = best_of(runs);
+        assert_eq!(Some(100), best.best_value);
+        assert_eq!(solution(2), sol);
+        assert!(!best.is_exact);
+    }
+
+    #[test]
+    fn it_is_exact_as_soon_as_one_run_proved_optimality() {
+        let runs = vec![
+            (Completion { is_exact: false, best_value: Some(100) }, solution(1)),
+            (Completion { is_exact: true,  best_value: Some(100) }, solution(2)),
+        ];
+        let (best, sol) = best_of(runs);
+        assert!(best.is_exact);
+        assert_eq!(Some(100), best.best_value);
+        // ties do not steal the solution from the run which came first
+        assert_eq!(solution(1), sol);
+    }
+
+    #[test]
+    fn runs_without_any_solution_are_simply_ignored() {
+        let runs = vec![
+            (Completion { is_exact: false, best_value: None }, None),
+            (Completion { is_exact: false, best_value: Some(42) }, solution(1)),
+            (Completion { is_exact: false, best_value: None }, None),
+        ];
+        let (best, sol) = best_of(runs);
+        assert_eq!(Some(42), best.best_value);
+        assert_eq!(solution(1), sol);
+    }
+
+    #[test]
+    fn an_empty_portfolio_yields_nothing() {
+        let (best, sol) = best_of(vec![]);
+        assert!(!best.is_exact);
+        assert_eq!(None, best.best_value);
+        assert_eq!(None, sol);
+    }
+}
+
 #[cfg(test)]
 mod test_var {
     use crate::Variable;
@@ -0,0 +1,228 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! This module provides a cache which keys its thresholds on compact 64-bit
+//! state fingerprints rather than on the full states.
+
+use std::{marker::PhantomData, sync::Arc, hash::Hash};
+
+use dashmap::DashMap;
+
+use crate::{Cache, Problem, Threshold};
+
+/// This cache behaves like `SimpleCache` except that it never stores any
+/// state: each threshold is keyed on a 64-bit fingerprint of the state
+/// instead. For problems with large states (think e.g. of a TSPTW state made
+/// of a bitset and time information), this can dramatically reduce the
+/// memory footprint of a wide search. The fingerprint is the one provided by
+/// `Problem::state_fingerprint` when the problem implements it, and a
+/// structural hash of the state otherwise.
+///
+/// # Warning
+/// Two distinct states mapping to the same fingerprint share their cached
+/// thresholds, which may prune a subproblem that still had to be explored:
+/// the reported value then remains a valid lower bound, but the optimality
+/// claim does not hold anymore. The collision probability is tiny (all 64
+/// bits of the fingerprint are used) yet nonzero: verify any optimality
+/// claim that matters, for instance by re-running the search with the exact
+/// `SimpleCache`.
+#[derive(Debug)]
+pub struct FingerprintCache<T>
+where T: Hash + Eq {
+    thresholds_by_layer: Vec<DashMap<u64, Threshold, fxhash::FxBuildHasher>>,
+    _phantom: PhantomData<T>,
+}
+impl<T> Default for FingerprintCache<T>
+where T: Hash + Eq {
+    fn default() -> Self {
+        Self { thresholds_by_layer: vec![], _phantom: PhantomData }
+    }
+}
+
+impl<T> FingerprintCache<T>
+where T: Hash + Eq {
+    /// The fingerprint used when the problem does not provide one (or when
+    /// the cache is consulted without a problem at hand): a structural hash
+    /// of the full state
+    fn structural_fingerprint(state: &T) -> u64 {
+        fxhash::hash64(state)
+    }
+
+    fn get(&self, fingerprint: u64, depth: usize) -> Option<Threshold> {
+        self.thresholds_by_layer[depth].get(&fingerprint).as_deref().copied()
+    }
+
+    fn update(&self, fingerprint: u64, depth: usize, value: isize, explored: bool) {
+        self.thresholds_by_layer[depth].entry(fingerprint)
+            .and_modify(|e| *e = Threshold { value, explored }.max(*e))
+            .or_insert(Threshold { value, explored });
+    }
+}
+
+impl<T> Cache for FingerprintCache<T>
+where T: Hash + Eq {
+    type State = T;
+
+    fn initialize(&mut self, problem: &dyn Problem<State = Self::State>) {
+        let nb_variables = problem.nb_variables();
+        for _ in 0..=nb_variables {
+            self.thresholds_by_layer.push(Default::default());
+        }
+    }
+
+    fn get_threshold(&self, state: &T, depth: usize) -> Option<Threshold> {
+        self.get(Self::structural_fingerprint(state), depth)
+    }
+
+    fn update_threshold(&self, state: Arc<T>, depth: usize, value: isize, explored: bool) {
+        self.update(Self::structural_fingerprint(state.as_ref()), depth, value, explored)
+    }
+
+    fn get_threshold_with_problem(&self, problem: &dyn Problem<State = Self::State>, state: &T, depth: usize) -> Option<Threshold> {
+        let fingerprint = problem.state_fingerprint(state)
+            .unwrap_or_else(|| Self::structural_fingerprint(state));
+        self.get(fingerprint, depth)
+    }
+
+    fn update_threshold_with_problem(&self, problem: &dyn Problem<State = Self::State>, state: Arc<T>, depth: usize, value: isize, explored: bool) {
+        let fingerprint = problem.state_fingerprint(state.as_ref())
+            .unwrap_or_else(|| Self::structural_fingerprint(state.as_ref()));
+        self.update(fingerprint, depth, value, explored)
+    }
+
+    fn clear_layer(&self, depth: usize) {
+        self.thresholds_by_layer[depth].clear();
+    }
+
+    fn clear(&self) {
+        self.thresholds_by_layer.iter().for_each(|l| l.clear());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::*;
+
+    /// A two-variable problem whose states collapse onto the fingerprint of
+    /// their first field: the second field is deliberately left out so that
+    /// the tests can observe fingerprint sharing between distinct states
+    struct Fingerprinted;
+    impl Problem for Fingerprinted {
+        type State = (u64, u64);
+
+        fn nb_variables(&self) -> usize {
+            2
+        }
+        fn initial_state(&self) -> Self::State {
+            (0, 0)
+        }
+        fn initial_value(&self) -> isize {
+            0
+        }
+        fn transition(&self, state: &Self::State, dec: Decision) -> Self::State {
+            (state.0 + dec.value as u64, state.1 + 1)
+        }
+        fn transition_cost(&self, _: &Self::State, _: &Self::State, dec: Decision) -> isize {
+            dec.value
+        }
+        fn next_variable(&self, depth: usize, _: &mut dyn Iterator<Item = &Self::State>) -> Option<Variable> {
+            if depth < self.nb_variables() {
+                Some(Variable(depth))
+            } else {
+                None
+            }
+        }
+        fn for_each_in_domain(&self, variable: Variable, _: &Self::State, f: &mut dyn DecisionCallback) {
+            f.apply(Decision { variable, value: 0 });
+            f.apply(Decision { variable, value: 1 });
+        }
+        fn state_fingerprint(&self, state: &Self::State) -> Option<u64> {
+            Some(state.0)
+        }
+    }
+
+    #[test]
+    fn the_thresholds_are_keyed_on_the_problem_fingerprint() {
+        let mut cache = FingerprintCache::<(u64, u64)>::default();
+        cache.initialize(&Fingerprinted);
+
+        cache.update_threshold_with_problem(&Fingerprinted, Arc::new((42, 0)), 1, 10, true);
+
+        // a distinct state with the same fingerprint shares the threshold
+        // (this is precisely the documented collision caveat)
+        let threshold = cache.get_threshold_with_problem(&Fingerprinted, &(42, 7), 1);
+        assert_eq!(Some(Threshold { value: 10, explored: true }), threshold);
+        // while a state with another fingerprint does not
+        assert_eq!(None, cache.get_threshold_with_problem(&Fingerprinted, &(43, 0), 1));
+    }
+
+    #[test]
+    fn must_explore_prunes_like_the_exact_cache() {
+        let mut cache = FingerprintCache::<(u64, u64)>::default();
+        cache.initialize(&Fingerprinted);
+        cache.update_threshold_with_problem(&Fingerprinted, Arc::new((42, 0)), 1, 10, true);
+
+        let subproblem = |value: isize| SubProblem {
+            state: Arc::new((42_u64, 0_u64)),
+            value,
+            path: vec![],
+            ub: isize::MAX,
+            depth: 1,
+        };
+        assert!(!cache.must_explore_with_problem(&Fingerprinted, &subproblem(10)));
+        assert!(cache.must_explore_with_problem(&Fingerprinted, &subproblem(11)));
+    }
+
+    #[test]
+    fn without_a_problem_fingerprint_the_structural_hash_is_used() {
+        /// The same problem, minus the fingerprint hook
+        struct NoFingerprint;
+        impl Problem for NoFingerprint {
+            type State = (u64, u64);
+
+            fn nb_variables(&self) -> usize { 2 }
+            fn initial_state(&self) -> Self::State { (0, 0) }
+            fn initial_value(&self) -> isize { 0 }
+            fn transition(&self, state: &Self::State, dec: Decision) -> Self::State {
+                (state.0 + dec.value as u64, state.1 + 1)
+            }
+            fn transition_cost(&self, _: &Self::State, _: &Self::State, dec: Decision) -> isize {
+                dec.value
+            }
+            fn next_variable(&self, depth: usize, _: &mut dyn Iterator<Item = &Self::State>) -> Option<Variable> {
+                if depth < self.nb_variables() { Some(Variable(depth)) } else { None }
+            }
+            fn for_each_in_domain(&self, variable: Variable, _: &Self::State, f: &mut dyn DecisionCallback) {
+                f.apply(Decision { variable, value: 0 });
+            }
+        }
+
+        let mut cache = FingerprintCache::<(u64, u64)>::default();
+        cache.initialize(&NoFingerprint);
+        cache.update_threshold_with_problem(&NoFingerprint, Arc::new((42, 0)), 1, 10, true);
+
+        // the structural hash keys on the full state: no sharing this time
+        assert!(cache.get_threshold_with_problem(&NoFingerprint, &(42, 0), 1).is_some());
+        assert!(cache.get_threshold_with_problem(&NoFingerprint, &(42, 7), 1).is_none());
+        // and the problem-less entry points agree with it
+        assert!(cache.get_threshold(&(42, 0), 1).is_some());
+    }
+}
@@ -25,7 +25,9 @@
 //! implement in order to be able to use our library.
 
 mod empty;
+mod fingerprint;
 mod simple;
 
 pub use empty::*;
+pub use fingerprint::*;
 pub use simple::*;
\ No newline at end of file
@@ -24,26 +24,82 @@
 //! `Relaxation` are defined. These are the two abstractions that one *must*
 //! implement in order to be able to use our library.
 
-use std::{sync::Arc, hash::Hash};
+use std::{sync::Arc, hash::{Hash, Hasher}};
 
 use dashmap::DashMap;
 
-use crate::{Cache, Threshold};
+use crate::{Cache, Problem, Threshold};
 
 /// Simple implementation of Cache using one hashmap for each layer,
 /// each protected with a read-write lock.
+///
+/// The thresholds stored in these maps are learned while compiling relaxed
+/// DDs: when the subtree below an exact node has been completely explored
+/// (or soundly pruned by the bounds), `update_threshold` records the largest
+/// longest-path value with which that state was reached. Whenever the
+/// branch-and-bound loop later pops a subproblem reaching the same state
+/// with a value that does not exceed the recorded threshold, `must_explore`
+/// returns false and the subproblem is pruned without compiling any DD:
+/// the search thereby shares the effort spent on a state across all the
+/// subproblems that reach it.
+///
+/// The maps bucket the states on a 64-bit hash of their identity and resolve
+/// the collisions with an equality check. By default that identity is the one
+/// of the derived `Hash` and `Eq` implementations; through the
+/// `_with_problem` entry points (the ones the solvers use), a problem may
+/// redefine it with `Problem::state_hash` and `Problem::state_eq` -- e.g. to
+/// ignore a bookkeeping field of the state -- without wrapping the state in
+/// a newtype. That custom identity is scoped to this cache: the layer maps
+/// of the DD compilers and the dominance store keep using the state's own
+/// `Hash` / `Eq`.
 #[derive(Debug)]
 pub struct SimpleCache<T>
 where T: Hash + Eq {
-    thresholds_by_layer: Vec<DashMap<Arc<T>, Threshold, fxhash::FxBuildHasher>>,
+    thresholds_by_layer: Vec<DashMap<u64, Bucket<T>, fxhash::FxBuildHasher>>,
 }
-impl <T> Default for SimpleCache<T> 
+
+/// The set of states sharing one hash value, along with their thresholds
+type Bucket<T> = Vec<(Arc<T>, Threshold)>;
+impl <T> Default for SimpleCache<T>
 where T: Hash + Eq {
     fn default() -> Self {
         Self { thresholds_by_layer: vec![] }
     }
 }
 
+impl<T> SimpleCache<T>
+where T: Hash + Eq {
+    /// The hash used when the cache is consulted without a problem at hand
+    /// (or when the problem does not redefine the state identity): the one of
+    /// the derived `Hash` implementation
+    fn structural_hash(state: &T) -> u64 {
+        let mut hasher = fxhash::FxHasher64::default();
+        state.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// The hash defining the identity of a state in the eyes of the problem
+    fn problem_hash(problem: &dyn Problem<State = T>, state: &T) -> u64 {
+        let mut hasher = fxhash::FxHasher64::default();
+        problem.state_hash(state, &mut hasher);
+        hasher.finish()
+    }
+
+    fn get(&self, hash: u64, depth: usize, eq: impl Fn(&T) -> bool) -> Option<Threshold> {
+        self.thresholds_by_layer[depth].get(&hash)
+            .and_then(|bucket| bucket.iter().find(|(s, _)| eq(s)).map(|(_, t)| *t))
+    }
+
+    fn update(&self, hash: u64, state: Arc<T>, depth: usize, value: isize, explored: bool, eq: impl Fn(&T) -> bool) {
+        let mut bucket = self.thresholds_by_layer[depth].entry(hash).or_default();
+        if let Some((_, threshold)) = bucket.iter_mut().find(|(s, _)| eq(s)) {
+            *threshold = Threshold { value, explored }.max(*threshold);
+        } else {
+            bucket.push((state, Threshold { value, explored }));
+        }
+    }
+}
+
 impl<T> Cache for SimpleCache<T>
 where T: Hash + Eq {
     type State = T;
@@ -56,13 +112,21 @@ where T: Hash + Eq {
     }
 
     fn get_threshold(&self, state: &T, depth: usize) -> Option<Threshold> {
-        self.thresholds_by_layer[depth].get(state).as_deref().copied()
+        self.get(Self::structural_hash(state), depth, |s| s == state)
     }
 
     fn update_threshold(&self, state: Arc<T>, depth: usize, value: isize, explored: bool) {
-        self.thresholds_by_layer[depth].entry(state)
-            .and_modify(|e| *e = Threshold { value, explored }.max(*e))
-            .or_insert(Threshold { value, explored });
+        let hash = Self::structural_hash(state.as_ref());
+        self.update(hash, state.clone(), depth, value, explored, |s| s == state.as_ref())
+    }
+
+    fn get_threshold_with_problem(&self, problem: &dyn Problem<State = Self::State>, state: &T, depth: usize) -> Option<Threshold> {
+        self.get(Self::problem_hash(problem, state), depth, |s| problem.state_eq(s, state))
+    }
+
+    fn update_threshold_with_problem(&self, problem: &dyn Problem<State = Self::State>, state: Arc<T>, depth: usize, value: isize, explored: bool) {
+        let hash = Self::problem_hash(problem, state.as_ref());
+        self.update(hash, state.clone(), depth, value, explored, |s| problem.state_eq(s, state.as_ref()))
     }
 
     fn clear_layer(&self, depth: usize) {
@@ -72,4 +136,117 @@ where T: Hash + Eq {
     fn clear(&self) {
         self.thresholds_by_layer.iter().for_each(|l| l.clear());
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use std::{hash::{Hash, Hasher}, sync::Arc};
+
+    use crate::*;
+
+    /// A two-variable problem whose states carry a bookkeeping field (the
+    /// second one) which is deliberately excluded from the state identity
+    /// through `state_hash` / `state_eq`
+    struct Bookkept;
+    impl Problem for Bookkept {
+        type State = (u64, u64);
+
+        fn nb_variables(&self) -> usize {
+            2
+        }
+        fn initial_state(&self) -> Self::State {
+            (0, 0)
+        }
+        fn initial_value(&self) -> isize {
+            0
+        }
+        fn transition(&self, state: &Self::State, dec: Decision) -> Self::State {
+            (state.0 + dec.value as u64, state.1 + 1)
+        }
+        fn transition_cost(&self, _: &Self::State, _: &Self::State, dec: Decision) -> isize {
+            dec.value
+        }
+        fn next_variable(&self, depth: usize, _: &mut dyn Iterator<Item = &Self::State>) -> Option<Variable> {
+            if depth < self.nb_variables() {
+                Some(Variable(depth))
+            } else {
+                None
+            }
+        }
+        fn for_each_in_domain(&self, variable: Variable, _: &Self::State, f: &mut dyn DecisionCallback) {
+            f.apply(Decision { variable, value: 0 });
+            f.apply(Decision { variable, value: 1 });
+        }
+        fn state_hash(&self, state: &Self::State, mut hasher: &mut dyn Hasher)
+        where Self::State: Hash {
+            state.0.hash(&mut hasher)
+        }
+        fn state_eq(&self, a: &Self::State, b: &Self::State) -> bool
+        where Self::State: Eq {
+            a.0 == b.0
+        }
+    }
+
+    #[test]
+    fn the_custom_identity_governs_the_problem_aware_entry_points() {
+        let mut cache = SimpleCache::<(u64, u64)>::default();
+        cache.initialize(&Bookkept);
+
+        cache.update_threshold_with_problem(&Bookkept, Arc::new((42, 0)), 1, 10, true);
+
+        // two states differing only in their bookkeeping field are one and
+        // the same in the eyes of the problem
+        let threshold = cache.get_threshold_with_problem(&Bookkept, &(42, 7), 1);
+        assert_eq!(Some(Threshold { value: 10, explored: true }), threshold);
+        // while a state with another identity is not
+        assert_eq!(None, cache.get_threshold_with_problem(&Bookkept, &(43, 0), 1));
+    }
+
+    #[test]
+    fn the_plain_entry_points_keep_the_derived_identity() {
+        let mut cache = SimpleCache::<(u64, u64)>::default();
+        cache.initialize(&Bookkept);
+
+        cache.update_threshold(Arc::new((42, 0)), 1, 10, true);
+
+        assert_eq!(
+            Some(Threshold { value: 10, explored: true }),
+            cache.get_threshold(&(42, 0), 1));
+        assert_eq!(None, cache.get_threshold(&(42, 7), 1));
+    }
+
+    #[test]
+    fn without_an_override_both_entry_points_agree() {
+        /// The same problem, minus the custom identity
+        struct Derived;
+        impl Problem for Derived {
+            type State = (u64, u64);
+
+            fn nb_variables(&self) -> usize { 2 }
+            fn initial_state(&self) -> Self::State { (0, 0) }
+            fn initial_value(&self) -> isize { 0 }
+            fn transition(&self, state: &Self::State, dec: Decision) -> Self::State {
+                (state.0 + dec.value as u64, state.1 + 1)
+            }
+            fn transition_cost(&self, _: &Self::State, _: &Self::State, dec: Decision) -> isize {
+                dec.value
+            }
+            fn next_variable(&self, depth: usize, _: &mut dyn Iterator<Item = &Self::State>) -> Option<Variable> {
+                if depth < self.nb_variables() { Some(Variable(depth)) } else { None }
+            }
+            fn for_each_in_domain(&self, variable: Variable, _: &Self::State, f: &mut dyn DecisionCallback) {
+                f.apply(Decision { variable, value: 0 });
+            }
+        }
+
+        let mut cache = SimpleCache::<(u64, u64)>::default();
+        cache.initialize(&Derived);
+        cache.update_threshold_with_problem(&Derived, Arc::new((42, 0)), 1, 10, true);
+
+        // the default identity is the derived one: the thresholds recorded
+        // through the problem-aware entry points are visible to the plain
+        // ones (and conversely)
+        assert!(cache.get_threshold(&(42, 0), 1).is_some());
+        assert!(cache.get_threshold(&(42, 7), 1).is_none());
+        assert!(cache.get_threshold_with_problem(&Derived, &(42, 0), 1).is_some());
+    }
+}
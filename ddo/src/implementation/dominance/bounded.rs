@@ -0,0 +1,319 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use std::{hash::Hash, cmp::Ordering, sync::{Arc, atomic::{AtomicUsize, Ordering as MemOrdering}}, fmt::Debug};
+use dashmap::{DashMap, mapref::entry::Entry};
+
+use crate::{Dominance, DominanceChecker, DominanceCmpResult, DominanceCheckResult};
+
+/// Implementation of a dominance checker that retains at most a fixed number
+/// of distinct keys and evicts the least recently used key when that capacity
+/// is exceeded. This keeps the memory consumed by the dominance front bounded
+/// on long runs, at the price of a less complete front: evicting a key only
+/// forgoes the prunes that its entries could have warranted, it can never
+/// cause a non-dominated state to be pruned.
+
+#[derive(Debug)]
+struct DominanceEntry<T> {
+    state: Arc<T>,
+    value: isize,
+}
+
+/// The non-dominated states associated to one key, along with the logical
+/// instant at which the key was last used (which is what the LRU eviction
+/// policy is based upon)
+#[derive(Debug)]
+struct DominanceFront<T> {
+    last_use: usize,
+    entries: Vec<DominanceEntry<T>>,
+}
+
+type DominanceMap<K, S> = DashMap<K, DominanceFront<S>, fxhash::FxBuildHasher>;
+
+pub struct BoundedDominanceChecker<D>
+where
+    D: Dominance,
+    D::Key: Eq + PartialEq + Hash,
+{
+    dominance: D,
+    data: Vec<DominanceMap<D::Key, D::State>>,
+    /// The maximum number of distinct keys which may be retained at any time
+    capacity: usize,
+    /// The number of keys currently retained (approximate under concurrency:
+    /// the capacity may transiently be exceeded by a few keys)
+    nb_keys: AtomicUsize,
+    /// A logical clock used to timestamp the use of the keys
+    clock: AtomicUsize,
+    /// The number of checks that concluded to a dominance (and hence a prune)
+    hits: AtomicUsize,
+    /// The number of checks that did not conclude to a dominance
+    misses: AtomicUsize,
+}
+
+impl<D> Debug for BoundedDominanceChecker<D>
+where
+    D: Dominance,
+    D::Key: Eq + PartialEq + Hash,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BoundedDominanceChecker").finish_non_exhaustive()
+    }
+}
+
+impl<D> BoundedDominanceChecker<D>
+where
+    D: Dominance,
+    D::Key: Eq + PartialEq + Hash,
+{
+    pub fn new(dominance: D, nb_variables: usize, capacity: usize) -> Self {
+        let mut data = vec![];
+        for _ in 0..=nb_variables {
+            data.push(Default::default());
+        }
+        Self {
+            dominance,
+            data,
+            capacity,
+            nb_keys: AtomicUsize::new(0),
+            clock: AtomicUsize::new(0),
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the number of checks that concluded to a dominance. Comparing
+    /// it to `nb_misses` tells how much pruning the front actually buys,
+    /// which helps tuning the capacity.
+    pub fn nb_hits(&self) -> usize {
+        self.hits.load(MemOrdering::Relaxed)
+    }
+
+    /// Returns the number of checks that did not conclude to a dominance
+    pub fn nb_misses(&self) -> usize {
+        self.misses.load(MemOrdering::Relaxed)
+    }
+
+    /// Evicts the least recently used key of the checker. The scan is linear
+    /// in the number of retained keys, but it only ever occurs when a brand
+    /// new key is inserted in an already full checker.
+    fn evict_lru(&self) {
+        let mut oldest: Option<(usize, usize)> = None; // (depth, last_use)
+        for (depth, map) in self.data.iter().enumerate() {
+            for front in map.iter() {
+                let last_use = front.value().last_use;
+                if oldest.is_none_or(|(_, use_)| last_use < use_) {
+                    oldest = Some((depth, last_use));
+                }
+            }
+        }
+        if let Some((depth, last_use)) = oldest {
+            let mut evicted = 0;
+            self.data[depth].retain(|_, front| {
+                if front.last_use == last_use {
+                    evicted += 1;
+                    false
+                } else {
+                    true
+                }
+            });
+            self.nb_keys.fetch_sub(evicted, MemOrdering::Relaxed);
+        }
+    }
+}
+
+impl<D> DominanceChecker for BoundedDominanceChecker<D>
+where
+    D: Dominance,
+    D::Key: Eq + PartialEq + Hash,
+{
+    type State = D::State;
+
+    fn clear_layer(&self, depth: usize) {
+        self.nb_keys.fetch_sub(self.data[depth].len(), MemOrdering::Relaxed);
+        self.data[depth].clear();
+    }
+
+    fn clear(&self) {
+        self.data.iter().for_each(|l| l.clear());
+        self.nb_keys.store(0, MemOrdering::Relaxed);
+        self.hits.store(0, MemOrdering::Relaxed);
+        self.misses.store(0, MemOrdering::Relaxed);
+    }
+
+    fn is_dominated_or_insert(&self, state: Arc<Self::State>, depth: usize, value: isize) -> DominanceCheckResult {
+        let now = self.clock.fetch_add(1, MemOrdering::Relaxed);
+        let result = if let Some(key) = self.dominance.get_key(state.clone()) {
+            let mut new_key = false;
+            let result = match self.data[depth].entry(key) {
+                Entry::Occupied(mut e) => {
+                    let front = e.get_mut();
+                    front.last_use = now;
+                    let mut dominated = false;
+                    let mut threshold = Some(isize::MAX);
+                    front.entries.retain(|other| {
+                        match self.dominance.partial_cmp(state.as_ref(), value, other.state.as_ref(), other.value) {
+                            Some(cmp) => match cmp {
+                                DominanceCmpResult { ordering: Ordering::Less, only_val_diff} => {
+                                    dominated = true;
+                                    if self.dominance.use_value() {
+                                        if only_val_diff {
+                                            threshold = threshold.min(Some(other.value.saturating_sub(1)));
+                                        } else {
+                                            threshold = threshold.min(Some(other.value));
+                                        }
+                                    }
+                                    true
+                                },
+                                DominanceCmpResult { ordering: Ordering::Equal, only_val_diff: _ } => false,
+                                DominanceCmpResult { ordering: Ordering::Greater, only_val_diff: _ } => false,
+                            },
+                            None => true,
+                        }
+                    });
+                    if !dominated {
+                        threshold = None;
+                        front.entries.push(DominanceEntry { state, value });
+                    }
+                    DominanceCheckResult { dominated, threshold }
+                },
+                Entry::Vacant(e) => {
+                    e.insert(DominanceFront {
+                        last_use: now,
+                        entries: vec![DominanceEntry { state, value }],
+                    });
+                    new_key = true;
+                    DominanceCheckResult { dominated: false, threshold: None }
+                },
+            };
+            if new_key && self.nb_keys.fetch_add(1, MemOrdering::Relaxed) + 1 > self.capacity {
+                self.evict_lru();
+            }
+            result
+        } else {
+            DominanceCheckResult { dominated: false, threshold: None }
+        };
+        if result.dominated {
+            self.hits.fetch_add(1, MemOrdering::Relaxed);
+        } else {
+            self.misses.fetch_add(1, MemOrdering::Relaxed);
+        }
+        result
+    }
+
+    fn cmp(&self, a: &Self::State, val_a: isize, b: &Self::State, val_b: isize) -> Ordering {
+        self.dominance.cmp(a, val_a, b, val_b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{Dominance, BoundedDominanceChecker, DominanceChecker, DominanceCheckResult};
+
+    #[test]
+    fn dominated_when_keys_are_equal() {
+        let dominance = BoundedDominanceChecker::new(DummyDominance, 0, 10);
+
+        assert_eq!(DominanceCheckResult{ dominated: false, threshold: None }, dominance.is_dominated_or_insert(Arc::new(vec![0, 3]), 0, 0));
+
+        let res = dominance.is_dominated_or_insert(Arc::new(vec![0, 2]), 0, 2);
+        assert!(res.dominated);
+
+        let res = dominance.is_dominated_or_insert(Arc::new(vec![0, 1]), 0, 1);
+        assert!(res.dominated);
+    }
+
+    #[test]
+    fn the_least_recently_used_key_is_evicted_when_over_capacity() {
+        let dominance = BoundedDominanceChecker::new(DummyDominance, 0, 2);
+
+        dominance.is_dominated_or_insert(Arc::new(vec![0, 3]), 0, 0);
+        dominance.is_dominated_or_insert(Arc::new(vec![1, 3]), 0, 0);
+        // this check touches the key 0, making the key 1 the lru one
+        dominance.is_dominated_or_insert(Arc::new(vec![0, 2]), 0, 0);
+        // this brand new key overflows the capacity: the key 1 must go
+        dominance.is_dominated_or_insert(Arc::new(vec![2, 3]), 0, 0);
+
+        assert!(dominance.data[0].get(&0).is_some());
+        assert!(dominance.data[0].get(&1).is_none());
+        assert!(dominance.data[0].get(&2).is_some());
+    }
+
+    #[test]
+    fn eviction_only_forgoes_prunes_it_never_causes_one() {
+        let dominance = BoundedDominanceChecker::new(DummyDominance, 0, 2);
+
+        dominance.is_dominated_or_insert(Arc::new(vec![0, 3]), 0, 0);
+        dominance.is_dominated_or_insert(Arc::new(vec![1, 3]), 0, 0);
+        dominance.is_dominated_or_insert(Arc::new(vec![0, 2]), 0, 0);
+        dominance.is_dominated_or_insert(Arc::new(vec![2, 3]), 0, 0);
+
+        // this state would have been pruned against the evicted front of
+        // key 1: it is merely re-admitted (and re-inserted) instead
+        let res = dominance.is_dominated_or_insert(Arc::new(vec![1, 2]), 0, 0);
+        assert!(!res.dominated);
+        assert!(dominance.data[0].get(&1).is_some());
+    }
+
+    #[test]
+    fn the_checks_are_accounted_as_hits_and_misses() {
+        let dominance = BoundedDominanceChecker::new(DummyDominance, 0, 10);
+
+        dominance.is_dominated_or_insert(Arc::new(vec![0, 3]), 0, 0);
+        dominance.is_dominated_or_insert(Arc::new(vec![0, 2]), 0, 0);
+        dominance.is_dominated_or_insert(Arc::new(vec![0, 4]), 0, 0);
+
+        assert_eq!(1, dominance.nb_hits());
+        assert_eq!(2, dominance.nb_misses());
+    }
+
+    #[test]
+    fn clearing_a_layer_releases_its_keys() {
+        let dominance = BoundedDominanceChecker::new(DummyDominance, 1, 2);
+
+        dominance.is_dominated_or_insert(Arc::new(vec![0, 3]), 0, 0);
+        dominance.is_dominated_or_insert(Arc::new(vec![1, 3]), 1, 0);
+        dominance.clear_layer(0);
+
+        // the capacity freed by the cleared layer is available again: no
+        // eviction is triggered by this brand new key
+        dominance.is_dominated_or_insert(Arc::new(vec![2, 3]), 1, 0);
+        assert!(dominance.data[1].get(&1).is_some());
+        assert!(dominance.data[1].get(&2).is_some());
+    }
+
+    struct DummyDominance;
+    impl Dominance for DummyDominance {
+        type State = Vec<isize>;
+        type Key = isize;
+
+        fn get_key(&self, state: Arc<Self::State>) -> Option<Self::Key> {
+            Some(state[0])
+        }
+
+        fn nb_dimensions(&self, state: &Self::State) -> usize {
+            state.len()
+        }
+
+        fn get_coordinate(&self, state: &Self::State, i: usize) -> isize {
+            state[i]
+        }
+    }
+}
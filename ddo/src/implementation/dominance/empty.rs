@@ -37,6 +37,8 @@ impl<T> DominanceChecker for EmptyDominanceChecker<T> {
 
     fn clear_layer(&self, _: usize) {}
 
+    fn clear(&self) {}
+
     fn is_dominated_or_insert(&self, _: Arc<Self::State>, _: usize, _: isize) -> DominanceCheckResult {
         DominanceCheckResult { dominated: false, threshold: None }
     }
@@ -0,0 +1,151 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use std::{cmp::Ordering, marker::PhantomData, sync::Arc};
+
+use crate::{Dominance, DominanceCmpResult};
+
+/// An adapter which expresses a dominance relation with two plain closures
+/// instead of the key-plus-coordinates scheme of the `Dominance` trait. This
+/// is the tool to reach for when the dominance relation of your problem is a
+/// genuine partial order which cannot be phrased as a component-wise
+/// comparison of integer coordinates.
+///
+/// The `key_fn` closure partitions the state space exactly like `get_key`
+/// does: only states mapped onto the same key are ever compared. Within a key
+/// class, `dominates_fn(a, b)` must tell whether state `a` dominates state
+/// `b`; the relation is expected to be reflexive and transitive, and the
+/// `SimpleDominanceChecker` consults it (through an overridden `partial_cmp`)
+/// in place of the coordinate comparison.
+///
+/// Note that since this adapter defines no coordinates, the `cmp` ordering it
+/// induces on the fringe is trivial (all states compare equal).
+pub struct FnDominance<State, Key, KeyFn, DomFn>
+where
+    KeyFn: Fn(&State) -> Option<Key>,
+    DomFn: Fn(&State, &State) -> bool,
+{
+    /// Maps every state onto the key identifying its class of comparable states
+    key_fn: KeyFn,
+    /// Tells whether the first state dominates the second one
+    dominates_fn: DomFn,
+    _phantom: PhantomData<fn(&State) -> Option<Key>>,
+}
+
+impl<State, Key, KeyFn, DomFn> FnDominance<State, Key, KeyFn, DomFn>
+where
+    KeyFn: Fn(&State) -> Option<Key>,
+    DomFn: Fn(&State, &State) -> bool,
+{
+    /// Creates an adapter from the given key and dominance closures
+    pub fn new(key_fn: KeyFn, dominates_fn: DomFn) -> Self {
+        Self { key_fn, dominates_fn, _phantom: PhantomData }
+    }
+}
+
+impl<State, Key, KeyFn, DomFn> Dominance for FnDominance<State, Key, KeyFn, DomFn>
+where
+    KeyFn: Fn(&State) -> Option<Key>,
+    DomFn: Fn(&State, &State) -> bool,
+{
+    type State = State;
+    type Key = Key;
+
+    fn get_key(&self, state: Arc<Self::State>) -> Option<Self::Key> {
+        (self.key_fn)(state.as_ref())
+    }
+
+    /// This adapter defines no coordinates: the comparison is entirely
+    /// performed by the overridden `partial_cmp` below
+    fn nb_dimensions(&self, _state: &Self::State) -> usize {
+        0
+    }
+
+    fn get_coordinate(&self, _state: &Self::State, _i: usize) -> isize {
+        0
+    }
+
+    fn partial_cmp(&self, a: &Self::State, _val_a: isize, b: &Self::State, _val_b: isize) -> Option<DominanceCmpResult> {
+        let a_dominates_b = (self.dominates_fn)(a, b);
+        let b_dominates_a = (self.dominates_fn)(b, a);
+
+        let ordering = match (a_dominates_b, b_dominates_a) {
+            (true, true)   => Ordering::Equal,
+            (true, false)  => Ordering::Greater,
+            (false, true)  => Ordering::Less,
+            (false, false) => return None,
+        };
+        Some(DominanceCmpResult { ordering, only_val_diff: false })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{DominanceChecker, FnDominance, SimpleDominanceChecker};
+
+    /// The states are bitmasks and a state dominates another iff its mask is
+    /// a superset of the other's: a genuine partial order which cannot be
+    /// phrased as a fixed component-wise coordinate comparison
+    fn superset_dominance() -> SimpleDominanceChecker<FnDominance<usize, usize, impl Fn(&usize) -> Option<usize>, impl Fn(&usize, &usize) -> bool>> {
+        let dominance = FnDominance::new(
+            |_: &usize| Some(0),
+            |a: &usize, b: &usize| a & b == *b,
+        );
+        SimpleDominanceChecker::new(dominance, 0)
+    }
+
+    #[test]
+    fn the_dominates_closure_drives_the_comparison() {
+        let dominance = superset_dominance();
+
+        assert!(!dominance.is_dominated_or_insert(Arc::new(0b101), 0, 0).dominated);
+        // a subset of a stored mask is dominated
+        assert!(dominance.is_dominated_or_insert(Arc::new(0b001), 0, 0).dominated);
+        // an incomparable mask is not
+        assert!(!dominance.is_dominated_or_insert(Arc::new(0b010), 0, 0).dominated);
+    }
+
+    #[test]
+    fn a_dominant_state_evicts_the_states_it_dominates() {
+        let dominance = superset_dominance();
+
+        assert!(!dominance.is_dominated_or_insert(Arc::new(0b101), 0, 0).dominated);
+        assert!(!dominance.is_dominated_or_insert(Arc::new(0b010), 0, 0).dominated);
+        // this mask is a superset of both stored ones: it takes their place
+        assert!(!dominance.is_dominated_or_insert(Arc::new(0b111), 0, 0).dominated);
+        // and it now dominates each of them
+        assert!(dominance.is_dominated_or_insert(Arc::new(0b101), 0, 0).dominated);
+        assert!(dominance.is_dominated_or_insert(Arc::new(0b010), 0, 0).dominated);
+    }
+
+    #[test]
+    fn states_with_different_keys_are_never_compared() {
+        let dominance = FnDominance::new(
+            |state: &usize| Some(state % 2),
+            |a: &usize, b: &usize| a & b == *b,
+        );
+        let dominance = SimpleDominanceChecker::new(dominance, 0);
+
+        assert!(!dominance.is_dominated_or_insert(Arc::new(0b111), 0, 0).dominated);
+        // a subset of the stored mask, but with a different parity key
+        assert!(!dominance.is_dominated_or_insert(Arc::new(0b110), 0, 0).dominated);
+    }
+}
@@ -17,8 +17,12 @@
 // IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
 // CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
+mod bounded;
 mod empty;
+mod function;
 mod simple;
 
+pub use bounded::*;
 pub use empty::*;
+pub use function::*;
 pub use simple::*;
\ No newline at end of file
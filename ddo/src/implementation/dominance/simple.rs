@@ -17,13 +17,23 @@
 // IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
 // CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
-use std::{hash::Hash, cmp::Ordering, sync::Arc, fmt::Debug};
+use std::{hash::Hash, cmp::Ordering, sync::{Arc, atomic::{AtomicUsize, Ordering as MemOrdering}}, fmt::Debug};
 use dashmap::{DashMap, mapref::entry::Entry};
 
 use crate::{Dominance, DominanceChecker, DominanceCmpResult, DominanceCheckResult};
 
 /// Simple implementation of a dominance checker that stores a vector of non-dominated
 /// states for each distinct key.
+///
+/// # Concurrency and locking
+/// This checker is designed to be shared by reference across all the threads
+/// of the parallel solver. It keeps one concurrent hash map per layer
+/// (depth), and each of these maps is internally sharded: a dominance check
+/// only write-locks the single shard holding its key, so two threads only
+/// ever contend when they simultaneously probe keys hashed to the same shard
+/// of the same layer. The lock is held for the duration of one check (the
+/// scan of the non-dominated front of one key) and never across checks.
+/// `ConcurrentDominanceChecker` names this very type, for discoverability.
 
 #[derive(Debug)]
 struct DominanceEntry<T> {
@@ -33,7 +43,23 @@ struct DominanceEntry<T> {
 
 type DominanceMap<K, S> = DashMap<K, Vec<DominanceEntry<S>>, fxhash::FxBuildHasher>;
 
-#[derive(Debug)]
+/// A user-provided sanity check reporting a warning message whenever two
+/// states mapped to the same key should in fact never have been compared.
+type KeyCheck<S> = Box<dyn Fn(&S, &S) -> Option<String> + Send + Sync>;
+
+/// The effectiveness counters of one coordinate dimension of a `Dominance`
+/// relation (see `SimpleDominanceChecker::dimension_stats`)
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DimensionStat {
+    /// The number of comparisons whose strict direction was settled by this
+    /// dimension (it was the first one where the two states differed)
+    pub decided: usize,
+    /// The number of comparisons which this dimension broke: it disagreed
+    /// with the direction established by the previous dimensions, making the
+    /// two states incomparable
+    pub blocked: usize,
+}
+
 pub struct SimpleDominanceChecker<D>
 where
     D: Dominance,
@@ -41,9 +67,29 @@ where
 {
     dominance: D,
     data: Vec<DominanceMap<D::Key, D::State>>,
+    key_check: Option<KeyCheck<D::State>>,
+    /// When set, the front is frozen (made read-only) once that many checks
+    /// have been performed
+    freeze_after: Option<usize>,
+    /// The number of checks performed so far (only maintained when a freeze
+    /// threshold has been configured)
+    nb_checks: AtomicUsize,
+    /// When set, every comparison is attributed to the coordinate dimension
+    /// which decided it (see `dimension_stats`)
+    dimension_stats: Option<DashMap<usize, DimensionStat, fxhash::FxBuildHasher>>,
+}
+
+impl<D> Debug for SimpleDominanceChecker<D>
+where
+    D: Dominance,
+    D::Key: Eq + PartialEq + Hash,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SimpleDominanceChecker").finish_non_exhaustive()
+    }
 }
 
-impl<D> SimpleDominanceChecker<D> 
+impl<D> SimpleDominanceChecker<D>
 where
     D: Dominance,
     D::Key: Eq + PartialEq + Hash,
@@ -53,7 +99,116 @@ where
         for _ in 0..=nb_variables {
             data.push(Default::default());
         }
-        Self { dominance, data }
+        Self { dominance, data, key_check: None, freeze_after: None, nb_checks: AtomicUsize::new(0), dimension_stats: None }
+    }
+
+    /// Freezes the dominance front after the given number of checks have been
+    /// performed. Past that warm-up phase, the front keeps being used to prune
+    /// dominated states (reads) but it is no longer updated (no writes): new
+    /// non-dominated states are not inserted and dominated entries are not
+    /// evicted anymore. This bounds the memory consumed by the checker and
+    /// makes the cost of a check predictable on long runs. Since freezing only
+    /// forgoes potential future prunes, it never compromises the correctness
+    /// of the search.
+    pub fn with_dominance_freeze_after(mut self, nb_checks: usize) -> Self {
+        self.freeze_after = Some(nb_checks);
+        self
+    }
+
+    /// Registers a debug-mode sanity check validating that the key provided by
+    /// `get_key` properly partitions the state space. A common `Dominance` bug
+    /// is to use a key that lets states at different "phases" of the problem
+    /// be compared when they should not be (e.g. comparing knapsack states at
+    /// different depths -- which is why the knapsack example keys by depth).
+    ///
+    /// The given `comparable` predicate must return true iff the two states
+    /// are genuinely comparable per the user's intent. In debug builds, every
+    /// pair of states that gets compared by this checker is validated against
+    /// the predicate and mismatches are reported on stderr along with the
+    /// offending states. In release builds the check is disabled entirely.
+    pub fn with_dominance_key_check<F>(mut self, comparable: F) -> Self
+    where
+        D::State: Debug,
+        F: Fn(&D::State, &D::State) -> bool + Send + Sync + 'static,
+    {
+        self.key_check = Some(Box::new(move |a, b| {
+            if comparable(a, b) {
+                None
+            } else {
+                Some(format!("dominance key check: states {a:?} and {b:?} are mapped to the same key but should not be compared"))
+            }
+        }));
+        self
+    }
+
+    /// Enables the per-dimension effectiveness instrumentation: every pair of
+    /// states compared by this checker gets attributed to the coordinate
+    /// dimension which decided the outcome of the comparison. The counters
+    /// can then be retrieved with `dimension_stats`; a dimension which never
+    /// decides nor blocks anything is a candidate for removal from
+    /// `nb_dimensions`/`get_coordinate` (shorter coordinate vectors make the
+    /// checks cheaper).
+    ///
+    /// # Note
+    /// The attribution mirrors the coordinate-wise comparison of the default
+    /// `Dominance::partial_cmp`; when that method is overridden with an
+    /// altogether different logic, the counters keep describing the default
+    /// coordinate-wise reading of the comparison.
+    pub fn with_dimension_stats(mut self) -> Self {
+        self.dimension_stats = Some(Default::default());
+        self
+    }
+
+    /// Returns the effectiveness counters of each coordinate dimension, in
+    /// dimension order (see `with_dimension_stats`, without which the
+    /// returned vector is empty). A dimension with a high `decided` count is
+    /// the one doing the actual pruning work; a dimension with a high
+    /// `blocked` count is the one preventing states from being compared;
+    /// a dimension with both counts at zero never influenced any comparison.
+    pub fn dimension_stats(&self) -> Vec<DimensionStat> {
+        let mut out = vec![];
+        if let Some(stats) = self.dimension_stats.as_ref() {
+            let nb_dims = stats.iter().map(|e| *e.key() + 1).max().unwrap_or(0);
+            out.resize(nb_dims, DimensionStat::default());
+            for entry in stats.iter() {
+                out[*entry.key()] = *entry.value();
+            }
+        }
+        out
+    }
+
+    /// Attributes one comparison of `a` against `b` to the dimension which
+    /// decided it: the first dimension where the states strictly differ sets
+    /// the direction of the comparison (`decided`), and the first dimension
+    /// disagreeing with that direction makes the states incomparable
+    /// (`blocked`). Comparisons where all the coordinates are equal (the
+    /// outcome then rests on the value alone) are attributed to no dimension.
+    fn record_comparison(&self, a: &D::State, b: &D::State) {
+        if let Some(stats) = self.dimension_stats.as_ref() {
+            let mut ordering = Ordering::Equal;
+            let mut decider = None;
+            for i in 0..self.dominance.nb_dimensions(a) {
+                match (ordering, self.dominance.get_coordinate(a, i).cmp(&self.dominance.get_coordinate(b, i))) {
+                    (Ordering::Less, Ordering::Greater) |
+                    (Ordering::Greater, Ordering::Less) => {
+                        stats.entry(i).or_default().blocked += 1;
+                        return;
+                    },
+                    (Ordering::Equal, Ordering::Greater) => {
+                        ordering = Ordering::Greater;
+                        decider = Some(i);
+                    },
+                    (Ordering::Equal, Ordering::Less) => {
+                        ordering = Ordering::Less;
+                        decider = Some(i);
+                    },
+                    (_, _) => (),
+                }
+            }
+            if let Some(i) = decider {
+                stats.entry(i).or_default().decided += 1;
+            }
+        }
     }
 }
 
@@ -68,13 +223,56 @@ where
         self.data[depth].clear();
     }
 
+    fn clear(&self) {
+        self.data.iter().for_each(|l| l.clear());
+        self.nb_checks.store(0, MemOrdering::Relaxed);
+        if let Some(stats) = self.dimension_stats.as_ref() {
+            stats.clear();
+        }
+    }
+
     fn is_dominated_or_insert(&self, state: Arc<Self::State>, depth: usize, value: isize) -> DominanceCheckResult {
+        let frozen = self.freeze_after
+            .is_some_and(|limit| self.nb_checks.fetch_add(1, MemOrdering::Relaxed) >= limit);
         if let Some(key) = self.dominance.get_key(state.clone()) {
+            if frozen {
+                // the warm-up phase is over: keep pruning against the frozen
+                // front but do not update it anymore
+                let mut dominated = false;
+                let mut threshold = Some(isize::MAX);
+                if let Some(entries) = self.data[depth].get(&key) {
+                    for other in entries.iter() {
+                        self.record_comparison(state.as_ref(), other.state.as_ref());
+                        if let Some(DominanceCmpResult { ordering: Ordering::Less, only_val_diff }) = self.dominance.partial_cmp(state.as_ref(), value, other.state.as_ref(), other.value) {
+                            dominated = true;
+                            if self.dominance.use_value() {
+                                if only_val_diff {
+                                    threshold = threshold.min(Some(other.value.saturating_sub(1)));
+                                } else {
+                                    threshold = threshold.min(Some(other.value));
+                                }
+                            }
+                        }
+                    }
+                }
+                if !dominated {
+                    threshold = None;
+                }
+                return DominanceCheckResult { dominated, threshold };
+            }
             match self.data[depth].entry(key) {
                 Entry::Occupied(mut e) => {
                     let mut dominated = false;
                     let mut threshold = Some(isize::MAX);
                     e.get_mut().retain(|other| {
+                        if cfg!(debug_assertions) {
+                            if let Some(check) = self.key_check.as_ref() {
+                                if let Some(warning) = check(state.as_ref(), other.state.as_ref()) {
+                                    eprintln!("[ddo] {warning}");
+                                }
+                            }
+                        }
+                        self.record_comparison(state.as_ref(), other.state.as_ref());
                         match self.dominance.partial_cmp(state.as_ref(), value, other.state.as_ref(), other.value) {
                             Some(cmp) => match cmp {
                                 DominanceCmpResult { ordering: Ordering::Less, only_val_diff} => {
@@ -115,11 +313,17 @@ where
     }
 }
 
+/// A dominance store meant to be shared across many threads. This is just
+/// another name for `SimpleDominanceChecker`, which is already a sharded
+/// concurrent store: see the "Concurrency and locking" section of its
+/// documentation for the contention characteristics.
+pub type ConcurrentDominanceChecker<D> = SimpleDominanceChecker<D>;
+
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
 
-    use crate::{Dominance, SimpleDominanceChecker, DominanceChecker, DominanceCheckResult};
+    use crate::{Dominance, DimensionStat, ConcurrentDominanceChecker, SimpleDominanceChecker, DominanceChecker, DominanceCheckResult};
 
     #[test]
     fn not_dominated_when_keys_are_different() {
@@ -223,6 +427,107 @@ mod tests {
         assert_eq!(1, dominance.data[0].get(&0).unwrap().len());
     }
 
+    #[test]
+    fn key_check_does_not_alter_the_outcome_of_the_checks() {
+        let dominance = SimpleDominanceChecker::new(DummyDominance, 0)
+            .with_dominance_key_check(|a: &Vec<isize>, b: &Vec<isize>| a[0] == b[0]);
+
+        assert_eq!(DominanceCheckResult{ dominated: false, threshold: None }, dominance.is_dominated_or_insert(Arc::new(vec![0, 3]), 0, 0));
+
+        let res = dominance.is_dominated_or_insert(Arc::new(vec![0, 2]), 0, 2);
+        assert!(res.dominated);
+
+        let res = dominance.is_dominated_or_insert(Arc::new(vec![0, 4]), 0, 4);
+        assert!(!res.dominated);
+    }
+
+    #[test]
+    fn the_front_is_no_longer_updated_once_frozen() {
+        let dominance = SimpleDominanceChecker::new(DummyDominanceWithValue, 0)
+            .with_dominance_freeze_after(1);
+
+        // the warm-up phase admits one single check: this entry makes it in
+        assert_eq!(DominanceCheckResult{ dominated: false, threshold: None }, dominance.is_dominated_or_insert(Arc::new(vec![0, 0]), 0, 3));
+        assert_eq!(1, dominance.data[0].get(&0).unwrap().len());
+
+        // this non-dominated state would have been inserted before the freeze
+        assert_eq!(DominanceCheckResult{ dominated: false, threshold: None }, dominance.is_dominated_or_insert(Arc::new(vec![0, 1]), 0, 1));
+        assert_eq!(1, dominance.data[0].get(&0).unwrap().len());
+
+        // this dominant state would have evicted the entry before the freeze
+        assert_eq!(DominanceCheckResult{ dominated: false, threshold: None }, dominance.is_dominated_or_insert(Arc::new(vec![0, 1]), 0, 5));
+        assert_eq!(1, dominance.data[0].get(&0).unwrap().len());
+    }
+
+    #[test]
+    fn the_frozen_front_keeps_pruning_dominated_states() {
+        let dominance = SimpleDominanceChecker::new(DummyDominanceWithValue, 0)
+            .with_dominance_freeze_after(1);
+
+        assert_eq!(DominanceCheckResult{ dominated: false, threshold: None }, dominance.is_dominated_or_insert(Arc::new(vec![0, 0]), 0, 3));
+
+        assert_eq!(DominanceCheckResult{ dominated: true, threshold: Some(2) }, dominance.is_dominated_or_insert(Arc::new(vec![0, 0]), 0, 2));
+        assert_eq!(DominanceCheckResult{ dominated: true, threshold: Some(3) }, dominance.is_dominated_or_insert(Arc::new(vec![0, -1]), 0, 0));
+    }
+
+    #[test]
+    fn the_checker_can_be_shared_across_threads() {
+        let dominance: ConcurrentDominanceChecker<DummyDominance> =
+            ConcurrentDominanceChecker::new(DummyDominance, 0);
+
+        // each thread works on its own key: none of the inserted fronts may
+        // be disturbed by the others
+        std::thread::scope(|s| {
+            for key in 0..4_isize {
+                let dominance = &dominance;
+                s.spawn(move || {
+                    assert_eq!(
+                        DominanceCheckResult { dominated: false, threshold: None },
+                        dominance.is_dominated_or_insert(Arc::new(vec![key, 3]), 0, 0));
+                    let res = dominance.is_dominated_or_insert(Arc::new(vec![key, 2]), 0, 0);
+                    assert!(res.dominated);
+                });
+            }
+        });
+
+        for key in 0..4_isize {
+            assert_eq!(1, dominance.data[0].get(&key).unwrap().len());
+        }
+    }
+
+    #[test]
+    fn without_instrumentation_the_dimension_stats_are_empty() {
+        let dominance = SimpleDominanceChecker::new(DummyDominance, 0);
+        assert_eq!(DominanceCheckResult{ dominated: false, threshold: None }, dominance.is_dominated_or_insert(Arc::new(vec![0, 0, 3]), 0, 0));
+        let res = dominance.is_dominated_or_insert(Arc::new(vec![0, 0, 2]), 0, 0);
+        assert!(res.dominated);
+        assert!(dominance.dimension_stats().is_empty());
+    }
+
+    #[test]
+    fn dimension_stats_attribute_each_comparison_to_its_deciding_dimension() {
+        let dominance = SimpleDominanceChecker::new(DummyDominance, 0)
+            .with_dimension_stats();
+
+        // the first state compares against nothing at all
+        dominance.is_dominated_or_insert(Arc::new(vec![0, 0, 3]), 0, 0);
+        // this comparison is settled (Less) by the third dimension
+        dominance.is_dominated_or_insert(Arc::new(vec![0, 0, 2]), 0, 0);
+        // the second dimension sets the direction (Greater) but the third
+        // one disagrees: the comparison is blocked by the third dimension
+        // (and the incomparable state joins the front)
+        dominance.is_dominated_or_insert(Arc::new(vec![0, 1, -1]), 0, 0);
+        // against [0, 0, 3] the second dimension settles it (Greater), and
+        // against [0, 1, -1] the third one does
+        dominance.is_dominated_or_insert(Arc::new(vec![0, 1, 3]), 0, 0);
+
+        let stats = dominance.dimension_stats();
+        assert_eq!(3, stats.len());
+        assert_eq!(DimensionStat { decided: 0, blocked: 0 }, stats[0]);
+        assert_eq!(DimensionStat { decided: 1, blocked: 0 }, stats[1]);
+        assert_eq!(DimensionStat { decided: 2, blocked: 1 }, stats[2]);
+    }
+
     struct DummyDominance;
     impl Dominance for DummyDominance {
         type State = Vec<isize>;
@@ -21,6 +21,8 @@
 
 mod simple;
 mod no_duplicate;
+mod sharded;
 
 pub use simple::*;
-pub use no_duplicate::*;
\ No newline at end of file
+pub use no_duplicate::*;
+pub use sharded::*;
\ No newline at end of file
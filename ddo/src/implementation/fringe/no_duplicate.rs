@@ -31,6 +31,10 @@ use fxhash::FxHashMap;
 use crate::*;
 use self::Action::{BubbleDown, BubbleUp, DoNothing};
 
+/// The function which computes the deduplication key of a subproblem from
+/// its state
+type KeyFn<S, K> = Box<dyn Fn(&Arc<S>) -> K + Send + Sync>;
+
 /// This is a type-safe identifier for some node in the queue.
 /// Basically, this NodeId equates to the position of the identified
 /// node in the `nodes` list from the `NoDupHeap`.
@@ -47,17 +51,24 @@ enum Action {
 
 /// This is an updatable binary heap backed by a vector which ensures that
 /// items remain ordered in the priority queue while guaranteeing that a
-/// given state will only ever be present *ONCE* in the priority queue (the
-/// node with the longest path to state is the only kept copy).
-pub struct NoDupFringe<O>
+/// given deduplication key will only ever be present *ONCE* in the priority
+/// queue (the node with the longest path to that key is the only kept copy).
+/// By default (see `new`), the key is the state itself and hence no two
+/// subproblems with the same state may coexist in the queue; but the
+/// deduplication may also be performed on an arbitrary projection of the
+/// states (see `new_with_key`).
+pub struct NoDupFringe<O, K = Arc<<O as SubProblemRanking>::State>>
 where
     O: SubProblemRanking,
     O::State: Eq + Hash + Clone,
+    K: Eq + Hash,
 {
     /// This is the comparator used to order the nodes in the binary heap
     cmp: CompareSubProblem<O>,
-    /// A mapping that associates some state to a node identifier.
-    states: FxHashMap<Arc<O::State>, NodeId>,
+    /// The function which computes the deduplication key of a subproblem
+    key_of: KeyFn<O::State, K>,
+    /// A mapping that associates some key to a node identifier.
+    states: FxHashMap<K, NodeId>,
     /// The actual payload (nodes) ordered in the list
     nodes: Vec<SubProblem<O::State>>,
     /// The position of the items in the heap
@@ -68,15 +79,16 @@ where
     recycle_bin: Vec<NodeId>,
 }
 
-impl<O> Fringe for NoDupFringe<O>
+impl<O, K> Fringe for NoDupFringe<O, K>
 where
     O: SubProblemRanking,
     O::State: Eq + Hash + Clone,
+    K: Eq + Hash,
 {
     type State = O::State;
 
     /// Pushes one node onto the heap while ensuring that only one copy of the
-    /// node (identified by its state) is kept in the heap.
+    /// node (identified by its deduplication key) is kept in the heap.
     ///
     /// # Note:
     /// In the event where the heap already contains a copy `x` of a node having
@@ -86,9 +98,9 @@ where
     /// increased. As always, in the event where the newly pushed node has a
     /// longer longest path than the pre-existing node, that one will be kept.
     fn push(&mut self, mut node: SubProblem<O::State>) {
-        let state = Arc::clone(&node.state);
+        let key = (self.key_of)(&node.state);
 
-        let action = match self.states.entry(state) {
+        let action = match self.states.entry(key) {
             Occupied(e) => {
                 let id = *e.get();
 
@@ -158,7 +170,7 @@ where
         self.recycle_bin.push(id);
 
         let node = self.nodes[id.0].clone();
-        self.states.remove(&node.state);
+        self.states.remove(&(self.key_of)(&node.state));
 
         Some(node)
     }
@@ -187,9 +199,45 @@ where
 {
     /// Creates a new instance of the no dup heap which uses cmp as
     /// comparison criterion.
-    pub fn new(ranking: O) -> Self {
+    pub fn new(ranking: O) -> Self
+    where
+        O::State: 'static,
+    {
         Self {
             cmp: CompareSubProblem::new(ranking),
+            key_of: Box::new(Arc::clone),
+            states: Default::default(),
+            nodes: vec![],
+            pos: vec![],
+            heap: vec![],
+            recycle_bin: vec![],
+        }
+    }
+}
+
+impl<O, K> NoDupFringe<O, K>
+where
+    O: SubProblemRanking,
+    O::State: Eq + Hash + Clone,
+    K: Eq + Hash,
+{
+    /// Creates a new instance of the no dup heap which uses cmp as
+    /// comparison criterion and deduplicates the subproblems on the key
+    /// computed by `key_of` rather than on the full state (much like
+    /// `Dominance::get_key` partitions the states compared by a dominance
+    /// checker). Only one subproblem per key is ever kept in the queue: the
+    /// best-valued one, whose ub is raised to the largest ub known for that
+    /// key. This is useful when two subproblems agreeing on some projection
+    /// of their states (e.g. the visited-set of a TSPTW state, regardless of
+    /// the elapsed time) are to be treated as duplicates.
+    pub fn new_with_key<F>(ranking: O, key_of: F) -> Self
+    where
+        O::State: 'static,
+        F: Fn(&O::State) -> K + Send + Sync + 'static,
+    {
+        Self {
+            cmp: CompareSubProblem::new(ranking),
+            key_of: Box::new(move |state| key_of(state.as_ref())),
             states: Default::default(),
             nodes: vec![],
             pos: vec![],
@@ -523,6 +571,35 @@ mod test_no_dup_fringe {
     }
 
 
+    // when the fringe dedups on a user-provided key, two subproblems mapped
+    // to the same key may not coexist: the best-valued one is kept
+    #[test]
+    fn dedup_can_be_done_on_a_user_provided_key() {
+        let mut fringe = keyed_fringe();
+
+        fringe.push(fnode(12, 10, 100));
+        fringe.push(fnode(22, 15,  90));
+        assert_eq!(fringe.len(), 1);
+
+        // the surviving subproblem is the best-valued one, and its ub is the
+        // largest ub known for the key
+        let popped = fringe.pop().unwrap();
+        assert_eq!(*popped.state, 22);
+        assert_eq!(popped.value, 15);
+        assert_eq!(popped.ub, 100);
+    }
+
+    // distinct keys are kept separate even when the full states would have
+    // collided under some other projection
+    #[test]
+    fn subproblems_with_distinct_keys_are_not_deduplicated() {
+        let mut fringe = keyed_fringe();
+
+        fringe.push(fnode(12, 10, 100));
+        fringe.push(fnode(23, 15,  90));
+        assert_eq!(fringe.len(), 2);
+    }
+
     /// A dummy state comparator for use in the tests
     #[derive(Debug, Clone, Copy, Default)]
     struct UsizeRanking;
@@ -538,6 +615,11 @@ mod test_no_dup_fringe {
     fn empty_fringe() -> NoDupFringe<MaxUB<'static, UsizeRanking>> {
         NoDupFringe::new(MaxUB::new(&UsizeRanking))
     }
+    fn keyed_fringe() -> NoDupFringe<MaxUB<'static, UsizeRanking>, usize> {
+        // two states are considered duplicates iff they agree on their last
+        // decimal digit
+        NoDupFringe::new_with_key(MaxUB::new(&UsizeRanking), |state: &usize| state % 10)
+    }
     fn non_empty_fringe() -> NoDupFringe<MaxUB<'static, UsizeRanking>> {
         let mut fringe = empty_fringe();
         fringe.push(SubProblem{
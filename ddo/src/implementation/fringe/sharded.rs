@@ -0,0 +1,216 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! This module provides the implementation of a sharded solver fringe: a set
+//! of priority queues which are periodically rebalanced.
+
+use binary_heap_plus::BinaryHeap;
+
+use crate::*;
+
+/// A fringe which spreads the subproblems over several priority queues (the
+/// shards) instead of one single big heap. The subproblems are pushed onto
+/// the shards in round-robin fashion and a pop compares the tops of all the
+/// shards to return the most promising open subproblem: the exploration
+/// order is the same as with a `SimpleFringe` using the same ranking. Every
+/// so often (see `with_rebalance_period`), the fringe redistributes its
+/// content evenly over the shards -- dealing the subproblems out by
+/// decreasing upper bound -- so that no shard grows much deeper than the
+/// others.
+///
+/// The point of the sharding is to keep the individual heaps shallow: the
+/// cost of a push or a pop grows with the logarithm of the heap size, and on
+/// the searches which keep millions of subproblems open (think of the large
+/// MISP instances of the test suite, solved many to a process), splitting
+/// one big heap into a handful of small ones measurably reduces the time
+/// spent maintaining the fringe -- and hence the time the parallel workers
+/// spend holding the lock that protects it.
+pub struct ShardedFringe<O: SubProblemRanking + Clone> {
+    /// The ranking used to compare the shard tops upon pop
+    ranking: O,
+    /// The per-shard priority queues
+    shards: Vec<BinaryHeap<SubProblem<O::State>, CompareSubProblem<O>>>,
+    /// The shard which receives the next pushed subproblem (round-robin)
+    cursor: usize,
+    /// The number of pushes performed since the last rebalancing
+    pushes: usize,
+    /// The number of pushes between two rebalancings
+    rebalance_period: usize,
+}
+impl <O> ShardedFringe<O> where O: SubProblemRanking + Clone {
+    /// This creates a new sharded fringe which uses a custom fringe order
+    /// and spreads its content over `nb_shards` priority queues (at least
+    /// one shard is always created).
+    pub fn new(o: O, nb_shards: usize) -> Self {
+        let nb_shards = nb_shards.max(1);
+        let shards = (0..nb_shards)
+            .map(|_| BinaryHeap::from_vec_cmp(vec![], CompareSubProblem::new(o.clone())))
+            .collect();
+        Self {
+            ranking: o,
+            shards,
+            cursor: 0,
+            pushes: 0,
+            rebalance_period: nb_shards * 64,
+        }
+    }
+
+    /// Sets the number of pushes between two rebalancings of the shards.
+    /// The default is 64 pushes per shard.
+    pub fn with_rebalance_period(mut self, period: usize) -> Self {
+        self.rebalance_period = period.max(1);
+        self
+    }
+
+    /// Redistributes the content of the shards evenly, dealing the
+    /// subproblems out by decreasing rank so that every shard holds a slice
+    /// of the whole spectrum of upper bounds.
+    fn rebalance(&mut self) {
+        let mut all = vec![];
+        for shard in self.shards.iter_mut() {
+            all.extend(shard.drain());
+        }
+        all.sort_unstable_by(|a, b| self.ranking.compare(a, b).reverse());
+        let nb_shards = self.shards.len();
+        for (i, node) in all.into_iter().enumerate() {
+            self.shards[i % nb_shards].push(node);
+        }
+        self.pushes = 0;
+    }
+}
+impl <O> Fringe for ShardedFringe<O> where O: SubProblemRanking + Clone {
+    type State = O::State;
+
+    fn push(&mut self, node: SubProblem<Self::State>) {
+        self.shards[self.cursor].push(node);
+        self.cursor = (self.cursor + 1) % self.shards.len();
+        self.pushes += 1;
+        if self.pushes >= self.rebalance_period {
+            self.rebalance();
+        }
+    }
+
+    fn pop(&mut self) -> Option<SubProblem<Self::State>> {
+        let mut best: Option<usize> = None;
+        for (i, shard) in self.shards.iter().enumerate() {
+            if let Some(top) = shard.peek() {
+                best = match best {
+                    Some(j) if self.ranking.compare(self.shards[j].peek().unwrap(), top).is_ge() => Some(j),
+                    _ => Some(i),
+                };
+            }
+        }
+        best.and_then(|i| self.shards[i].pop())
+    }
+
+    fn clear(&mut self) {
+        self.shards.iter_mut().for_each(|shard| shard.clear());
+        self.cursor = 0;
+        self.pushes = 0;
+    }
+
+    fn len(&self) -> usize {
+        self.shards.iter().map(|shard| shard.len()).sum()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::many_single_char_names)]
+mod test_sharded_fringe {
+    use crate::*;
+    use std::{sync::Arc, cmp::Ordering};
+
+    /// A dummy state comparator for use in the tests
+    #[derive(Clone, Copy)]
+    struct CharRanking;
+    impl StateRanking for CharRanking {
+        type State = char;
+
+        fn compare(&self, a: &Self::State, b: &Self::State) -> Ordering {
+            a.cmp(b)
+        }
+    }
+
+    fn subproblem(state: char, ub: isize) -> SubProblem<char> {
+        SubProblem {
+            state: Arc::new(state),
+            value: 0,
+            ub,
+            path : vec![],
+            depth: 0,
+        }
+    }
+
+    #[test]
+    fn by_default_it_is_empty() {
+        let order = MaxUB::new(&CharRanking);
+        let front = ShardedFringe::new(order, 4);
+        assert_eq!(front.len(), 0);
+        assert!(front.is_empty())
+    }
+
+    #[test]
+    fn the_nodes_pop_by_decreasing_upper_bound_across_the_shards() {
+        let order = MaxUB::new(&CharRanking);
+        let mut fringe = ShardedFringe::new(order, 3);
+        fringe.push(subproblem('a', 300));
+        fringe.push(subproblem('b', 100));
+        fringe.push(subproblem('c', 150));
+        fringe.push(subproblem('d', 13));
+        fringe.push(subproblem('e', 700));
+
+        assert_eq!('e', *fringe.pop().unwrap().state);
+        assert_eq!('a', *fringe.pop().unwrap().state);
+        assert_eq!('c', *fringe.pop().unwrap().state);
+        assert_eq!('b', *fringe.pop().unwrap().state);
+        assert_eq!('d', *fringe.pop().unwrap().state);
+        assert!(fringe.pop().is_none());
+    }
+
+    #[test]
+    fn rebalancing_loses_no_node_and_keeps_the_order() {
+        let order = MaxUB::new(&CharRanking);
+        // a period of one forces a rebalancing after every single push
+        let mut fringe = ShardedFringe::new(order, 4).with_rebalance_period(1);
+        for (i, state) in ('a'..='z').enumerate() {
+            fringe.push(subproblem(state, i as isize));
+        }
+        assert_eq!(26, fringe.len());
+
+        let mut popped = vec![];
+        while let Some(node) = fringe.pop() {
+            popped.push(*node.state);
+        }
+        let expected = ('a'..='z').rev().collect::<Vec<_>>();
+        assert_eq!(expected, popped);
+    }
+
+    #[test]
+    fn clear_empties_every_shard() {
+        let order = MaxUB::new(&CharRanking);
+        let mut fringe = ShardedFringe::new(order, 2);
+        fringe.push(subproblem('a', 1));
+        fringe.push(subproblem('b', 2));
+        fringe.push(subproblem('c', 3));
+
+        fringe.clear();
+        assert!(fringe.is_empty());
+        assert!(fringe.pop().is_none());
+    }
+}
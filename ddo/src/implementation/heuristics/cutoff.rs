@@ -20,7 +20,7 @@
 //! This module provides the implementation of various cutoff heuristics that can 
 //! be used to tune the behavior of a MDD solver.
 
-use std::{sync::{Arc, atomic::AtomicBool}, time::Duration};
+use std::{sync::{Arc, atomic::{AtomicBool, AtomicIsize, AtomicU64, AtomicUsize, Ordering}}, time::{Duration, Instant}};
 
 use crate::Cutoff;
 
@@ -300,26 +300,482 @@ impl Cutoff for NoCutoff {
 /// ```
 #[derive(Debug, Clone)]
 pub struct TimeBudget {
+    /// The instant the budget started running
+    start : Instant,
+    /// The total time budget granted to the search
+    budget: Duration,
     stop  : Arc<AtomicBool>
 }
 impl TimeBudget {
     pub fn new(budget: Duration) -> Self {
         let stop   = Arc::new(AtomicBool::new(false));
         let t_flag = Arc::clone(&stop);
-        
+
         // timer
         std::thread::spawn(move || {
             std::thread::sleep(budget);
             t_flag.store(true, std::sync::atomic::Ordering::Relaxed);
         });
 
-        TimeBudget { stop }
+        TimeBudget { start: Instant::now(), budget, stop }
     }
 }
 impl Cutoff for TimeBudget {
     fn must_stop(&self) -> bool {
         self.stop.load(std::sync::atomic::Ordering::Relaxed)
     }
+    fn remaining_budget_ratio(&self) -> Option<f64> {
+        if self.budget.is_zero() {
+            Some(0.0)
+        } else {
+            let remaining = self.budget.saturating_sub(self.start.elapsed());
+            Some(remaining.as_secs_f64() / self.budget.as_secs_f64())
+        }
+    }
+}
+
+/// This cutoff grants a time budget which only starts running when the first
+/// incumbent solution is found: "keep improving for up to T seconds after the
+/// first feasible solution" rather than "run for T seconds total". This is
+/// the anytime counterpart of `TimeBudget`: the search is free to take as
+/// long as it needs to produce one solution, but it gives up at most `budget`
+/// after that point.
+///
+/// The criterion learns about the first incumbent through the bounds which
+/// the solvers report (via the `set_bounds` method of the `Cutoff` trait):
+/// its clock starts the first time the reported lower bound rises above
+/// `isize::MIN`.
+#[derive(Debug, Clone)]
+pub struct TimeBudgetAfterFirstSolution {
+    /// The time budget granted once the first incumbent has been found
+    budget: Duration,
+    /// Whether the timer has been armed already (it is armed exactly once,
+    /// upon the first incumbent)
+    started: Arc<AtomicBool>,
+    /// The flag which the timer raises once the budget is elapsed
+    stop: Arc<AtomicBool>,
+}
+impl TimeBudgetAfterFirstSolution {
+    pub fn new(budget: Duration) -> Self {
+        TimeBudgetAfterFirstSolution {
+            budget,
+            started: Arc::new(AtomicBool::new(false)),
+            stop: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+impl Cutoff for TimeBudgetAfterFirstSolution {
+    fn must_stop(&self) -> bool {
+        self.stop.load(Ordering::Relaxed)
+    }
+    fn set_bounds(&self, lb: isize, _ub: isize) {
+        if lb > isize::MIN && !self.started.swap(true, Ordering::Relaxed) {
+            let budget = self.budget;
+            let t_flag = Arc::clone(&self.stop);
+
+            // timer, armed upon the first incumbent
+            std::thread::spawn(move || {
+                std::thread::sleep(budget);
+                t_flag.store(true, Ordering::Relaxed);
+            });
+        }
+    }
+}
+
+/// This cutoff allows one to specify a budget expressed in a number of nodes
+/// developed during the compilation of the decision diagrams. Because that
+/// number does not depend on the hardware running the search, this cutoff is
+/// typically used to make benchmarks reproducible across machines (which a
+/// `TimeBudget` cannot guarantee). Once the number of developed nodes crosses
+/// the given threshold, the optimization stops and the best solution that has
+/// been found (so far) is returned.
+///
+/// The counter of developed nodes is shared across all the worker threads:
+/// the decision diagrams notify this criterion (through the `add_expanded`
+/// method of the `Cutoff` trait) each time they develop the nodes of a layer.
+///
+/// # Typical Usage Example
+/// The cutoff policy is typically created when instantiating a solver. The following
+/// example shows how one can create a solver that is allowed to develop no more
+/// than 100_000 nodes.
+///
+/// ```
+/// # use ddo::*;
+/// # use std::sync::Arc;
+/// #
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// pub struct KnapsackState {
+///       // details omitted in this example
+/// #     depth: usize,
+/// #     capacity: usize
+/// }
+/// #
+/// struct Knapsack {
+///       // details omitted in this example
+/// #     capacity: usize,
+/// #     profit: Vec<usize>,
+/// #     weight: Vec<usize>,
+/// }
+/// #
+/// # const TAKE_IT: isize = 1;
+/// # const LEAVE_IT_OUT: isize = 0;
+/// #
+/// impl Problem for Knapsack {
+///       // details omitted in this example
+/// #     type State = KnapsackState;
+/// #     fn nb_variables(&self) -> usize {
+/// #         self.profit.len()
+/// #     }
+/// #     fn initial_state(&self) -> Self::State {
+/// #         KnapsackState{ depth: 0, capacity: self.capacity }
+/// #     }
+/// #     fn initial_value(&self) -> isize {
+/// #         0
+/// #     }
+/// #     fn transition(&self, state: &Self::State, dec: Decision) -> Self::State {
+/// #         let mut ret = state.clone();
+/// #         ret.depth  += 1;
+/// #         if dec.value == TAKE_IT {
+/// #             ret.capacity -= self.weight[dec.variable.id()]
+/// #         }
+/// #         ret
+/// #     }
+/// #     fn transition_cost(&self, _state: &Self::State, _: &Self::State, dec: Decision) -> isize {
+/// #         self.profit[dec.variable.id()] as isize * dec.value
+/// #     }
+/// #     fn next_variable(&self, depth: usize, _: &mut dyn Iterator<Item = &Self::State>) -> Option<Variable> {
+/// #         let n = self.nb_variables();
+/// #         if depth < n {
+/// #             Some(Variable(depth))
+/// #         } else {
+/// #             None
+/// #         }
+/// #     }
+/// #     fn for_each_in_domain(&self, variable: Variable, state: &Self::State, f: &mut dyn DecisionCallback)
+/// #     {
+/// #         if state.capacity >= self.weight[variable.id()] {
+/// #             f.apply(Decision { variable, value: TAKE_IT });
+/// #             f.apply(Decision { variable, value: LEAVE_IT_OUT });
+/// #         } else {
+/// #             f.apply(Decision { variable, value: LEAVE_IT_OUT });
+/// #         }
+/// #     }
+/// }
+/// struct KPRelax<'a>{pb: &'a Knapsack}
+/// impl Relaxation for KPRelax<'_> {
+///       // details omitted in this example
+/// #     type State = KnapsackState;
+/// #     fn merge(&self, states: &mut dyn Iterator<Item = &Self::State>) -> Self::State {
+/// #         states.max_by_key(|node| node.capacity).copied().unwrap()
+/// #     }
+/// #     fn relax(&self, _source: &Self::State, _dest: &Self::State, _merged: &Self::State, _decision: Decision, cost: isize) -> isize {
+/// #         cost
+/// #     }
+/// }
+/// #
+/// struct KPRanking;
+/// impl StateRanking for KPRanking {
+///       // details omitted in this example
+/// #     type State = KnapsackState;
+/// #     fn compare(&self, a: &Self::State, b: &Self::State) -> std::cmp::Ordering {
+/// #         a.capacity.cmp(&b.capacity)
+/// #     }
+/// }
+///
+/// pub struct KPDominance;
+/// impl Dominance for KPDominance {
+///       // details omitted in this example
+/// #     type State = KnapsackState;
+/// #     type Key = usize;
+/// #     fn get_key(&self, state: Arc<Self::State>) -> Option<Self::Key> {
+/// #        Some(state.depth)
+/// #     }
+/// #     fn nb_dimensions(&self, _state: &Self::State) -> usize {
+/// #         1
+/// #     }
+/// #     fn get_coordinate(&self, state: &Self::State, _: usize) -> isize {
+/// #         state.capacity as isize
+/// #     }
+/// #     fn use_value(&self) -> bool {
+/// #         true
+/// #     }
+/// }
+///
+/// let problem = Knapsack {
+///       // details omitted
+/// #     capacity: 50,
+/// #     profit  : vec![60, 100, 120],
+/// #     weight  : vec![10,  20,  30]
+/// };
+/// let relaxation = KPRelax{pb: &problem};
+/// let width = FixedWidth(100);
+/// let dominance = SimpleDominanceChecker::new(KPDominance, problem.nb_variables());
+/// let heuristic = KPRanking;
+///
+/// // this solver will be allowed to develop no more than 100_000 nodes
+/// let cutoff = NodeBudget::new(100_000);
+/// let mut fringe = SimpleFringe::new(MaxUB::new(&heuristic));
+/// #
+/// let mut solver = DefaultSolver::new(
+///       &problem,
+///       &relaxation,
+///       &heuristic,
+///       &width,
+///       &dominance,
+///       &cutoff,
+///       &mut fringe);
+/// let outcome = solver.maximize();
+/// ```
+#[derive(Debug, Clone)]
+pub struct NodeBudget {
+    /// The maximum number of nodes the search is allowed to develop
+    budget: usize,
+    /// The number of nodes developed so far (shared across all the workers)
+    nb_expanded: Arc<AtomicUsize>,
+}
+impl NodeBudget {
+    pub fn new(budget: usize) -> Self {
+        NodeBudget { budget, nb_expanded: Arc::new(AtomicUsize::new(0)) }
+    }
+}
+impl Cutoff for NodeBudget {
+    fn must_stop(&self) -> bool {
+        self.nb_expanded.load(Ordering::Relaxed) >= self.budget
+    }
+    fn add_expanded(&self, nb_nodes: usize) {
+        self.nb_expanded.fetch_add(nb_nodes, Ordering::Relaxed);
+    }
+    fn remaining_budget_ratio(&self) -> Option<f64> {
+        let expanded  = self.nb_expanded.load(Ordering::Relaxed);
+        let remaining = self.budget.saturating_sub(expanded);
+        Some(remaining as f64 / self.budget.max(1) as f64)
+    }
+}
+
+/// This cutoff imposes a limit on the number of subproblems which may sit in
+/// the solver fringe at any point in time. On the large instances where the
+/// fringe is what blows the memory up, this lets the search abort gracefully
+/// -- reporting the best bounds found so far with `is_exact == false` --
+/// instead of letting the process get killed by the OS.
+///
+/// The solvers keep this criterion informed of the fringe length (through
+/// the `set_fringe_size` method of the `Cutoff` trait) every time they pop
+/// from or push onto the fringe. The search stops as soon as the reported
+/// length exceeds the given limit.
+#[derive(Debug, Clone)]
+pub struct FringeSizeLimit {
+    /// The maximum number of subproblems the fringe is allowed to hold
+    limit: usize,
+    /// The last fringe length which has been reported by the solver
+    size: Arc<AtomicUsize>,
+}
+impl FringeSizeLimit {
+    pub fn new(limit: usize) -> Self {
+        FringeSizeLimit { limit, size: Arc::new(AtomicUsize::new(0)) }
+    }
+}
+impl Cutoff for FringeSizeLimit {
+    fn must_stop(&self) -> bool {
+        self.size.load(Ordering::Relaxed) > self.limit
+    }
+    fn set_fringe_size(&self, nb_subproblems: usize) {
+        self.size.store(nb_subproblems, Ordering::Relaxed);
+    }
+}
+
+/// This cutoff stops the search as soon as the relative optimality gap drops
+/// below the given threshold. This is the criterion of choice when a proven
+/// optimum is not required and a solution within -- say -- 1% of the bound
+/// (`GapLimit::new(0.01)`) is all you need.
+///
+/// The solvers keep this criterion informed of the current best lower and
+/// upper bounds (through the `set_bounds` method of the `Cutoff` trait); the
+/// gap is derived from those bounds exactly like `Solver::gap` derives it.
+/// As long as either bound is unknown, the gap is considered to be 100% and
+/// the search goes on.
+#[derive(Debug, Clone)]
+pub struct GapLimit {
+    /// The relative gap below which the search must stop
+    limit: f32,
+    /// The best lower bound which has been reported by the solver
+    lb: Arc<AtomicIsize>,
+    /// The best upper bound which has been reported by the solver
+    ub: Arc<AtomicIsize>,
+}
+impl GapLimit {
+    pub fn new(limit: f32) -> Self {
+        GapLimit {
+            limit,
+            lb: Arc::new(AtomicIsize::new(isize::MIN)),
+            ub: Arc::new(AtomicIsize::new(isize::MAX)),
+        }
+    }
+    /// Computes the relative optimality gap warranted by the given bounds
+    /// (this is the same formula as `Solver::gap`)
+    fn gap(lb: isize, ub: isize) -> f32 {
+        if ub == isize::MAX || lb == isize::MIN {
+            1.0
+        } else {
+            let aub = ub.abs();
+            let alb = lb.abs();
+            let u = aub.max(alb);
+            let l = aub.min(alb);
+
+            (u - l) as f32 / u as f32
+        }
+    }
+}
+impl Cutoff for GapLimit {
+    fn must_stop(&self) -> bool {
+        Self::gap(self.lb.load(Ordering::Relaxed), self.ub.load(Ordering::Relaxed)) < self.limit
+    }
+    fn set_bounds(&self, lb: isize, ub: isize) {
+        // the bounds only ever tighten over the course of one resolution
+        self.lb.fetch_max(lb, Ordering::Relaxed);
+        self.ub.fetch_min(ub, Ordering::Relaxed);
+    }
+}
+
+/// This cutoff stops the search when the bounds stagnate: it trips as soon
+/// as neither the lower nor the upper bound has improved for the duration of
+/// the given `window`. On hard instances the bounds sometimes stop moving
+/// long before the time budget runs out, and stopping then spares the CPU
+/// a hopeless proof.
+///
+/// The solvers keep this criterion informed of the current best lower and
+/// upper bounds (through the `set_bounds` method of the `Cutoff` trait);
+/// every improvement of either bound resets the stagnation timer. The timer
+/// initially starts at the creation of the cutoff, so a search which never
+/// reports any bound at all also gets stopped after one `window`.
+#[derive(Debug, Clone)]
+pub struct StagnationCutoff {
+    /// The duration for which the bounds are allowed to stagnate before the
+    /// search gets stopped
+    window: Duration,
+    /// The instant this cutoff was created; the stagnation timer is measured
+    /// as an offset from this origin
+    start: Instant,
+    /// The best lower bound which has been reported by the solver
+    lb: Arc<AtomicIsize>,
+    /// The best upper bound which has been reported by the solver
+    ub: Arc<AtomicIsize>,
+    /// The offset from `start` (in milliseconds) of the last improvement of
+    /// either bound
+    last_change: Arc<AtomicU64>,
+}
+impl StagnationCutoff {
+    pub fn new(window: Duration) -> Self {
+        StagnationCutoff {
+            window,
+            start: Instant::now(),
+            lb: Arc::new(AtomicIsize::new(isize::MIN)),
+            ub: Arc::new(AtomicIsize::new(isize::MAX)),
+            last_change: Arc::new(AtomicU64::new(0)),
+        }
+    }
+}
+impl Cutoff for StagnationCutoff {
+    fn must_stop(&self) -> bool {
+        let last_change = Duration::from_millis(self.last_change.load(Ordering::Relaxed));
+        self.start.elapsed().saturating_sub(last_change) > self.window
+    }
+    fn set_bounds(&self, lb: isize, ub: isize) {
+        // the bounds only ever tighten over the course of one resolution
+        let old_lb = self.lb.fetch_max(lb, Ordering::Relaxed);
+        let old_ub = self.ub.fetch_min(ub, Ordering::Relaxed);
+        if lb > old_lb || ub < old_ub {
+            let elapsed = self.start.elapsed().as_millis() as u64;
+            self.last_change.fetch_max(elapsed, Ordering::Relaxed);
+        }
+    }
+}
+
+/// This cutoff combines several criteria and stops the search as soon as
+/// *any* of them trips. This is the combinator you want when the criteria
+/// express independent budgets, e.g. "stop after 60 seconds or 10 million
+/// nodes, whichever comes first":
+///
+/// ```
+/// # use ddo::*;
+/// # use std::time::Duration;
+/// let cutoff = AnyCutoff::new(vec![
+///     Box::new(TimeBudget::new(Duration::from_secs(60))),
+///     Box::new(NodeBudget::new(10_000_000)),
+/// ]);
+/// ```
+///
+/// Every notification of the `Cutoff` trait (expanded nodes, fringe size,
+/// bounds) is forwarded to all the children, so each criterion observes
+/// exactly what it would observe if it were installed alone.
+pub struct AnyCutoff {
+    /// The criteria being combined; the first one to trip stops the search
+    cutoffs: Vec<Box<dyn Cutoff + Send + Sync>>,
+}
+impl AnyCutoff {
+    pub fn new(cutoffs: Vec<Box<dyn Cutoff + Send + Sync>>) -> Self {
+        AnyCutoff { cutoffs }
+    }
+}
+impl Cutoff for AnyCutoff {
+    fn must_stop(&self) -> bool {
+        self.cutoffs.iter().any(|cutoff| cutoff.must_stop())
+    }
+    fn remaining_budget_ratio(&self) -> Option<f64> {
+        // the binding budget is the one which will run dry first
+        self.cutoffs.iter()
+            .filter_map(|cutoff| cutoff.remaining_budget_ratio())
+            .min_by(f64::total_cmp)
+    }
+    fn add_expanded(&self, nb_nodes: usize) {
+        self.cutoffs.iter().for_each(|cutoff| cutoff.add_expanded(nb_nodes));
+    }
+    fn set_fringe_size(&self, nb_subproblems: usize) {
+        self.cutoffs.iter().for_each(|cutoff| cutoff.set_fringe_size(nb_subproblems));
+    }
+    fn set_bounds(&self, lb: isize, ub: isize) {
+        self.cutoffs.iter().for_each(|cutoff| cutoff.set_bounds(lb, ub));
+    }
+}
+
+/// This cutoff combines several criteria and stops the search only when
+/// *all* of them trip. It is the conjunctive counterpart of `AnyCutoff`:
+/// use it when every budget must be exhausted before giving up, e.g. "only
+/// abort when the gap is below 5% and at least one minute was spent proving
+/// it". An empty conjunction is vacuously satisfied: an `AllCutoff` built
+/// over no criterion at all stops the search right away.
+///
+/// Every notification of the `Cutoff` trait (expanded nodes, fringe size,
+/// bounds) is forwarded to all the children, so each criterion observes
+/// exactly what it would observe if it were installed alone.
+pub struct AllCutoff {
+    /// The criteria being combined; the search goes on until all have tripped
+    cutoffs: Vec<Box<dyn Cutoff + Send + Sync>>,
+}
+impl AllCutoff {
+    pub fn new(cutoffs: Vec<Box<dyn Cutoff + Send + Sync>>) -> Self {
+        AllCutoff { cutoffs }
+    }
+}
+impl Cutoff for AllCutoff {
+    fn must_stop(&self) -> bool {
+        self.cutoffs.iter().all(|cutoff| cutoff.must_stop())
+    }
+    fn remaining_budget_ratio(&self) -> Option<f64> {
+        // the search only stops when every budget has run dry: the binding
+        // one is the budget with the most slack left
+        self.cutoffs.iter()
+            .filter_map(|cutoff| cutoff.remaining_budget_ratio())
+            .max_by(f64::total_cmp)
+    }
+    fn add_expanded(&self, nb_nodes: usize) {
+        self.cutoffs.iter().for_each(|cutoff| cutoff.add_expanded(nb_nodes));
+    }
+    fn set_fringe_size(&self, nb_subproblems: usize) {
+        self.cutoffs.iter().for_each(|cutoff| cutoff.set_fringe_size(nb_subproblems));
+    }
+    fn set_bounds(&self, lb: isize, ub: isize) {
+        self.cutoffs.iter().for_each(|cutoff| cutoff.set_bounds(lb, ub));
+    }
 }
 
 #[cfg(test)]
@@ -341,4 +797,176 @@ mod tests {
         thread::sleep(Duration::from_secs(4));
         assert!(cutoff.must_stop());
     }
+
+    #[test]
+    fn a_time_budget_after_first_solution_never_stops_while_no_incumbent_was_found() {
+        let cutoff = TimeBudgetAfterFirstSolution::new(Duration::from_millis(100));
+        assert!(!cutoff.must_stop());
+        // reporting bounds without an incumbent does not arm the timer
+        cutoff.set_bounds(isize::MIN, 100);
+        thread::sleep(Duration::from_millis(500));
+        assert!(!cutoff.must_stop());
+    }
+
+    #[test]
+    fn a_time_budget_after_first_solution_starts_its_clock_at_the_first_incumbent() {
+        let cutoff = TimeBudgetAfterFirstSolution::new(Duration::from_secs(1));
+        cutoff.set_bounds(42, 100);
+        assert!(!cutoff.must_stop());
+        thread::sleep(Duration::from_secs(2));
+        assert!(cutoff.must_stop());
+    }
+
+    #[test]
+    fn a_stagnation_cutoff_stops_once_the_bounds_stop_improving() {
+        let cutoff = StagnationCutoff::new(Duration::from_secs(1));
+        assert!(!cutoff.must_stop());
+        // improving bounds keep resetting the stagnation timer
+        thread::sleep(Duration::from_millis(600));
+        cutoff.set_bounds(10, 100);
+        thread::sleep(Duration::from_millis(600));
+        cutoff.set_bounds(20, 90);
+        thread::sleep(Duration::from_millis(600));
+        assert!(!cutoff.must_stop());
+        // reporting the same bounds again is not an improvement: the timer
+        // keeps running and eventually trips
+        cutoff.set_bounds(20, 90);
+        thread::sleep(Duration::from_secs(1));
+        assert!(cutoff.must_stop());
+    }
+
+    #[test]
+    fn node_budget_must_stop_only_when_the_budget_is_exhausted() {
+        let cutoff = NodeBudget::new(5);
+        assert!(!cutoff.must_stop());
+        cutoff.add_expanded(3);
+        assert!(!cutoff.must_stop());
+        cutoff.add_expanded(2);
+        assert!(cutoff.must_stop());
+    }
+
+    #[test]
+    fn a_fringe_size_limit_must_stop_only_while_the_limit_is_exceeded() {
+        let cutoff = FringeSizeLimit::new(3);
+        assert!(!cutoff.must_stop());
+        cutoff.set_fringe_size(3);
+        assert!(!cutoff.must_stop());
+        cutoff.set_fringe_size(4);
+        assert!(cutoff.must_stop());
+        // the criterion tracks the current length: it relaxes when the
+        // fringe shrinks back under the limit
+        cutoff.set_fringe_size(2);
+        assert!(!cutoff.must_stop());
+    }
+
+    #[test]
+    fn a_gap_limit_must_stop_once_the_gap_is_small_enough() {
+        let cutoff = GapLimit::new(0.1);
+        // as long as either bound is unknown, the gap is considered to be
+        // 100% and the search goes on
+        assert!(!cutoff.must_stop());
+        cutoff.set_bounds(80, isize::MAX);
+        assert!(!cutoff.must_stop());
+        // a 20% gap does not warrant a stop
+        cutoff.set_bounds(80, 100);
+        assert!(!cutoff.must_stop());
+        // a 5% gap does
+        cutoff.set_bounds(95, 100);
+        assert!(cutoff.must_stop());
+    }
+
+    #[test]
+    fn a_gap_limit_only_lets_the_bounds_tighten() {
+        let cutoff = GapLimit::new(0.1);
+        cutoff.set_bounds(95, 100);
+        assert!(cutoff.must_stop());
+        // looser bounds reported afterwards (e.g. by a lagging thread) are
+        // simply ignored
+        cutoff.set_bounds(50, 200);
+        assert!(cutoff.must_stop());
+    }
+
+    #[test]
+    fn clones_of_a_node_budget_share_the_same_counter() {
+        let cutoff = NodeBudget::new(4);
+        let clone = cutoff.clone();
+        clone.add_expanded(2);
+        cutoff.add_expanded(2);
+        assert!(cutoff.must_stop());
+        assert!(clone.must_stop());
+    }
+
+    #[test]
+    fn any_cutoff_stops_as_soon_as_one_criterion_trips() {
+        let cutoff = AnyCutoff::new(vec![
+            Box::new(NodeBudget::new(5)),
+            Box::new(GapLimit::new(0.1)),
+        ]);
+        assert!(!cutoff.must_stop());
+
+        // the node budget trips even though the gap is still wide open
+        cutoff.add_expanded(5);
+        assert!(cutoff.must_stop());
+    }
+
+    #[test]
+    fn any_cutoff_forwards_the_notifications_to_all_its_children() {
+        let cutoff = AnyCutoff::new(vec![
+            Box::new(NodeBudget::new(5)),
+            Box::new(GapLimit::new(0.1)),
+        ]);
+        assert!(!cutoff.must_stop());
+
+        // this time the gap criterion trips first
+        cutoff.set_bounds(95, 100);
+        assert!(cutoff.must_stop());
+    }
+
+    #[test]
+    fn all_cutoff_stops_only_when_every_criterion_trips() {
+        let cutoff = AllCutoff::new(vec![
+            Box::new(NodeBudget::new(5)),
+            Box::new(GapLimit::new(0.1)),
+        ]);
+        assert!(!cutoff.must_stop());
+
+        // one exhausted budget is not enough
+        cutoff.add_expanded(5);
+        assert!(!cutoff.must_stop());
+
+        // but once the gap has dropped below the limit too, the search stops
+        cutoff.set_bounds(95, 100);
+        assert!(cutoff.must_stop());
+    }
+
+    #[test]
+    fn a_node_budget_reports_its_remaining_budget_ratio() {
+        let cutoff = NodeBudget::new(100);
+        assert_eq!(Some(1.0), cutoff.remaining_budget_ratio());
+        cutoff.add_expanded(25);
+        assert_eq!(Some(0.75), cutoff.remaining_budget_ratio());
+        // the ratio never drops below zero, even when the budget is overshot
+        cutoff.add_expanded(100);
+        assert_eq!(Some(0.0), cutoff.remaining_budget_ratio());
+    }
+
+    #[test]
+    fn criteria_without_a_measurable_budget_report_no_ratio() {
+        assert_eq!(None, NoCutoff.remaining_budget_ratio());
+        assert_eq!(None, GapLimit::new(0.1).remaining_budget_ratio());
+        assert_eq!(None, FringeSizeLimit::new(100).remaining_budget_ratio());
+    }
+
+    #[test]
+    fn a_time_budget_reports_its_remaining_budget_ratio() {
+        let cutoff = TimeBudget::new(Duration::from_secs(3600));
+        let ratio  = cutoff.remaining_budget_ratio().unwrap();
+        assert!(ratio > 0.99 && ratio <= 1.0);
+    }
+
+    #[test]
+    fn an_empty_all_cutoff_is_vacuously_satisfied() {
+        let cutoff = AllCutoff::new(vec![]);
+        assert!(cutoff.must_stop());
+    }
 }
\ No newline at end of file
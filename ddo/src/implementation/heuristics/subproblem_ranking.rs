@@ -90,6 +90,51 @@ impl<O: StateRanking> SubProblemRanking for MaxUB<'_, O> {
     }
 }
 
+/// The MaxBy strategy orders the fringe by an arbitrary scalar key computed
+/// from the subproblems: the node with the highest key pops first. It
+/// generalizes `MaxUB` (which amounts to `MaxBy::new(|sp| sp.ub)` up to its
+/// tie-breaking) and is typically used to dive towards good incumbents
+/// faster, e.g. by keying on the accumulated value plus some heuristic
+/// estimate of the value to go.
+///
+/// # Example
+/// ```
+/// # use std::sync::Arc;
+/// # use ddo::*;
+/// let a = SubProblem {state: Arc::new('a'), value: 42, ub: 300, path: vec![], depth: 0};
+/// let b = SubProblem {state: Arc::new('b'), value: 65, ub: 100, path: vec![], depth: 0};
+///
+/// // order by accumulated value rather than by upper bound
+/// let mut fringe = SimpleFringe::new(MaxBy::new(|sp: &SubProblem<char>| sp.value));
+/// fringe.push(a);
+/// fringe.push(b);
+///
+/// assert_eq!('b', *fringe.pop().unwrap().state); // because 65 > 42
+/// assert_eq!('a', *fringe.pop().unwrap().state);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct MaxBy<State, F>
+where F: Fn(&SubProblem<State>) -> isize {
+    /// The function which computes the ordering key of a subproblem
+    func: F,
+    _phantom: std::marker::PhantomData<State>,
+}
+impl <State, F> MaxBy<State, F>
+where F: Fn(&SubProblem<State>) -> isize {
+    /// Creates a new instance ordering the fringe by the given key
+    pub fn new(func: F) -> Self {
+        Self { func, _phantom: std::marker::PhantomData }
+    }
+}
+impl <State, F> SubProblemRanking for MaxBy<State, F>
+where F: Fn(&SubProblem<State>) -> isize {
+    type State = State;
+
+    fn compare(&self, l: &SubProblem<State>, r: &SubProblem<State>) -> Ordering {
+        (self.func)(l).cmp(&(self.func)(r))
+    }
+}
+
 
 #[cfg(test)]
 #[allow(clippy::many_single_char_names)]
@@ -175,3 +220,46 @@ mod test_maxub {
     }
 }
 
+
+#[cfg(test)]
+mod test_maxby {
+    use std::cmp::Ordering;
+    use std::sync::Arc;
+
+    use crate::*;
+
+    #[test]
+    fn the_highest_key_ranks_first() {
+        let a = SubProblem {state: Arc::new('a'), value: 42, ub: 300, path: vec![], depth: 0};
+        let b = SubProblem {state: Arc::new('b'), value: 65, ub: 100, path: vec![], depth: 0};
+        let cmp = MaxBy::new(|sp: &SubProblem<char>| sp.value);
+        assert_eq!(Ordering::Less,    cmp.compare(&a, &b));
+        assert_eq!(Ordering::Greater, cmp.compare(&b, &a));
+    }
+    #[test]
+    fn equal_keys_compare_equal() {
+        let a = SubProblem {state: Arc::new('a'), value: 42, ub: 300, path: vec![], depth: 0};
+        let b = SubProblem {state: Arc::new('b'), value: 42, ub: 100, path: vec![], depth: 0};
+        let cmp = MaxBy::new(|sp: &SubProblem<char>| sp.value);
+        assert_eq!(Ordering::Equal, cmp.compare(&a, &b));
+    }
+    #[test]
+    fn keyed_on_ub_it_agrees_with_maxub() {
+        let a = SubProblem {state: Arc::new('a'), value: 42, ub: 300, path: vec![], depth: 0};
+        let b = SubProblem {state: Arc::new('b'), value:  2, ub: 100, path: vec![], depth: 0};
+        let cmp = MaxBy::new(|sp: &SubProblem<char>| sp.ub);
+        assert_eq!(Ordering::Greater, cmp.compare(&a, &b));
+        assert_eq!(Ordering::Less,    cmp.compare(&b, &a));
+    }
+    #[test]
+    fn it_plugs_into_a_fringe() {
+        let mut fringe = SimpleFringe::new(MaxBy::new(|sp: &SubProblem<char>| sp.value));
+        fringe.push(SubProblem {state: Arc::new('a'), value: 42, ub: 300, path: vec![], depth: 0});
+        fringe.push(SubProblem {state: Arc::new('b'), value: 65, ub: 100, path: vec![], depth: 0});
+        fringe.push(SubProblem {state: Arc::new('c'), value: 13, ub: 700, path: vec![], depth: 0});
+
+        assert_eq!('b', *fringe.pop().unwrap().state);
+        assert_eq!('a', *fringe.pop().unwrap().state);
+        assert_eq!('c', *fringe.pop().unwrap().state);
+    }
+}
@@ -19,7 +19,7 @@
 
 //! This module provides the implementation of various maximum width heuristics.
 
-use crate::{WidthHeuristic, SubProblem};
+use crate::{Cutoff, WidthHeuristic, SubProblem};
 
 
 /// This strategy specifies a fixed maximum width for all the layers of an
@@ -381,14 +381,15 @@ impl <X> WidthHeuristic<X> for FixedWidth {
 /// let relaxation = KPRelax{pb: &problem};
 /// let heuristic = KPRanking;
 /// let dominance = SimpleDominanceChecker::new(KPDominance, problem.nb_variables());
-/// let cutoff = NoCutoff; // might as well be a TimeBudget (or something else) 
+/// let cutoff = NoCutoff; // might as well be a TimeBudget (or something else)
+/// let width = NbUnassignedWidth(problem.nb_variables());
 /// let mut fringe = SimpleFringe::new(MaxUB::new(&heuristic));
 /// #
 /// let mut solver = DefaultSolver::new(
-///       &problem, 
-///       &relaxation, 
-///       &heuristic, 
-///       &NbUnassignedWidth(problem.nb_variables()),
+///       &problem,
+///       &relaxation,
+///       &heuristic,
+///       &width,
 ///       &dominance,
 ///       &cutoff, 
 ///       &mut fringe);
@@ -621,13 +622,14 @@ impl <X> WidthHeuristic<X> for NbUnassignedWidth {
 /// let heuristic = KPRanking;
 /// let dominance = SimpleDominanceChecker::new(KPDominance, problem.nb_variables());
 /// let cutoff = NoCutoff; // might as well be a TimeBudget (or something else) 
+/// let width = Times(5, NbUnassignedWidth(problem.nb_variables()));
 /// let mut fringe = SimpleFringe::new(MaxUB::new(&heuristic));
 /// #
 /// let mut solver = DefaultSolver::new(
 ///       &problem, 
 ///       &relaxation, 
 ///       &heuristic, 
-///       &Times(5, NbUnassignedWidth(problem.nb_variables())),
+///       &width,
 ///       &dominance,
 ///       &cutoff, 
 ///       &mut fringe);
@@ -639,6 +641,9 @@ impl <S, X: WidthHeuristic<S>> WidthHeuristic<S> for Times<X> {
     fn max_width(&self, x: &SubProblem<S>) -> usize {
         1.max(self.0 * self.1.max_width(x))
     }
+    fn max_width_with_bounds(&self, x: &SubProblem<S>, best_lb: isize, best_ub: isize) -> usize {
+        1.max(self.0 * self.1.max_width_with_bounds(x, best_lb, best_ub))
+    }
 }
 
 /// This strategy acts as a decorator for an other max width heuristic. It
@@ -860,13 +865,14 @@ impl <S, X: WidthHeuristic<S>> WidthHeuristic<S> for Times<X> {
 /// let heuristic = KPRanking;
 /// let dominance = SimpleDominanceChecker::new(KPDominance, problem.nb_variables());
 /// let cutoff = NoCutoff; // might as well be a TimeBudget (or something else) 
+/// let width = DivBy(2, NbUnassignedWidth(problem.nb_variables()));
 /// let mut fringe = SimpleFringe::new(MaxUB::new(&heuristic));
 /// #
 /// let mut solver = DefaultSolver::new(
 ///       &problem, 
 ///       &relaxation, 
 ///       &heuristic, 
-///       &DivBy(2, NbUnassignedWidth(problem.nb_variables())),
+///       &width,
 ///       &dominance,
 ///       &cutoff, 
 ///       &mut fringe);
@@ -878,6 +884,148 @@ impl <S, X: WidthHeuristic<S>> WidthHeuristic<S> for DivBy<X> {
     fn max_width(&self, x: &SubProblem<S>) -> usize {
         1.max(self.1.max_width(x) / self.0)
     }
+    fn max_width_with_bounds(&self, x: &SubProblem<S>, best_lb: isize, best_ub: isize) -> usize {
+        1.max(self.1.max_width_with_bounds(x, best_lb, best_ub) / self.0)
+    }
+}
+
+/// This strategy derives the maximum width from the current optimality gap:
+/// the wider the gap, the wider the compiled DDs. When the search just started
+/// and the gap is still huge, compiling wide DDs pays off because it yields
+/// tighter bounds. On the other hand, when the gap is nearly closed, narrower
+/// (hence faster) DDs suffice to finish the search. This heuristic thus
+/// automates a tuning which users would otherwise perform by hand.
+///
+/// Concretely, the maximum width evolves linearly between `min` (gap closed)
+/// and `max` (gap wide open) with the *relative* gap `(ub - lb) / max(|lb|, |ub|)`.
+/// As long as one of the two bounds is unknown (infinite), the gap is
+/// considered wide open and the `max` width is used.
+///
+/// # Example
+/// ```
+/// # use ddo::*;
+/// # use std::sync::Arc;
+/// let heuristic = GapScaledWidth {min: 10, max: 100};
+/// let subproblem = SubProblem {state: Arc::new('a'), value: 42, ub: 100, depth: 0, path: vec![]};
+///
+/// // as long as no bound is known, the heuristic plays it safe and returns `max`
+/// assert_eq!(100, heuristic.max_width_with_bounds(&subproblem, isize::MIN, isize::MAX));
+/// // when the gap is closed, it returns `min`
+/// assert_eq!( 10, heuristic.max_width_with_bounds(&subproblem, 100, 100));
+/// // in between, the width tracks the relative gap (here, 50%)
+/// assert_eq!( 55, heuristic.max_width_with_bounds(&subproblem, 50, 100));
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct GapScaledWidth {
+    /// The width to use when the optimality gap is (nearly) closed
+    pub min: usize,
+    /// The width to use when the optimality gap is still wide open
+    pub max: usize,
+}
+impl <X> WidthHeuristic<X> for GapScaledWidth {
+    fn max_width(&self, _: &SubProblem<X>) -> usize {
+        self.max
+    }
+    fn max_width_with_bounds(&self, _: &SubProblem<X>, best_lb: isize, best_ub: isize) -> usize {
+        if best_lb == isize::MIN || best_ub == isize::MAX {
+            return self.max;
+        }
+        if best_ub <= best_lb {
+            return self.min;
+        }
+        let gap   = (best_ub - best_lb) as f64;
+        let scale = best_lb.abs().max(best_ub.abs()).max(1) as f64;
+        let ratio = (gap / scale).min(1.0);
+        self.min + (ratio * (self.max - self.min) as f64).round() as usize
+    }
+}
+
+/// This strategy lets the maximum width decay linearly with the depth of the
+/// subproblem being explored. The rationale is that the early layers of a DD
+/// are the ones which matter most for the quality of the derived bounds:
+/// spending the width budget close to the root pays off more than spending it
+/// deep down the diagram. Concretely, the maximum width for a subproblem
+/// rooted at depth `d` is `max(floor, base - d)`.
+///
+/// # Example
+/// ```
+/// # use ddo::*;
+/// # use std::sync::Arc;
+/// let heuristic = DepthDecayWidth {base: 100, floor: 5};
+///
+/// let shallow = SubProblem {state: Arc::new('a'), value: 42, ub: 100, depth:  10, path: vec![]};
+/// let deep    = SubProblem {state: Arc::new('a'), value: 42, ub: 100, depth: 120, path: vec![]};
+///
+/// // close to the root, the heuristic grants (nearly) the full base width
+/// assert_eq!(90, heuristic.max_width(&shallow));
+/// // deep in the search, the width never drops below the floor
+/// assert_eq!( 5, heuristic.max_width(&deep));
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct DepthDecayWidth {
+    /// The width which is granted to the subproblem rooted at depth zero
+    pub base: usize,
+    /// The minimum width which is granted, no matter how deep the subproblem
+    pub floor: usize,
+}
+impl <X> WidthHeuristic<X> for DepthDecayWidth {
+    fn max_width(&self, x: &SubProblem<X>) -> usize {
+        self.floor.max(self.base.saturating_sub(x.depth))
+    }
+}
+
+/// This strategy adapts the maximum width to the budget which remains
+/// available to the search: the compilations start narrow while the budget
+/// is still intact -- so that good incumbents are found quickly -- and widen
+/// as the budget shrinks, so the last DDs are the most refined ones. The
+/// remaining budget is the one reported by the cutoff installed on the solver
+/// (through `Cutoff::remaining_budget_ratio`): with a `TimeBudget` the width
+/// follows the remaining wall time and with a `NodeBudget` it follows the
+/// remaining node budget. When the cutoff has no measurable budget at all
+/// (e.g. `NoCutoff`), the heuristic falls back to the fixed `max` width.
+///
+/// Concretely, the maximum width evolves linearly from `min` (whole budget
+/// remaining) to `max` (budget exhausted).
+///
+/// # Example
+/// ```
+/// # use ddo::*;
+/// # use std::sync::Arc;
+/// let subproblem = SubProblem {state: Arc::new('a'), value: 42, ub: 100, depth: 0, path: vec![]};
+///
+/// let cutoff = NodeBudget::new(100);
+/// let heuristic = BudgetScaledWidth {cutoff: &cutoff, min: 10, max: 100};
+/// // with the budget untouched, the heuristic starts narrow
+/// assert_eq!(10, heuristic.max_width(&subproblem));
+/// // half way through the budget, the width sits half way too
+/// cutoff.add_expanded(50);
+/// assert_eq!(55, heuristic.max_width(&subproblem));
+///
+/// // a cutoff without a measurable budget falls back to the fixed `max`
+/// let heuristic = BudgetScaledWidth {cutoff: &NoCutoff, min: 10, max: 100};
+/// assert_eq!(100, heuristic.max_width(&subproblem));
+/// ```
+#[derive(Clone, Copy)]
+pub struct BudgetScaledWidth<'a> {
+    /// The cutoff installed on the solver, whose remaining budget drives
+    /// the width
+    pub cutoff: &'a dyn Cutoff,
+    /// The width to use when the whole budget is still available
+    pub min: usize,
+    /// The width to use when the budget is exhausted (and the fallback when
+    /// the cutoff has no measurable budget)
+    pub max: usize,
+}
+impl <X> WidthHeuristic<X> for BudgetScaledWidth<'_> {
+    fn max_width(&self, _: &SubProblem<X>) -> usize {
+        match self.cutoff.remaining_budget_ratio() {
+            None => self.max,
+            Some(remaining) => {
+                let consumed = 1.0 - remaining.clamp(0.0, 1.0);
+                self.min + (consumed * (self.max - self.min) as f64).round() as usize
+            }
+        }
+    }
 }
 
 
@@ -986,6 +1134,137 @@ mod test_fixedwidth {
     }
 }
 #[cfg(test)]
+mod test_gapscaled {
+    use std::sync::Arc;
+
+    use crate::*;
+
+    fn subproblem() -> SubProblem<char> {
+        SubProblem {
+            state: Arc::new('a'),
+            value: 10,
+            ub   : 100,
+            path : vec![],
+            depth: 0,
+        }
+    }
+
+    #[test]
+    fn without_bounds_it_uses_the_max_width() {
+        let heu = GapScaledWidth {min: 10, max: 100};
+        assert_eq!(100, heu.max_width(&subproblem()));
+    }
+    #[test]
+    fn while_a_bound_is_unknown_it_uses_the_max_width() {
+        let heu = GapScaledWidth {min: 10, max: 100};
+        assert_eq!(100, heu.max_width_with_bounds(&subproblem(), isize::MIN, isize::MAX));
+        assert_eq!(100, heu.max_width_with_bounds(&subproblem(), 50,         isize::MAX));
+        assert_eq!(100, heu.max_width_with_bounds(&subproblem(), isize::MIN, 50));
+    }
+    #[test]
+    fn when_the_gap_is_closed_it_uses_the_min_width() {
+        let heu = GapScaledWidth {min: 10, max: 100};
+        assert_eq!(10, heu.max_width_with_bounds(&subproblem(), 100, 100));
+        assert_eq!(10, heu.max_width_with_bounds(&subproblem(), 100,  90));
+    }
+    #[test]
+    fn in_between_the_width_tracks_the_relative_gap() {
+        let heu = GapScaledWidth {min: 10, max: 100};
+        // relative gap of 50% --> halfway between min and max
+        assert_eq!(55, heu.max_width_with_bounds(&subproblem(), 50, 100));
+        // relative gap of 10%
+        assert_eq!(19, heu.max_width_with_bounds(&subproblem(), 90, 100));
+        // a gap larger than 100% is capped at the max width
+        assert_eq!(100, heu.max_width_with_bounds(&subproblem(), -100, 100));
+    }
+}
+#[cfg(test)]
+mod test_depthdecay {
+    use std::sync::Arc;
+
+    use crate::*;
+
+    fn subproblem(depth: usize) -> SubProblem<char> {
+        SubProblem {
+            state: Arc::new('a'),
+            value: 10,
+            ub   : 100,
+            path : vec![],
+            depth,
+        }
+    }
+
+    #[test]
+    fn at_the_root_it_grants_the_full_base_width() {
+        let heu = DepthDecayWidth {base: 100, floor: 5};
+        assert_eq!(100, heu.max_width(&subproblem(0)));
+    }
+    #[test]
+    fn the_width_shrinks_as_the_depth_increases() {
+        let heu = DepthDecayWidth {base: 100, floor: 5};
+        assert_eq!(99, heu.max_width(&subproblem(1)));
+        assert_eq!(50, heu.max_width(&subproblem(50)));
+        assert_eq!( 6, heu.max_width(&subproblem(94)));
+    }
+    #[test]
+    fn it_never_drops_below_the_floor() {
+        let heu = DepthDecayWidth {base: 100, floor: 5};
+        assert_eq!(5, heu.max_width(&subproblem(95)));
+        assert_eq!(5, heu.max_width(&subproblem(100)));
+        assert_eq!(5, heu.max_width(&subproblem(usize::MAX)));
+    }
+}
+#[cfg(test)]
+mod test_budgetscaled {
+    use std::sync::Arc;
+
+    use crate::*;
+
+    fn subproblem() -> SubProblem<char> {
+        SubProblem {
+            state: Arc::new('a'),
+            value: 10,
+            ub   : 100,
+            path : vec![],
+            depth: 0,
+        }
+    }
+
+    #[test]
+    fn with_the_whole_budget_remaining_it_starts_narrow() {
+        let cutoff = NodeBudget::new(100);
+        let heu = BudgetScaledWidth {cutoff: &cutoff, min: 10, max: 100};
+        assert_eq!(10, heu.max_width(&subproblem()));
+    }
+    #[test]
+    fn the_width_grows_as_the_budget_shrinks() {
+        let cutoff = NodeBudget::new(100);
+        let heu = BudgetScaledWidth {cutoff: &cutoff, min: 10, max: 100};
+        cutoff.add_expanded(50);
+        assert_eq!(55, heu.max_width(&subproblem()));
+        // once the budget is exhausted (even overshot), the width is capped
+        // at the max
+        cutoff.add_expanded(75);
+        assert_eq!(100, heu.max_width(&subproblem()));
+    }
+    #[test]
+    fn without_a_measurable_budget_it_falls_back_to_the_max_width() {
+        let heu = BudgetScaledWidth {cutoff: &NoCutoff, min: 10, max: 100};
+        assert_eq!(100, heu.max_width(&subproblem()));
+    }
+    #[test]
+    fn with_a_disjunction_of_cutoffs_the_binding_budget_drives_the_width() {
+        let cutoff = AnyCutoff::new(vec![
+            Box::new(NodeBudget::new(100)),
+            Box::new(GapLimit::new(0.1)),
+        ]);
+        let heu = BudgetScaledWidth {cutoff: &cutoff, min: 10, max: 100};
+        // the gap limit has no measurable budget: only the node budget counts
+        cutoff.add_expanded(50);
+        assert_eq!(55, heu.max_width(&subproblem()));
+    }
+}
+#[cfg(test)]
 mod test_adapters {
     use std::sync::Arc;
 
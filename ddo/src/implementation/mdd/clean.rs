@@ -5,12 +5,12 @@
 //! ``Decision Diagram-Based Branch-and-Bound with Caching
 //! for Dominance and Suboptimality Detection''.
 
-use std::{sync::Arc, hash::Hash, collections::hash_map::Entry, fmt::Debug};
+use std::{sync::Arc, hash::Hash, cmp::Reverse, collections::hash_map::Entry, fmt::Debug};
 
 use derive_builder::Builder;
-use fxhash::FxHashMap;
+use fxhash::{FxHashMap, FxHashSet};
 
-use crate::{NodeFlags, Decision, CutsetType, CompilationInput, Completion, Reason, CompilationType, Problem, LAST_EXACT_LAYER, DecisionDiagram, SubProblem, FRONTIER, Solution, DominanceCheckResult};
+use crate::{NodeFlags, Decision, CutsetType, CompilationInput, Completion, Reason, CompilationType, Problem, LAST_EXACT_LAYER, DecisionDiagram, SubProblem, FRONTIER, Solution, Variable, DominanceCheckResult};
 
 /// The identifier of a node: it indicates the position of the referenced node 
 /// in the ’nodes’ vector of the mdd structure.
@@ -151,6 +151,11 @@ where
     lel: Option<LayerId>,
     /// The cut-set of the decision diagram (only maintained for relaxed dd)
     cutset: Vec<NodeId>,
+    /// The nodes whose state was flagged a leaf by `Problem::is_leaf`: their
+    /// paths were terminated early, before all the variables were branched
+    /// on. Together with the nodes of the last layer (`next_l`), they form
+    /// the terminal nodes of the diagram.
+    leaves: Vec<NodeId>,
     /// The identifier of the best terminal node of the diagram (None when the
     /// problem compiled into this dd is infeasible)
     best_node: Option<NodeId>,
@@ -163,6 +168,24 @@ where
     /// A flag set to true when the longest r-t path of this decision diagram
     /// traverses no merged node (Exact Best Path Optimization aka EBPO).
     has_exact_best_path: bool,
+    /// When set, relaxed compilations are hybridized: the surplus layers
+    /// sitting at a (global) depth shallower than this threshold are
+    /// restricted rather than relaxed (see `HybridMdd`)
+    hybrid_threshold: Option<usize>,
+    /// A flag set to true when at least one layer of the last compilation was
+    /// restricted because of the hybridization threshold. When it is set, the
+    /// diagram no longer over-approximates the compiled subproblem: only the
+    /// rough upper bounds can be trusted
+    hybridized: bool,
+
+    /// The total number of edges materialized over all the compilations that
+    /// have been performed with this structure (this counter is *not* reset
+    /// between compilations: it is a cumulative statistic)
+    total_edges: usize,
+    /// The total number of non-leaf nodes materialized over all the
+    /// compilations that have been performed with this structure (cumulative
+    /// as well)
+    total_internal_nodes: usize,
 }
 
 const NIL: EdgesListId = EdgesListId(0);
@@ -247,7 +270,7 @@ where
     }
 
     fn best_solution(&self) -> Option<Solution> {
-        self._best_solution()
+        self._best_solution().map(Solution::new)
     }
 
     fn best_exact_value(&self) -> Option<isize> {
@@ -255,7 +278,31 @@ where
     }
 
     fn best_exact_solution(&self) -> Option<Solution> {
-        self._best_exact_solution()
+        self._best_exact_solution().map(Solution::new)
+    }
+
+    fn count_best_paths(&self) -> u128 {
+        self._count_best_paths()
+    }
+
+    fn solutions_above(&self, threshold: isize) -> Vec<(isize, Solution)> {
+        self._solutions_above(threshold)
+    }
+
+    fn avg_branching_factor(&self) -> f64 {
+        if self.total_internal_nodes == 0 {
+            0.0
+        } else {
+            self.total_edges as f64 / self.total_internal_nodes as f64
+        }
+    }
+
+    fn best_k_solutions(&self, k: usize) -> Vec<(isize, Solution)> {
+        self._best_k_solutions(k)
+    }
+
+    fn for_each_edge_decision(&self, func: &mut dyn FnMut(Decision)) {
+        self.edges.iter().for_each(|edge| func(edge.decision))
     }
 
     fn drain_cutset<F>(&mut self, func: F)
@@ -283,13 +330,26 @@ where
             path_to_root: vec![],
             lel: None,
             cutset: vec![],
+            leaves: vec![],
             best_node: None,
             best_exact_node: None,
             is_exact: true,
             has_exact_best_path: false,
+            hybrid_threshold: None,
+            hybridized: false,
+            //
+            total_edges: 0,
+            total_internal_nodes: 0,
         }
     }
-    
+
+    /// Sets the depth threshold below which the surplus layers of a relaxed
+    /// compilation are restricted rather than relaxed. This is the mechanism
+    /// on which `HybridMdd` builds; it is not meant to be used directly.
+    pub(crate) fn set_hybrid_threshold(&mut self, threshold: Option<usize>) {
+        self.hybrid_threshold = threshold;
+    }
+
     fn _clear(&mut self) {
         self.layers.clear();
         self.nodes.clear();
@@ -299,11 +359,20 @@ where
         self.next_l.clear();
         self.path_to_root.clear();
         self.cutset.clear();
+        self.leaves.clear();
         self.lel = None;
         self.best_node = None;
         self.best_exact_node = None;
         self.is_exact = true;
         self.has_exact_best_path = false;
+        self.hybridized = false;
+    }
+
+    /// Returns an iterator over the terminal nodes of the diagram: the nodes
+    /// of the last layer plus the leaves whose paths were terminated early
+    /// (see `Problem::is_leaf`)
+    fn _terminals(&self) -> impl Iterator<Item = NodeId> + '_ {
+        self.next_l.values().copied().chain(self.leaves.iter().copied())
     }
 
     fn _best_value(&self) -> Option<isize> {
@@ -314,6 +383,85 @@ where
         self.best_node.map(|id| self._best_path(id))
     }
 
+    /// Counts the number of distinct root-to-terminal paths achieving the
+    /// best value of this dd, saturating at `u128::MAX`. This dp exploits the
+    /// fact that the nodes are laid out in topological order in the `nodes`
+    /// vector: the number of optimal prefixes reaching a node is the sum of
+    /// the counts of its parents along the tight edges (those lying on a
+    /// longest path).
+    fn _count_best_paths(&self) -> u128 {
+        match self._best_value() {
+            None => 0,
+            Some(best) => {
+                let mut ways = vec![0_u128; self.nodes.len()];
+                ways[0] = 1;
+                for id in 1..self.nodes.len() {
+                    let node_id = NodeId(id);
+                    let value = get!(node node_id, self).value_top;
+                    let mut count = 0_u128;
+                    foreach!(edge of node_id, self, |edge: Edge| {
+                        let parent = get!(node edge.from, self);
+                        if parent.value_top.saturating_add(edge.cost) == value {
+                            count = count.saturating_add(ways[edge.from.0]);
+                        }
+                    });
+                    ways[id] = count;
+                }
+                self._terminals()
+                    .filter(|id| get!(node id, self).value_top == best)
+                    .fold(0_u128, |tot, id| tot.saturating_add(ways[id.0]))
+            }
+        }
+    }
+
+    /// Enumerates all the root-to-terminal paths achieving a value of at
+    /// least `threshold`, sorted by decreasing value. The enumeration walks
+    /// the diagram backwards from each terminal node and prunes a partial
+    /// suffix as soon as even the best prefix completing it (`value_top`)
+    /// falls short of the threshold.
+    fn _solutions_above(&self, threshold: isize) -> Vec<(isize, Solution)> {
+        let mut out = vec![];
+        let mut decisions = vec![];
+        for id in self._terminals() {
+            self._collect_paths_above(id, 0, threshold, &mut decisions, &mut out);
+        }
+        out.sort_unstable_by_key(|entry| Reverse(entry.0));
+        out
+    }
+
+    /// Recursively walks the in-edges of `id`, accumulating the cost of the
+    /// suffix built so far, and emits a solution whenever the root is reached
+    /// with a total value of at least `threshold`.
+    fn _collect_paths_above(
+        &self,
+        id: NodeId,
+        suffix: isize,
+        threshold: isize,
+        decisions: &mut Vec<Decision>,
+        out: &mut Vec<(isize, Solution)>,
+    ) {
+        let node = get!(node id, self);
+        // value_top is the value of the *best* prefix reaching this node:
+        // when even that one cannot lift the suffix above the threshold,
+        // no path through this suffix qualifies
+        if node.value_top.saturating_add(suffix) < threshold {
+            return;
+        }
+        if id.0 == 0 {
+            // the root was reached: the prefix value is exact there
+            let total = node.value_top.saturating_add(suffix);
+            let mut sol = self.path_to_root.clone();
+            sol.extend_from_slice(decisions);
+            out.push((total, Solution::new(sol)));
+            return;
+        }
+        foreach!(edge of id, self, |edge: Edge| {
+            decisions.push(edge.decision);
+            self._collect_paths_above(edge.from, suffix.saturating_add(edge.cost), threshold, decisions, out);
+            decisions.pop();
+        });
+    }
+
     fn _best_exact_value(&self) -> Option<isize> {
         self.best_exact_node.map(|id| get!(node id, self).value_top)
     }
@@ -326,6 +474,75 @@ where
         Self::_best_path_partial_borrow(id, &self.path_to_root, &self.nodes, &self.edges)
     }
 
+    /// Computes the k best root-to-terminal paths of this dd. To that end, it
+    /// propagates -- in topological order -- a table which associates to each
+    /// node its up-to-k best path values, each one remembering the incoming
+    /// edge achieving it along with the rank of the prolonged value in the
+    /// parent's own table (this is what allows the paths to be rebuilt).
+    fn _best_k_solutions(&self, k: usize) -> Vec<(isize, Solution)> {
+        // one entry of the per-node table: a path value along with the
+        // incoming edge achieving it and the rank of the prolonged value in
+        // the parent's own table (None for the root)
+        type KBestEntry = (isize, Option<(EdgeId, usize)>);
+
+        if k == 0 || self.nodes.is_empty() {
+            return vec![];
+        }
+        // the edges arena (which also comprises the stale edges left over by
+        // the merge of relaxed nodes) gives the partial order on the nodes
+        let nb_nodes = self.nodes.len();
+        let mut in_degree = vec![0_usize; nb_nodes];
+        let mut outbound = vec![vec![]; nb_nodes];
+        for (eid, edge) in self.edges.iter().enumerate() {
+            in_degree[edge.to.0] += 1;
+            outbound[edge.from.0].push(EdgeId(eid));
+        }
+
+        let mut table: Vec<Vec<KBestEntry>> = vec![vec![]; nb_nodes];
+        table[0].push((get!(node NodeId(0), self).value_top, None));
+
+        let mut open = (0..nb_nodes).filter(|id| in_degree[*id] == 0).collect::<Vec<_>>();
+        while let Some(id) = open.pop() {
+            // the candidates of this node are complete: close its table and
+            // prolong each of its entries along every outgoing edge
+            table[id].sort_unstable_by_key(|entry| Reverse(entry.0));
+            table[id].truncate(k);
+            for eid in outbound[id].iter().copied() {
+                let edge = *get!(edge eid, self);
+                for rank in 0..table[id].len() {
+                    let value = table[id][rank].0.saturating_add(edge.cost);
+                    table[edge.to.0].push((value, Some((eid, rank))));
+                }
+                in_degree[edge.to.0] -= 1;
+                if in_degree[edge.to.0] == 0 {
+                    open.push(edge.to.0);
+                }
+            }
+        }
+
+        let mut terminal = vec![];
+        for id in self._terminals() {
+            for (rank, entry) in table[id.0].iter().enumerate() {
+                terminal.push((entry.0, id, rank));
+            }
+        }
+        terminal.sort_unstable_by_key(|entry| Reverse(entry.0));
+        terminal.truncate(k);
+
+        terminal.iter()
+            .map(|&(value, id, rank)| {
+                let mut sol = self.path_to_root.to_owned();
+                let mut cursor = (id, rank);
+                while let Some((eid, parent_rank)) = table[cursor.0.0][cursor.1].1 {
+                    let edge = *get!(edge eid, self);
+                    sol.push(edge.decision);
+                    cursor = (edge.from, parent_rank);
+                }
+                (value, Solution::new(sol))
+            })
+            .collect()
+    }
+
     fn _best_path_partial_borrow(
         id: NodeId,
         root_pa: &[Decision],
@@ -347,7 +564,11 @@ where
         self._initialize(input);
         
         let mut curr_l = vec![];
-        while let Some(var) = input.problem.next_variable(self.curr_depth, &mut self.next_l.keys().map(|s| s.as_ref())) {
+        let static_order = input.problem.static_order();
+        while let Some(var) = match &static_order {
+            Some(order) => order.get(self.curr_depth).copied(),
+            None => input.problem.next_variable(self.curr_depth, &mut self.next_l.keys().map(|s| s.as_ref())),
+        } {
             // Did the cutoff kick in ?
             if input.cutoff.must_stop() {
                 return Err(Reason::CutoffOccurred);
@@ -359,19 +580,70 @@ where
 
             for node_id in curr_l.iter() {
                 let state = self.nodes[node_id.0].state.clone();
-                let rub = input.relaxation.fast_upper_bound(state.as_ref());
-                self.nodes[node_id.0].rub = rub;
-                let ub = rub.saturating_add(self.nodes[node_id.0].value_top);
+                let value_top = self.nodes[node_id.0].value_top;
+                let ub = input.relaxation.fast_upper_bound_with_value(state.as_ref(), value_top, self.curr_depth);
+                self.nodes[node_id.0].rub = ub.saturating_sub(value_top);
                 if ub > input.best_lb {
-                    input.problem.for_each_in_domain(var, state.as_ref(), &mut |decision| {
-                        self._branch_on(*node_id, decision, input.problem)
-                    })
+                    if input.problem.is_leaf(state.as_ref()) {
+                        // this path ends here: the value of the node is final
+                        // even though some variables remain unassigned. The
+                        // node is recorded as a terminal instead of being
+                        // branched on.
+                        self.leaves.push(*node_id);
+                        continue;
+                    }
+                    let nb_edges_before = self.edges.len();
+                    if input.max_out_degree != usize::MAX {
+                        self._branch_on_capped(*node_id, var, state.as_ref(), input);
+                    } else if input.problem.has_lazy_domain_iter() {
+                        for value in input.problem.domain_iter(var, state.as_ref()) {
+                            // when the next layer of a restricted dd is
+                            // already full, the surplus of the domain would
+                            // be dropped by the very next restriction anyway:
+                            // there is no point in materializing it (the
+                            // domain might be huge). Note: cutting the domain
+                            // short makes the dd inexact just like an actual
+                            // restriction would.
+                            if matches!(input.comp_type, CompilationType::Restricted)
+                                && self.next_l.len() >= input.max_width {
+                                self._maybe_save_lel();
+                                break;
+                            }
+                            self._branch_on(*node_id, Decision{variable: var, value}, input.problem);
+                        }
+                    } else if input.problem.has_path_dependent_domains() {
+                        let path = self._best_path(*node_id);
+                        input.problem.for_each_in_domain_with_path(var, state.as_ref(), &path, &mut |decision| {
+                            self._branch_on(*node_id, decision, input.problem)
+                        })
+                    } else {
+                        input.problem.for_each_in_domain(var, state.as_ref(), &mut |decision| {
+                            self._branch_on(*node_id, decision, input.problem)
+                        })
+                    }
+                    // when every state of the problem admits a feasible
+                    // completion, a node left without any successor (empty
+                    // domain, every transition rejected, ...) is not an
+                    // infeasible dead end: its current value is that of a
+                    // valid solution, so the node is recorded as a terminal
+                    // instead of silently vanishing from the diagram.
+                    if input.problem.always_feasible() && self.edges.len() == nb_edges_before {
+                        self.leaves.push(*node_id);
+                    }
                 }
             }
+            input.cutoff.add_expanded(curr_l.len());
 
             self.curr_depth += 1;
         }
 
+        // at this point, next_l (together with the early-terminated leaves)
+        // holds the terminal nodes: every other node of the diagram is an
+        // internal (non-leaf) node
+        self.total_edges = self.total_edges.saturating_add(self.edges.len());
+        self.total_internal_nodes = self.total_internal_nodes
+            .saturating_add(self.nodes.len().saturating_sub(self.next_l.len()).saturating_sub(self.leaves.len()));
+
         self._finalize(input);
 
         Ok(Completion { 
@@ -408,6 +680,8 @@ where
         self._finalize_layers();
         self._find_best_node();
         self._finalize_exact(input);
+        #[cfg(debug_assertions)]
+        self._debug_check_rub_covers_best_path();
         self._finalize_cutset(input);
         self._compute_local_bounds(input);
         self._compute_thresholds(input);
@@ -422,10 +696,21 @@ where
             for id in self.cutset.drain(..) {
                 let node = get!(node id, self);
 
-                if node.flags.is_marked() {
-                    let rub  = node.value_top.saturating_add(node.rub);
-                    let locb = node.value_top.saturating_add(node.value_bot);
-                    let ub = rub.min(locb).min(best_value);
+                // when the diagram was hybridized, a cutset node may have had
+                // all its descendants dropped by a restricted layer: it is
+                // not marked by the bottom-up traversal but must be enqueued
+                // nonetheless, otherwise its solutions would be lost
+                if node.flags.is_marked() || self.hybridized {
+                    let rub = node.value_top.saturating_add(node.rub);
+                    let ub = if self.hybridized {
+                        // a restricted layer drops nodes: neither the local
+                        // bounds nor the best value of the diagram can be
+                        // trusted as upper bounds anymore
+                        rub
+                    } else {
+                        let locb = node.value_top.saturating_add(node.value_bot);
+                        rub.min(locb).min(best_value)
+                    };
 
                     func(SubProblem {
                         state: node.state.clone(),
@@ -444,6 +729,91 @@ where
         }
     }
 
+    /// Returns an iterator over the nodes of the exact cutset identified by
+    /// the last compilation: for each cutset node, it yields the state, the
+    /// length of the longest path from the root to that node, and the
+    /// tightest upper bound known for the subproblem rooted in it. Unlike
+    /// `drain_cutset`, this does not consume the cutset: it is meant to let
+    /// you study the cutset nodes (e.g. to craft your own subproblem
+    /// selection policy) without disturbing the usual resolution process.
+    pub fn exact_cutset(&self) -> impl Iterator<Item = (&T, isize, isize)> {
+        let best_value = self.best_value();
+        self.cutset.iter().filter_map(move |id| {
+            let node = get!(node * id, self);
+            if let Some(best_value) = best_value {
+                if node.flags.is_marked() || self.hybridized {
+                    let rub = node.value_top.saturating_add(node.rub);
+                    let ub = if self.hybridized {
+                        rub
+                    } else {
+                        let locb = node.value_top.saturating_add(node.value_bot);
+                        rub.min(locb).min(best_value)
+                    };
+                    return Some((node.state.as_ref(), node.value_top, ub));
+                }
+            }
+            None
+        })
+    }
+
+    /// Measures the size -- as a `(nb_nodes, nb_edges)` pair -- of the exact
+    /// DD rooted in the given residual subproblem, without compiling that DD:
+    /// only the states of two consecutive layers are ever materialized (no
+    /// node payload, no edge), so the measure uses a small fraction of the
+    /// memory an actual `Exact` compilation would claim. This is the tool of
+    /// choice to decide whether exactly solving a subproblem (`solve_exact`)
+    /// is viable before committing to it.
+    ///
+    /// # Note
+    /// The walk enumerates the very same transitions as an exact compilation
+    /// with unbounded width and in-/out-degrees: rejected transitions do not
+    /// count, `is_leaf` states are not branched on, and states reached along
+    /// several paths are merged (counted once per layer). Path-dependent
+    /// domains (`for_each_in_domain_with_path`) are the one exception: the
+    /// paths are not tracked, so the measure falls back to the plain domains.
+    pub fn measure_exact(input: &CompilationInput<T>) -> (usize, usize) {
+        let mut nb_nodes = 1;
+        let mut nb_edges = 0;
+
+        let mut curr_l = FxHashSet::<Arc<T>>::default();
+        let mut next_l = FxHashSet::<Arc<T>>::default();
+        next_l.insert(input.residual.state.clone());
+
+        let mut depth = input.residual.depth;
+        let static_order = input.problem.static_order();
+        while let Some(var) = match &static_order {
+            Some(order) => order.get(depth).copied(),
+            None => input.problem.next_variable(depth, &mut next_l.iter().map(|s| s.as_ref())),
+        } {
+            curr_l.clear();
+            std::mem::swap(&mut curr_l, &mut next_l);
+
+            for state in curr_l.iter() {
+                if input.problem.is_leaf(state.as_ref()) {
+                    continue;
+                }
+                let mut count_decision = |decision: Decision| {
+                    if let Some(next) = input.problem.transition_checked(state.as_ref(), decision) {
+                        nb_edges += 1;
+                        if next_l.insert(Arc::new(next)) {
+                            nb_nodes += 1;
+                        }
+                    }
+                };
+                if input.problem.has_lazy_domain_iter() {
+                    for value in input.problem.domain_iter(var, state.as_ref()) {
+                        count_decision(Decision { variable: var, value });
+                    }
+                } else {
+                    input.problem.for_each_in_domain(var, state.as_ref(), &mut count_decision);
+                }
+            }
+            depth += 1;
+        }
+
+        (nb_nodes, nb_edges)
+    }
+
     #[allow(clippy::redundant_closure_call)]
     fn _compute_local_bounds(&mut self, input: &CompilationInput<T>) {
         if self.lel.unwrap().0 < self.layers.len() && input.comp_type == CompilationType::Relaxed {
@@ -453,6 +823,13 @@ where
                 node.value_bot = 0;
                 node.flags.set_marked(true);
             }
+            // the early-terminated leaves are terminal nodes too, no matter
+            // which layer they sit in
+            for id in self.leaves.iter() {
+                let node = get!(mut node id, self);
+                node.value_bot = 0;
+                node.flags.set_marked(true);
+            }
 
             // traverse bottom-up
             // note: cache requires that all nodes have an associated locb. not only those below cutset
@@ -476,7 +853,10 @@ where
     
     #[allow(clippy::redundant_closure_call)]
     fn _compute_thresholds(&mut self, input: &CompilationInput<T>) {
-        if input.comp_type == CompilationType::Relaxed || self.is_exact {
+        // when the compilation was hybridized, the bottom-up values are no
+        // valid bounds: recording thresholds from them would let the cache
+        // prune subproblems it should not
+        if (input.comp_type == CompilationType::Relaxed || self.is_exact) && !self.hybridized {
             let mut best_known = input.best_lb;
 
             if let Some(best_exact_node) = self.best_exact_node {
@@ -488,6 +868,11 @@ where
                         self.nodes[id.0].theta = Some(best_known);
                     }
                 }
+                for id in self.leaves.iter() {
+                    if (CUTSET_TYPE == LAST_EXACT_LAYER && self.is_exact) || (CUTSET_TYPE == FRONTIER && self.nodes[id.0].flags.is_exact()) {
+                        self.nodes[id.0].theta = Some(best_known);
+                    }
+                }
             }
 
             for Layer{from, to} in self.layers.iter().rev().copied() {
@@ -535,10 +920,11 @@ where
         // A node can only be added to the cache if it belongs to the cutset or is above it
         if let Some(theta) = node.theta {
             if node.flags.is_above_cutset() {
-                input.cache.update_threshold(
-                    node.state.clone(), 
-                    node.depth, 
-                    theta, 
+                input.cache.update_threshold_with_problem(
+                    input.problem,
+                    node.state.clone(),
+                    node.depth,
+                    theta,
                     !node.flags.is_cutset()) // if it is in the cutset it has not been explored !
             }
         }
@@ -619,15 +1005,11 @@ where
 
     fn _find_best_node(&mut self) {
         self.best_node = self
-            .next_l
-            .values()
-            .copied()
+            ._terminals()
             .max_by_key(|id| get!(node id, self).value_top);
         self.best_exact_node = self
-            .next_l
-            .values()
+            ._terminals()
             .filter(|id| get!(node id, self).flags.is_exact())
-            .copied()
             .max_by_key(|id| get!(node id, self).value_top);
     }
 
@@ -707,7 +1089,7 @@ where
     fn _filter_with_cache(&mut self, input: &CompilationInput<T>, curr_l: &mut Vec<NodeId>) {
         curr_l.retain(|id| {
             let node = get!(mut node id, self);
-            let threshold = input.cache.get_threshold(node.state.as_ref(), node.depth);
+            let threshold = input.cache.get_threshold_with_problem(input.problem, node.state.as_ref(), node.depth);
             if let Some(threshold) = threshold {
                 if node.value_top > threshold.value {
                     true
@@ -722,6 +1104,37 @@ where
         });
     }
 
+    /// Expands the given node while materializing at most `max_out_degree`
+    /// outgoing decisions. When the domain is larger than the limit, only the
+    /// decisions leading to the best-ranked successor states are kept; the
+    /// surplus is dropped, which makes the dd inexact just like an actual
+    /// restriction would.
+    fn _branch_on_capped(
+        &mut self,
+        from_id: NodeId,
+        var: Variable,
+        state: &T,
+        input: &CompilationInput<T>,
+    ) {
+        let mut decisions = vec![];
+        input.problem.for_each_in_domain(var, state, &mut |decision| decisions.push(decision));
+
+        if decisions.len() > input.max_out_degree {
+            let mut successors = decisions.drain(..)
+                .filter_map(|decision| input.problem.transition_checked(state, decision).map(|s| (s, decision)))
+                .collect::<Vec<_>>();
+            // greater means more likely to be kept
+            successors.sort_unstable_by(|a, b| input.ranking.compare(&a.0, &b.0).reverse());
+            successors.truncate(input.max_out_degree);
+            decisions.extend(successors.into_iter().map(|(_, decision)| decision));
+            self._maybe_save_lel();
+        }
+
+        for decision in decisions {
+            self._branch_on(from_id, decision, input.problem);
+        }
+    }
+
     fn _branch_on(
         &mut self,
         from_id: NodeId,
@@ -729,7 +1142,10 @@ where
         problem: &dyn Problem<State = T>,
     ) {
         let state = get!(node from_id, self).state.as_ref();
-        let next_state = Arc::new(problem.transition(state, decision));
+        let Some(next_state) = problem.transition_checked(state, decision) else {
+            return; // the transition rejected the decision: no child, no edge
+        };
+        let next_state = Arc::new(next_state);
         let cost = problem.transition_cost(state, next_state.as_ref(), decision);
 
         match self.next_l.entry(next_state.clone()) {
@@ -785,7 +1201,12 @@ where
             CompilationType::Relaxed => {
                 if curr_l.len() > input.max_width && self.layers.len() > 1 {
                     self._maybe_save_lel();
-                    self._relax(input, curr_l)
+                    if self.hybrid_threshold.is_some_and(|threshold| self.curr_depth < threshold) {
+                        self.hybridized = true;
+                        self._restrict(input, curr_l)
+                    } else {
+                        self._relax(input, curr_l)
+                    }
                 }
             },
         }
@@ -801,8 +1222,10 @@ where
             get!(node a, self).value_top
                 .cmp(&get!(node b, self).value_top)
                 .then_with(|| input.ranking.compare(get!(node a, self).state.as_ref(), get!(node b, self).state.as_ref()))
-                .reverse()
-        }); // reverse because greater means more likely to be kept
+                .then_with(|| input.ranking.tie_break(get!(node a, self).state.as_ref(), get!(node b, self).state.as_ref()))
+                .reverse() // reverse because greater means more likely to be kept
+                .then_with(|| a.0.cmp(&b.0)) // stable insertion-order fallback
+        });
 
         for drop_id in curr_l.iter().skip(input.max_width).copied() {
             get!(mut node drop_id, self).flags.set_deleted(true);
@@ -811,18 +1234,54 @@ where
         curr_l.truncate(input.max_width);
     }
 
-    #[allow(clippy::redundant_closure_call)]
     fn _relax(&mut self, input: &CompilationInput<T>, curr_l: &mut Vec<NodeId>) {
         curr_l.sort_unstable_by(|a, b| {
             get!(node a, self).value_top
                 .cmp(&get!(node b, self).value_top)
                 .then_with(|| input.ranking.compare(get!(node a, self).state.as_ref(), get!(node b, self).state.as_ref()))
-                .reverse()
-        }); // reverse because greater means more likely to be kept
+                .then_with(|| input.ranking.tie_break(get!(node a, self).state.as_ref(), get!(node b, self).state.as_ref()))
+                .reverse() // reverse because greater means more likely to be kept
+                .then_with(|| a.0.cmp(&b.0)) // stable insertion-order fallback
+        });
+
+        let states = curr_l.iter().map(|id| get!(node id, self).state.clone()).collect::<Vec<_>>();
+        let state_refs = states.iter().map(|state| state.as_ref()).collect::<Vec<_>>();
+        let groups = input.relaxation.select_merge_groups(&state_refs, input.max_width);
+
+        if Self::_is_default_grouping(&groups, input.max_width, curr_l.len()) {
+            self._relax_surplus(input, curr_l);
+        } else {
+            self._relax_groups(input, curr_l, groups);
+        }
+    }
+
+    /// Tells whether the given merge grouping is the default one (one single
+    /// group comprising all the surplus nodes), in which case the historic
+    /// merge code path is used
+    fn _is_default_grouping(groups: &[Vec<usize>], width: usize, layer_len: usize) -> bool {
+        groups.len() == 1 && groups[0].iter().copied().eq(width.saturating_sub(1)..layer_len)
+    }
 
+    /// This is the usual relaxation scheme: all the surplus nodes (beyond the
+    /// `max_width - 1` most promising ones) are merged into one single node
+    #[allow(clippy::redundant_closure_call)]
+    fn _relax_surplus(&mut self, input: &CompilationInput<T>, curr_l: &mut Vec<NodeId>) {
         //--
         let (keep, merge) = curr_l.split_at_mut(input.max_width - 1);
-        let merged = Arc::new(input.relaxation.merge(&mut merge.iter().map(|id| get!(node id, self).state.as_ref())));
+        let merged = input.relaxation.merge_opt_with_type(input.comp_type, &mut merge.iter().map(|id| get!(node id, self).state.as_ref()));
+
+        // when the merge signals that nothing feasible remains reachable
+        // from the merged node, the surplus nodes are simply dropped (just
+        // like a restriction would drop them) and their arcs are not
+        // redirected anywhere
+        let Some(merged) = merged else {
+            for drop_id in merge {
+                get!(mut node drop_id, self).flags.set_deleted(true);
+            }
+            curr_l.truncate(input.max_width - 1);
+            return;
+        };
+        let merged = Arc::new(merged);
 
         let recycled = keep.iter().find(|id| get!(node *id, self).state.eq(&merged)).copied();
 
@@ -845,15 +1304,16 @@ where
 
         get!(mut node merged_id, self).flags.set_relaxed(true);
 
-        for drop_id in merge {
+        let mut redirected = vec![];
+        for drop_id in merge.iter() {
             get!(mut node drop_id, self).flags.set_deleted(true);
 
             foreach!(edge of drop_id, self, |edge: Edge| {
                 let src   = get!(node edge.from, self).state.as_ref();
                 let dst   = get!(node edge.to,   self).state.as_ref();
-                let rcost = input.relaxation.relax(src, dst, merged.as_ref(), edge.decision, edge.cost);
+                let rcost = input.relaxation.relax_with_type(input.comp_type, src, dst, merged.as_ref(), edge.decision, edge.cost);
 
-                append_edge_to!(self, Edge {
+                redirected.push(Edge {
                     from: edge.from,
                     to: merged_id,
                     decision: edge.decision,
@@ -861,6 +1321,24 @@ where
                 });
             });
         }
+        // when the in-degree of the merged node is capped, only the edges
+        // realizing the best value at the merged node are retained (beware:
+        // this drops paths from the relaxed dd, see the documentation of
+        // `CompilationInput::max_in_degree`)
+        if let Some(cap) = input.max_in_degree {
+            if redirected.len() > cap {
+                redirected.sort_unstable_by_key(|edge| {
+                    std::cmp::Reverse(get!(node edge.from, self).value_top.saturating_add(edge.cost))
+                });
+                redirected.truncate(cap);
+            }
+        }
+        for edge in redirected {
+            append_edge_to!(self, edge);
+        }
+        self._inherit_representative(input, merged_id, merge);
+        #[cfg(debug_assertions)]
+        self._debug_check_merge(input, merged_id, merge);
 
         if recycled.is_some() {
             curr_l.truncate(input.max_width);
@@ -871,6 +1349,212 @@ where
             curr_l.push(merged_id);
         }
     }
+
+    /// Lets the relaxation pick the representative of a group of merged
+    /// nodes (see `Relaxation::select_representative`): unless the choice is
+    /// the default one (the maximum-value node of the group), the merged
+    /// node is made to inherit the best incoming arc of the chosen
+    /// representative instead of its overall maximum-value arc
+    fn _inherit_representative(&mut self, input: &CompilationInput<T>, merged_id: NodeId, group: &[NodeId]) {
+        let entries = group.iter()
+            .map(|id| (get!(node id, self).state.as_ref(), get!(node id, self).value_top))
+            .collect::<Vec<_>>();
+        let chosen = input.relaxation.select_representative(&mut entries.iter().copied());
+        let default = entries.iter().enumerate()
+            .max_by_key(|(_, (_, value))| *value)
+            .map(|(i, _)| i)
+            .unwrap_or(0);
+        // the default choice is precisely what the merge naturally yields:
+        // there is nothing to adjust in that case (nor when the returned
+        // index is out of bounds)
+        if chosen == default || chosen >= group.len() {
+            return;
+        }
+
+        let representative = group[chosen];
+        let Some(rep_best) = get!(node representative, self).best else { return };
+        let rep_edge = *get!(edge rep_best, self);
+
+        // find the redirected counterpart of the representative's best arc
+        // among the inbound arcs of the merged node (it may have been capped
+        // away, in which case the default parent is kept)
+        let mut found = None;
+        let mut list = get!(node merged_id, self).inbound;
+        while let EdgesList::Cons { head, tail } = *get!(edgelist list, self) {
+            let edge = *get!(edge head, self);
+            if edge.from.0 == rep_edge.from.0 && edge.decision == rep_edge.decision {
+                found = Some(head);
+                break;
+            }
+            list = tail;
+        }
+        if let Some(found) = found {
+            get!(mut node merged_id, self).best = Some(found);
+        }
+    }
+
+    /// This is the custom relaxation scheme: each group of nodes selected by
+    /// the relaxation (see `Relaxation::select_merge_groups`) is merged into
+    /// one single node while the ungrouped nodes are kept as they are
+    #[allow(clippy::redundant_closure_call)]
+    fn _relax_groups(&mut self, input: &CompilationInput<T>, curr_l: &mut Vec<NodeId>, groups: Vec<Vec<usize>>) {
+        // sanitize the user-provided groups: an index may belong to at most
+        // one group, must be in range, and the singleton groups are dropped
+        // (their node is simply kept in the layer)
+        let mut grouped = vec![false; curr_l.len()];
+        let mut sane_groups = vec![];
+        for group in groups {
+            let group = group.into_iter()
+                .filter(|i| *i < curr_l.len() && !std::mem::replace(&mut grouped[*i], true))
+                .collect::<Vec<_>>();
+            if group.len() >= 2 {
+                sane_groups.push(group);
+            } else if let Some(i) = group.first() {
+                grouped[*i] = false;
+            }
+        }
+
+        let mut merged_ids = vec![];
+        for group in sane_groups {
+            let merged = input.relaxation.merge_opt_with_type(input.comp_type, &mut group.iter().map(|i| get!(node curr_l[*i], self).state.as_ref()));
+
+            // when the merge signals that nothing feasible remains reachable
+            // from the merged node, the whole group is dropped and its arcs
+            // are not redirected anywhere
+            let Some(merged) = merged else {
+                for i in group {
+                    let drop_id = curr_l[i];
+                    get!(mut node drop_id, self).flags.set_deleted(true);
+                }
+                continue;
+            };
+            let merged = Arc::new(merged);
+
+            // when the merged state coincides with one of the kept nodes,
+            // that node is recycled rather than duplicated in the layer
+            let recycled = curr_l.iter().enumerate()
+                .filter(|(i, _)| !grouped[*i])
+                .find(|(_, id)| get!(node *id, self).state.eq(&merged))
+                .map(|(_, id)| *id);
+
+            let depth = get!(node curr_l[group[0]], self).depth;
+            let merged_id = recycled.unwrap_or_else(|| {
+                let node_id = NodeId(self.nodes.len());
+                self.nodes.push(Node {
+                    state: merged.clone(),
+                    value_top: isize::MIN,
+                    value_bot: isize::MIN,
+                    best: None,    // yet
+                    inbound: NIL,  // yet
+                    //
+                    rub: isize::MAX,
+                    theta: None,
+                    flags: NodeFlags::new_relaxed(),
+                    depth,
+                });
+                node_id
+            });
+
+            get!(mut node merged_id, self).flags.set_relaxed(true);
+
+            let drop_ids = group.iter().map(|i| curr_l[*i]).collect::<Vec<_>>();
+            let mut redirected = vec![];
+            for drop_id in drop_ids.iter().copied() {
+                get!(mut node drop_id, self).flags.set_deleted(true);
+
+                foreach!(edge of drop_id, self, |edge: Edge| {
+                    let src   = get!(node edge.from, self).state.as_ref();
+                    let dst   = get!(node edge.to,   self).state.as_ref();
+                    let rcost = input.relaxation.relax_with_type(input.comp_type, src, dst, merged.as_ref(), edge.decision, edge.cost);
+
+                    redirected.push(Edge {
+                        from: edge.from,
+                        to: merged_id,
+                        decision: edge.decision,
+                        cost: rcost
+                    });
+                });
+            }
+            // see the documentation of `CompilationInput::max_in_degree`
+            if let Some(cap) = input.max_in_degree {
+                if redirected.len() > cap {
+                    redirected.sort_unstable_by_key(|edge| {
+                        std::cmp::Reverse(get!(node edge.from, self).value_top.saturating_add(edge.cost))
+                    });
+                    redirected.truncate(cap);
+                }
+            }
+            for edge in redirected {
+                append_edge_to!(self, edge);
+            }
+            self._inherit_representative(input, merged_id, &drop_ids);
+            #[cfg(debug_assertions)]
+            self._debug_check_merge(input, merged_id, &drop_ids);
+
+            if recycled.is_none() {
+                merged_ids.push(merged_id);
+            }
+        }
+
+        let mut kept = curr_l.iter().enumerate()
+            .filter(|(i, _)| !grouped[*i])
+            .map(|(_, id)| *id)
+            .collect::<Vec<_>>();
+        kept.append(&mut merged_ids);
+        *curr_l = kept;
+    }
+
+    /// Debug-only sanity check: along the best exact path of the dd, every
+    /// node must have a rough upper bound which covers the value of that very
+    /// path -- an actual solution through the node. A violation proves that
+    /// `fast_upper_bound` is not an over-approximation of the best achievable
+    /// completion -- a bug which otherwise silently produces wrong optima.
+    /// (Note that the bound need *not* be monotone along the arcs: a looser
+    /// bound at a child than at its parent is perfectly legitimate, as the
+    /// mcp relaxation illustrates.) The offending state can be recovered by
+    /// replaying the best solution down to the reported depth.
+    #[cfg(debug_assertions)]
+    fn _debug_check_rub_covers_best_path(&self) {
+        if let Some(best_id) = self.best_exact_node {
+            let best_value = get!(node best_id, self).value_top;
+            let mut cursor = Some(best_id);
+            while let Some(id) = cursor {
+                let node = get!(node id, self);
+                if node.rub != isize::MAX {
+                    assert!(
+                        node.value_top.saturating_add(node.rub) >= best_value,
+                        "relaxation quality: the fast upper bound is not an over-approximation at depth {}: value at the node ({}) + bound ({}) < value of an exact solution through it ({})",
+                        node.depth, node.value_top, node.rub, best_value
+                    );
+                }
+                cursor = node.best.map(|e| get!(edge e, self).from);
+            }
+        }
+    }
+
+    /// Debug-only sanity check: the upper bound of a merged node must cover
+    /// the value of every member of the merged group, since every path
+    /// traversing a member must survive (possibly lengthened) through the
+    /// merged node. A violation means the merge/relax pair is not an actual
+    /// over-approximation -- a bug which otherwise silently produces wrong
+    /// optima. The check does not apply when the in-degree of the merged
+    /// node is capped, because capping legitimately drops paths.
+    #[cfg(debug_assertions)]
+    fn _debug_check_merge(&self, input: &CompilationInput<T>, merged_id: NodeId, group: &[NodeId]) {
+        if input.max_in_degree.is_some() {
+            return;
+        }
+        let merged = get!(node merged_id, self);
+        let bound = input.relaxation.fast_upper_bound_with_value(merged.state.as_ref(), merged.value_top, merged.depth);
+        for member_id in group.iter() {
+            let member = get!(node member_id, self);
+            assert!(
+                bound >= member.value_top,
+                "relaxation quality: at depth {}, the bound of the merged node ({}) does not cover the value of a merged member ({}): the merge is not an over-approximation",
+                merged.depth, bound, member.value_top
+            );
+        }
+    }
 }
 
 // ############################################################################
@@ -956,6 +1640,99 @@ where T: Debug + Eq + PartialEq + Hash + Clone {
         out
     }
 
+    /// Emits the compiled diagram as a JSON document, which is much easier to
+    /// post-process than the 'dot' output (e.g. to feed a custom web viewer).
+    /// The configuration flags are honored exactly as in `as_graphviz`:
+    /// `show_deleted` decides whether the nodes deleted by a restriction (and
+    /// their incident edges) appear in the output, while `show_value`,
+    /// `show_locb`, `show_rub` and `show_threshold` decide which attributes
+    /// are emitted along with each node.
+    pub fn as_json(&self, config: &VizConfig) -> String {
+        let mut out = String::new();
+        out.push_str("{\n\t\"nodes\": [\n");
+
+        let mut first = true;
+        for (id, node) in self.nodes.iter().enumerate() {
+            if !config.show_deleted && node.flags.is_deleted() {
+                continue;
+            }
+            if !first {
+                out.push_str(",\n");
+            }
+            first = false;
+            out.push_str(&self.json_node(id, config));
+        }
+
+        out.push_str("\n\t],\n\t\"edges\": [\n");
+        let mut first = true;
+        for (id, node) in self.nodes.iter().enumerate() {
+            if !config.show_deleted && node.flags.is_deleted() {
+                continue;
+            }
+            out.push_str(&self.json_edges_of(id, node, config, &mut first));
+        }
+
+        out.push_str("\n\t]\n}\n");
+        out
+    }
+
+    /// Creates the JSON representation of one single node
+    fn json_node(&self, id: usize, config: &VizConfig) -> String {
+        let node = &self.nodes[id];
+        let state = Self::json_escape(&format!("{:?}", node.state.as_ref()));
+
+        let mut out = format!("\t\t{{\"id\": {id}, \"state\": \"{state}\"");
+        if config.show_value {
+            out.push_str(&format!(", \"value\": {}", node.value_top));
+        }
+        if config.show_locb {
+            out.push_str(&format!(", \"locb\": {}", node.value_bot));
+        }
+        if config.show_rub {
+            out.push_str(&format!(", \"rub\": {}", node.rub));
+        }
+        if config.show_threshold {
+            match node.theta {
+                Some(theta) => out.push_str(&format!(", \"theta\": {theta}")),
+                None        => out.push_str(", \"theta\": null"),
+            }
+        }
+        out.push_str(&format!(
+            ", \"exact\": {}, \"merged\": {}, \"deleted\": {}, \"cutset\": {}}}",
+            node.flags.is_exact(),
+            node.flags.is_relaxed(),
+            node.flags.is_deleted(),
+            node.flags.is_cutset()
+        ));
+        out
+    }
+
+    #[allow(clippy::redundant_closure_call)]
+    /// Creates the JSON representation of the edges incident to one node
+    fn json_edges_of(&self, id: usize, node: &Node<T>, config: &VizConfig, first: &mut bool) -> String {
+        let mut out = String::new();
+        let best = node.best.map(|eid| *get!(edge eid, self));
+        foreach!(edge of NodeId(id), self, |edge: Edge| {
+            let Edge{from, to, decision, cost} = edge;
+            if config.show_deleted || !get!(node from, self).flags.is_deleted() {
+                if !*first {
+                    out.push_str(",\n");
+                }
+                *first = false;
+                out.push_str(&format!(
+                    "\t\t{{\"from\": {}, \"to\": {}, \"variable\": {}, \"value\": {}, \"cost\": {}, \"best\": {}}}",
+                    from.0, to.0, decision.variable.0, decision.value, cost, Some(edge) == best
+                ));
+            }
+        });
+        out
+    }
+
+    /// Escapes a string so that it can be embedded in a JSON document
+    fn json_escape(s: &str) -> String {
+        s.replace('\\', "\\\\").replace('"', "\\\"")
+    }
+
     /// Creates a string representation of one single node
     fn node(&self, id: usize, config: &VizConfig) -> String {
         let attributes = self.node_attributes(id, config);
@@ -1117,6 +1894,8 @@ mod test_default_mdd {
         let dominance = EmptyDominanceChecker::default();
         let mut input = CompilationInput {
             comp_type: crate::CompilationType::Exact,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
@@ -1154,6 +1933,8 @@ mod test_default_mdd {
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Exact,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
@@ -1185,21 +1966,23 @@ mod test_default_mdd {
     }
 
     #[test]
-    fn restricted_drops_the_less_interesting_nodes() {
+    fn best_k_solutions_enumerates_the_k_best_terminal_paths() {
         let cache = EmptyCache::new();
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
-            comp_type: crate::CompilationType::Restricted,
+            comp_type: crate::CompilationType::Exact,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
             cutoff:     &NoCutoff,
-            max_width:  1,
+            max_width:  usize::MAX,
             best_lb:    isize::MIN,
-            residual: &SubProblem { 
-                state: Arc::new(DummyState{depth: 0, value: 0}), 
-                value: 0, 
-                path:  vec![], 
+            residual: &SubProblem {
+                state: Arc::new(DummyState{depth: 0, value: 0}),
+                value: 0,
+                path:  vec![],
                 ub:    isize::MAX,
                 depth: 0,
             },
@@ -1209,23 +1992,26 @@ mod test_default_mdd {
         let mut mdd = DefaultMDD::new();
 
         assert!(mdd.compile(&input).is_ok());
-        assert!(mdd.best_solution().is_some());
-        assert_eq!(mdd.best_value().unwrap(), 6);
-        assert_eq!(mdd.best_solution().unwrap(),
-                   vec![
-                       Decision{variable: Variable(2), value: 2},
-                       Decision{variable: Variable(1), value: 2},
-                       Decision{variable: Variable(0), value: 2},
-                   ]
-        );
+        // the single best path is worth 2+2+2 and the three runners-up each
+        // trade one of the decisions for a 1
+        let k_best = mdd.best_k_solutions(4);
+        let values = k_best.iter().map(|entry| entry.0).collect::<Vec<_>>();
+        assert_eq!(vec![6, 5, 5, 5], values);
+        for (i, (_, a)) in k_best.iter().enumerate() {
+            for (_, b) in k_best.iter().skip(i + 1) {
+                assert_ne!(a, b);
+            }
+        }
     }
 
     #[test]
-    fn exact_no_cutoff_completion_must_be_coherent_with_outcome() {
+    fn restricted_drops_the_less_interesting_nodes() {
         let cache = EmptyCache::new();
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
-            comp_type: crate::CompilationType::Exact,
+            comp_type: crate::CompilationType::Restricted,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
@@ -1243,27 +2029,260 @@ mod test_default_mdd {
             dominance: &dominance,
         };
         let mut mdd = DefaultMDD::new();
-        let result = mdd.compile(&input);
 
-        assert!(result.is_ok());
-        let completion = result.unwrap();
-        assert_eq!(completion.is_exact  , mdd.is_exact());
-        assert_eq!(completion.best_value, mdd.best_value());
+        assert!(mdd.compile(&input).is_ok());
+        assert!(mdd.best_solution().is_some());
+        assert_eq!(mdd.best_value().unwrap(), 6);
+        assert_eq!(mdd.best_solution().unwrap(),
+                   vec![
+                       Decision{variable: Variable(2), value: 2},
+                       Decision{variable: Variable(1), value: 2},
+                       Decision{variable: Variable(0), value: 2},
+                   ]
+        );
     }
+
     #[test]
-    fn restricted_no_cutoff_completion_must_be_coherent_with_outcome_() {
+    fn the_default_domain_iter_yields_the_same_values_as_for_each_in_domain() {
+        let state = DummyProblem.initial_state();
+        let mut eager = vec![];
+        DummyProblem.for_each_in_domain(Variable(0), &state, &mut |d: Decision| eager.push(d.value));
+        let lazy = DummyProblem.domain_iter(Variable(0), &state).collect::<Vec<_>>();
+        assert_eq!(eager, lazy);
+    }
+
+    #[test]
+    fn a_lazy_domain_is_cut_short_by_a_restricted_compilation() {
         let cache = EmptyCache::new();
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Restricted,
-            problem:    &DummyProblem,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
+            problem:    &LazyDummyProblem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
             cutoff:     &NoCutoff,
             max_width:  1,
             best_lb:    isize::MIN,
-            residual: &SubProblem { 
-                state: Arc::new(DummyState{depth: 0, value: 0}), 
+            residual: &SubProblem {
+                state: Arc::new(DummyState{depth: 0, value: 0}),
+                value: 0,
+                path:  vec![],
+                ub:    isize::MAX,
+                depth: 0,
+            },
+            cache: &cache,
+            dominance: &dominance,
+        };
+        let mut mdd = DefaultMDD::new();
+
+        assert!(mdd.compile(&input).is_ok());
+        // once the (only) slot of the next layer is taken, the rest of the
+        // domain is never drawn: the nodes that survive are the first
+        // generated ones, and the dd is inexact just like after a restriction
+        assert!(!mdd.is_exact());
+        assert!(mdd.best_solution().is_some());
+        assert_eq!(mdd.best_value().unwrap(), 0);
+        assert_eq!(mdd.best_solution().unwrap(),
+                   vec![
+                       Decision{variable: Variable(2), value: 0},
+                       Decision{variable: Variable(1), value: 0},
+                       Decision{variable: Variable(0), value: 0},
+                   ]
+        );
+    }
+
+    #[test]
+    fn a_lazy_domain_is_fully_explored_by_an_exact_compilation() {
+        let cache = EmptyCache::new();
+        let dominance = EmptyDominanceChecker::default();
+        let input = CompilationInput {
+            comp_type: crate::CompilationType::Exact,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
+            problem:    &LazyDummyProblem,
+            relaxation: &DummyRelax,
+            ranking:    &DummyRanking,
+            cutoff:     &NoCutoff,
+            max_width:  usize::MAX,
+            best_lb:    isize::MIN,
+            residual: &SubProblem {
+                state: Arc::new(DummyState{depth: 0, value: 0}),
+                value: 0,
+                path:  vec![],
+                ub:    isize::MAX,
+                depth: 0,
+            },
+            cache: &cache,
+            dominance: &dominance,
+        };
+        let mut mdd = DefaultMDD::new();
+
+        assert!(mdd.compile(&input).is_ok());
+        assert!(mdd.is_exact());
+        assert_eq!(mdd.best_value().unwrap(), 6);
+    }
+
+    #[test]
+    fn a_path_dependent_domain_receives_the_accumulated_path() {
+        let cache = EmptyCache::new();
+        let dominance = EmptyDominanceChecker::default();
+        let input = CompilationInput {
+            comp_type: crate::CompilationType::Exact,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
+            problem:    &PathDummyProblem,
+            relaxation: &DummyRelax,
+            ranking:    &DummyRanking,
+            cutoff:     &NoCutoff,
+            max_width:  usize::MAX,
+            best_lb:    isize::MIN,
+            residual: &SubProblem {
+                state: Arc::new(DummyState{depth: 0, value: 0}),
+                value: 0,
+                path:  vec![],
+                ub:    isize::MAX,
+                depth: 0,
+            },
+            cache: &cache,
+            dominance: &dominance,
+        };
+        let mut mdd = DefaultMDD::new();
+
+        assert!(mdd.compile(&input).is_ok());
+        assert!(mdd.is_exact());
+        // each value may only be used once along a path: the best assignment
+        // is a permutation of {0, 1, 2} rather than the unconstrained 2+2+2
+        assert_eq!(mdd.best_value().unwrap(), 3);
+        let mut values = mdd.best_solution().unwrap().iter()
+            .map(|d| d.value).collect::<Vec<_>>();
+        values.sort_unstable();
+        assert_eq!(values, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn a_rejected_transition_creates_neither_node_nor_edge() {
+        let cache = EmptyCache::new();
+        let dominance = EmptyDominanceChecker::default();
+        let input = CompilationInput {
+            comp_type: crate::CompilationType::Exact,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
+            problem:    &CheckedDummyProblem,
+            relaxation: &DummyRelax,
+            ranking:    &DummyRanking,
+            cutoff:     &NoCutoff,
+            max_width:  usize::MAX,
+            best_lb:    isize::MIN,
+            residual: &SubProblem {
+                state: Arc::new(DummyState{depth: 0, value: 0}),
+                value: 0,
+                path:  vec![],
+                ub:    isize::MAX,
+                depth: 0,
+            },
+            cache: &cache,
+            dominance: &dominance,
+        };
+        let mut mdd = DefaultMDD::new();
+
+        assert!(mdd.compile(&input).is_ok());
+        assert!(mdd.is_exact());
+        // the transition rejects the decisions assigning 2: the unconstrained
+        // optimum 2+2+2 is unreachable and the best path assigns 1 throughout
+        assert_eq!(mdd.best_value().unwrap(), 3);
+        assert!(mdd.best_solution().unwrap().iter().all(|d| d.value <= 1));
+    }
+
+    #[test]
+    fn a_static_order_replaces_the_calls_to_next_variable() {
+        let cache = EmptyCache::new();
+        let dominance = EmptyDominanceChecker::default();
+        let input = CompilationInput {
+            comp_type: crate::CompilationType::Exact,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
+            problem:    &StaticOrderDummyProblem,
+            relaxation: &DummyRelax,
+            ranking:    &DummyRanking,
+            cutoff:     &NoCutoff,
+            max_width:  usize::MAX,
+            best_lb:    isize::MIN,
+            residual: &SubProblem {
+                state: Arc::new(DummyState{depth: 0, value: 0}),
+                value: 0,
+                path:  vec![],
+                ub:    isize::MAX,
+                depth: 0,
+            },
+            cache: &cache,
+            dominance: &dominance,
+        };
+        let mut mdd = DefaultMDD::new();
+
+        // the fixture panics whenever next_variable is consulted: compiling
+        // succeeds only because the compiler indexes into the static order
+        assert!(mdd.compile(&input).is_ok());
+        assert!(mdd.is_exact());
+        assert_eq!(mdd.best_value().unwrap(), 6);
+        assert_eq!(mdd.best_solution().unwrap(),
+                   vec![
+                       Decision{variable: Variable(0), value: 2},
+                       Decision{variable: Variable(1), value: 2},
+                       Decision{variable: Variable(2), value: 2},
+                   ]
+        );
+    }
+
+    #[test]
+    fn exact_no_cutoff_completion_must_be_coherent_with_outcome() {
+        let cache = EmptyCache::new();
+        let dominance = EmptyDominanceChecker::default();
+        let input = CompilationInput {
+            comp_type: crate::CompilationType::Exact,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
+            problem:    &DummyProblem,
+            relaxation: &DummyRelax,
+            ranking:    &DummyRanking,
+            cutoff:     &NoCutoff,
+            max_width:  1,
+            best_lb:    isize::MIN,
+            residual: &SubProblem { 
+                state: Arc::new(DummyState{depth: 0, value: 0}), 
+                value: 0, 
+                path:  vec![], 
+                ub:    isize::MAX,
+                depth: 0,
+            },
+            cache: &cache,
+            dominance: &dominance,
+        };
+        let mut mdd = DefaultMDD::new();
+        let result = mdd.compile(&input);
+
+        assert!(result.is_ok());
+        let completion = result.unwrap();
+        assert_eq!(completion.is_exact  , mdd.is_exact());
+        assert_eq!(completion.best_value, mdd.best_value());
+    }
+    #[test]
+    fn restricted_no_cutoff_completion_must_be_coherent_with_outcome_() {
+        let cache = EmptyCache::new();
+        let dominance = EmptyDominanceChecker::default();
+        let input = CompilationInput {
+            comp_type: crate::CompilationType::Restricted,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
+            problem:    &DummyProblem,
+            relaxation: &DummyRelax,
+            ranking:    &DummyRanking,
+            cutoff:     &NoCutoff,
+            max_width:  1,
+            best_lb:    isize::MIN,
+            residual: &SubProblem { 
+                state: Arc::new(DummyState{depth: 0, value: 0}), 
                 value: 0, 
                 path:  vec![], 
                 ub:    isize::MAX,
@@ -1286,6 +2305,8 @@ mod test_default_mdd {
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Relaxed,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
@@ -1322,6 +2343,8 @@ mod test_default_mdd {
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Exact,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
@@ -1350,6 +2373,8 @@ mod test_default_mdd {
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Restricted,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
@@ -1377,6 +2402,8 @@ mod test_default_mdd {
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Relaxed,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
@@ -1405,6 +2432,8 @@ mod test_default_mdd {
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Relaxed,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
@@ -1437,21 +2466,23 @@ mod test_default_mdd {
     }
 
     #[test]
-    fn relaxed_populates_the_cutset_and_will_not_squash_first_layer() {
+    fn relaxed_caps_the_in_degree_of_merged_nodes_when_asked_to() {
         let cache = EmptyCache::new();
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Relaxed,
+            max_in_degree: Some(1),
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
             cutoff:     &NoCutoff,
             max_width:  1,
             best_lb:    isize::MIN,
-            residual:  &SubProblem { 
-                state: Arc::new(DummyState{depth: 0, value: 0}), 
-                value: 0, 
-                path:  vec![], 
+            residual:  &SubProblem {
+                state: Arc::new(DummyState{depth: 0, value: 0}),
+                value: 0,
+                path:  vec![],
                 ub:    isize::MAX,
                 depth: 0,
             },
@@ -1460,29 +2491,35 @@ mod test_default_mdd {
         };
         let mut mdd = DefaultMDD::new();
         let result = mdd.compile(&input);
+
+        // only the incoming edge realizing the best value at the merged node
+        // is retained: the dd remains usable and yields the same longest path
+        // as the uncapped relaxation on this toy instance
         assert!(result.is_ok());
-        
-        let mut cutset = vec![];
-        mdd.drain_cutset(|n| cutset.push(n));
-        assert_eq!(cutset.len(), 3); // L1 was not squashed even though it was 3 wide
+        assert!(mdd.best_solution().is_some());
+        assert_eq!(mdd.best_value().unwrap(), 24);
     }
 
     #[test]
-    fn an_exact_mdd_must_be_exact() {
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "relaxation quality")]
+    fn debug_checks_catch_a_fast_upper_bound_which_underestimates() {
         let cache = EmptyCache::new();
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Exact,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
-            relaxation: &DummyRelax,
+            relaxation: &UnderestimatingRubRelax,
             ranking:    &DummyRanking,
             cutoff:     &NoCutoff,
-            max_width:  1,
+            max_width:  usize::MAX,
             best_lb:    isize::MIN,
-            residual:  &SubProblem { 
-                state: Arc::new(DummyState{depth: 0, value: 0}), 
-                value: 0, 
-                path:  vec![], 
+            residual:  &SubProblem {
+                state: Arc::new(DummyState{depth: 0, value: 0}),
+                value: 0,
+                path:  vec![],
                 ub:    isize::MAX,
                 depth: 0,
             },
@@ -1490,28 +2527,29 @@ mod test_default_mdd {
             dominance: &dominance,
         };
         let mut mdd = DefaultMDD::new();
-        let result = mdd.compile(&input);
-        assert!(result.is_ok());
-        
-        assert!(mdd.is_exact())
+        let _ = mdd.compile(&input);
     }
 
     #[test]
-    fn a_relaxed_mdd_is_exact_as_long_as_no_merge_occurs() {
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "relaxation quality")]
+    fn debug_checks_catch_a_merge_which_loses_value() {
         let cache = EmptyCache::new();
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Relaxed,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
-            relaxation: &DummyRelax,
+            relaxation: &ShorteningMergeRelax,
             ranking:    &DummyRanking,
             cutoff:     &NoCutoff,
-            max_width:  10,
+            max_width:  1,
             best_lb:    isize::MIN,
-            residual:  &SubProblem { 
-                state: Arc::new(DummyState{depth: 0, value: 0}), 
-                value: 0, 
-                path:  vec![], 
+            residual:  &SubProblem {
+                state: Arc::new(DummyState{depth: 0, value: 0}),
+                value: 0,
+                path:  vec![],
                 ub:    isize::MAX,
                 depth: 0,
             },
@@ -1519,28 +2557,27 @@ mod test_default_mdd {
             dominance: &dominance,
         };
         let mut mdd = DefaultMDD::new();
-        let result = mdd.compile(&input);
-        assert!(result.is_ok());
-        
-        assert!(mdd.is_exact())
+        let _ = mdd.compile(&input);
     }
 
     #[test]
-    fn a_relaxed_mdd_is_not_exact_when_a_merge_occurred() {
+    fn relaxed_uses_the_merge_groups_selected_by_the_relaxation() {
         let cache = EmptyCache::new();
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Relaxed,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
-            relaxation: &DummyRelax,
+            relaxation: &PairMergeRelax,
             ranking:    &DummyRanking,
             cutoff:     &NoCutoff,
-            max_width:  1,
+            max_width:  2,
             best_lb:    isize::MIN,
-            residual:  &SubProblem { 
-                state: Arc::new(DummyState{depth: 0, value: 0}), 
-                value: 0, 
-                path:  vec![], 
+            residual:  &SubProblem {
+                state: Arc::new(DummyState{depth: 0, value: 0}),
+                value: 0,
+                path:  vec![],
                 ub:    isize::MAX,
                 depth: 0,
             },
@@ -1549,26 +2586,33 @@ mod test_default_mdd {
         };
         let mut mdd = DefaultMDD::new();
         let result = mdd.compile(&input);
+
+        // merging only the two least promising nodes is a milder relaxation
+        // than squashing all the surplus into one: the dd remains a valid
+        // relaxation and its longest path is an upper bound on the optimum
         assert!(result.is_ok());
-        
-        assert!(!mdd.is_exact())
+        assert!(mdd.best_value().is_some());
+        assert!(mdd.best_value().unwrap() >= 6);
     }
+
     #[test]
-    fn a_restricted_mdd_is_exact_as_long_as_no_restriction_occurs() {
+    fn relaxed_drops_the_merged_node_when_the_merge_is_infeasible() {
         let cache = EmptyCache::new();
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
-            comp_type: crate::CompilationType::Restricted,
+            comp_type: crate::CompilationType::Relaxed,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
-            relaxation: &DummyRelax,
+            relaxation: &InfeasibleMergeRelax,
             ranking:    &DummyRanking,
             cutoff:     &NoCutoff,
-            max_width:  10,
+            max_width:  2,
             best_lb:    isize::MIN,
-            residual:  &SubProblem { 
-                state: Arc::new(DummyState{depth: 0, value: 0}), 
-                value: 0, 
-                path:  vec![], 
+            residual:  &SubProblem {
+                state: Arc::new(DummyState{depth: 0, value: 0}),
+                value: 0,
+                path:  vec![],
                 ub:    isize::MAX,
                 depth: 0,
             },
@@ -1577,26 +2621,42 @@ mod test_default_mdd {
         };
         let mut mdd = DefaultMDD::new();
         let result = mdd.compile(&input);
+
+        // the surplus nodes are dropped rather than merged: the dd behaves
+        // like a restriction (only the most promising node of the squashed
+        // layer survives) and the longest path goes through that node. Since
+        // that path traverses no merged node, it is an exact best path
         assert!(result.is_ok());
-        
-        assert!(mdd.is_exact())
+        assert!(mdd.is_exact());
+        assert!(mdd.best_solution().is_some());
+        assert_eq!(mdd.best_value().unwrap(), 6);
+        assert_eq!(mdd.best_solution().unwrap(),
+                   vec![
+                       Decision{variable: Variable(2), value: 2},
+                       Decision{variable: Variable(1), value: 2},
+                       Decision{variable: Variable(0), value: 2},
+                   ]
+        );
     }
+
     #[test]
-    fn a_restricted_mdd_is_not_exact_when_a_restriction_occurred() {
+    fn relaxed_survives_an_infeasible_merge_wiping_the_whole_layer() {
         let cache = EmptyCache::new();
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Relaxed,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
-            relaxation: &DummyRelax,
+            relaxation: &InfeasibleMergeRelax,
             ranking:    &DummyRanking,
             cutoff:     &NoCutoff,
             max_width:  1,
             best_lb:    isize::MIN,
-            residual:  &SubProblem { 
-                state: Arc::new(DummyState{depth: 0, value: 0}), 
-                value: 0, 
-                path:  vec![], 
+            residual:  &SubProblem {
+                state: Arc::new(DummyState{depth: 0, value: 0}),
+                value: 0,
+                path:  vec![],
                 ub:    isize::MAX,
                 depth: 0,
             },
@@ -1605,21 +2665,26 @@ mod test_default_mdd {
         };
         let mut mdd = DefaultMDD::new();
         let result = mdd.compile(&input);
+
+        // with a width of one, the squashed layer is dropped in its entirety:
+        // the compilation ends gracefully with no solution at all
         assert!(result.is_ok());
-        
-        assert!(!mdd.is_exact())
+        assert!(mdd.best_solution().is_none());
     }
+
     #[test]
-    fn when_the_problem_is_infeasible_there_is_no_solution() {
+    fn relaxed_populates_the_cutset_and_will_not_squash_first_layer() {
         let cache = EmptyCache::new();
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
-            comp_type: crate::CompilationType::Exact,
-            problem:    &DummyInfeasibleProblem,
-            relaxation: &DummyRelax,
+            comp_type: crate::CompilationType::Relaxed,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
+            problem:    &DummyProblem,
+            relaxation: &DummyRelax,
             ranking:    &DummyRanking,
             cutoff:     &NoCutoff,
-            max_width:  usize::MAX,
+            max_width:  1,
             best_lb:    isize::MIN,
             residual:  &SubProblem { 
                 state: Arc::new(DummyState{depth: 0, value: 0}), 
@@ -1634,19 +2699,25 @@ mod test_default_mdd {
         let mut mdd = DefaultMDD::new();
         let result = mdd.compile(&input);
         assert!(result.is_ok());
-        assert!(mdd.best_solution().is_none())
+        
+        let mut cutset = vec![];
+        mdd.drain_cutset(|n| cutset.push(n));
+        assert_eq!(cutset.len(), 3); // L1 was not squashed even though it was 3 wide
     }
+
     #[test]
-    fn when_the_problem_is_infeasible_there_is_no_best_value() {
+    fn an_exact_mdd_must_be_exact() {
         let cache = EmptyCache::new();
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Exact,
-            problem:    &DummyInfeasibleProblem,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
+            problem:    &DummyProblem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
             cutoff:     &NoCutoff,
-            max_width:  usize::MAX,
+            max_width:  1,
             best_lb:    isize::MIN,
             residual:  &SubProblem { 
                 state: Arc::new(DummyState{depth: 0, value: 0}), 
@@ -1661,24 +2732,29 @@ mod test_default_mdd {
         let mut mdd = DefaultMDD::new();
         let result = mdd.compile(&input);
         assert!(result.is_ok());
-        assert!(mdd.best_value().is_none())
+
+        assert!(mdd.is_exact())
     }
+
     #[test]
-    fn exact_skips_node_with_an_ub_less_than_best_known_lb() {
+    fn a_leaf_state_terminates_its_path_early() {
+        let problem = LeafDummyProblem { leaf_threshold: 4 };
         let cache = EmptyCache::new();
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Exact,
-            problem:    &DummyProblem,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
+            problem:    &problem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
             cutoff:     &NoCutoff,
             max_width:  usize::MAX,
-            best_lb:    1000,
-            residual: &SubProblem { 
-                state: Arc::new(DummyState{depth: 0, value: 0}), 
-                value: 0, 
-                path:  vec![], 
+            best_lb:    isize::MIN,
+            residual:  &SubProblem {
+                state: Arc::new(DummyState{depth: 0, value: 0}),
+                value: 0,
+                path:  vec![],
                 ub:    isize::MAX,
                 depth: 0,
             },
@@ -1688,24 +2764,34 @@ mod test_default_mdd {
         let mut mdd = DefaultMDD::new();
         let result = mdd.compile(&input);
         assert!(result.is_ok());
-        assert!(mdd.best_solution().is_none())
+        assert!(mdd.is_exact());
+
+        // the (2, 2) path reaches the value 4 after two decisions and gets
+        // terminated there: the optimum is now achieved by the paths keeping
+        // their depth-2 value below the leaf threshold, e.g. (2, 1, 2)
+        assert_eq!(Some(5), mdd.best_value());
+        assert_eq!(3, mdd.best_solution().unwrap().len());
     }
+
     #[test]
-    fn relaxed_skips_node_with_an_ub_less_than_best_known_lb() {
+    fn by_default_a_dead_end_is_not_a_solution() {
+        let problem = DeadEndDummyProblem { always_feasible: false };
         let cache = EmptyCache::new();
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
-            comp_type: crate::CompilationType::Relaxed,
-            problem:    &DummyProblem,
+            comp_type: crate::CompilationType::Exact,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
+            problem:    &problem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
             cutoff:     &NoCutoff,
             max_width:  usize::MAX,
-            best_lb:    1000,
-            residual: &SubProblem { 
-                state: Arc::new(DummyState{depth: 0, value: 0}), 
-                value: 0, 
-                path:  vec![], 
+            best_lb:    isize::MIN,
+            residual:  &SubProblem {
+                state: Arc::new(DummyState{depth: 0, value: 0}),
+                value: 0,
+                path:  vec![],
                 ub:    isize::MAX,
                 depth: 0,
             },
@@ -1715,24 +2801,32 @@ mod test_default_mdd {
         let mut mdd = DefaultMDD::new();
         let result = mdd.compile(&input);
         assert!(result.is_ok());
-        assert!(mdd.best_solution().is_none())
+
+        // the path taking 5 at the first variable hits a dead end and its
+        // value is lost: the only surviving path is the all-zero one
+        assert_eq!(Some(0), mdd.best_value());
+        assert_eq!(3, mdd.best_solution().unwrap().len());
     }
+
     #[test]
-    fn restricted_skips_node_with_an_ub_less_than_best_known_lb() {
+    fn an_always_feasible_dead_end_yields_an_incumbent() {
+        let problem = DeadEndDummyProblem { always_feasible: true };
         let cache = EmptyCache::new();
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
-            comp_type: crate::CompilationType::Restricted,
-            problem:    &DummyProblem,
+            comp_type: crate::CompilationType::Exact,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
+            problem:    &problem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
             cutoff:     &NoCutoff,
             max_width:  usize::MAX,
-            best_lb:    1000,
-            residual: &SubProblem { 
-                state: Arc::new(DummyState{depth: 0, value: 0}), 
-                value: 0, 
-                path:  vec![], 
+            best_lb:    isize::MIN,
+            residual:  &SubProblem {
+                state: Arc::new(DummyState{depth: 0, value: 0}),
+                value: 0,
+                path:  vec![],
                 ub:    isize::MAX,
                 depth: 0,
             },
@@ -1742,90 +2836,92 @@ mod test_default_mdd {
         let mut mdd = DefaultMDD::new();
         let result = mdd.compile(&input);
         assert!(result.is_ok());
-        assert!(mdd.best_solution().is_none())
+        assert!(mdd.is_exact());
+
+        // the dead end reached by taking 5 at the first variable now counts
+        // as a terminal: its value beats the all-zero completion
+        assert_eq!(Some(5), mdd.best_value());
+        assert_eq!(1, mdd.best_solution().unwrap().len());
     }
+
     #[test]
-    fn exact_skips_nodes_with_a_value_less_than_known_threshold() {
-        let mut cache = SimpleCache::default();
-        cache.initialize(&DummyProblem);
-        cache.update_threshold(Arc::new(DummyState{depth: 1, value: 0}), 1, 0, true);
-        cache.update_threshold(Arc::new(DummyState{depth: 1, value: 1}), 1, 1, true);
-        cache.update_threshold(Arc::new(DummyState{depth: 1, value: 2}), 1, 2, true);
+    fn measure_exact_counts_the_nodes_and_edges_of_the_exact_dd() {
+        let cache = EmptyCache::new();
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Exact,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
             cutoff:     &NoCutoff,
             max_width:  usize::MAX,
             best_lb:    isize::MIN,
-            residual: &SubProblem { 
-                state: Arc::new(DummyState{depth: 0, value: 0}), 
-                value: 0, 
-                path:  vec![], 
+            residual:  &SubProblem {
+                state: Arc::new(DummyState{depth: 0, value: 0}),
+                value: 0,
+                path:  vec![],
                 ub:    isize::MAX,
                 depth: 0,
             },
             cache: &cache,
             dominance: &dominance,
         };
-        let mut mdd = DefaultMDD::new();
-        let result = mdd.compile(&input);
-        assert!(result.is_ok());
-        assert!(mdd.best_solution().is_none())
+        // with 3 ternary variables and merged states, the layers comprise
+        // 1, 3, 5 and 7 nodes, connected by 3, 9 and 15 edges
+        assert_eq!((16, 27), DefaultMDD::measure_exact(&input));
     }
+
     #[test]
-    fn relaxed_skips_nodes_with_a_value_less_than_known_threshold() {
-        let mut cache = SimpleCache::default();
-        cache.initialize(&DummyProblem);
-        cache.update_threshold(Arc::new(DummyState{depth: 1, value: 0}), 1, 0, true);
-        cache.update_threshold(Arc::new(DummyState{depth: 1, value: 1}), 1, 1, true);
-        cache.update_threshold(Arc::new(DummyState{depth: 1, value: 2}), 1, 2, true);
+    fn measure_exact_does_not_branch_on_leaf_states() {
+        let problem = LeafDummyProblem { leaf_threshold: 4 };
+        let cache = EmptyCache::new();
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
-            comp_type: crate::CompilationType::Relaxed,
-            problem:    &DummyProblem,
+            comp_type: crate::CompilationType::Exact,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
+            problem:    &problem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
             cutoff:     &NoCutoff,
             max_width:  usize::MAX,
             best_lb:    isize::MIN,
-            residual: &SubProblem { 
-                state: Arc::new(DummyState{depth: 0, value: 0}), 
-                value: 0, 
-                path:  vec![], 
+            residual:  &SubProblem {
+                state: Arc::new(DummyState{depth: 0, value: 0}),
+                value: 0,
+                path:  vec![],
                 ub:    isize::MAX,
                 depth: 0,
             },
             cache: &cache,
             dominance: &dominance,
         };
-        let mut mdd = DefaultMDD::new();
-        let result = mdd.compile(&input);
-        assert!(result.is_ok());
-        assert!(mdd.best_solution().is_none())
+        // the depth-2 state of value 4 is a leaf: it contributes no edge
+        // and the last layer only spans the values 0..=5
+        assert_eq!((15, 24), DefaultMDD::measure_exact(&input));
     }
+
     #[test]
-    fn restricted_skips_nodes_with_a_value_less_than_known_threshold() {
-        let mut cache = SimpleCache::default();
-        cache.initialize(&DummyProblem);
-        cache.update_threshold(Arc::new(DummyState{depth: 1, value: 0}), 1, 0, true);
-        cache.update_threshold(Arc::new(DummyState{depth: 1, value: 1}), 1, 1, true);
-        cache.update_threshold(Arc::new(DummyState{depth: 1, value: 2}), 1, 2, true);
+    fn a_leaf_root_makes_the_whole_problem_a_leaf() {
+        let problem = LeafDummyProblem { leaf_threshold: 0 };
+        let cache = EmptyCache::new();
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
-            comp_type: crate::CompilationType::Restricted,
-            problem:    &DummyProblem,
+            comp_type: crate::CompilationType::Exact,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
+            problem:    &problem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
             cutoff:     &NoCutoff,
             max_width:  usize::MAX,
             best_lb:    isize::MIN,
-            residual: &SubProblem { 
-                state: Arc::new(DummyState{depth: 0, value: 0}), 
-                value: 0, 
-                path:  vec![], 
+            residual:  &SubProblem {
+                state: Arc::new(DummyState{depth: 0, value: 0}),
+                value: 0,
+                path:  vec![],
                 ub:    isize::MAX,
                 depth: 0,
             },
@@ -1835,16 +2931,22 @@ mod test_default_mdd {
         let mut mdd = DefaultMDD::new();
         let result = mdd.compile(&input);
         assert!(result.is_ok());
-        assert!(mdd.best_solution().is_none())
+        assert!(mdd.is_exact());
+
+        // the root itself is a leaf: its value is final and no decision is
+        // ever taken
+        assert_eq!(Some(0), mdd.best_value());
+        assert_eq!(0, mdd.best_solution().unwrap().len());
     }
 
     #[test]
-    fn restricted_mdd_computes_thresholds_when_exact() {
-        let mut cache = SimpleCache::default();
-        cache.initialize(&DummyProblem);
+    fn a_relaxed_mdd_is_exact_as_long_as_no_merge_occurs() {
+        let cache = EmptyCache::new();
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
-            comp_type: crate::CompilationType::Restricted,
+            comp_type: crate::CompilationType::Relaxed,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
@@ -1865,44 +2967,22 @@ mod test_default_mdd {
         let result = mdd.compile(&input);
         assert!(result.is_ok());
         
-        assert!(mdd.is_exact());
-
-        let expected = vec![
-            (DummyState{depth: 0, value: 0}, Some(Threshold {value: 0, explored: true})),
-            (DummyState{depth: 1, value: 0}, Some(Threshold {value: 2, explored: true})),
-            (DummyState{depth: 1, value: 1}, Some(Threshold {value: 2, explored: true})),
-            (DummyState{depth: 1, value: 2}, Some(Threshold {value: 2, explored: true})),
-            (DummyState{depth: 2, value: 0}, Some(Threshold {value: 4, explored: true})),
-            (DummyState{depth: 2, value: 1}, Some(Threshold {value: 4, explored: true})),
-            (DummyState{depth: 2, value: 2}, Some(Threshold {value: 4, explored: true})),
-            (DummyState{depth: 2, value: 3}, Some(Threshold {value: 4, explored: true})),
-            (DummyState{depth: 2, value: 4}, Some(Threshold {value: 4, explored: true})),
-            (DummyState{depth: 3, value: 0}, Some(Threshold {value: 6, explored: true})),
-            (DummyState{depth: 3, value: 1}, Some(Threshold {value: 6, explored: true})),
-            (DummyState{depth: 3, value: 2}, Some(Threshold {value: 6, explored: true})),
-            (DummyState{depth: 3, value: 3}, Some(Threshold {value: 6, explored: true})),
-            (DummyState{depth: 3, value: 4}, Some(Threshold {value: 6, explored: true})),
-            (DummyState{depth: 3, value: 5}, Some(Threshold {value: 6, explored: true})),
-            (DummyState{depth: 3, value: 6}, Some(Threshold {value: 6, explored: true})),
-        ];
-
-        for (state, threshold) in expected.iter().copied() {
-            assert_eq!(threshold, cache.get_threshold(&state, state.depth));
-        }
+        assert!(mdd.is_exact())
     }
 
     #[test]
-    fn relaxed_mdd_computes_thresholds_when_exact() {
-        let mut cache = SimpleCache::default();
-        cache.initialize(&DummyProblem);
+    fn a_relaxed_mdd_is_not_exact_when_a_merge_occurred() {
+        let cache = EmptyCache::new();
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Relaxed,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
             cutoff:     &NoCutoff,
-            max_width:  10,
+            max_width:  1,
             best_lb:    isize::MIN,
             residual:  &SubProblem { 
                 state: Arc::new(DummyState{depth: 0, value: 0}), 
@@ -1918,45 +2998,22 @@ mod test_default_mdd {
         let result = mdd.compile(&input);
         assert!(result.is_ok());
         
-        assert!(mdd.is_exact());
-
-        let expected = vec![
-            (DummyState{depth: 0, value: 0}, Some(Threshold {value: 0, explored: true})),
-            (DummyState{depth: 1, value: 0}, Some(Threshold {value: 2, explored: true})),
-            (DummyState{depth: 1, value: 1}, Some(Threshold {value: 2, explored: true})),
-            (DummyState{depth: 1, value: 2}, Some(Threshold {value: 2, explored: true})),
-            (DummyState{depth: 2, value: 0}, Some(Threshold {value: 4, explored: true})),
-            (DummyState{depth: 2, value: 1}, Some(Threshold {value: 4, explored: true})),
-            (DummyState{depth: 2, value: 2}, Some(Threshold {value: 4, explored: true})),
-            (DummyState{depth: 2, value: 3}, Some(Threshold {value: 4, explored: true})),
-            (DummyState{depth: 2, value: 4}, Some(Threshold {value: 4, explored: true})),
-            (DummyState{depth: 3, value: 0}, Some(Threshold {value: 6, explored: true})),
-            (DummyState{depth: 3, value: 1}, Some(Threshold {value: 6, explored: true})),
-            (DummyState{depth: 3, value: 2}, Some(Threshold {value: 6, explored: true})),
-            (DummyState{depth: 3, value: 3}, Some(Threshold {value: 6, explored: true})),
-            (DummyState{depth: 3, value: 4}, Some(Threshold {value: 6, explored: true})),
-            (DummyState{depth: 3, value: 5}, Some(Threshold {value: 6, explored: true})),
-            (DummyState{depth: 3, value: 6}, Some(Threshold {value: 6, explored: true})),
-        ];
-
-        for (state, threshold) in expected.iter().copied() {
-            assert_eq!(threshold, cache.get_threshold(&state, state.depth));
-        }
+        assert!(!mdd.is_exact())
     }
-
     #[test]
-    fn restricted_mdd_computes_thresholds_when_all_pruned() {
-        let mut cache = SimpleCache::default();
-        cache.initialize(&DummyProblem);
+    fn a_restricted_mdd_is_exact_as_long_as_no_restriction_occurs() {
+        let cache = EmptyCache::new();
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Restricted,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
             cutoff:     &NoCutoff,
             max_width:  10,
-            best_lb:    15,
+            best_lb:    isize::MIN,
             residual:  &SubProblem { 
                 state: Arc::new(DummyState{depth: 0, value: 0}), 
                 value: 0, 
@@ -1971,45 +3028,22 @@ mod test_default_mdd {
         let result = mdd.compile(&input);
         assert!(result.is_ok());
         
-        assert!(mdd.is_exact());
-
-        let expected = vec![
-            (DummyState{depth: 0, value: 0}, Some(Threshold {value: 1, explored: true})),
-            (DummyState{depth: 1, value: 0}, Some(Threshold {value: 3, explored: true})),
-            (DummyState{depth: 1, value: 1}, Some(Threshold {value: 3, explored: true})),
-            (DummyState{depth: 1, value: 2}, Some(Threshold {value: 3, explored: true})),
-            (DummyState{depth: 2, value: 0}, Some(Threshold {value: 5, explored: true})),
-            (DummyState{depth: 2, value: 1}, Some(Threshold {value: 5, explored: true})),
-            (DummyState{depth: 2, value: 2}, Some(Threshold {value: 5, explored: true})),
-            (DummyState{depth: 2, value: 3}, Some(Threshold {value: 5, explored: true})),
-            (DummyState{depth: 2, value: 4}, Some(Threshold {value: 5, explored: true})),
-            (DummyState{depth: 3, value: 0}, None),
-            (DummyState{depth: 3, value: 1}, None),
-            (DummyState{depth: 3, value: 2}, None),
-            (DummyState{depth: 3, value: 3}, None),
-            (DummyState{depth: 3, value: 4}, None),
-            (DummyState{depth: 3, value: 5}, None),
-            (DummyState{depth: 3, value: 6}, None),
-        ];
-
-        for (state, threshold) in expected.iter().copied() {
-            assert_eq!(threshold, cache.get_threshold(&state, state.depth));
-        }
+        assert!(mdd.is_exact())
     }
-
     #[test]
-    fn relaxed_mdd_computes_thresholds_when_all_pruned() {
-        let mut cache = SimpleCache::default();
-        cache.initialize(&DummyProblem);
+    fn a_restricted_mdd_is_not_exact_when_a_restriction_occurred() {
+        let cache = EmptyCache::new();
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Relaxed,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
             cutoff:     &NoCutoff,
-            max_width:  10,
-            best_lb:    15,
+            max_width:  1,
+            best_lb:    isize::MIN,
             residual:  &SubProblem { 
                 state: Arc::new(DummyState{depth: 0, value: 0}), 
                 value: 0, 
@@ -2024,37 +3058,545 @@ mod test_default_mdd {
         let result = mdd.compile(&input);
         assert!(result.is_ok());
         
-        assert!(mdd.is_exact());
-
-        let expected = vec![
-            (DummyState{depth: 0, value: 0}, Some(Threshold {value: 1, explored: true})),
-            (DummyState{depth: 1, value: 0}, Some(Threshold {value: 3, explored: true})),
-            (DummyState{depth: 1, value: 1}, Some(Threshold {value: 3, explored: true})),
-            (DummyState{depth: 1, value: 2}, Some(Threshold {value: 3, explored: true})),
-            (DummyState{depth: 2, value: 0}, Some(Threshold {value: 5, explored: true})),
-            (DummyState{depth: 2, value: 1}, Some(Threshold {value: 5, explored: true})),
-            (DummyState{depth: 2, value: 2}, Some(Threshold {value: 5, explored: true})),
-            (DummyState{depth: 2, value: 3}, Some(Threshold {value: 5, explored: true})),
-            (DummyState{depth: 2, value: 4}, Some(Threshold {value: 5, explored: true})),
-            (DummyState{depth: 3, value: 0}, None),
-            (DummyState{depth: 3, value: 1}, None),
-            (DummyState{depth: 3, value: 2}, None),
-            (DummyState{depth: 3, value: 3}, None),
-            (DummyState{depth: 3, value: 4}, None),
-            (DummyState{depth: 3, value: 5}, None),
-            (DummyState{depth: 3, value: 6}, None),
-        ];
-
-        for (state, threshold) in expected.iter().copied() {
-            assert_eq!(threshold, cache.get_threshold(&state, state.depth));
-        }
+        assert!(!mdd.is_exact())
     }
-
-    /// The example problem and relaxation for the local bounds should generate
-    /// the following relaxed MDD in which the layer 'a','b' is the LEL.
-    ///
-    /// ```plain
-    ///                      r
+    #[test]
+    fn a_dd_compiled_with_a_capped_out_degree_is_not_exact() {
+        let cache = EmptyCache::new();
+        let dominance = EmptyDominanceChecker::default();
+        let input = CompilationInput {
+            comp_type: crate::CompilationType::Exact,
+            max_in_degree: None,
+            max_out_degree: 1,
+            problem:    &DummyProblem,
+            relaxation: &DummyRelax,
+            ranking:    &DummyRanking,
+            cutoff:     &NoCutoff,
+            max_width:  usize::MAX,
+            best_lb:    isize::MIN,
+            residual:  &SubProblem {
+                state: Arc::new(DummyState{depth: 0, value: 0}),
+                value: 0,
+                path:  vec![],
+                ub:    isize::MAX,
+                depth: 0,
+            },
+            cache: &cache,
+            dominance: &dominance,
+        };
+        let mut mdd = DefaultMDD::new();
+        let result = mdd.compile(&input);
+        assert!(result.is_ok());
+
+        // the ranking of the dummy problem prefers the low-valued states, so
+        // capping the out-degree to one only keeps the zero-valued decision
+        // out of every node
+        assert!(!mdd.is_exact());
+        assert_eq!(mdd.best_value(), Some(0));
+    }
+    #[test]
+    fn the_out_degree_cap_keeps_the_decisions_leading_to_the_best_ranked_states() {
+        let cache = EmptyCache::new();
+        let dominance = EmptyDominanceChecker::default();
+        let input = CompilationInput {
+            comp_type: crate::CompilationType::Exact,
+            max_in_degree: None,
+            max_out_degree: 2,
+            problem:    &DummyProblem,
+            relaxation: &DummyRelax,
+            ranking:    &DummyRanking,
+            cutoff:     &NoCutoff,
+            max_width:  usize::MAX,
+            best_lb:    isize::MIN,
+            residual:  &SubProblem {
+                state: Arc::new(DummyState{depth: 0, value: 0}),
+                value: 0,
+                path:  vec![],
+                ub:    isize::MAX,
+                depth: 0,
+            },
+            cache: &cache,
+            dominance: &dominance,
+        };
+        let mut mdd = DefaultMDD::new();
+        let result = mdd.compile(&input);
+        assert!(result.is_ok());
+
+        // every node only branches on the decisions 0 and 1: the best path
+        // of the capped dd uses the decision 1 at every level
+        assert!(!mdd.is_exact());
+        assert_eq!(mdd.best_value(), Some(3));
+    }
+    #[test]
+    fn when_the_problem_is_infeasible_there_is_no_solution() {
+        let cache = EmptyCache::new();
+        let dominance = EmptyDominanceChecker::default();
+        let input = CompilationInput {
+            comp_type: crate::CompilationType::Exact,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
+            problem:    &DummyInfeasibleProblem,
+            relaxation: &DummyRelax,
+            ranking:    &DummyRanking,
+            cutoff:     &NoCutoff,
+            max_width:  usize::MAX,
+            best_lb:    isize::MIN,
+            residual:  &SubProblem { 
+                state: Arc::new(DummyState{depth: 0, value: 0}), 
+                value: 0, 
+                path:  vec![], 
+                ub:    isize::MAX,
+                depth: 0,
+            },
+            cache: &cache,
+            dominance: &dominance,
+        };
+        let mut mdd = DefaultMDD::new();
+        let result = mdd.compile(&input);
+        assert!(result.is_ok());
+        assert!(mdd.best_solution().is_none())
+    }
+    #[test]
+    fn when_the_problem_is_infeasible_there_is_no_best_value() {
+        let cache = EmptyCache::new();
+        let dominance = EmptyDominanceChecker::default();
+        let input = CompilationInput {
+            comp_type: crate::CompilationType::Exact,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
+            problem:    &DummyInfeasibleProblem,
+            relaxation: &DummyRelax,
+            ranking:    &DummyRanking,
+            cutoff:     &NoCutoff,
+            max_width:  usize::MAX,
+            best_lb:    isize::MIN,
+            residual:  &SubProblem { 
+                state: Arc::new(DummyState{depth: 0, value: 0}), 
+                value: 0, 
+                path:  vec![], 
+                ub:    isize::MAX,
+                depth: 0,
+            },
+            cache: &cache,
+            dominance: &dominance,
+        };
+        let mut mdd = DefaultMDD::new();
+        let result = mdd.compile(&input);
+        assert!(result.is_ok());
+        assert!(mdd.best_value().is_none())
+    }
+    #[test]
+    fn exact_skips_node_with_an_ub_less_than_best_known_lb() {
+        let cache = EmptyCache::new();
+        let dominance = EmptyDominanceChecker::default();
+        let input = CompilationInput {
+            comp_type: crate::CompilationType::Exact,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
+            problem:    &DummyProblem,
+            relaxation: &DummyRelax,
+            ranking:    &DummyRanking,
+            cutoff:     &NoCutoff,
+            max_width:  usize::MAX,
+            best_lb:    1000,
+            residual: &SubProblem { 
+                state: Arc::new(DummyState{depth: 0, value: 0}), 
+                value: 0, 
+                path:  vec![], 
+                ub:    isize::MAX,
+                depth: 0,
+            },
+            cache: &cache,
+            dominance: &dominance,
+        };
+        let mut mdd = DefaultMDD::new();
+        let result = mdd.compile(&input);
+        assert!(result.is_ok());
+        assert!(mdd.best_solution().is_none())
+    }
+    #[test]
+    fn relaxed_skips_node_with_an_ub_less_than_best_known_lb() {
+        let cache = EmptyCache::new();
+        let dominance = EmptyDominanceChecker::default();
+        let input = CompilationInput {
+            comp_type: crate::CompilationType::Relaxed,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
+            problem:    &DummyProblem,
+            relaxation: &DummyRelax,
+            ranking:    &DummyRanking,
+            cutoff:     &NoCutoff,
+            max_width:  usize::MAX,
+            best_lb:    1000,
+            residual: &SubProblem { 
+                state: Arc::new(DummyState{depth: 0, value: 0}), 
+                value: 0, 
+                path:  vec![], 
+                ub:    isize::MAX,
+                depth: 0,
+            },
+            cache: &cache,
+            dominance: &dominance,
+        };
+        let mut mdd = DefaultMDD::new();
+        let result = mdd.compile(&input);
+        assert!(result.is_ok());
+        assert!(mdd.best_solution().is_none())
+    }
+    #[test]
+    fn restricted_skips_node_with_an_ub_less_than_best_known_lb() {
+        let cache = EmptyCache::new();
+        let dominance = EmptyDominanceChecker::default();
+        let input = CompilationInput {
+            comp_type: crate::CompilationType::Restricted,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
+            problem:    &DummyProblem,
+            relaxation: &DummyRelax,
+            ranking:    &DummyRanking,
+            cutoff:     &NoCutoff,
+            max_width:  usize::MAX,
+            best_lb:    1000,
+            residual: &SubProblem { 
+                state: Arc::new(DummyState{depth: 0, value: 0}), 
+                value: 0, 
+                path:  vec![], 
+                ub:    isize::MAX,
+                depth: 0,
+            },
+            cache: &cache,
+            dominance: &dominance,
+        };
+        let mut mdd = DefaultMDD::new();
+        let result = mdd.compile(&input);
+        assert!(result.is_ok());
+        assert!(mdd.best_solution().is_none())
+    }
+    #[test]
+    fn exact_skips_nodes_with_a_value_less_than_known_threshold() {
+        let mut cache = SimpleCache::default();
+        cache.initialize(&DummyProblem);
+        cache.update_threshold(Arc::new(DummyState{depth: 1, value: 0}), 1, 0, true);
+        cache.update_threshold(Arc::new(DummyState{depth: 1, value: 1}), 1, 1, true);
+        cache.update_threshold(Arc::new(DummyState{depth: 1, value: 2}), 1, 2, true);
+        let dominance = EmptyDominanceChecker::default();
+        let input = CompilationInput {
+            comp_type: crate::CompilationType::Exact,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
+            problem:    &DummyProblem,
+            relaxation: &DummyRelax,
+            ranking:    &DummyRanking,
+            cutoff:     &NoCutoff,
+            max_width:  usize::MAX,
+            best_lb:    isize::MIN,
+            residual: &SubProblem { 
+                state: Arc::new(DummyState{depth: 0, value: 0}), 
+                value: 0, 
+                path:  vec![], 
+                ub:    isize::MAX,
+                depth: 0,
+            },
+            cache: &cache,
+            dominance: &dominance,
+        };
+        let mut mdd = DefaultMDD::new();
+        let result = mdd.compile(&input);
+        assert!(result.is_ok());
+        assert!(mdd.best_solution().is_none())
+    }
+    #[test]
+    fn relaxed_skips_nodes_with_a_value_less_than_known_threshold() {
+        let mut cache = SimpleCache::default();
+        cache.initialize(&DummyProblem);
+        cache.update_threshold(Arc::new(DummyState{depth: 1, value: 0}), 1, 0, true);
+        cache.update_threshold(Arc::new(DummyState{depth: 1, value: 1}), 1, 1, true);
+        cache.update_threshold(Arc::new(DummyState{depth: 1, value: 2}), 1, 2, true);
+        let dominance = EmptyDominanceChecker::default();
+        let input = CompilationInput {
+            comp_type: crate::CompilationType::Relaxed,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
+            problem:    &DummyProblem,
+            relaxation: &DummyRelax,
+            ranking:    &DummyRanking,
+            cutoff:     &NoCutoff,
+            max_width:  usize::MAX,
+            best_lb:    isize::MIN,
+            residual: &SubProblem { 
+                state: Arc::new(DummyState{depth: 0, value: 0}), 
+                value: 0, 
+                path:  vec![], 
+                ub:    isize::MAX,
+                depth: 0,
+            },
+            cache: &cache,
+            dominance: &dominance,
+        };
+        let mut mdd = DefaultMDD::new();
+        let result = mdd.compile(&input);
+        assert!(result.is_ok());
+        assert!(mdd.best_solution().is_none())
+    }
+    #[test]
+    fn restricted_skips_nodes_with_a_value_less_than_known_threshold() {
+        let mut cache = SimpleCache::default();
+        cache.initialize(&DummyProblem);
+        cache.update_threshold(Arc::new(DummyState{depth: 1, value: 0}), 1, 0, true);
+        cache.update_threshold(Arc::new(DummyState{depth: 1, value: 1}), 1, 1, true);
+        cache.update_threshold(Arc::new(DummyState{depth: 1, value: 2}), 1, 2, true);
+        let dominance = EmptyDominanceChecker::default();
+        let input = CompilationInput {
+            comp_type: crate::CompilationType::Restricted,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
+            problem:    &DummyProblem,
+            relaxation: &DummyRelax,
+            ranking:    &DummyRanking,
+            cutoff:     &NoCutoff,
+            max_width:  usize::MAX,
+            best_lb:    isize::MIN,
+            residual: &SubProblem { 
+                state: Arc::new(DummyState{depth: 0, value: 0}), 
+                value: 0, 
+                path:  vec![], 
+                ub:    isize::MAX,
+                depth: 0,
+            },
+            cache: &cache,
+            dominance: &dominance,
+        };
+        let mut mdd = DefaultMDD::new();
+        let result = mdd.compile(&input);
+        assert!(result.is_ok());
+        assert!(mdd.best_solution().is_none())
+    }
+
+    #[test]
+    fn restricted_mdd_computes_thresholds_when_exact() {
+        let mut cache = SimpleCache::default();
+        cache.initialize(&DummyProblem);
+        let dominance = EmptyDominanceChecker::default();
+        let input = CompilationInput {
+            comp_type: crate::CompilationType::Restricted,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
+            problem:    &DummyProblem,
+            relaxation: &DummyRelax,
+            ranking:    &DummyRanking,
+            cutoff:     &NoCutoff,
+            max_width:  10,
+            best_lb:    isize::MIN,
+            residual:  &SubProblem { 
+                state: Arc::new(DummyState{depth: 0, value: 0}), 
+                value: 0, 
+                path:  vec![], 
+                ub:    isize::MAX,
+                depth: 0,
+            },
+            cache: &cache,
+            dominance: &dominance,
+        };
+        let mut mdd = DefaultMDD::new();
+        let result = mdd.compile(&input);
+        assert!(result.is_ok());
+        
+        assert!(mdd.is_exact());
+
+        let expected = vec![
+            (DummyState{depth: 0, value: 0}, Some(Threshold {value: 0, explored: true})),
+            (DummyState{depth: 1, value: 0}, Some(Threshold {value: 2, explored: true})),
+            (DummyState{depth: 1, value: 1}, Some(Threshold {value: 2, explored: true})),
+            (DummyState{depth: 1, value: 2}, Some(Threshold {value: 2, explored: true})),
+            (DummyState{depth: 2, value: 0}, Some(Threshold {value: 4, explored: true})),
+            (DummyState{depth: 2, value: 1}, Some(Threshold {value: 4, explored: true})),
+            (DummyState{depth: 2, value: 2}, Some(Threshold {value: 4, explored: true})),
+            (DummyState{depth: 2, value: 3}, Some(Threshold {value: 4, explored: true})),
+            (DummyState{depth: 2, value: 4}, Some(Threshold {value: 4, explored: true})),
+            (DummyState{depth: 3, value: 0}, Some(Threshold {value: 6, explored: true})),
+            (DummyState{depth: 3, value: 1}, Some(Threshold {value: 6, explored: true})),
+            (DummyState{depth: 3, value: 2}, Some(Threshold {value: 6, explored: true})),
+            (DummyState{depth: 3, value: 3}, Some(Threshold {value: 6, explored: true})),
+            (DummyState{depth: 3, value: 4}, Some(Threshold {value: 6, explored: true})),
+            (DummyState{depth: 3, value: 5}, Some(Threshold {value: 6, explored: true})),
+            (DummyState{depth: 3, value: 6}, Some(Threshold {value: 6, explored: true})),
+        ];
+
+        for (state, threshold) in expected.iter().copied() {
+            assert_eq!(threshold, cache.get_threshold(&state, state.depth));
+        }
+    }
+
+    #[test]
+    fn relaxed_mdd_computes_thresholds_when_exact() {
+        let mut cache = SimpleCache::default();
+        cache.initialize(&DummyProblem);
+        let dominance = EmptyDominanceChecker::default();
+        let input = CompilationInput {
+            comp_type: crate::CompilationType::Relaxed,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
+            problem:    &DummyProblem,
+            relaxation: &DummyRelax,
+            ranking:    &DummyRanking,
+            cutoff:     &NoCutoff,
+            max_width:  10,
+            best_lb:    isize::MIN,
+            residual:  &SubProblem { 
+                state: Arc::new(DummyState{depth: 0, value: 0}), 
+                value: 0, 
+                path:  vec![], 
+                ub:    isize::MAX,
+                depth: 0,
+            },
+            cache: &cache,
+            dominance: &dominance,
+        };
+        let mut mdd = DefaultMDD::new();
+        let result = mdd.compile(&input);
+        assert!(result.is_ok());
+        
+        assert!(mdd.is_exact());
+
+        let expected = vec![
+            (DummyState{depth: 0, value: 0}, Some(Threshold {value: 0, explored: true})),
+            (DummyState{depth: 1, value: 0}, Some(Threshold {value: 2, explored: true})),
+            (DummyState{depth: 1, value: 1}, Some(Threshold {value: 2, explored: true})),
+            (DummyState{depth: 1, value: 2}, Some(Threshold {value: 2, explored: true})),
+            (DummyState{depth: 2, value: 0}, Some(Threshold {value: 4, explored: true})),
+            (DummyState{depth: 2, value: 1}, Some(Threshold {value: 4, explored: true})),
+            (DummyState{depth: 2, value: 2}, Some(Threshold {value: 4, explored: true})),
+            (DummyState{depth: 2, value: 3}, Some(Threshold {value: 4, explored: true})),
+            (DummyState{depth: 2, value: 4}, Some(Threshold {value: 4, explored: true})),
+            (DummyState{depth: 3, value: 0}, Some(Threshold {value: 6, explored: true})),
+            (DummyState{depth: 3, value: 1}, Some(Threshold {value: 6, explored: true})),
+            (DummyState{depth: 3, value: 2}, Some(Threshold {value: 6, explored: true})),
+            (DummyState{depth: 3, value: 3}, Some(Threshold {value: 6, explored: true})),
+            (DummyState{depth: 3, value: 4}, Some(Threshold {value: 6, explored: true})),
+            (DummyState{depth: 3, value: 5}, Some(Threshold {value: 6, explored: true})),
+            (DummyState{depth: 3, value: 6}, Some(Threshold {value: 6, explored: true})),
+        ];
+
+        for (state, threshold) in expected.iter().copied() {
+            assert_eq!(threshold, cache.get_threshold(&state, state.depth));
+        }
+    }
+
+    #[test]
+    fn restricted_mdd_computes_thresholds_when_all_pruned() {
+        let mut cache = SimpleCache::default();
+        cache.initialize(&DummyProblem);
+        let dominance = EmptyDominanceChecker::default();
+        let input = CompilationInput {
+            comp_type: crate::CompilationType::Restricted,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
+            problem:    &DummyProblem,
+            relaxation: &DummyRelax,
+            ranking:    &DummyRanking,
+            cutoff:     &NoCutoff,
+            max_width:  10,
+            best_lb:    15,
+            residual:  &SubProblem { 
+                state: Arc::new(DummyState{depth: 0, value: 0}), 
+                value: 0, 
+                path:  vec![], 
+                ub:    isize::MAX,
+                depth: 0,
+            },
+            cache: &cache,
+            dominance: &dominance,
+        };
+        let mut mdd = DefaultMDD::new();
+        let result = mdd.compile(&input);
+        assert!(result.is_ok());
+        
+        assert!(mdd.is_exact());
+
+        let expected = vec![
+            (DummyState{depth: 0, value: 0}, Some(Threshold {value: 1, explored: true})),
+            (DummyState{depth: 1, value: 0}, Some(Threshold {value: 3, explored: true})),
+            (DummyState{depth: 1, value: 1}, Some(Threshold {value: 3, explored: true})),
+            (DummyState{depth: 1, value: 2}, Some(Threshold {value: 3, explored: true})),
+            (DummyState{depth: 2, value: 0}, Some(Threshold {value: 5, explored: true})),
+            (DummyState{depth: 2, value: 1}, Some(Threshold {value: 5, explored: true})),
+            (DummyState{depth: 2, value: 2}, Some(Threshold {value: 5, explored: true})),
+            (DummyState{depth: 2, value: 3}, Some(Threshold {value: 5, explored: true})),
+            (DummyState{depth: 2, value: 4}, Some(Threshold {value: 5, explored: true})),
+            (DummyState{depth: 3, value: 0}, None),
+            (DummyState{depth: 3, value: 1}, None),
+            (DummyState{depth: 3, value: 2}, None),
+            (DummyState{depth: 3, value: 3}, None),
+            (DummyState{depth: 3, value: 4}, None),
+            (DummyState{depth: 3, value: 5}, None),
+            (DummyState{depth: 3, value: 6}, None),
+        ];
+
+        for (state, threshold) in expected.iter().copied() {
+            assert_eq!(threshold, cache.get_threshold(&state, state.depth));
+        }
+    }
+
+    #[test]
+    fn relaxed_mdd_computes_thresholds_when_all_pruned() {
+        let mut cache = SimpleCache::default();
+        cache.initialize(&DummyProblem);
+        let dominance = EmptyDominanceChecker::default();
+        let input = CompilationInput {
+            comp_type: crate::CompilationType::Relaxed,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
+            problem:    &DummyProblem,
+            relaxation: &DummyRelax,
+            ranking:    &DummyRanking,
+            cutoff:     &NoCutoff,
+            max_width:  10,
+            best_lb:    15,
+            residual:  &SubProblem { 
+                state: Arc::new(DummyState{depth: 0, value: 0}), 
+                value: 0, 
+                path:  vec![], 
+                ub:    isize::MAX,
+                depth: 0,
+            },
+            cache: &cache,
+            dominance: &dominance,
+        };
+        let mut mdd = DefaultMDD::new();
+        let result = mdd.compile(&input);
+        assert!(result.is_ok());
+        
+        assert!(mdd.is_exact());
+
+        let expected = vec![
+            (DummyState{depth: 0, value: 0}, Some(Threshold {value: 1, explored: true})),
+            (DummyState{depth: 1, value: 0}, Some(Threshold {value: 3, explored: true})),
+            (DummyState{depth: 1, value: 1}, Some(Threshold {value: 3, explored: true})),
+            (DummyState{depth: 1, value: 2}, Some(Threshold {value: 3, explored: true})),
+            (DummyState{depth: 2, value: 0}, Some(Threshold {value: 5, explored: true})),
+            (DummyState{depth: 2, value: 1}, Some(Threshold {value: 5, explored: true})),
+            (DummyState{depth: 2, value: 2}, Some(Threshold {value: 5, explored: true})),
+            (DummyState{depth: 2, value: 3}, Some(Threshold {value: 5, explored: true})),
+            (DummyState{depth: 2, value: 4}, Some(Threshold {value: 5, explored: true})),
+            (DummyState{depth: 3, value: 0}, None),
+            (DummyState{depth: 3, value: 1}, None),
+            (DummyState{depth: 3, value: 2}, None),
+            (DummyState{depth: 3, value: 3}, None),
+            (DummyState{depth: 3, value: 4}, None),
+            (DummyState{depth: 3, value: 5}, None),
+            (DummyState{depth: 3, value: 6}, None),
+        ];
+
+        for (state, threshold) in expected.iter().copied() {
+            assert_eq!(threshold, cache.get_threshold(&state, state.depth));
+        }
+    }
+
+    /// The example problem and relaxation for the local bounds should generate
+    /// the following relaxed MDD in which the layer 'a','b' is the LEL.
+    ///
+    /// ```plain
+    ///                      r
     ///                   /     \
     ///                10        7
     ///               /           |
@@ -2184,6 +3726,8 @@ mod test_default_mdd {
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Relaxed,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &LocBoundsAndThresholdsExamplePb,
             relaxation: &LocBoundsAndThresholdsExampleRelax,
             ranking:    &CmpChar,
@@ -2233,9 +3777,53 @@ mod test_default_mdd {
         assert_eq!(10, threshold.value);
         assert!(!threshold.explored);
 
-        threshold = cache.get_threshold(&'b', 1).unwrap();
-        assert_eq!(7, threshold.value);
-        assert!(!threshold.explored);
+        threshold = cache.get_threshold(&'b', 1).unwrap();
+        assert_eq!(7, threshold.value);
+        assert!(!threshold.explored);
+    }
+
+    #[test]
+    fn exact_cutset_exposes_the_cutset_nodes_without_draining_them() {
+        let mut cache = SimpleCache::default();
+        cache.initialize(&LocBoundsAndThresholdsExamplePb);
+        let dominance = EmptyDominanceChecker::default();
+        let input = CompilationInput {
+            comp_type: crate::CompilationType::Relaxed,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
+            problem:    &LocBoundsAndThresholdsExamplePb,
+            relaxation: &LocBoundsAndThresholdsExampleRelax,
+            ranking:    &CmpChar,
+            cutoff:     &NoCutoff,
+            max_width:  3,
+            best_lb:    0,
+            residual: &SubProblem {
+                state: Arc::new('r'),
+                value: 0,
+                path:  vec![],
+                ub:    isize::MAX,
+                depth: 0,
+            },
+            cache: &cache,
+            dominance: &dominance,
+        };
+        let mut mdd = DefaultMDDLEL::new();
+        let result = mdd.compile(&input);
+        assert!(result.is_ok());
+        assert!(!mdd.is_exact());
+
+        let inspect = mdd.exact_cutset()
+            .map(|(state, value, ub)| (*state, (value, ub)))
+            .collect::<FxHashMap<_, _>>();
+        assert_eq!(2, inspect.len());
+        assert_eq!(16, inspect[&'a'].1);
+        assert_eq!(14, inspect[&'b'].1);
+
+        // inspecting the cutset does not drain it: the subproblems can still
+        // be spawned and they carry the very same information
+        let mut drained = FxHashMap::<char, (isize, isize)>::default();
+        mdd.drain_cutset(|n| { drained.insert(*n.state, (n.value, n.ub)); });
+        assert_eq!(inspect, drained);
     }
 
     #[test]
@@ -2245,6 +3833,8 @@ mod test_default_mdd {
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Relaxed,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &LocBoundsAndThresholdsExamplePb,
             relaxation: &LocBoundsAndThresholdsExampleRelax,
             ranking:    &CmpChar,
@@ -2324,6 +3914,8 @@ mod test_default_mdd {
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Relaxed,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &LocBoundsAndThresholdsExamplePb,
             relaxation: &LocBoundsAndThresholdsExampleRelax,
             ranking:    &CmpChar,
@@ -2400,7 +3992,9 @@ mod test_default_mdd {
         cache.initialize(&LocBoundsAndThresholdsExamplePb);
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
-            comp_type:  crate::CompilationType::Relaxed,
+            comp_type: crate::CompilationType::Relaxed,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &LocBoundsAndThresholdsExamplePb,
             relaxation: &LocBoundsAndThresholdsExampleRelax,
             ranking:    &CmpChar,
@@ -2427,13 +4021,49 @@ mod test_default_mdd {
         assert_eq!(strip_format(dot), strip_format(&s));
     }
 
+    #[test]
+    fn test_json_visualisation() {
+        let mut cache = SimpleCache::default();
+        cache.initialize(&LocBoundsAndThresholdsExamplePb);
+        let dominance = EmptyDominanceChecker::default();
+        let input = CompilationInput {
+            comp_type: crate::CompilationType::Relaxed,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
+            problem:    &LocBoundsAndThresholdsExamplePb,
+            relaxation: &LocBoundsAndThresholdsExampleRelax,
+            ranking:    &CmpChar,
+            cutoff:     &NoCutoff,
+            max_width:  3,
+            best_lb:    0,
+            residual: &SubProblem {
+                state: Arc::new('r'),
+                value: 0,
+                path:  vec![],
+                ub:    isize::MAX,
+                depth: 0,
+            },
+            cache: &cache,
+            dominance: &dominance,
+        };
+        let mut mdd = DefaultMDDFC::new();
+        let _ = mdd.compile(&input);
+
+        let json = include_str!("../../../../resources/visualisation_tests/default_viz.json");
+        let config = VizConfigBuilder::default().build().unwrap();
+        let s = mdd.as_json(&config);
+        assert_eq!(strip_format(json), strip_format(&s));
+    }
+
     #[test]
     fn test_terse_visualisation() {
         let mut cache = SimpleCache::default();
         cache.initialize(&LocBoundsAndThresholdsExamplePb);
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
-            comp_type:  crate::CompilationType::Relaxed,
+            comp_type: crate::CompilationType::Relaxed,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &LocBoundsAndThresholdsExamplePb,
             relaxation: &LocBoundsAndThresholdsExampleRelax,
             ranking:    &CmpChar,
@@ -2471,7 +4101,9 @@ mod test_default_mdd {
         cache.initialize(&LocBoundsAndThresholdsExamplePb);
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
-            comp_type:  crate::CompilationType::Relaxed,
+            comp_type: crate::CompilationType::Relaxed,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &LocBoundsAndThresholdsExamplePb,
             relaxation: &LocBoundsAndThresholdsExampleRelax,
             ranking:    &CmpChar,
@@ -2503,58 +4135,523 @@ mod test_default_mdd {
         assert_eq!(strip_format(dot), strip_format(&s));
     }
 
-    #[test]
-    fn test_show_group_merged() {
-        let mut cache = SimpleCache::default();
-        cache.initialize(&LocBoundsAndThresholdsExamplePb);
-        let dominance = EmptyDominanceChecker::default();
-        let input = CompilationInput {
-            comp_type:  crate::CompilationType::Relaxed,
-            problem:    &LocBoundsAndThresholdsExamplePb,
-            relaxation: &LocBoundsAndThresholdsExampleRelax,
-            ranking:    &CmpChar,
-            cutoff:     &NoCutoff,
-            max_width:  3,
-            best_lb:    0,
-            residual: &SubProblem { 
-                state: Arc::new('r'), 
-                value: 0, 
-                path:  vec![], 
-                ub:    isize::MAX,
-                depth: 0,
-            },
-            cache: &cache,
-            dominance: &dominance,
-        };
-        let mut mdd = DefaultMDDFC::new();
-        let _ = mdd.compile(&input);
-        
-        let dot = include_str!("../../../../resources/visualisation_tests/clusters_viz.dot");
-        let config = VizConfigBuilder::default()
-            .show_value(false)
-            .show_deleted(true)
-            .show_rub(false)
-            .show_locb(false)
-            .show_threshold(false)
-            .group_merged(true)
-            .build().unwrap();
-        let s = mdd.as_graphviz(&config);
-        assert_eq!(strip_format(dot), strip_format(&s));
+    #[test]
+    fn test_show_group_merged() {
+        let mut cache = SimpleCache::default();
+        cache.initialize(&LocBoundsAndThresholdsExamplePb);
+        let dominance = EmptyDominanceChecker::default();
+        let input = CompilationInput {
+            comp_type: crate::CompilationType::Relaxed,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
+            problem:    &LocBoundsAndThresholdsExamplePb,
+            relaxation: &LocBoundsAndThresholdsExampleRelax,
+            ranking:    &CmpChar,
+            cutoff:     &NoCutoff,
+            max_width:  3,
+            best_lb:    0,
+            residual: &SubProblem { 
+                state: Arc::new('r'), 
+                value: 0, 
+                path:  vec![], 
+                ub:    isize::MAX,
+                depth: 0,
+            },
+            cache: &cache,
+            dominance: &dominance,
+        };
+        let mut mdd = DefaultMDDFC::new();
+        let _ = mdd.compile(&input);
+        
+        let dot = include_str!("../../../../resources/visualisation_tests/clusters_viz.dot");
+        let config = VizConfigBuilder::default()
+            .show_value(false)
+            .show_deleted(true)
+            .show_rub(false)
+            .show_locb(false)
+            .show_threshold(false)
+            .group_merged(true)
+            .build().unwrap();
+        let s = mdd.as_graphviz(&config);
+        assert_eq!(strip_format(dot), strip_format(&s));
+    }
+
+    fn strip_format(s: &str) -> String {
+        s.lines().map(|l| l.trim()).collect()
+    }
+
+    #[test]
+    fn the_count_of_best_paths_is_one_when_the_optimum_is_unique() {
+        let cache = EmptyCache::new();
+        let dominance = EmptyDominanceChecker::default();
+        let input = CompilationInput {
+            comp_type: crate::CompilationType::Exact,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
+            problem:    &DummyProblem,
+            relaxation: &DummyRelax,
+            ranking:    &DummyRanking,
+            cutoff:     &NoCutoff,
+            max_width:  usize::MAX,
+            best_lb:    isize::MIN,
+            residual:  &SubProblem {
+                state: Arc::new(DummyState{depth: 0, value: 0}),
+                value: 0,
+                path:  vec![],
+                ub:    isize::MAX,
+                depth: 0,
+            },
+            cache: &cache,
+            dominance: &dominance,
+        };
+        let mut mdd = DefaultMDD::new();
+        let result = mdd.compile(&input);
+        assert!(result.is_ok());
+
+        // only the all-twos path reaches the value 6
+        assert_eq!(mdd.best_value(), Some(6));
+        assert_eq!(mdd.count_best_paths(), 1);
+    }
+
+    #[test]
+    fn the_relaxation_can_pick_the_representative_of_a_merged_node() {
+        // compiled with a width of 2, the second layer of this problem keeps
+        // the node with state 2 (which cannot be expanded any further) and
+        // merges the nodes with states 1 and 0: the best path necessarily
+        // traverses the merged node
+        let best_first_decision = |relax: &dyn Relaxation<State = usize>| {
+            let cache = EmptyCache::new();
+            let dominance = EmptyDominanceChecker::default();
+            let input = CompilationInput {
+                comp_type: crate::CompilationType::Relaxed,
+                max_in_degree: None,
+                max_out_degree: usize::MAX,
+                problem:    &RepProblem,
+                relaxation: relax,
+                ranking:    &TiedRanking,
+                cutoff:     &NoCutoff,
+                max_width:  2,
+                best_lb:    isize::MIN,
+                residual:  &SubProblem {
+                    state: Arc::new(0),
+                    value: 0,
+                    path:  vec![],
+                    ub:    isize::MAX,
+                    depth: 0,
+                },
+                cache: &cache,
+                dominance: &dominance,
+            };
+            let mut mdd = DefaultMDD::new();
+            let result = mdd.compile(&input);
+            assert!(result.is_ok());
+            assert_eq!(mdd.best_value(), Some(3));
+
+            let mut solution = mdd.best_solution().unwrap().to_vec();
+            solution.sort_unstable_by_key(|d| d.variable.0);
+            solution[0].value
+        };
+
+        // by default, the merged node inherits the best arc of its
+        // max-value member (the state 1); selecting the min-value member
+        // as representative makes it inherit the arc of the state 0
+        assert_eq!(1, best_first_decision(&RepRelax));
+        assert_eq!(0, best_first_decision(&MinRepRelax));
+    }
+
+    #[test]
+    fn the_tie_break_drives_the_squashing_when_the_ranking_is_tied() {
+        // a width-1 hybridized dive through TieProblem must drop two of the
+        // three (equally valued, equally ranked) nodes of the first layer
+        let best_with_ranking = |ranking: &dyn StateRanking<State = usize>| {
+            let cache = EmptyCache::new();
+            let dominance = EmptyDominanceChecker::default();
+            let input = CompilationInput {
+                comp_type: crate::CompilationType::Relaxed,
+                max_in_degree: None,
+                max_out_degree: usize::MAX,
+                problem:    &TieProblem,
+                relaxation: &TiedRelax,
+                ranking,
+                cutoff:     &NoCutoff,
+                max_width:  1,
+                best_lb:    isize::MIN,
+                residual:  &SubProblem {
+                    state: Arc::new(0),
+                    value: 0,
+                    path:  vec![],
+                    ub:    isize::MAX,
+                    depth: 0,
+                },
+                cache: &cache,
+                dominance: &dominance,
+            };
+            let mut mdd = DefaultMDD::new();
+            mdd.set_hybrid_threshold(Some(usize::MAX));
+            let result = mdd.compile(&input);
+            assert!(result.is_ok());
+            mdd.best_value()
+        };
+
+        // when everything ties, the insertion-order fallback keeps the node
+        // reached by the first branching decision (the state 0)
+        assert_eq!(Some(0), best_with_ranking(&TiedRanking));
+        // the tie-break overrides that default and keeps the max state
+        assert_eq!(Some(20), best_with_ranking(&TieBreakRanking));
+    }
+
+    #[test]
+    fn an_exact_dd_counts_all_the_tied_optimal_paths() {
+        let cache = EmptyCache::new();
+        let dominance = EmptyDominanceChecker::default();
+        let input = CompilationInput {
+            comp_type: crate::CompilationType::Exact,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
+            problem:    &TiedProblem,
+            relaxation: &TiedRelax,
+            ranking:    &TiedRanking,
+            cutoff:     &NoCutoff,
+            max_width:  usize::MAX,
+            best_lb:    isize::MIN,
+            residual:  &SubProblem {
+                state: Arc::new(0),
+                value: 0,
+                path:  vec![],
+                ub:    isize::MAX,
+                depth: 0,
+            },
+            cache: &cache,
+            dominance: &dominance,
+        };
+        let mut mdd = DefaultMDD::new();
+        let result = mdd.compile(&input);
+        assert!(result.is_ok());
+
+        // every decision costs the same and the states collapse into a
+        // single node per layer: the four decision paths are all tied and
+        // each of them must be accounted for
+        assert_eq!(mdd.best_value(), Some(2));
+        assert_eq!(mdd.count_best_paths(), 4);
+    }
+
+    #[test]
+    fn all_the_paths_above_a_threshold_are_enumerated() {
+        let cache = EmptyCache::new();
+        let dominance = EmptyDominanceChecker::default();
+        let input = CompilationInput {
+            comp_type: crate::CompilationType::Exact,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
+            problem:    &DummyProblem,
+            relaxation: &DummyRelax,
+            ranking:    &DummyRanking,
+            cutoff:     &NoCutoff,
+            max_width:  usize::MAX,
+            best_lb:    isize::MIN,
+            residual: &SubProblem {
+                state: Arc::new(DummyState{depth: 0, value: 0}),
+                value: 0,
+                path:  vec![],
+                ub:    isize::MAX,
+                depth: 0,
+            },
+            cache: &cache,
+            dominance: &dominance,
+        };
+        let mut mdd = DefaultMDD::new();
+        assert!(mdd.compile(&input).is_ok());
+
+        // the optimum (2+2+2 = 6) is unique, and the value 5 is achieved by
+        // the three permutations of (2, 2, 1)
+        let solutions = mdd.solutions_above(5);
+        assert_eq!(4, solutions.len());
+        assert_eq!(6, solutions[0].0);
+        assert!(solutions[1..].iter().all(|(value, _)| *value == 5));
+
+        for (value, sol) in solutions {
+            assert_eq!(value, sol.decisions().map(|d| d.value).sum::<isize>());
+        }
+    }
+
+    #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+    struct DummyState {
+        value: isize,
+        depth: usize,
+    }
+
+    #[derive(Copy, Clone)]
+    struct DummyProblem;
+    impl Problem for DummyProblem {
+        type State = DummyState;
+
+        fn nb_variables(&self)  -> usize { 3 }
+        fn initial_value(&self) -> isize { 0 }
+        fn initial_state(&self) -> Self::State {
+            DummyState {
+                value: 0,
+                depth: 0,
+            }
+        }
+
+        fn transition(&self, state: &Self::State, decision: crate::Decision) -> Self::State {
+            DummyState {
+                value: state.value + decision.value,
+                depth: 1 + state.depth
+            }
+        }
+
+        fn transition_cost(&self, _: &Self::State, _: &Self::State, decision: crate::Decision) -> isize {
+            decision.value
+        }
+
+        fn next_variable(&self, depth: usize, _: &mut dyn Iterator<Item = &Self::State>)
+            -> Option<crate::Variable> {
+            if depth < self.nb_variables() {
+                Some(Variable(depth))
+            } else {
+                None
+            }
+        }
+
+        fn for_each_in_domain(&self, var: crate::Variable, _: &Self::State, f: &mut dyn DecisionCallback) {
+            for d in 0..=2 {
+                f.apply(Decision {variable: var, value: d})
+            }
+        }
+    }
+
+    /// A variant of the dummy problem which draws its domain values through
+    /// a genuinely lazy iterator
+    struct LazyDummyProblem;
+    impl Problem for LazyDummyProblem {
+        type State = DummyState;
+
+        fn nb_variables(&self)  -> usize { DummyProblem.nb_variables() }
+        fn initial_value(&self) -> isize { DummyProblem.initial_value() }
+        fn initial_state(&self) -> Self::State {
+            DummyProblem.initial_state()
+        }
+
+        fn transition(&self, state: &Self::State, decision: crate::Decision) -> Self::State {
+            DummyProblem.transition(state, decision)
+        }
+
+        fn transition_cost(&self, source: &Self::State, dest: &Self::State, decision: crate::Decision) -> isize {
+            DummyProblem.transition_cost(source, dest, decision)
+        }
+
+        fn next_variable(&self, depth: usize, next_layer: &mut dyn Iterator<Item = &Self::State>)
+            -> Option<crate::Variable> {
+            DummyProblem.next_variable(depth, next_layer)
+        }
+
+        fn for_each_in_domain(&self, var: crate::Variable, state: &Self::State, f: &mut dyn DecisionCallback) {
+            DummyProblem.for_each_in_domain(var, state, f)
+        }
+
+        fn domain_iter<'a>(&'a self, _: crate::Variable, _: &'a Self::State) -> Box<dyn Iterator<Item = isize> + 'a> {
+            Box::new(0..=2)
+        }
+
+        fn has_lazy_domain_iter(&self) -> bool {
+            true
+        }
+    }
+
+    /// A variant of the dummy problem whose domains depend on the partial
+    /// assignment: a value may only be used once along a path
+    struct PathDummyProblem;
+    impl Problem for PathDummyProblem {
+        type State = DummyState;
+
+        fn nb_variables(&self)  -> usize { DummyProblem.nb_variables() }
+        fn initial_value(&self) -> isize { DummyProblem.initial_value() }
+        fn initial_state(&self) -> Self::State {
+            DummyProblem.initial_state()
+        }
+
+        fn transition(&self, state: &Self::State, decision: crate::Decision) -> Self::State {
+            DummyProblem.transition(state, decision)
+        }
+
+        fn transition_cost(&self, source: &Self::State, dest: &Self::State, decision: crate::Decision) -> isize {
+            DummyProblem.transition_cost(source, dest, decision)
+        }
+
+        fn next_variable(&self, depth: usize, next_layer: &mut dyn Iterator<Item = &Self::State>)
+            -> Option<crate::Variable> {
+            DummyProblem.next_variable(depth, next_layer)
+        }
+
+        fn for_each_in_domain(&self, var: crate::Variable, state: &Self::State, f: &mut dyn DecisionCallback) {
+            DummyProblem.for_each_in_domain(var, state, f)
+        }
+
+        fn for_each_in_domain_with_path(&self, var: crate::Variable, _: &Self::State, path: &[Decision], f: &mut dyn DecisionCallback) {
+            for d in 0..=2 {
+                if !path.iter().any(|dec| dec.value == d) {
+                    f.apply(Decision {variable: var, value: d})
+                }
+            }
+        }
+
+        fn has_path_dependent_domains(&self) -> bool {
+            true
+        }
+    }
+
+    /// A variant of the dummy problem whose transition rejects the decisions
+    /// assigning the value 2 (the infeasibility only shows in the transition)
+    struct CheckedDummyProblem;
+    impl Problem for CheckedDummyProblem {
+        type State = DummyState;
+
+        fn nb_variables(&self)  -> usize { DummyProblem.nb_variables() }
+        fn initial_value(&self) -> isize { DummyProblem.initial_value() }
+        fn initial_state(&self) -> Self::State {
+            DummyProblem.initial_state()
+        }
+
+        fn transition(&self, state: &Self::State, decision: crate::Decision) -> Self::State {
+            DummyProblem.transition(state, decision)
+        }
+
+        fn transition_checked(&self, state: &Self::State, decision: crate::Decision) -> Option<Self::State> {
+            if decision.value == 2 {
+                None
+            } else {
+                Some(DummyProblem.transition(state, decision))
+            }
+        }
+
+        fn transition_cost(&self, source: &Self::State, dest: &Self::State, decision: crate::Decision) -> isize {
+            DummyProblem.transition_cost(source, dest, decision)
+        }
+
+        fn next_variable(&self, depth: usize, next_layer: &mut dyn Iterator<Item = &Self::State>)
+            -> Option<crate::Variable> {
+            DummyProblem.next_variable(depth, next_layer)
+        }
+
+        fn for_each_in_domain(&self, var: crate::Variable, state: &Self::State, f: &mut dyn DecisionCallback) {
+            DummyProblem.for_each_in_domain(var, state, f)
+        }
+    }
+
+    /// A variant of the dummy problem whose states become leaves as soon as
+    /// their accumulated value reaches the given threshold: the paths ending
+    /// there are terminated early, before all the variables are branched on
+    struct LeafDummyProblem { leaf_threshold: isize }
+    impl Problem for LeafDummyProblem {
+        type State = DummyState;
+
+        fn nb_variables(&self)  -> usize { DummyProblem.nb_variables() }
+        fn initial_value(&self) -> isize { DummyProblem.initial_value() }
+        fn initial_state(&self) -> Self::State {
+            DummyProblem.initial_state()
+        }
+
+        fn transition(&self, state: &Self::State, decision: crate::Decision) -> Self::State {
+            DummyProblem.transition(state, decision)
+        }
+
+        fn transition_cost(&self, source: &Self::State, dest: &Self::State, decision: crate::Decision) -> isize {
+            DummyProblem.transition_cost(source, dest, decision)
+        }
+
+        fn next_variable(&self, depth: usize, next_layer: &mut dyn Iterator<Item = &Self::State>)
+            -> Option<crate::Variable> {
+            DummyProblem.next_variable(depth, next_layer)
+        }
+
+        fn for_each_in_domain(&self, var: crate::Variable, state: &Self::State, f: &mut dyn DecisionCallback) {
+            DummyProblem.for_each_in_domain(var, state, f)
+        }
+
+        fn is_leaf(&self, state: &Self::State) -> bool {
+            state.value >= self.leaf_threshold
+        }
     }
 
-    fn strip_format(s: &str) -> String {
-        s.lines().map(|l| l.trim()).collect()
+    /// A variant of the dummy problem where any state having accumulated a
+    /// strictly positive value is a dead end (its domain is empty). Whether
+    /// such a dead end still counts as a solution is controlled by the
+    /// `always_feasible` flag.
+    struct DeadEndDummyProblem { always_feasible: bool }
+    impl Problem for DeadEndDummyProblem {
+        type State = DummyState;
+
+        fn nb_variables(&self)  -> usize { DummyProblem.nb_variables() }
+        fn initial_value(&self) -> isize { DummyProblem.initial_value() }
+        fn initial_state(&self) -> Self::State {
+            DummyProblem.initial_state()
+        }
+
+        fn transition(&self, state: &Self::State, decision: crate::Decision) -> Self::State {
+            DummyProblem.transition(state, decision)
+        }
+
+        fn transition_cost(&self, source: &Self::State, dest: &Self::State, decision: crate::Decision) -> isize {
+            DummyProblem.transition_cost(source, dest, decision)
+        }
+
+        fn next_variable(&self, depth: usize, next_layer: &mut dyn Iterator<Item = &Self::State>)
+            -> Option<crate::Variable> {
+            DummyProblem.next_variable(depth, next_layer)
+        }
+
+        fn for_each_in_domain(&self, var: crate::Variable, state: &Self::State, f: &mut dyn DecisionCallback) {
+            if state.value == 0 {
+                f.apply(Decision { variable: var, value: 0 });
+                if state.depth == 0 {
+                    f.apply(Decision { variable: var, value: 5 });
+                }
+            }
+        }
+
+        fn always_feasible(&self) -> bool {
+            self.always_feasible
+        }
     }
 
-    #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
-    struct DummyState {
-        value: isize,
-        depth: usize,
+    /// A variant of the dummy problem which provides its branching order
+    /// upfront (from the last variable down to the first one)
+    struct StaticOrderDummyProblem;
+    impl Problem for StaticOrderDummyProblem {
+        type State = DummyState;
+
+        fn nb_variables(&self)  -> usize { DummyProblem.nb_variables() }
+        fn initial_value(&self) -> isize { DummyProblem.initial_value() }
+        fn initial_state(&self) -> Self::State {
+            DummyProblem.initial_state()
+        }
+
+        fn transition(&self, state: &Self::State, decision: crate::Decision) -> Self::State {
+            DummyProblem.transition(state, decision)
+        }
+
+        fn transition_cost(&self, source: &Self::State, dest: &Self::State, decision: crate::Decision) -> isize {
+            DummyProblem.transition_cost(source, dest, decision)
+        }
+
+        fn next_variable(&self, _: usize, _: &mut dyn Iterator<Item = &Self::State>)
+            -> Option<crate::Variable> {
+            panic!("next_variable must not be consulted when a static order is provided")
+        }
+
+        fn for_each_in_domain(&self, var: crate::Variable, state: &Self::State, f: &mut dyn DecisionCallback) {
+            DummyProblem.for_each_in_domain(var, state, f)
+        }
+
+        fn static_order(&self) -> Option<Vec<Variable>> {
+            Some(vec![Variable(2), Variable(1), Variable(0)])
+        }
     }
 
-    #[derive(Copy, Clone)]
-    struct DummyProblem;
-    impl Problem for DummyProblem {
+    #[derive(Clone,Copy)]
+    struct DummyInfeasibleProblem;
+    impl Problem for DummyInfeasibleProblem {
         type State = DummyState;
 
         fn nb_variables(&self)  -> usize { 3 }
@@ -2586,36 +4683,183 @@ mod test_default_mdd {
             }
         }
 
-        fn for_each_in_domain(&self, var: crate::Variable, _: &Self::State, f: &mut dyn DecisionCallback) {
-            for d in 0..=2 {
-                f.apply(Decision {variable: var, value: d})
+        fn for_each_in_domain(&self, _: crate::Variable, _: &Self::State, _: &mut dyn DecisionCallback) {
+            /* do nothing, just consider that all domains are empty */
+        }
+    }
+
+    /// A tiny problem whose decisions all cost the same and whose states
+    /// collapse per layer: every complete decision path is tied at the
+    /// optimum
+    #[derive(Copy, Clone)]
+    struct TiedProblem;
+    impl Problem for TiedProblem {
+        type State = usize;
+
+        fn nb_variables(&self)  -> usize { 2 }
+        fn initial_value(&self) -> isize { 0 }
+        fn initial_state(&self) -> Self::State { 0 }
+
+        fn transition(&self, state: &Self::State, _: crate::Decision) -> Self::State {
+            state + 1
+        }
+
+        fn transition_cost(&self, _: &Self::State, _: &Self::State, _: crate::Decision) -> isize {
+            1
+        }
+
+        fn next_variable(&self, depth: usize, _: &mut dyn Iterator<Item = &Self::State>)
+            -> Option<crate::Variable> {
+            if depth < self.nb_variables() {
+                Some(Variable(depth))
+            } else {
+                None
             }
         }
+
+        fn for_each_in_domain(&self, var: crate::Variable, _: &Self::State, f: &mut dyn DecisionCallback) {
+            f.apply(Decision {variable: var, value: 0});
+            f.apply(Decision {variable: var, value: 1});
+        }
     }
 
-    #[derive(Clone,Copy)]
-    struct DummyInfeasibleProblem;
-    impl Problem for DummyInfeasibleProblem {
-        type State = DummyState;
+    /// A problem whose relaxed compilation with a width of 2 merges the
+    /// second layer and forces the best path through the merged node: the
+    /// kept node of that layer (state 2) has an empty domain and cannot be
+    /// expanded any further
+    struct RepProblem;
+    impl Problem for RepProblem {
+        type State = usize;
 
         fn nb_variables(&self)  -> usize { 3 }
         fn initial_value(&self) -> isize { 0 }
-        fn initial_state(&self) -> Self::State {
-            DummyState {
-                value: 0,
-                depth: 0,
+        fn initial_state(&self) -> Self::State { 0 }
+
+        fn transition(&self, state: &Self::State, d: crate::Decision) -> Self::State {
+            state + d.value as usize
+        }
+
+        fn transition_cost(&self, _: &Self::State, _: &Self::State, d: crate::Decision) -> isize {
+            d.value
+        }
+
+        fn next_variable(&self, depth: usize, _: &mut dyn Iterator<Item = &Self::State>)
+            -> Option<crate::Variable> {
+            if depth < self.nb_variables() {
+                Some(Variable(depth))
+            } else {
+                None
             }
         }
 
-        fn transition(&self, state: &Self::State, decision: crate::Decision) -> Self::State {
-            DummyState {
-                value: state.value + decision.value,
-                depth: 1 + state.depth
+        fn for_each_in_domain(&self, var: crate::Variable, state: &Self::State, f: &mut dyn DecisionCallback) {
+            match var.id() {
+                0 => for d in 0..=2 {
+                    f.apply(Decision {variable: var, value: d})
+                },
+                1 => f.apply(Decision {variable: var, value: 0}),
+                _ => if *state != 2 {
+                    for d in 0..=2 {
+                        f.apply(Decision {variable: var, value: d})
+                    }
+                },
             }
         }
+    }
 
-        fn transition_cost(&self, _: &Self::State, _: &Self::State, decision: crate::Decision) -> isize {
-            decision.value
+    /// A relaxation which merges states onto their minimum, sticking to the
+    /// default (max-value) representative
+    struct RepRelax;
+    impl Relaxation for RepRelax {
+        type State = usize;
+
+        fn merge(&self, s: &mut dyn Iterator<Item=&Self::State>) -> Self::State {
+            *s.min().unwrap()
+        }
+        fn relax(&self, _: &Self::State, _: &Self::State, _: &Self::State, _: Decision, cost: isize) -> isize {
+            cost
+        }
+    }
+
+    /// The same relaxation as `RepRelax`, except that it elects the
+    /// min-value member of a merged group as its representative
+    struct MinRepRelax;
+    impl Relaxation for MinRepRelax {
+        type State = usize;
+
+        fn merge(&self, s: &mut dyn Iterator<Item=&Self::State>) -> Self::State {
+            *s.min().unwrap()
+        }
+        fn relax(&self, _: &Self::State, _: &Self::State, _: &Self::State, _: Decision, cost: isize) -> isize {
+            cost
+        }
+        fn select_representative(&self, states: &mut dyn Iterator<Item = (&Self::State, isize)>) -> usize {
+            states.enumerate()
+                .min_by_key(|(_, (_, value))| *value)
+                .map(|(i, _)| i)
+                .unwrap_or(0)
+        }
+    }
+
+    struct TiedRelax;
+    impl Relaxation for TiedRelax {
+        type State = usize;
+
+        fn merge(&self, s: &mut dyn Iterator<Item=&Self::State>) -> Self::State {
+            *s.next().unwrap()
+        }
+        fn relax(&self, _: &Self::State, _: &Self::State, _: &Self::State, _: Decision, cost: isize) -> isize {
+            cost
+        }
+    }
+
+    struct TiedRanking;
+    impl StateRanking for TiedRanking {
+        type State = usize;
+
+        fn compare(&self, _: &Self::State, _: &Self::State) -> Ordering {
+            Ordering::Equal
+        }
+    }
+
+    /// A ranking whose primary criterion never discriminates but whose
+    /// tie-break prefers the max-state nodes
+    struct TieBreakRanking;
+    impl StateRanking for TieBreakRanking {
+        type State = usize;
+
+        fn compare(&self, _: &Self::State, _: &Self::State) -> Ordering {
+            Ordering::Equal
+        }
+        fn tie_break(&self, a: &Self::State, b: &Self::State) -> Ordering {
+            a.cmp(b)
+        }
+    }
+
+    /// A problem whose second layer comprises three nodes with the very same
+    /// value (zero): only the last transition reveals that the max-state
+    /// node was the best one to keep. The tied layer sits at depth 2 because
+    /// the compiler never squashes the very first branched layer.
+    struct TieProblem;
+    impl Problem for TieProblem {
+        type State = usize;
+
+        fn nb_variables(&self)  -> usize { 3 }
+        fn initial_value(&self) -> isize { 0 }
+        fn initial_state(&self) -> Self::State { 0 }
+
+        fn transition(&self, state: &Self::State, d: crate::Decision) -> Self::State {
+            match d.variable.id() {
+                1 => d.value as usize,
+                _ => *state,
+            }
+        }
+
+        fn transition_cost(&self, state: &Self::State, _: &Self::State, d: crate::Decision) -> isize {
+            match d.variable.id() {
+                2 => *state as isize * 10,
+                _ => 0,
+            }
         }
 
         fn next_variable(&self, depth: usize, _: &mut dyn Iterator<Item = &Self::State>)
@@ -2627,8 +4871,13 @@ mod test_default_mdd {
             }
         }
 
-        fn for_each_in_domain(&self, _: crate::Variable, _: &Self::State, _: &mut dyn DecisionCallback) {
-            /* do nothing, just consider that all domains are empty */
+        fn for_each_in_domain(&self, var: crate::Variable, _: &Self::State, f: &mut dyn DecisionCallback) {
+            match var.id() {
+                1 => for d in 0..=2 {
+                    f.apply(Decision {variable: var, value: d})
+                },
+                _ => f.apply(Decision {variable: var, value: 0}),
+            }
         }
     }
 
@@ -2653,6 +4902,62 @@ mod test_default_mdd {
         }
     }
 
+    /// A variant of the dummy relaxation for which every merge signals that
+    /// nothing feasible remains reachable from the merged node
+    struct InfeasibleMergeRelax;
+    impl Relaxation for InfeasibleMergeRelax {
+        type State = DummyState;
+
+        fn merge(&self, s: &mut dyn Iterator<Item=&Self::State>) -> Self::State {
+            DummyRelax.merge(s)
+        }
+        fn merge_opt(&self, _: &mut dyn Iterator<Item=&Self::State>) -> Option<Self::State> {
+            None
+        }
+        fn relax(&self, a: &Self::State, b: &Self::State, c: &Self::State, d: Decision, e: isize) -> isize {
+            DummyRelax.relax(a, b, c, d, e)
+        }
+        fn fast_upper_bound(&self, state: &Self::State) -> isize {
+            DummyRelax.fast_upper_bound(state)
+        }
+    }
+
+    /// A deliberately broken relaxation whose fast upper bound underestimates
+    /// the remaining objective near the root (it is not an over-approximation
+    /// of it): used to check that the debug-only quality assertions fire
+    struct UnderestimatingRubRelax;
+    impl Relaxation for UnderestimatingRubRelax {
+        type State = DummyState;
+
+        fn merge(&self, s: &mut dyn Iterator<Item=&Self::State>) -> Self::State {
+            DummyRelax.merge(s)
+        }
+        fn relax(&self, a: &Self::State, b: &Self::State, c: &Self::State, d: Decision, e: isize) -> isize {
+            DummyRelax.relax(a, b, c, d, e)
+        }
+        fn fast_upper_bound(&self, state: &Self::State) -> isize {
+            state.depth as isize * 10
+        }
+    }
+
+    /// A deliberately broken relaxation whose merged arcs are shortened so
+    /// much that the merged node no longer covers the value of its members:
+    /// used to check that the debug-only quality assertions fire
+    struct ShorteningMergeRelax;
+    impl Relaxation for ShorteningMergeRelax {
+        type State = DummyState;
+
+        fn merge(&self, s: &mut dyn Iterator<Item=&Self::State>) -> Self::State {
+            DummyRelax.merge(s)
+        }
+        fn relax(&self, _: &Self::State, _: &Self::State, _: &Self::State, _: Decision, cost: isize) -> isize {
+            cost - 1000
+        }
+        fn fast_upper_bound(&self, state: &Self::State) -> isize {
+            DummyRelax.fast_upper_bound(state)
+        }
+    }
+
     #[derive(Copy, Clone)]
     struct DummyRanking;
     impl StateRanking for DummyRanking {
@@ -2662,4 +4967,24 @@ mod test_default_mdd {
             a.value.cmp(&b.value).reverse()
         }
     }
+
+    /// A relaxation which overrides the merge-group selection so as to only
+    /// merge the two least promising nodes of an overflowing layer
+    struct PairMergeRelax;
+    impl Relaxation for PairMergeRelax {
+        type State = DummyState;
+
+        fn merge(&self, s: &mut dyn Iterator<Item=&Self::State>) -> Self::State {
+            DummyRelax.merge(s)
+        }
+        fn relax(&self, a: &Self::State, b: &Self::State, c: &Self::State, d: Decision, e: isize) -> isize {
+            DummyRelax.relax(a, b, c, d, e)
+        }
+        fn fast_upper_bound(&self, state: &Self::State) -> isize {
+            DummyRelax.fast_upper_bound(state)
+        }
+        fn select_merge_groups(&self, states: &[&Self::State], _width: usize) -> Vec<Vec<usize>> {
+            vec![vec![states.len() - 2, states.len() - 1]]
+        }
+    }
 }
\ No newline at end of file
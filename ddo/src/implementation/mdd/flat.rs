@@ -0,0 +1,616 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! This module provides the implementation of a shallow ("flat") decision
+//! diagram which only ever materializes two layers at a time.
+
+use std::{hash::Hash, sync::Arc};
+
+use fxhash::FxHashMap;
+
+use crate::{
+    CompilationInput, CompilationType, Completion, Decision, DecisionDiagram, Reason, Solution,
+    SubProblem,
+};
+
+/// One link of the chain of decisions leading from the root of the diagram
+/// to a node. Because a flat diagram drops its layers as it unrolls, this
+/// chain is all that remains of the best path of a node; sharing the common
+/// prefixes through reference counting keeps it affordable.
+struct PathLink {
+    /// The decision labeling the last arc of the best path
+    decision: Decision,
+    /// The chain of the decisions leading to the source of that arc
+    parent: Option<Arc<PathLink>>,
+}
+
+/// An effective node from the flat decision diagram: its state, the length
+/// of its best path and the chain materializing that path
+#[derive(Clone)]
+struct FlatNode<T> {
+    /// The state associated to this node
+    state: Arc<T>,
+    /// The length of the longest path between the problem root and this node
+    value: isize,
+    /// Whether this node is exact (no merged node occurs on any path to it)
+    exact: bool,
+    /// The chain of decisions of the best path to this node (None for the
+    /// root of the compiled subproblem)
+    path: Option<Arc<PathLink>>,
+}
+
+/// An edge reaching into the layer under construction. The edges of a layer
+/// only live for the time of building that layer: they are collapsed onto
+/// their destination node as soon as the layer is complete.
+struct InEdge<T> {
+    /// The state at the source of this edge
+    from_state: Arc<T>,
+    /// The length of the best path to the source of this edge
+    from_value: isize,
+    /// Whether the source of this edge is an exact node
+    from_exact: bool,
+    /// The chain of decisions of the best path to the source of this edge
+    from_path: Option<Arc<PathLink>>,
+    /// The decision labeling this edge
+    decision: Decision,
+    /// The transition cost of this edge
+    cost: isize,
+}
+
+/// This structure implements a flat MDD: a diagram which only ever keeps two
+/// layers in memory (the layer being expanded and the one under
+/// construction). This is the memory-thrifty counterpart of the deep `Mdd`:
+/// it recovers the best paths from reference-counted decision chains instead
+/// of materialized edges, which made it the structure of choice for problems
+/// with very wide layers and little per-node information (e.g. MISP).
+///
+/// # Tradeoff
+/// The low memory footprint comes at the price of the prunings that require
+/// the complete diagram to be kept around: a flat MDD forgoes the local
+/// (bottom-up) bounds, the threshold cache and the dominance filtering, and
+/// it ignores the in/out-degree caps of the compilation input. Only the
+/// rough-upper-bound pruning remains. The cut-set of its relaxed
+/// compilations is the last exact layer, whose subproblems are bounded with
+/// the rough upper bound alone. Prefer `DefaultMDD` whenever those prunings
+/// pay for their memory.
+pub struct FlatMdd<T>
+where
+    T: Eq + PartialEq + Hash + Clone,
+{
+    /// The path to the root of the subproblem being compiled
+    path_to_root: Vec<Decision>,
+    /// The layer being expanded (the only complete layer held in memory)
+    curr: Vec<FlatNode<T>>,
+    /// The subproblems of the last exact layer of the last relaxed
+    /// compilation (the cut-set which `drain_cutset` hands out)
+    cutset: Vec<SubProblem<T>>,
+    /// Whether the last compilation was exact (no node was dropped or merged)
+    is_exact: bool,
+    /// Whether the best path of the last (relaxed) compilation only
+    /// traverses exact nodes
+    has_exact_best_path: bool,
+    /// The best terminal node of the last compilation, if any
+    best_node: Option<FlatNode<T>>,
+    /// The best *exact* terminal node of the last compilation, if any
+    best_exact_node: Option<FlatNode<T>>,
+}
+
+impl<T> Default for FlatMdd<T>
+where
+    T: Eq + PartialEq + Hash + Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> FlatMdd<T>
+where
+    T: Eq + PartialEq + Hash + Clone,
+{
+    pub fn new() -> Self {
+        Self {
+            path_to_root: vec![],
+            curr: vec![],
+            cutset: vec![],
+            is_exact: true,
+            has_exact_best_path: false,
+            best_node: None,
+            best_exact_node: None,
+        }
+    }
+
+    fn _clear(&mut self) {
+        self.path_to_root.clear();
+        self.curr.clear();
+        self.cutset.clear();
+        self.is_exact = true;
+        self.has_exact_best_path = false;
+        self.best_node = None;
+        self.best_exact_node = None;
+    }
+
+    fn _compile(&mut self, input: &CompilationInput<T>) -> Result<Completion, Reason> {
+        self._clear();
+        self.path_to_root.extend_from_slice(&input.residual.path);
+        self.curr.push(FlatNode {
+            state: input.residual.state.clone(),
+            value: input.residual.value,
+            exact: true,
+            path: None,
+        });
+
+        let mut curr_depth = input.residual.depth;
+        let mut next: FxHashMap<Arc<T>, Vec<InEdge<T>>> = Default::default();
+
+        while let Some(var) =
+            input.problem.next_variable(curr_depth, &mut self.curr.iter().map(|n| n.state.as_ref()))
+        {
+            // Did the cutoff kick in ?
+            if input.cutoff.must_stop() {
+                return Err(Reason::CutoffOccurred);
+            }
+
+            for node in self.curr.iter() {
+                let rub = input.relaxation.fast_upper_bound_with_value(
+                    node.state.as_ref(),
+                    node.value,
+                    curr_depth,
+                );
+                if rub <= input.best_lb {
+                    continue;
+                }
+                input.problem.for_each_in_domain(var, node.state.as_ref(), &mut |decision| {
+                    if let Some(to) = input.problem.transition_checked(node.state.as_ref(), decision) {
+                        let cost = input.problem.transition_cost(node.state.as_ref(), &to, decision);
+                        next.entry(Arc::new(to)).or_default().push(InEdge {
+                            from_state: node.state.clone(),
+                            from_value: node.value,
+                            from_exact: node.exact,
+                            from_path: node.path.clone(),
+                            decision,
+                            cost,
+                        });
+                    }
+                });
+            }
+            input.cutoff.add_expanded(self.curr.len());
+            curr_depth += 1;
+
+            if next.is_empty() {
+                // nothing feasible remains reachable: the diagram dead-ends
+                self.curr.clear();
+                break;
+            }
+            self._next_layer(input, &mut next);
+        }
+
+        for node in self.curr.iter() {
+            if self.best_node.as_ref().is_none_or(|best| node.value > best.value) {
+                self.best_node = Some(node.clone());
+            }
+            if node.exact
+                && self.best_exact_node.as_ref().is_none_or(|best| node.value > best.value)
+            {
+                self.best_exact_node = Some(node.clone());
+            }
+        }
+
+        self.has_exact_best_path = matches!(input.comp_type, CompilationType::Relaxed)
+            && self.best_node.as_ref().is_some_and(|node| node.exact);
+
+        Ok(Completion {
+            is_exact: self.is_exact(),
+            best_value: self.best_node.as_ref().map(|node| node.value),
+        })
+    }
+
+    /// Replaces the current layer with the one that has been accumulated in
+    /// `next`, restricting or relaxing it when it overflows the maximum
+    /// width of the compilation
+    fn _next_layer(&mut self, input: &CompilationInput<T>, next: &mut FxHashMap<Arc<T>, Vec<InEdge<T>>>) {
+        let mut groups = next.drain().collect::<Vec<_>>();
+
+        let overflowing = groups.len() > input.max_width;
+        if overflowing && !matches!(input.comp_type, CompilationType::Exact) {
+            // greater means better and hence more likely to be kept
+            groups.sort_unstable_by(|a, b| {
+                Self::_group_value(&a.1)
+                    .cmp(&Self::_group_value(&b.1))
+                    .then_with(|| input.ranking.compare(a.0.as_ref(), b.0.as_ref()))
+                    .then_with(|| input.ranking.tie_break(a.0.as_ref(), b.0.as_ref()))
+                    .reverse()
+            });
+            if matches!(input.comp_type, CompilationType::Relaxed) {
+                self._save_cutset(input);
+                self._relax_surplus(input, &mut groups);
+            } else {
+                groups.truncate(input.max_width);
+            }
+            self.is_exact = false;
+        }
+
+        self.curr.clear();
+        for (state, edges) in groups {
+            self.curr.push(Self::_collapse(state, edges));
+        }
+    }
+
+    /// Collapses the in-edges of a node of the freshly completed layer onto
+    /// the node itself: only the best path (and the exactness info) survives
+    fn _collapse(state: Arc<T>, edges: Vec<InEdge<T>>) -> FlatNode<T> {
+        let mut value = isize::MIN;
+        let mut exact = true;
+        let mut path = None;
+        for edge in edges {
+            exact &= edge.from_exact;
+            let through = edge.from_value.saturating_add(edge.cost);
+            if through > value {
+                value = through;
+                path = Some(Arc::new(PathLink {
+                    decision: edge.decision,
+                    parent: edge.from_path,
+                }));
+            }
+        }
+        FlatNode { state, value, exact, path }
+    }
+
+    /// The length of the best path reaching a node of the layer under
+    /// construction (used to rank the nodes before squashing the layer)
+    fn _group_value(edges: &[InEdge<T>]) -> isize {
+        edges.iter()
+            .map(|edge| edge.from_value.saturating_add(edge.cost))
+            .max()
+            .unwrap_or(isize::MIN)
+    }
+
+    /// Merges the surplus groups of an overflowing relaxed layer into one
+    /// single group associated to the merged state (or simply drops them
+    /// when the relaxation signals that nothing feasible remains reachable
+    /// from the merged node)
+    fn _relax_surplus(&mut self, input: &CompilationInput<T>, groups: &mut Vec<(Arc<T>, Vec<InEdge<T>>)>) {
+        let mut merge = groups.split_off(input.max_width - 1);
+        let merged = input
+            .relaxation
+            .merge_opt_with_type(input.comp_type, &mut merge.iter().map(|(state, _)| state.as_ref()));
+
+        // when the relaxation yields no merged state, the surplus is simply
+        // dropped (the same outcome as a restriction)
+        let Some(merged) = merged else { return };
+        let merged = Arc::new(merged);
+
+        let mut redirected = vec![];
+        for (state, edges) in merge.iter_mut() {
+            for edge in edges.drain(..) {
+                let rcost = input.relaxation.relax_with_type(
+                    input.comp_type,
+                    edge.from_state.as_ref(),
+                    state.as_ref(),
+                    merged.as_ref(),
+                    edge.decision,
+                    edge.cost,
+                );
+                redirected.push(InEdge {
+                    from_exact: false, // the merged node is inexact by definition
+                    cost: rcost,
+                    ..edge
+                });
+            }
+        }
+
+        if let Some((_, edges)) = groups.iter_mut().find(|(state, _)| state.eq(&merged)) {
+            // the merged state already exists among the kept nodes: that node
+            // inherits the redirected arcs and becomes inexact
+            edges.extend(redirected);
+            edges.iter_mut().for_each(|edge| edge.from_exact = false);
+        } else {
+            groups.push((merged, redirected));
+        }
+    }
+
+    /// Captures the cut-set of the compilation: the current layer is the
+    /// last exact one when the very first squashing occurs. Without local
+    /// bounds, the cut-set subproblems are bounded with the rough upper
+    /// bound alone.
+    fn _save_cutset(&mut self, input: &CompilationInput<T>) {
+        if self.is_exact {
+            for node in self.curr.iter() {
+                let rub = input.relaxation.fast_upper_bound_with_value(
+                    node.state.as_ref(),
+                    node.value,
+                    input.residual.depth + self.path_len(node),
+                );
+                self.cutset.push(SubProblem {
+                    state: node.state.clone(),
+                    value: node.value,
+                    path: self._best_path(node),
+                    ub: input.residual.ub.min(rub),
+                    depth: input.residual.depth + self.path_len(node),
+                });
+            }
+        }
+    }
+
+    /// The number of decisions chained on the best path of the given node
+    /// (that is, its depth relative to the root of the compiled subproblem)
+    fn path_len(&self, node: &FlatNode<T>) -> usize {
+        let mut len = 0;
+        let mut link = node.path.as_ref();
+        while let Some(chain) = link {
+            len += 1;
+            link = chain.parent.as_ref();
+        }
+        len
+    }
+
+    /// Materializes the best path of the given node: the path to the root of
+    /// the compiled subproblem extended with the decisions of its chain
+    fn _best_path(&self, node: &FlatNode<T>) -> Vec<Decision> {
+        let mut chained = vec![];
+        let mut link = node.path.as_ref();
+        while let Some(chain) = link {
+            chained.push(chain.decision);
+            link = chain.parent.as_ref();
+        }
+        let mut path = self.path_to_root.clone();
+        path.extend(chained.into_iter().rev());
+        path
+    }
+}
+
+impl<T> DecisionDiagram for FlatMdd<T>
+where
+    T: Eq + PartialEq + Hash + Clone,
+{
+    type State = T;
+
+    fn compile(&mut self, input: &CompilationInput<Self::State>) -> Result<Completion, Reason> {
+        self._compile(input)
+    }
+
+    fn is_exact(&self) -> bool {
+        self.is_exact || self.has_exact_best_path
+    }
+
+    fn best_value(&self) -> Option<isize> {
+        self.best_node.as_ref().map(|node| node.value)
+    }
+
+    fn best_solution(&self) -> Option<Solution> {
+        self.best_node.clone().map(|node| Solution::new(self._best_path(&node)))
+    }
+
+    fn best_exact_value(&self) -> Option<isize> {
+        self.best_exact_node.as_ref().map(|node| node.value)
+    }
+
+    fn best_exact_solution(&self) -> Option<Solution> {
+        self.best_exact_node.clone().map(|node| Solution::new(self._best_path(&node)))
+    }
+
+    fn drain_cutset<F>(&mut self, func: F)
+    where
+        F: FnMut(SubProblem<Self::State>),
+    {
+        self.cutset.drain(..).for_each(func)
+    }
+}
+
+// ############################################################################
+// #### TESTS #################################################################
+// ############################################################################
+
+/// These tests validate the flat diagram on the same tiny knapsack instance
+/// as the other compilation strategies.
+#[cfg(test)]
+mod test_flat_mdd {
+    use std::sync::Arc;
+
+    use crate::*;
+
+    fn root() -> SubProblem<KnapsackState> {
+        SubProblem {
+            state: Arc::new(KnapsackState { depth: 0, capacity: 50 }),
+            value: 0,
+            path: vec![],
+            ub: isize::MAX,
+            depth: 0,
+        }
+    }
+
+    fn compile(comp_type: CompilationType, max_width: usize, mdd: &mut FlatMdd<KnapsackState>) -> Completion {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let cache = EmptyCache::new();
+        let dominance = EmptyDominanceChecker::default();
+        let input = CompilationInput {
+            comp_type,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
+            max_width,
+            problem: &problem,
+            relaxation: &relax,
+            ranking: &KPRanking,
+            cutoff: &NoCutoff,
+            cache: &cache,
+            dominance: &dominance,
+            residual: &root(),
+            best_lb: isize::MIN,
+        };
+        mdd.compile(&input).unwrap()
+    }
+
+    #[test]
+    fn an_exact_compilation_finds_the_optimum() {
+        let mut mdd = FlatMdd::new();
+        let completion = compile(CompilationType::Exact, usize::MAX, &mut mdd);
+
+        assert!(completion.is_exact);
+        assert_eq!(completion.best_value, Some(220));
+        let mut solution = mdd.best_solution().unwrap().to_vec();
+        solution.sort_unstable_by_key(|d| d.variable.0);
+        assert_eq!(vec![0, 1, 1], solution.iter().map(|d| d.value).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn a_restricted_compilation_is_flagged_inexact() {
+        let mut mdd = FlatMdd::new();
+        let completion = compile(CompilationType::Restricted, 1, &mut mdd);
+
+        assert!(!completion.is_exact);
+        // whatever survived the dive is a feasible solution
+        assert!(completion.best_value.unwrap() <= 220);
+        assert_eq!(completion.best_value, mdd.best_exact_value());
+    }
+
+    #[test]
+    fn a_relaxed_compilation_bounds_the_optimum_and_yields_a_cutset() {
+        let mut mdd = FlatMdd::new();
+        let completion = compile(CompilationType::Relaxed, 2, &mut mdd);
+
+        assert!(!completion.is_exact);
+        assert!(completion.best_value.unwrap() >= 220);
+
+        let mut cutset = vec![];
+        mdd.drain_cutset(|subproblem| cutset.push(subproblem));
+        assert!(!cutset.is_empty());
+        for subproblem in cutset {
+            assert!(subproblem.depth > 0);
+            assert_eq!(subproblem.depth, subproblem.path.len());
+            assert!(subproblem.ub >= subproblem.value);
+        }
+    }
+
+    #[test]
+    fn the_flat_solver_alias_finds_the_optimum() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = FixedWidth(2);
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SeqNoCachingSolverFlat::custom(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+
+        let maximized = solver.maximize();
+        assert!(maximized.is_exact);
+        assert_eq!(maximized.best_value, Some(220));
+    }
+
+    #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+    struct KnapsackState {
+        depth: usize,
+        capacity: usize
+    }
+
+    struct Knapsack {
+        capacity: usize,
+        profit: Vec<usize>,
+        weight: Vec<usize>,
+    }
+
+    const TAKE_IT: isize = 1;
+    const LEAVE_IT_OUT: isize = 0;
+
+    impl Problem for Knapsack {
+        type State = KnapsackState;
+
+        fn nb_variables(&self) -> usize {
+            self.profit.len()
+        }
+        fn initial_state(&self) -> Self::State {
+            KnapsackState{ depth: 0, capacity: self.capacity }
+        }
+        fn initial_value(&self) -> isize {
+            0
+        }
+        fn transition(&self, state: &Self::State, dec: Decision) -> Self::State {
+            let mut ret = *state;
+            ret.depth += 1;
+            if dec.value == TAKE_IT {
+                ret.capacity -= self.weight[dec.variable.id()]
+            }
+            ret
+        }
+        fn transition_cost(&self, _state: &Self::State, _next: &Self::State, dec: Decision) -> isize {
+            self.profit[dec.variable.id()] as isize * dec.value
+        }
+        fn next_variable(&self, depth: usize, _: &mut dyn Iterator<Item = &Self::State>) -> Option<Variable> {
+            let n = self.nb_variables();
+            if depth < n {
+                Some(Variable(depth))
+            } else {
+                None
+            }
+        }
+        fn for_each_in_domain(&self, variable: Variable, state: &Self::State, f: &mut dyn DecisionCallback) {
+            if state.capacity >= self.weight[variable.id()] {
+                f.apply(Decision { variable, value: TAKE_IT });
+            }
+            f.apply(Decision { variable, value: LEAVE_IT_OUT });
+        }
+    }
+
+    struct KPRelax<'a> {
+        pb: &'a Knapsack,
+    }
+    impl Relaxation for KPRelax<'_> {
+        type State = KnapsackState;
+
+        fn merge(&self, states: &mut dyn Iterator<Item = &Self::State>) -> Self::State {
+            states.max_by_key(|node| node.capacity).copied().unwrap()
+        }
+        fn relax(&self, _source: &Self::State, _dest: &Self::State, _merged: &Self::State, _decision: Decision, cost: isize) -> isize {
+            cost
+        }
+        fn fast_upper_bound(&self, state: &Self::State) -> isize {
+            let mut tot = 0;
+            for var in state.depth..self.pb.nb_variables() {
+                tot += self.pb.profit[var];
+            }
+            tot as isize
+        }
+    }
+
+    struct KPRanking;
+    impl StateRanking for KPRanking {
+        type State = KnapsackState;
+
+        fn compare(&self, a: &Self::State, b: &Self::State) -> std::cmp::Ordering {
+            a.capacity.cmp(&b.capacity)
+        }
+    }
+}
@@ -0,0 +1,385 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! This module provides a hybrid decision diagram which mixes restriction and
+//! relaxation within one single compilation, based on the depth of the layers.
+
+use std::hash::Hash;
+
+use crate::{
+    CompilationInput, Completion, Decision, DecisionDiagram, DefaultMDDLEL, Reason, Solution,
+    SubProblem,
+};
+
+/// A decision diagram that hybridizes its relaxed compilations based on depth:
+/// the surplus layers sitting at a depth shallower than the given threshold
+/// are restricted (the least promising nodes are dropped, which is cheap and
+/// performs a deep dive likely to yield good incumbents) while the surplus
+/// layers at or below the threshold are relaxed as usual (the surplus nodes
+/// are merged, which is what provides the dual bounds). Exact and restricted
+/// compilations are not affected: they behave exactly as with `DefaultMDD`.
+///
+/// # Correctness
+/// Mixing the two approximations within one relaxed diagram is safe because of
+/// the following three observations.
+///
+/// 1. The cutset of this diagram is the last exact layer (LEL), which is saved
+///    before the *first* approximation of either kind occurs. That layer is
+///    complete: every solution of the compiled subproblem traverses one of its
+///    nodes, so re-enqueueing the cutset keeps branch-and-bound complete.
+///    Cutset nodes whose descendants were all dropped by a restricted layer
+///    are enqueued as well, even though no terminal of the diagram can be
+///    reached from them anymore.
+///    (A frontier cutset would *not* be safe: restriction drops nodes without
+///    marking any surviving node inexact, so the frontier would not cover the
+///    lost solutions. This is why the hybrid is hardwired to use a LEL.)
+///
+/// 2. As soon as one layer has been restricted, the diagram no longer
+///    over-approximates the subproblem: neither its best value nor the local
+///    (bottom-up) bounds are valid upper bounds. The cutset nodes are
+///    therefore enqueued with the rough upper bound (`fast_upper_bound`) only,
+///    and no threshold is recorded in the cache. This only loosens the bounds
+///    and forgoes some cache prunings; it never prunes a subproblem wrongly.
+///
+/// 3. Incumbent solutions are only ever harvested from exact best paths,
+///    which correspond to actual paths of the *un*approximated diagram and
+///    are hence feasible -- dropped nodes never lie on any path, and merged
+///    nodes disqualify a path from being exact, just like in a plain relaxed
+///    compilation.
+///
+/// A threshold of 0 never restricts anything: the hybrid then behaves exactly
+/// like `DefaultMDD`. Note that the threshold is compared to the *global*
+/// depth of the layers (the depth wrt the root of the problem, not the root
+/// of the compiled subproblem): it delimits the portion of the search space
+/// where dives are preferred over bounds, regardless of the subproblem that
+/// is being compiled.
+pub struct HybridMdd<T>
+where
+    T: Eq + PartialEq + Hash + Clone,
+{
+    /// The actual diagram which this hybrid configures and delegates to
+    mdd: DefaultMDDLEL<T>,
+}
+
+impl<T> HybridMdd<T>
+where
+    T: Eq + PartialEq + Hash + Clone,
+{
+    /// Creates a hybrid diagram which restricts the surplus layers shallower
+    /// than the given depth threshold and relaxes the deeper ones.
+    pub fn new(threshold: usize) -> Self {
+        let mut mdd = DefaultMDDLEL::new();
+        mdd.set_hybrid_threshold(Some(threshold));
+        Self { mdd }
+    }
+}
+
+impl<T> Default for HybridMdd<T>
+where
+    T: Eq + PartialEq + Hash + Clone,
+{
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl<T> DecisionDiagram for HybridMdd<T>
+where
+    T: Eq + PartialEq + Hash + Clone,
+{
+    type State = T;
+
+    fn compile(&mut self, input: &CompilationInput<Self::State>) -> Result<Completion, Reason> {
+        self.mdd.compile(input)
+    }
+
+    fn is_exact(&self) -> bool {
+        self.mdd.is_exact()
+    }
+
+    fn best_value(&self) -> Option<isize> {
+        self.mdd.best_value()
+    }
+
+    fn best_solution(&self) -> Option<Solution> {
+        self.mdd.best_solution()
+    }
+
+    fn best_exact_value(&self) -> Option<isize> {
+        self.mdd.best_exact_value()
+    }
+
+    fn best_exact_solution(&self) -> Option<Solution> {
+        self.mdd.best_exact_solution()
+    }
+
+    fn avg_branching_factor(&self) -> f64 {
+        self.mdd.avg_branching_factor()
+    }
+
+    fn best_k_solutions(&self, k: usize) -> Vec<(isize, Solution)> {
+        self.mdd.best_k_solutions(k)
+    }
+
+    fn count_best_paths(&self) -> u128 {
+        self.mdd.count_best_paths()
+    }
+
+    fn solutions_above(&self, threshold: isize) -> Vec<(isize, Solution)> {
+        self.mdd.solutions_above(threshold)
+    }
+
+    fn for_each_edge_decision(&self, func: &mut dyn FnMut(Decision)) {
+        self.mdd.for_each_edge_decision(func)
+    }
+
+    fn drain_cutset<F>(&mut self, func: F)
+    where
+        F: FnMut(SubProblem<Self::State>),
+    {
+        self.mdd.drain_cutset(func)
+    }
+}
+
+#[cfg(test)]
+mod test_hybrid_mdd {
+    use std::cmp::Ordering;
+    use std::sync::Arc;
+
+    use crate::*;
+
+    #[test]
+    fn a_zero_threshold_behaves_exactly_like_the_default_mdd() {
+        let cache = EmptyCache::new();
+        let dominance = EmptyDominanceChecker::default();
+        let input = CompilationInput {
+            comp_type: CompilationType::Relaxed,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
+            problem:    &DummyProblem,
+            relaxation: &DummyRelax,
+            ranking:    &DummyRanking,
+            cutoff:     &NoCutoff,
+            max_width:  1,
+            best_lb:    isize::MIN,
+            residual:  &SubProblem {
+                state: Arc::new(DummyState{value: 0, depth: 0}),
+                value: 0,
+                path:  vec![],
+                ub:    isize::MAX,
+                depth: 0,
+            },
+            cache: &cache,
+            dominance: &dominance,
+        };
+
+        let mut hybrid = HybridMdd::default();
+        let mut plain = DefaultMDD::new();
+        assert!(hybrid.compile(&input).is_ok());
+        assert!(plain.compile(&input).is_ok());
+
+        // every surplus layer is relaxed: same diagram, same (inflated) bound
+        assert_eq!(hybrid.best_value(), plain.best_value());
+        assert_eq!(hybrid.best_value(), Some(44));
+        assert!(!hybrid.is_exact());
+    }
+
+    #[test]
+    fn the_layers_shallower_than_the_threshold_are_restricted() {
+        let cache = EmptyCache::new();
+        let dominance = EmptyDominanceChecker::default();
+        let input = CompilationInput {
+            comp_type: CompilationType::Relaxed,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
+            problem:    &DummyProblem,
+            relaxation: &DummyRelax,
+            ranking:    &DummyRanking,
+            cutoff:     &NoCutoff,
+            max_width:  1,
+            best_lb:    isize::MIN,
+            residual:  &SubProblem {
+                state: Arc::new(DummyState{value: 0, depth: 0}),
+                value: 0,
+                path:  vec![],
+                ub:    isize::MAX,
+                depth: 0,
+            },
+            cache: &cache,
+            dominance: &dominance,
+        };
+
+        let mut hybrid = HybridMdd::new(3);
+        assert!(hybrid.compile(&input).is_ok());
+
+        // the layer at depth 2 is restricted (only the node reached with
+        // value 4 survives) while the layer at depth 3 is relaxed (its
+        // surplus is merged with a relax cost of 20): the longest path is
+        // hence 4 + 20 + 2 = 26 instead of the fully relaxed 44
+        assert!(!hybrid.is_exact());
+        assert_eq!(hybrid.best_value(), Some(26));
+    }
+
+    #[test]
+    fn a_threshold_covering_the_whole_diagram_restricts_every_layer() {
+        let cache = EmptyCache::new();
+        let dominance = EmptyDominanceChecker::default();
+        let input = CompilationInput {
+            comp_type: CompilationType::Relaxed,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
+            problem:    &DummyProblem,
+            relaxation: &DummyRelax,
+            ranking:    &DummyRanking,
+            cutoff:     &NoCutoff,
+            max_width:  1,
+            best_lb:    isize::MIN,
+            residual:  &SubProblem {
+                state: Arc::new(DummyState{value: 0, depth: 0}),
+                value: 0,
+                path:  vec![],
+                ub:    isize::MAX,
+                depth: 0,
+            },
+            cache: &cache,
+            dominance: &dominance,
+        };
+
+        let mut hybrid = HybridMdd::new(usize::MAX);
+        assert!(hybrid.compile(&input).is_ok());
+
+        // the compilation degenerates into a plain restriction: it keeps the
+        // most promising node of every layer and the best path it finds is
+        // the actual optimum. Since that path traverses no merged node, the
+        // exact best path optimization even declares the diagram exact
+        assert!(hybrid.is_exact());
+        assert_eq!(hybrid.best_value(), Some(8));
+    }
+
+    #[test]
+    fn the_cutset_of_a_hybridized_diagram_only_trusts_the_rough_bounds() {
+        let cache = EmptyCache::new();
+        let dominance = EmptyDominanceChecker::default();
+        let input = CompilationInput {
+            comp_type: CompilationType::Relaxed,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
+            problem:    &DummyProblem,
+            relaxation: &DummyRelax,
+            ranking:    &DummyRanking,
+            cutoff:     &NoCutoff,
+            max_width:  1,
+            best_lb:    isize::MIN,
+            residual:  &SubProblem {
+                state: Arc::new(DummyState{value: 0, depth: 0}),
+                value: 0,
+                path:  vec![],
+                ub:    isize::MAX,
+                depth: 0,
+            },
+            cache: &cache,
+            dominance: &dominance,
+        };
+
+        let mut hybrid = HybridMdd::new(3);
+        assert!(hybrid.compile(&input).is_ok());
+
+        // the cutset is the layer at depth 1 (the last complete layer before
+        // the first restriction). Its nodes must be enqueued with their rough
+        // upper bounds (value + 3 * 10): the local bounds computed through
+        // the restricted layer would wrongly cap them at the value of the
+        // mixed diagram (26)
+        let mut cutset = vec![];
+        hybrid.drain_cutset(|sub| cutset.push(sub));
+        let mut ubs = cutset.iter().map(|sub| sub.ub).collect::<Vec<_>>();
+        ubs.sort_unstable();
+        assert_eq!(ubs, vec![30, 31, 32]);
+    }
+
+
+    #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+    struct DummyState {
+        value: isize,
+        depth: usize,
+    }
+
+    #[derive(Copy, Clone)]
+    struct DummyProblem;
+    impl Problem for DummyProblem {
+        type State = DummyState;
+
+        fn nb_variables(&self) -> usize { 4 }
+        fn initial_value(&self) -> isize { 0 }
+        fn initial_state(&self) -> Self::State {
+            DummyState { value: 0, depth: 0 }
+        }
+
+        fn transition(&self, state: &Self::State, decision: Decision) -> Self::State {
+            DummyState {
+                value: state.value + decision.value,
+                depth: 1 + state.depth,
+            }
+        }
+
+        fn transition_cost(&self, _: &Self::State, _: &Self::State, decision: Decision) -> isize {
+            decision.value
+        }
+
+        fn next_variable(&self, depth: usize, _: &mut dyn Iterator<Item = &Self::State>)
+            -> Option<Variable> {
+            if depth < self.nb_variables() {
+                Some(Variable(depth))
+            } else {
+                None
+            }
+        }
+
+        fn for_each_in_domain(&self, var: Variable, _: &Self::State, f: &mut dyn DecisionCallback) {
+            for d in 0..=2 {
+                f.apply(Decision { variable: var, value: d })
+            }
+        }
+    }
+
+    struct DummyRelax;
+    impl Relaxation for DummyRelax {
+        type State = DummyState;
+
+        fn merge(&self, s: &mut dyn Iterator<Item = &Self::State>) -> Self::State {
+            s.next()
+                .map(|s| DummyState { value: 100, depth: s.depth })
+                .unwrap()
+        }
+        fn relax(&self, _: &Self::State, _: &Self::State, _: &Self::State, _: Decision, _: isize) -> isize {
+            20
+        }
+        fn fast_upper_bound(&self, state: &Self::State) -> isize {
+            (DummyProblem.nb_variables() - state.depth) as isize * 10
+        }
+    }
+
+    struct DummyRanking;
+    impl StateRanking for DummyRanking {
+        type State = DummyState;
+
+        fn compare(&self, a: &Self::State, b: &Self::State) -> Ordering {
+            a.value.cmp(&b.value).reverse()
+        }
+    }
+}
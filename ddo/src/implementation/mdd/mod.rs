@@ -28,10 +28,14 @@
 //! a previous version of ddo (<= 0.5.0).
 mod node_flags;
 mod clean;
+mod flat;
+mod hybrid;
 mod pooled;
 
 pub use node_flags::*;
 pub use clean::*;
+pub use flat::*;
+pub use hybrid::*;
 pub use pooled::*;
 
 use crate::{LAST_EXACT_LAYER, FRONTIER};
@@ -5,7 +5,7 @@
 //! ``Decision Diagram-Based Branch-and-Bound with Caching
 //! for Dominance and Suboptimality Detection''.
 
-use std::{sync::Arc, hash::Hash, collections::{hash_map::Entry, BTreeMap}, fmt::Debug};
+use std::{sync::Arc, hash::Hash, cmp::Reverse, collections::{hash_map::Entry, BTreeMap}, fmt::Debug};
 
 use fxhash::FxHashMap;
 
@@ -158,6 +158,15 @@ where
     /// A flag set to true when the longest r-t path of this decision diagram
     /// traverses no merged node (Exact Best Path Optimization aka EBPO).
     has_exact_best_path: bool,
+
+    /// The total number of edges materialized over all the compilations that
+    /// have been performed with this structure (this counter is *not* reset
+    /// between compilations: it is a cumulative statistic)
+    total_edges: usize,
+    /// The total number of non-leaf nodes materialized over all the
+    /// compilations that have been performed with this structure (cumulative
+    /// as well)
+    total_internal_nodes: usize,
 }
 
 const NIL: EdgesListId = EdgesListId(0);
@@ -240,7 +249,7 @@ where
     }
 
     fn best_solution(&self) -> Option<Solution> {
-        self._best_solution()
+        self._best_solution().map(Solution::new)
     }
 
     fn best_exact_value(&self) -> Option<isize> {
@@ -248,7 +257,23 @@ where
     }
 
     fn best_exact_solution(&self) -> Option<Solution> {
-        self._best_exact_solution()
+        self._best_exact_solution().map(Solution::new)
+    }
+
+    fn avg_branching_factor(&self) -> f64 {
+        if self.total_internal_nodes == 0 {
+            0.0
+        } else {
+            self.total_edges as f64 / self.total_internal_nodes as f64
+        }
+    }
+
+    fn best_k_solutions(&self, k: usize) -> Vec<(isize, Solution)> {
+        self._best_k_solutions(k)
+    }
+
+    fn for_each_edge_decision(&self, func: &mut dyn FnMut(Decision)) {
+        self.edges.iter().for_each(|edge| func(edge.decision))
     }
 
     fn drain_cutset<F>(&mut self, func: F)
@@ -278,9 +303,12 @@ where
             best_exact_node: None,
             is_exact: true,
             has_exact_best_path: false,
+            //
+            total_edges: 0,
+            total_internal_nodes: 0,
         }
     }
-    
+
     fn _clear(&mut self) {
         self.layers.clear();
         self.nodes.clear();
@@ -315,6 +343,75 @@ where
         Self::_best_path_partial_borrow(id, &self.path_to_root, &self.nodes, &self.edges)
     }
 
+    /// Computes the k best root-to-terminal paths of this dd. To that end, it
+    /// propagates -- in topological order -- a table which associates to each
+    /// node its up-to-k best path values, each one remembering the incoming
+    /// edge achieving it along with the rank of the prolonged value in the
+    /// parent's own table (this is what allows the paths to be rebuilt).
+    fn _best_k_solutions(&self, k: usize) -> Vec<(isize, Solution)> {
+        // one entry of the per-node table: a path value along with the
+        // incoming edge achieving it and the rank of the prolonged value in
+        // the parent's own table (None for the root)
+        type KBestEntry = (isize, Option<(EdgeId, usize)>);
+
+        if k == 0 || self.nodes.is_empty() {
+            return vec![];
+        }
+        // the edges arena (which also comprises the stale edges left over by
+        // the merge of relaxed nodes) gives the partial order on the nodes
+        let nb_nodes = self.nodes.len();
+        let mut in_degree = vec![0_usize; nb_nodes];
+        let mut outbound = vec![vec![]; nb_nodes];
+        for (eid, edge) in self.edges.iter().enumerate() {
+            in_degree[edge.to.0] += 1;
+            outbound[edge.from.0].push(EdgeId(eid));
+        }
+
+        let mut table: Vec<Vec<KBestEntry>> = vec![vec![]; nb_nodes];
+        table[0].push((get!(node NodeId(0), self).value_top, None));
+
+        let mut open = (0..nb_nodes).filter(|id| in_degree[*id] == 0).collect::<Vec<_>>();
+        while let Some(id) = open.pop() {
+            // the candidates of this node are complete: close its table and
+            // prolong each of its entries along every outgoing edge
+            table[id].sort_unstable_by_key(|entry| Reverse(entry.0));
+            table[id].truncate(k);
+            for eid in outbound[id].iter().copied() {
+                let edge = *get!(edge eid, self);
+                for rank in 0..table[id].len() {
+                    let value = table[id][rank].0.saturating_add(edge.cost);
+                    table[edge.to.0].push((value, Some((eid, rank))));
+                }
+                in_degree[edge.to.0] -= 1;
+                if in_degree[edge.to.0] == 0 {
+                    open.push(edge.to.0);
+                }
+            }
+        }
+
+        let mut terminal = vec![];
+        for id in self.pool.values().copied() {
+            for (rank, entry) in table[id.0].iter().enumerate() {
+                terminal.push((entry.0, id, rank));
+            }
+        }
+        terminal.sort_unstable_by_key(|entry| Reverse(entry.0));
+        terminal.truncate(k);
+
+        terminal.iter()
+            .map(|&(value, id, rank)| {
+                let mut sol = self.path_to_root.to_owned();
+                let mut cursor = (id, rank);
+                while let Some((eid, parent_rank)) = table[cursor.0.0][cursor.1].1 {
+                    let edge = *get!(edge eid, self);
+                    sol.push(edge.decision);
+                    cursor = (edge.from, parent_rank);
+                }
+                (value, Solution::new(sol))
+            })
+            .collect()
+    }
+
     fn _best_path_partial_borrow(
         id: NodeId,
         root_pa: &[Decision],
@@ -335,7 +432,11 @@ where
         self._clear();
         self._initialize(input);
 
-        while let Some(var) = input.problem.next_variable(self.curr_l.0, &mut self.pool.keys().map(|s| s.as_ref())) {
+        let static_order = input.problem.static_order();
+        while let Some(var) = match &static_order {
+            Some(order) => order.get(self.curr_l.0).copied(),
+            None => input.problem.next_variable(self.curr_l.0, &mut self.pool.keys().map(|s| s.as_ref())),
+        } {
             // Did the cutoff kick in ?
             if input.cutoff.must_stop() {
                 return Err(Reason::CutoffOccurred);
@@ -349,19 +450,30 @@ where
 
             for node_id in curr_l.iter() {
                 let state = self.nodes[node_id.0].state.clone();
-                let rub = input.relaxation.fast_upper_bound(state.as_ref());
-                self.nodes[node_id.0].rub = rub;
-                let ub = rub.saturating_add(self.nodes[node_id.0].value_top);
+                let value_top = self.nodes[node_id.0].value_top;
+                let ub = input.relaxation.fast_upper_bound_with_value(state.as_ref(), value_top, self.curr_l.0);
+                self.nodes[node_id.0].rub = ub.saturating_sub(value_top);
                 if ub > input.best_lb {
-                    input.problem.for_each_in_domain(var, state.as_ref(), &mut |decision| {
-                        self._branch_on(*node_id, decision, input.problem)
-                    })
+                    if input.max_out_degree != usize::MAX {
+                        self._branch_on_capped(*node_id, var, state.as_ref(), input);
+                    } else {
+                        input.problem.for_each_in_domain(var, state.as_ref(), &mut |decision| {
+                            self._branch_on(*node_id, decision, input.problem)
+                        })
+                    }
                 }
             }
+            input.cutoff.add_expanded(curr_l.len());
 
             self.curr_l.0 += 1;
         }
 
+        // at this point, the pool holds the terminal layer: every other node
+        // of the diagram is an internal (non-leaf) node
+        self.total_edges = self.total_edges.saturating_add(self.edges.len());
+        self.total_internal_nodes = self.total_internal_nodes
+            .saturating_add(self.nodes.len().saturating_sub(self.pool.len()));
+
         self._finalize(input);
 
         Ok(Completion { 
@@ -524,10 +636,11 @@ where
         // A node can only be added to the cache if it belongs to the cutset or is above it
         if let Some(theta) = node.theta {
             if node.flags.is_above_cutset() {
-                input.cache.update_threshold(
-                    node.state.clone(), 
-                    node.depth, 
-                    theta, 
+                input.cache.update_threshold_with_problem(
+                    input.problem,
+                    node.state.clone(),
+                    node.depth,
+                    theta,
                     !node.flags.is_cutset()) // if it is in the cutset it has not been explored !
             }
         }
@@ -662,7 +775,7 @@ where
     fn _filter_with_cache(&mut self, input: &CompilationInput<T>, curr_l: &mut Vec<NodeId>) {
         curr_l.retain(|id| {
             let node = get!(mut node id, self);
-            let threshold = input.cache.get_threshold(node.state.as_ref(), node.depth);
+            let threshold = input.cache.get_threshold_with_problem(input.problem, node.state.as_ref(), node.depth);
             if let Some(threshold) = threshold {
                 if node.value_top > threshold.value {
                     true
@@ -677,6 +790,37 @@ where
         });
     }
 
+    /// Expands the given node while materializing at most `max_out_degree`
+    /// outgoing decisions. When the domain is larger than the limit, only the
+    /// decisions leading to the best-ranked successor states are kept; the
+    /// surplus is dropped, which makes the dd inexact just like an actual
+    /// restriction would.
+    fn _branch_on_capped(
+        &mut self,
+        from_id: NodeId,
+        var: Variable,
+        state: &T,
+        input: &CompilationInput<T>,
+    ) {
+        let mut decisions = vec![];
+        input.problem.for_each_in_domain(var, state, &mut |decision| decisions.push(decision));
+
+        if decisions.len() > input.max_out_degree {
+            let mut successors = decisions.drain(..)
+                .filter_map(|decision| input.problem.transition_checked(state, decision).map(|s| (s, decision)))
+                .collect::<Vec<_>>();
+            // greater means more likely to be kept
+            successors.sort_unstable_by(|a, b| input.ranking.compare(&a.0, &b.0).reverse());
+            successors.truncate(input.max_out_degree);
+            decisions.extend(successors.into_iter().map(|(_, decision)| decision));
+            self.is_exact = false;
+        }
+
+        for decision in decisions {
+            self._branch_on(from_id, decision, input.problem);
+        }
+    }
+
     fn _branch_on(
         &mut self,
         from_id: NodeId,
@@ -684,7 +828,10 @@ where
         problem: &dyn Problem<State = T>,
     ) {
         let state = get!(node from_id, self).state.as_ref();
-        let next_state = Arc::new(problem.transition(state, decision));
+        let Some(next_state) = problem.transition_checked(state, decision) else {
+            return; // the transition rejected the decision: no child, no edge
+        };
+        let next_state = Arc::new(next_state);
         let cost = problem.transition_cost(state, next_state.as_ref(), decision);
 
         match self.pool.entry(next_state.clone()) {
@@ -770,9 +917,43 @@ where
                 .reverse()
         }); // reverse because greater means more likely to be kept
 
+        let states = curr_l.iter().map(|id| get!(node id, self).state.clone()).collect::<Vec<_>>();
+        let state_refs = states.iter().map(|state| state.as_ref()).collect::<Vec<_>>();
+        let groups = input.relaxation.select_merge_groups(&state_refs, input.max_width);
+
+        if Self::_is_default_grouping(&groups, input.max_width, curr_l.len()) {
+            self._relax_surplus(input, curr_l);
+        } else {
+            self._relax_groups(input, curr_l, groups);
+        }
+    }
+
+    /// Tells whether the given merge grouping is the default one (one single
+    /// group comprising all the surplus nodes), in which case the historic
+    /// merge code path is used
+    fn _is_default_grouping(groups: &[Vec<usize>], width: usize, layer_len: usize) -> bool {
+        groups.len() == 1 && groups[0].iter().copied().eq(width.saturating_sub(1)..layer_len)
+    }
+
+    /// This is the usual relaxation scheme: all the surplus nodes (beyond the
+    /// `max_width - 1` most promising ones) are merged into one single node
+    fn _relax_surplus(&mut self, input: &CompilationInput<T>, curr_l: &mut Vec<NodeId>) {
         //--
         let (keep, merge) = curr_l.split_at_mut(input.max_width - 1);
-        let merged = Arc::new(input.relaxation.merge(&mut merge.iter().map(|id| get!(node id, self).state.as_ref())));
+        let merged = input.relaxation.merge_opt_with_type(input.comp_type, &mut merge.iter().map(|id| get!(node id, self).state.as_ref()));
+
+        // when the merge signals that nothing feasible remains reachable
+        // from the merged node, the surplus nodes are simply dropped (just
+        // like a restriction would drop them) and their arcs are not
+        // redirected anywhere
+        let Some(merged) = merged else {
+            for drop_id in merge {
+                get!(mut node drop_id, self).flags.set_deleted(true);
+            }
+            curr_l.truncate(input.max_width - 1);
+            return;
+        };
+        let merged = Arc::new(merged);
 
         let recycled = keep.iter().find(|id| get!(node *id, self).state.eq(&merged)).copied();
 
@@ -795,15 +976,16 @@ where
 
         get!(mut node merged_id, self).flags.set_relaxed(true);
 
+        let mut redirected = vec![];
         for drop_id in merge {
             get!(mut node drop_id, self).flags.set_deleted(true);
 
             foreach!(edge of drop_id, self, |edge: Edge| {
                 let src   = get!(node edge.from, self).state.as_ref();
                 let dst   = get!(node edge.to,   self).state.as_ref();
-                let rcost = input.relaxation.relax(src, dst, merged.as_ref(), edge.decision, edge.cost);
+                let rcost = input.relaxation.relax_with_type(input.comp_type, src, dst, merged.as_ref(), edge.decision, edge.cost);
 
-                append_edge_to!(self, Edge {
+                redirected.push(Edge {
                     from: edge.from,
                     to: merged_id,
                     decision: edge.decision,
@@ -811,6 +993,21 @@ where
                 });
             });
         }
+        // when the in-degree of the merged node is capped, only the edges
+        // realizing the best value at the merged node are retained (beware:
+        // this drops paths from the relaxed dd, see the documentation of
+        // `CompilationInput::max_in_degree`)
+        if let Some(cap) = input.max_in_degree {
+            if redirected.len() > cap {
+                redirected.sort_unstable_by_key(|edge| {
+                    std::cmp::Reverse(get!(node edge.from, self).value_top.saturating_add(edge.cost))
+                });
+                redirected.truncate(cap);
+            }
+        }
+        for edge in redirected {
+            append_edge_to!(self, edge);
+        }
 
         if recycled.is_some() {
             curr_l.truncate(input.max_width);
@@ -821,6 +1018,113 @@ where
             curr_l.push(merged_id);
         }
     }
+
+    /// This is the custom relaxation scheme: each group of nodes selected by
+    /// the relaxation (see `Relaxation::select_merge_groups`) is merged into
+    /// one single node while the ungrouped nodes are kept as they are
+    fn _relax_groups(&mut self, input: &CompilationInput<T>, curr_l: &mut Vec<NodeId>, groups: Vec<Vec<usize>>) {
+        // sanitize the user-provided groups: an index may belong to at most
+        // one group, must be in range, and the singleton groups are dropped
+        // (their node is simply kept in the layer)
+        let mut grouped = vec![false; curr_l.len()];
+        let mut sane_groups = vec![];
+        for group in groups {
+            let group = group.into_iter()
+                .filter(|i| *i < curr_l.len() && !std::mem::replace(&mut grouped[*i], true))
+                .collect::<Vec<_>>();
+            if group.len() >= 2 {
+                sane_groups.push(group);
+            } else if let Some(i) = group.first() {
+                grouped[*i] = false;
+            }
+        }
+
+        let mut merged_ids = vec![];
+        for group in sane_groups {
+            let merged = input.relaxation.merge_opt(&mut group.iter().map(|i| get!(node curr_l[*i], self).state.as_ref()));
+
+            // when the merge signals that nothing feasible remains reachable
+            // from the merged node, the whole group is dropped and its arcs
+            // are not redirected anywhere
+            let Some(merged) = merged else {
+                for i in group {
+                    let drop_id = curr_l[i];
+                    get!(mut node drop_id, self).flags.set_deleted(true);
+                }
+                continue;
+            };
+            let merged = Arc::new(merged);
+
+            // when the merged state coincides with one of the kept nodes,
+            // that node is recycled rather than duplicated in the layer
+            let recycled = curr_l.iter().enumerate()
+                .filter(|(i, _)| !grouped[*i])
+                .find(|(_, id)| get!(node *id, self).state.eq(&merged))
+                .map(|(_, id)| *id);
+
+            let depth = get!(node curr_l[group[0]], self).depth;
+            let merged_id = recycled.unwrap_or_else(|| {
+                let node_id = NodeId(self.nodes.len());
+                self.nodes.push(Node {
+                    state: merged.clone(),
+                    value_top: isize::MIN,
+                    value_bot: isize::MIN,
+                    best: None,    // yet
+                    inbound: NIL,  // yet
+                    //
+                    rub: isize::MAX,
+                    theta: None,
+                    flags: NodeFlags::new_relaxed(),
+                    depth,
+                });
+                node_id
+            });
+
+            get!(mut node merged_id, self).flags.set_relaxed(true);
+
+            let mut redirected = vec![];
+            for i in group {
+                let drop_id = curr_l[i];
+                get!(mut node drop_id, self).flags.set_deleted(true);
+
+                foreach!(edge of drop_id, self, |edge: Edge| {
+                    let src   = get!(node edge.from, self).state.as_ref();
+                    let dst   = get!(node edge.to,   self).state.as_ref();
+                    let rcost = input.relaxation.relax_with_type(input.comp_type, src, dst, merged.as_ref(), edge.decision, edge.cost);
+
+                    redirected.push(Edge {
+                        from: edge.from,
+                        to: merged_id,
+                        decision: edge.decision,
+                        cost: rcost
+                    });
+                });
+            }
+            // see the documentation of `CompilationInput::max_in_degree`
+            if let Some(cap) = input.max_in_degree {
+                if redirected.len() > cap {
+                    redirected.sort_unstable_by_key(|edge| {
+                        std::cmp::Reverse(get!(node edge.from, self).value_top.saturating_add(edge.cost))
+                    });
+                    redirected.truncate(cap);
+                }
+            }
+            for edge in redirected {
+                append_edge_to!(self, edge);
+            }
+
+            if recycled.is_none() {
+                merged_ids.push(merged_id);
+            }
+        }
+
+        let mut kept = curr_l.iter().enumerate()
+            .filter(|(i, _)| !grouped[*i])
+            .map(|(_, id)| *id)
+            .collect::<Vec<_>>();
+        kept.append(&mut merged_ids);
+        *curr_l = kept;
+    }
 }
 
 impl <T> Pooled<T> 
@@ -1033,6 +1337,8 @@ mod test_default_mdd {
         let dominance = EmptyDominanceChecker::default();
         let mut input = CompilationInput {
             comp_type: crate::CompilationType::Exact,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
@@ -1070,6 +1376,8 @@ mod test_default_mdd {
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Exact,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
@@ -1106,6 +1414,8 @@ mod test_default_mdd {
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Restricted,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
@@ -1142,6 +1452,8 @@ mod test_default_mdd {
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Exact,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
@@ -1172,6 +1484,8 @@ mod test_default_mdd {
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Restricted,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
@@ -1202,6 +1516,8 @@ mod test_default_mdd {
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Relaxed,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
@@ -1238,6 +1554,8 @@ mod test_default_mdd {
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Exact,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
@@ -1266,6 +1584,8 @@ mod test_default_mdd {
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Restricted,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
@@ -1293,6 +1613,8 @@ mod test_default_mdd {
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Relaxed,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
@@ -1321,6 +1643,8 @@ mod test_default_mdd {
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Relaxed,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
@@ -1358,6 +1682,8 @@ mod test_default_mdd {
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Relaxed,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
@@ -1389,6 +1715,8 @@ mod test_default_mdd {
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Exact,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
@@ -1418,6 +1746,8 @@ mod test_default_mdd {
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Relaxed,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
@@ -1447,6 +1777,8 @@ mod test_default_mdd {
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Relaxed,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
@@ -1475,6 +1807,8 @@ mod test_default_mdd {
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Restricted,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
@@ -1503,6 +1837,8 @@ mod test_default_mdd {
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Relaxed,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
@@ -1531,6 +1867,8 @@ mod test_default_mdd {
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Exact,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyInfeasibleProblem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
@@ -1558,6 +1896,8 @@ mod test_default_mdd {
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Exact,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyInfeasibleProblem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
@@ -1585,6 +1925,8 @@ mod test_default_mdd {
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Exact,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
@@ -1612,6 +1954,8 @@ mod test_default_mdd {
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Relaxed,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
@@ -1639,6 +1983,8 @@ mod test_default_mdd {
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Restricted,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
@@ -1670,6 +2016,8 @@ mod test_default_mdd {
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Exact,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
@@ -1701,6 +2049,8 @@ mod test_default_mdd {
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Relaxed,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
@@ -1732,6 +2082,8 @@ mod test_default_mdd {
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Restricted,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
@@ -1761,6 +2113,8 @@ mod test_default_mdd {
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Restricted,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
@@ -1814,6 +2168,8 @@ mod test_default_mdd {
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Relaxed,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
@@ -1867,6 +2223,8 @@ mod test_default_mdd {
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Restricted,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
@@ -1920,6 +2278,8 @@ mod test_default_mdd {
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Relaxed,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &DummyProblem,
             relaxation: &DummyRelax,
             ranking:    &DummyRanking,
@@ -2100,6 +2460,8 @@ mod test_default_mdd {
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Relaxed,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &LocBoundsAndThresholdsExamplePb,
             relaxation: &LocBoundsAndThresholdsExampleRelax,
             ranking:    &CmpChar,
@@ -2179,6 +2541,8 @@ mod test_default_mdd {
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
             comp_type: crate::CompilationType::Relaxed,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &LocBoundsAndThresholdsExamplePb,
             relaxation: &LocBoundsAndThresholdsExampleRelax,
             ranking:    &CmpChar,
@@ -2255,7 +2619,9 @@ mod test_default_mdd {
         cache.initialize(&LocBoundsAndThresholdsExamplePb);
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
-            comp_type:  crate::CompilationType::Relaxed,
+            comp_type: crate::CompilationType::Relaxed,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &LocBoundsAndThresholdsExamplePb,
             relaxation: &LocBoundsAndThresholdsExampleRelax,
             ranking:    &CmpChar,
@@ -2288,7 +2654,9 @@ mod test_default_mdd {
         cache.initialize(&LocBoundsAndThresholdsExamplePb);
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
-            comp_type:  crate::CompilationType::Relaxed,
+            comp_type: crate::CompilationType::Relaxed,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &LocBoundsAndThresholdsExamplePb,
             relaxation: &LocBoundsAndThresholdsExampleRelax,
             ranking:    &CmpChar,
@@ -2326,7 +2694,9 @@ mod test_default_mdd {
         cache.initialize(&LocBoundsAndThresholdsExamplePb);
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
-            comp_type:  crate::CompilationType::Relaxed,
+            comp_type: crate::CompilationType::Relaxed,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &LocBoundsAndThresholdsExamplePb,
             relaxation: &LocBoundsAndThresholdsExampleRelax,
             ranking:    &CmpChar,
@@ -2364,7 +2734,9 @@ mod test_default_mdd {
         cache.initialize(&LocBoundsAndThresholdsExamplePb);
         let dominance = EmptyDominanceChecker::default();
         let input = CompilationInput {
-            comp_type:  crate::CompilationType::Relaxed,
+            comp_type: crate::CompilationType::Relaxed,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
             problem:    &LocBoundsAndThresholdsExamplePb,
             relaxation: &LocBoundsAndThresholdsExampleRelax,
             ranking:    &CmpChar,
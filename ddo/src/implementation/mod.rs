@@ -21,6 +21,7 @@
 //! abstractions of the library (those are described in `ddo::abstraction::*`).
 
 mod heuristics;
+mod problem;
 mod fringe;
 mod mdd;
 mod cache;
@@ -28,6 +29,7 @@ mod dominance;
 mod solver;
 
 pub use heuristics::*;
+pub use problem::*;
 pub use fringe::*;
 pub use mdd::*;
 pub use cache::*;
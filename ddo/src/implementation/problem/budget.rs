@@ -0,0 +1,303 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! This module provides the implementation of a `Problem` combinator which
+//! augments the decorated problem with a global (knapsack-style) budget
+//! constraint spanning all the decisions.
+
+use std::hash::{Hash, Hasher};
+
+use crate::{Decision, DecisionCallback, Problem, Variable};
+
+/// The state of a budget-constrained problem: it couples the state of the
+/// inner problem with the amount of resource that has not been consumed yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BudgetState<S> {
+    /// The state of the decorated problem
+    pub inner: S,
+    /// The amount of resource which may still be consumed
+    pub budget: usize,
+}
+
+/// This combinator adds a single linear side-constraint to the problem it
+/// decorates: each decision consumes some amount of a shared resource (as
+/// told by the `resource_use` function) and the total consumption over a
+/// complete assignment may not exceed the given budget. The state of the
+/// inner problem is augmented with a remaining-budget counter and the domain
+/// of each variable is filtered so as to never exceed the budget. This saves
+/// users from rewriting a whole model just to add a knapsack-style resource
+/// constraint on top of it.
+pub struct WithBudget<P, F>
+where
+    P: Problem,
+    F: Fn(&P::State, Decision) -> usize,
+{
+    /// The problem being decorated with a budget constraint
+    problem: P,
+    /// The total amount of resource which may be consumed
+    budget: usize,
+    /// Tells the amount of resource which the given decision consumes when
+    /// it is taken from the given state
+    resource_use: F,
+}
+
+impl<P, F> WithBudget<P, F>
+where
+    P: Problem,
+    F: Fn(&P::State, Decision) -> usize,
+{
+    /// Creates a new combinator constraining the total resource consumption
+    /// of the given problem to the given budget
+    pub fn new(problem: P, budget: usize, resource_use: F) -> Self {
+        Self { problem, budget, resource_use }
+    }
+
+    /// Returns a reference to the decorated problem
+    pub fn inner(&self) -> &P {
+        &self.problem
+    }
+}
+
+impl<P, F> Problem for WithBudget<P, F>
+where
+    P: Problem,
+    P::State: Eq + Hash,
+    F: Fn(&P::State, Decision) -> usize,
+{
+    type State = BudgetState<P::State>;
+
+    fn nb_variables(&self) -> usize {
+        self.problem.nb_variables()
+    }
+
+    fn initial_state(&self) -> Self::State {
+        BudgetState {
+            inner: self.problem.initial_state(),
+            budget: self.budget,
+        }
+    }
+
+    fn initial_value(&self) -> isize {
+        self.problem.initial_value()
+    }
+
+    fn transition(&self, state: &Self::State, decision: Decision) -> Self::State {
+        BudgetState {
+            inner: self.problem.transition(&state.inner, decision),
+            budget: state.budget - (self.resource_use)(&state.inner, decision),
+        }
+    }
+
+    fn transition_checked(&self, state: &Self::State, decision: Decision) -> Option<Self::State> {
+        let consumed = (self.resource_use)(&state.inner, decision);
+        if consumed > state.budget {
+            None
+        } else {
+            self.problem.transition_checked(&state.inner, decision).map(|inner| BudgetState {
+                inner,
+                budget: state.budget - consumed,
+            })
+        }
+    }
+
+    fn transition_cost(&self, source: &Self::State, dest: &Self::State, decision: Decision) -> isize {
+        self.problem.transition_cost(&source.inner, &dest.inner, decision)
+    }
+
+    fn next_variable(&self, depth: usize, next_layer: &mut dyn Iterator<Item = &Self::State>) -> Option<Variable> {
+        self.problem.next_variable(depth, &mut next_layer.map(|state| &state.inner))
+    }
+
+    fn static_order(&self) -> Option<Vec<Variable>> {
+        self.problem.static_order()
+    }
+
+    fn for_each_in_domain(&self, var: Variable, state: &Self::State, f: &mut dyn DecisionCallback) {
+        self.problem.for_each_in_domain(var, &state.inner, &mut |decision: Decision| {
+            if (self.resource_use)(&state.inner, decision) <= state.budget {
+                f.apply(decision);
+            }
+        });
+    }
+
+    fn for_each_in_domain_with_path(&self, var: Variable, state: &Self::State, path: &[Decision], f: &mut dyn DecisionCallback) {
+        self.problem.for_each_in_domain_with_path(var, &state.inner, path, &mut |decision: Decision| {
+            if (self.resource_use)(&state.inner, decision) <= state.budget {
+                f.apply(decision);
+            }
+        });
+    }
+
+    fn has_path_dependent_domains(&self) -> bool {
+        self.problem.has_path_dependent_domains()
+    }
+
+    fn domain_iter<'a>(&'a self, var: Variable, state: &'a Self::State) -> Box<dyn Iterator<Item = isize> + 'a> {
+        Box::new(self.problem.domain_iter(var, &state.inner).filter(move |value| {
+            let decision = Decision { variable: var, value: *value };
+            (self.resource_use)(&state.inner, decision) <= state.budget
+        }))
+    }
+
+    fn has_lazy_domain_iter(&self) -> bool {
+        self.problem.has_lazy_domain_iter()
+    }
+
+    fn supports_caching(&self) -> bool {
+        // the remaining budget is part of the state: two equal `BudgetState`s
+        // denote the same residual problem whenever the inner states do
+        self.problem.supports_caching()
+    }
+
+    fn state_fingerprint(&self, state: &Self::State) -> Option<u64> {
+        self.problem.state_fingerprint(&state.inner).map(|fingerprint| {
+            // mix the remaining budget in, so that two states which only
+            // differ by their budget do not share their cached thresholds
+            let mut hasher = fxhash::FxHasher::default();
+            fingerprint.hash(&mut hasher);
+            state.budget.hash(&mut hasher);
+            hasher.finish()
+        })
+    }
+
+    fn state_hash(&self, state: &Self::State, mut hasher: &mut dyn Hasher)
+    where Self::State: Hash {
+        self.problem.state_hash(&state.inner, &mut *hasher);
+        state.budget.hash(&mut hasher);
+    }
+
+    fn state_eq(&self, a: &Self::State, b: &Self::State) -> bool
+    where Self::State: Eq {
+        a.budget == b.budget && self.problem.state_eq(&a.inner, &b.inner)
+    }
+
+    fn is_impacted_by(&self, var: Variable, state: &Self::State) -> bool {
+        self.problem.is_impacted_by(var, &state.inner)
+    }
+
+    fn is_leaf(&self, state: &Self::State) -> bool {
+        self.problem.is_leaf(&state.inner)
+    }
+
+    // `always_feasible` is deliberately *not* forwarded: even when every
+    // state of the inner problem admits a feasible completion, the budget
+    // constraint may empty the domain of some variable and leave a state
+    // without any completion at all.
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    /// A dummy problem with binary domains and no constraint whatsoever
+    struct Unconstrained;
+    impl Problem for Unconstrained {
+        type State = usize;
+
+        fn nb_variables(&self) -> usize {
+            3
+        }
+        fn initial_state(&self) -> Self::State {
+            0
+        }
+        fn initial_value(&self) -> isize {
+            0
+        }
+        fn transition(&self, state: &Self::State, d: Decision) -> Self::State {
+            state + d.value as usize
+        }
+        fn transition_cost(&self, _: &Self::State, _: &Self::State, d: Decision) -> isize {
+            d.value
+        }
+        fn next_variable(&self, depth: usize, _: &mut dyn Iterator<Item = &Self::State>) -> Option<Variable> {
+            if depth < self.nb_variables() {
+                Some(Variable(depth))
+            } else {
+                None
+            }
+        }
+        fn for_each_in_domain(&self, variable: Variable, _: &Self::State, f: &mut dyn DecisionCallback) {
+            f.apply(Decision { variable, value: 1 });
+            f.apply(Decision { variable, value: 0 });
+        }
+    }
+
+    fn domain_of(problem: &dyn Problem<State = BudgetState<usize>>, var: Variable, state: &BudgetState<usize>) -> Vec<isize> {
+        let mut domain = vec![];
+        problem.for_each_in_domain(var, state, &mut |d: Decision| domain.push(d.value));
+        domain
+    }
+
+    #[test]
+    fn the_domain_is_filtered_by_the_remaining_budget() {
+        // taking a decision with value 1 consumes one unit of resource
+        let constrained = WithBudget::new(Unconstrained, 1, |_: &usize, d: Decision| d.value as usize);
+
+        let initial = constrained.initial_state();
+        assert_eq!(vec![1, 0], domain_of(&constrained, Variable(0), &initial));
+
+        // once the budget is exhausted, only the free value remains
+        let spent = constrained.transition(&initial, Decision { variable: Variable(0), value: 1 });
+        assert_eq!(0, spent.budget);
+        assert_eq!(vec![0], domain_of(&constrained, Variable(1), &spent));
+    }
+
+    #[test]
+    fn transitions_compose_and_track_the_budget() {
+        let constrained = WithBudget::new(Unconstrained, 2, |_: &usize, d: Decision| d.value as usize);
+
+        let initial = constrained.initial_state();
+        let next = constrained.transition(&initial, Decision { variable: Variable(0), value: 1 });
+
+        assert_eq!(1, next.inner);
+        assert_eq!(1, next.budget);
+        assert_eq!(1, constrained.transition_cost(&initial, &next, Decision { variable: Variable(0), value: 1 }));
+    }
+
+    #[test]
+    fn checked_transitions_reject_decisions_exceeding_the_budget() {
+        let constrained = WithBudget::new(Unconstrained, 1, |_: &usize, d: Decision| d.value as usize);
+        let decision = Decision { variable: Variable(0), value: 1 };
+
+        let initial = constrained.initial_state();
+        let spent = constrained.transition_checked(&initial, decision).unwrap();
+        assert_eq!(0, spent.budget);
+
+        // the budget is exhausted: the costly decision is now rejected
+        assert_eq!(None, constrained.transition_checked(&spent, decision));
+    }
+
+    #[test]
+    fn the_lazy_domain_is_filtered_by_the_remaining_budget_too() {
+        let constrained = WithBudget::new(Unconstrained, 0, |_: &usize, d: Decision| d.value as usize);
+
+        let initial = constrained.initial_state();
+        assert_eq!(vec![0], constrained.domain_iter(Variable(0), &initial).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn the_other_methods_delegate_to_the_inner_problem() {
+        let constrained = WithBudget::new(Unconstrained, 2, |_: &usize, d: Decision| d.value as usize);
+
+        assert_eq!(3, constrained.nb_variables());
+        assert_eq!(0, constrained.initial_value());
+        assert_eq!(Some(Variable(1)), constrained.next_variable(1, &mut std::iter::empty()));
+        assert!(constrained.is_impacted_by(Variable(0), &constrained.initial_state()));
+    }
+}
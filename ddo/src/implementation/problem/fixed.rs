@@ -0,0 +1,256 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! This module provides a combinator which fixes the value of some variables
+//! of the problem it decorates.
+
+use std::hash::{Hash, Hasher};
+
+use crate::{Decision, DecisionCallback, Problem, Variable};
+
+/// This combinator constrains the problem it decorates with a partial
+/// assignment: the domain of each fixed variable is narrowed to its single
+/// fixed value, while the other variables are branched on as usual. The
+/// exploration is thus restricted to the subspace consistent with the given
+/// decisions, which is the building block for scenario analysis ("what is the
+/// best solution with $x_3 = 1$ ?") and for user-guided search.
+///
+/// The fixed values are validated against the domains of the reachable
+/// states: a fixed value is only ever branched on from the states whose
+/// domain actually contains it. Consequently, fixing a value which no
+/// reachable state admits does not panic; it makes the subspace infeasible
+/// and the search reports that no solution exists.
+///
+/// Unlike the other combinators of this module, it decorates a *reference*
+/// to the problem (possibly a trait object): this is what lets a solver,
+/// which only holds such a reference, wrap its own problem (see
+/// `SequentialSolver::with_fixed`).
+pub struct FixedDecisions<'a, P: Problem + ?Sized> {
+    /// The problem being constrained with a partial assignment
+    problem: &'a P,
+    /// The value each variable is fixed to, if any (indexed by variable id)
+    fixed: Vec<Option<isize>>,
+}
+
+impl<'a, P: Problem + ?Sized> FixedDecisions<'a, P> {
+    /// Creates a combinator constraining the given problem with the given
+    /// partial assignment
+    ///
+    /// # Panics
+    /// When a decision bears on a variable which does not belong to the
+    /// problem, or when two decisions fix the same variable to different
+    /// values
+    pub fn new(problem: &'a P, decisions: Vec<Decision>) -> Self {
+        let mut fixed = vec![None; problem.nb_variables()];
+        for decision in decisions {
+            assert!(decision.variable.id() < problem.nb_variables(),
+                "FixedDecisions: variable {} does not belong to the problem",
+                decision.variable.id());
+            let slot = &mut fixed[decision.variable.id()];
+            assert!(slot.is_none() || *slot == Some(decision.value),
+                "FixedDecisions: variable {} is fixed to two different values",
+                decision.variable.id());
+            *slot = Some(decision.value);
+        }
+        Self { problem, fixed }
+    }
+
+    /// Returns the value the given variable is fixed to, if any
+    fn fixed_value(&self, var: Variable) -> Option<isize> {
+        self.fixed.get(var.id()).copied().flatten()
+    }
+}
+
+impl<P: Problem + ?Sized> Problem for FixedDecisions<'_, P> {
+    type State = P::State;
+
+    fn nb_variables(&self) -> usize {
+        self.problem.nb_variables()
+    }
+    fn initial_state(&self) -> Self::State {
+        self.problem.initial_state()
+    }
+    fn initial_value(&self) -> isize {
+        self.problem.initial_value()
+    }
+    fn transition(&self, state: &Self::State, decision: Decision) -> Self::State {
+        self.problem.transition(state, decision)
+    }
+    fn transition_checked(&self, state: &Self::State, decision: Decision) -> Option<Self::State> {
+        match self.fixed_value(decision.variable) {
+            Some(value) if value != decision.value => None,
+            _ => self.problem.transition_checked(state, decision),
+        }
+    }
+    fn transition_cost(&self, source: &Self::State, dest: &Self::State, decision: Decision) -> isize {
+        self.problem.transition_cost(source, dest, decision)
+    }
+    fn next_variable(&self, depth: usize, next_layer: &mut dyn Iterator<Item = &Self::State>) -> Option<Variable> {
+        self.problem.next_variable(depth, next_layer)
+    }
+    fn static_order(&self) -> Option<Vec<Variable>> {
+        self.problem.static_order()
+    }
+    fn for_each_in_domain(&self, var: Variable, state: &Self::State, f: &mut dyn DecisionCallback) {
+        match self.fixed_value(var) {
+            None => self.problem.for_each_in_domain(var, state, f),
+            Some(value) => self.problem.for_each_in_domain(var, state, &mut |d: Decision| {
+                if d.value == value {
+                    f.apply(d);
+                }
+            }),
+        }
+    }
+    fn for_each_in_domain_with_path(&self, var: Variable, state: &Self::State, path: &[Decision], f: &mut dyn DecisionCallback) {
+        match self.fixed_value(var) {
+            None => self.problem.for_each_in_domain_with_path(var, state, path, f),
+            Some(value) => self.problem.for_each_in_domain_with_path(var, state, path, &mut |d: Decision| {
+                if d.value == value {
+                    f.apply(d);
+                }
+            }),
+        }
+    }
+    fn has_path_dependent_domains(&self) -> bool {
+        self.problem.has_path_dependent_domains()
+    }
+    fn domain_iter<'b>(&'b self, var: Variable, state: &'b Self::State) -> Box<dyn Iterator<Item = isize> + 'b> {
+        match self.fixed_value(var) {
+            None => self.problem.domain_iter(var, state),
+            Some(value) => Box::new(self.problem.domain_iter(var, state).filter(move |v| *v == value)),
+        }
+    }
+    fn has_lazy_domain_iter(&self) -> bool {
+        self.problem.has_lazy_domain_iter()
+    }
+    fn supports_caching(&self) -> bool {
+        self.problem.supports_caching()
+    }
+    fn state_fingerprint(&self, state: &Self::State) -> Option<u64> {
+        self.problem.state_fingerprint(state)
+    }
+    fn state_hash(&self, state: &Self::State, hasher: &mut dyn Hasher)
+    where Self::State: Hash {
+        self.problem.state_hash(state, hasher)
+    }
+    fn state_eq(&self, a: &Self::State, b: &Self::State) -> bool
+    where Self::State: Eq {
+        self.problem.state_eq(a, b)
+    }
+    fn is_impacted_by(&self, var: Variable, state: &Self::State) -> bool {
+        self.problem.is_impacted_by(var, state)
+    }
+    fn is_leaf(&self, state: &Self::State) -> bool {
+        self.problem.is_leaf(state)
+    }
+    fn always_feasible(&self) -> bool {
+        self.problem.always_feasible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    /// A tiny binary problem: each of the 3 variables takes value 0 or 1 and
+    /// contributes its value to the objective. The state is the depth.
+    struct Binary;
+    impl Problem for Binary {
+        type State = usize;
+
+        fn nb_variables(&self) -> usize {
+            3
+        }
+        fn initial_state(&self) -> Self::State {
+            0
+        }
+        fn initial_value(&self) -> isize {
+            0
+        }
+        fn transition(&self, state: &Self::State, _: Decision) -> Self::State {
+            state + 1
+        }
+        fn transition_cost(&self, _: &Self::State, _: &Self::State, decision: Decision) -> isize {
+            decision.value
+        }
+        fn next_variable(&self, depth: usize, _: &mut dyn Iterator<Item = &Self::State>) -> Option<Variable> {
+            if depth < 3 { Some(Variable(depth)) } else { None }
+        }
+        fn for_each_in_domain(&self, var: Variable, _: &Self::State, f: &mut dyn DecisionCallback) {
+            f.apply(Decision { variable: var, value: 0 });
+            f.apply(Decision { variable: var, value: 1 });
+        }
+    }
+
+    fn domain_of(problem: &dyn Problem<State = usize>, var: Variable) -> Vec<isize> {
+        let mut domain = vec![];
+        problem.for_each_in_domain(var, &0, &mut |d: Decision| domain.push(d.value));
+        domain
+    }
+
+    #[test]
+    fn the_domain_of_a_fixed_variable_shrinks_to_the_fixed_value() {
+        let problem = Binary;
+        let fixed = FixedDecisions::new(&problem, vec![Decision { variable: Variable(1), value: 1 }]);
+
+        assert_eq!(vec![1], domain_of(&fixed, Variable(1)));
+        assert_eq!(vec![1], fixed.domain_iter(Variable(1), &0).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn the_domains_of_the_other_variables_are_untouched() {
+        let problem = Binary;
+        let fixed = FixedDecisions::new(&problem, vec![Decision { variable: Variable(1), value: 1 }]);
+
+        assert_eq!(vec![0, 1], domain_of(&fixed, Variable(0)));
+        assert_eq!(vec![0, 1], domain_of(&fixed, Variable(2)));
+    }
+
+    #[test]
+    fn an_out_of_domain_fixed_value_empties_the_domain() {
+        let problem = Binary;
+        let fixed = FixedDecisions::new(&problem, vec![Decision { variable: Variable(1), value: 7 }]);
+
+        assert_eq!(Vec::<isize>::new(), domain_of(&fixed, Variable(1)));
+    }
+
+    #[test]
+    fn inconsistent_decisions_are_rejected_by_transition_checked() {
+        let problem = Binary;
+        let fixed = FixedDecisions::new(&problem, vec![Decision { variable: Variable(1), value: 1 }]);
+
+        assert_eq!(None, fixed.transition_checked(&1, Decision { variable: Variable(1), value: 0 }));
+        assert_eq!(Some(2), fixed.transition_checked(&1, Decision { variable: Variable(1), value: 1 }));
+    }
+
+    #[test]
+    #[should_panic]
+    fn fixing_a_foreign_variable_panics() {
+        let _ = FixedDecisions::new(&Binary, vec![Decision { variable: Variable(5), value: 0 }]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn fixing_a_variable_to_two_different_values_panics() {
+        let _ = FixedDecisions::new(&Binary, vec![
+            Decision { variable: Variable(0), value: 0 },
+            Decision { variable: Variable(0), value: 1 },
+        ]);
+    }
+}
@@ -0,0 +1,191 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! This module provides first-class support for DP models whose objective
+//! values are not `isize` -- typically models with genuinely fractional
+//! (`f64`) transition costs. Instead of forcing such models through a lossy
+//! fixed-point encoding, the objective type is made generic: the `Objective`
+//! trait captures the complete set of operations the engine performs on
+//! objective values (ordering, addition, negation and the two sentinels), and
+//! the `ObjectiveProblem` / `ObjectiveRelaxation` traits mirror their `isize`
+//! counterparts with that generic type. Models expressed in these terms are
+//! solved with the `ObjectiveSolver` (see the solver module), which reports
+//! its `best_value` in the very same type -- no precision is ever lost.
+//!
+//! With the `fractional` cargo feature enabled, `Objective` is implemented
+//! for `ordered_float::OrderedFloat<f64>`, which is the natural choice for
+//! fractional costs; the trait is also implemented for `isize` so that the
+//! generic machinery can always be exercised without any extra dependency.
+
+use std::ops::{Add, Neg};
+
+use crate::{Decision, DecisionCallback, Variable};
+
+/// The contract an objective value type must fulfill for the generic solver
+/// to maximize it. This is the complete set of operations the engine performs
+/// on objective values: total ordering (to compare bounds and incumbents),
+/// addition (to accumulate transition costs along a path), negation (so the
+/// minimization adapters keep working) and the two sentinels. `min_value` is
+/// the "no information" lower bound (every value must compare greater than
+/// it) and `max_value` the "no pruning information" upper bound; for floats
+/// these are the infinities, which is why the arithmetic is exposed through
+/// `saturating_add` -- adding anything to a sentinel must stay a sentinel
+/// rather than wrap around or degenerate into a NaN.
+pub trait Objective: Copy + Ord + Add<Output = Self> + Neg<Output = Self> {
+    /// The neutral element of the addition: the value of an empty path
+    fn zero() -> Self;
+    /// The smallest representable value: every actual objective value must
+    /// compare strictly greater than it ("no solution is known")
+    fn min_value() -> Self;
+    /// The largest representable value: every actual objective value must
+    /// compare strictly less than it ("no pruning information")
+    fn max_value() -> Self;
+    /// Adds the two values without ever overflowing: the result clamps to
+    /// (or remains at) a sentinel instead
+    fn saturating_add(self, other: Self) -> Self;
+}
+
+impl Objective for isize {
+    fn zero() -> Self {
+        0
+    }
+    fn min_value() -> Self {
+        isize::MIN
+    }
+    fn max_value() -> Self {
+        isize::MAX
+    }
+    fn saturating_add(self, other: Self) -> Self {
+        isize::saturating_add(self, other)
+    }
+}
+
+#[cfg(feature = "fractional")]
+impl Objective for ordered_float::OrderedFloat<f64> {
+    fn zero() -> Self {
+        ordered_float::OrderedFloat(0.0)
+    }
+    fn min_value() -> Self {
+        ordered_float::OrderedFloat(f64::NEG_INFINITY)
+    }
+    fn max_value() -> Self {
+        ordered_float::OrderedFloat(f64::INFINITY)
+    }
+    fn saturating_add(self, other: Self) -> Self {
+        // adding the two opposite infinities would yield a NaN: keep the
+        // pessimistic sentinel instead so that the sum of a bound with "no
+        // information" never looks like valuable information
+        if self == Self::min_value() || other == Self::min_value() {
+            Self::min_value()
+        } else if self == Self::max_value() || other == Self::max_value() {
+            Self::max_value()
+        } else {
+            self + other
+        }
+    }
+}
+
+/// The counterpart of the `Problem` trait for DP models whose objective
+/// values are of a generic `Objective` type rather than `isize`. It exposes
+/// the same core methods as `Problem`, except that `initial_value` and
+/// `transition_cost` return `Self::Objective` values. Such a model is solved
+/// with the `ObjectiveSolver`, which reports its bounds and best value in
+/// that very type.
+pub trait ObjectiveProblem {
+    /// The DP type of the state
+    type State;
+    /// The type of the objective values (e.g. `OrderedFloat<f64>` for a
+    /// model with fractional costs)
+    type Objective: Objective;
+
+    /// How many decision variables does this problem comprise ?
+    fn nb_variables(&self) -> usize;
+    /// What is the intial state of the problem ?
+    fn initial_state(&self) -> Self::State;
+    /// What is the intial value of the objective function ?
+    fn initial_value(&self) -> Self::Objective;
+    /// What is the next state of the problem when the given decision is taken ?
+    fn transition(&self, state: &Self::State, decision: Decision) -> Self::State;
+    /// What is the cost of taking the given decision ?
+    fn transition_cost(&self, source: &Self::State, dest: &Self::State, decision: Decision) -> Self::Objective;
+    /// What is the next variable to branch on given the nodes of the next layer ?
+    fn next_variable(&self, depth: usize, next_layer: &mut dyn Iterator<Item = &Self::State>)
+        -> Option<Variable>;
+    /// Calls the given callback for each value in the domain of the variable
+    fn for_each_in_domain(&self, var: Variable, state: &Self::State, f: &mut dyn DecisionCallback);
+    /// Is the state of a node impacted by the given variable ?
+    fn is_impacted_by(&self, _var: Variable, _state: &Self::State) -> bool {
+        true
+    }
+}
+
+/// The counterpart of the `Relaxation` trait for models expressed over a
+/// generic `Objective` type: the merging scheme is the same, only the cost
+/// carried by the relaxed arcs and the rough upper bound are of the generic
+/// type.
+pub trait ObjectiveRelaxation {
+    /// Similar to the DP model of the problem, this relaxation operates on
+    /// states of some type
+    type State;
+    /// The type of the objective values: it must match that of the problem
+    /// being relaxed
+    type Objective: Objective;
+
+    /// This method implements the merge operation: given a set of states, it
+    /// yields a new state which is an over-approximation of them all
+    fn merge(&self, states: &mut dyn Iterator<Item = &Self::State>) -> Self::State;
+    /// This method relaxes the cost of the arc labeled `decision` when it is
+    /// redirected from connecting `source` with `dest` to connecting `source`
+    /// with the merged state `new`
+    fn relax(&self, source: &Self::State, dest: &Self::State, new: &Self::State, decision: Decision, cost: Self::Objective) -> Self::Objective;
+    /// Returns a very rough estimation (upper bound) of the optimal value
+    /// that could be reached if `state` were the initial state
+    fn fast_upper_bound(&self, _state: &Self::State) -> Self::Objective {
+        Self::Objective::max_value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn the_isize_objective_matches_the_native_arithmetic() {
+        assert_eq!(0, <isize as Objective>::zero());
+        assert_eq!(isize::MIN, <isize as Objective>::min_value());
+        assert_eq!(isize::MAX, <isize as Objective>::max_value());
+        assert_eq!(isize::MAX, Objective::saturating_add(isize::MAX, 1));
+        assert_eq!(3, Objective::saturating_add(1_isize, 2));
+    }
+
+    #[cfg(feature = "fractional")]
+    #[test]
+    fn the_float_objective_keeps_its_sentinels_out_of_the_arithmetic() {
+        use ordered_float::OrderedFloat;
+
+        let min = <OrderedFloat<f64> as Objective>::min_value();
+        let max = <OrderedFloat<f64> as Objective>::max_value();
+
+        assert_eq!(OrderedFloat(0.75), OrderedFloat(0.25).saturating_add(OrderedFloat(0.5)));
+        // a sentinel stays a sentinel: no NaN may ever be produced
+        assert_eq!(min, min.saturating_add(max));
+        assert_eq!(min, min.saturating_add(OrderedFloat(1.0)));
+        assert_eq!(max, max.saturating_add(OrderedFloat(1.0)));
+    }
+}
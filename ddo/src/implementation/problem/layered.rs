@@ -0,0 +1,197 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! This module provides a zero-DP-modeling on-ramp for the problems which
+//! are literally longest-path computations on an explicit layered DAG.
+
+use crate::{Decision, DecisionCallback, Problem, Relaxation, Variable};
+
+/// One weighted edge leaving a node of the layered graph
+pub struct LayeredGraphEdge<S> {
+    /// The length of the edge (the quantity being maximized along the path)
+    pub weight: isize,
+    /// The state of the node this edge leads to (in the next layer)
+    pub to: S,
+}
+
+/// This helper implements `Problem` for a longest-path computation on an
+/// explicit layered DAG: the user supplies the number of edge layers, the
+/// state of the root node, and a closure enumerating the weighted edges
+/// which leave any given node. The decision taken at each node is simply the
+/// index of the edge being followed (in the order the closure lists them).
+///
+/// This spares the boilerplate DP model for the problems (e.g. minla, mcp)
+/// where one builds an adjacency structure first and where the "DP" is
+/// nothing but a walk of that structure.
+pub struct LayeredGraphProblem<S, F>
+where
+    S: Clone,
+    F: Fn(usize, &S) -> Vec<LayeredGraphEdge<S>>,
+{
+    /// The number of edge layers of the DAG (one decision is taken per layer)
+    nb_layers: usize,
+    /// The state of the root node of the DAG
+    root: S,
+    /// Given a layer and the state of one of its nodes, this closure lists
+    /// the weighted edges leaving that node
+    edges: F,
+}
+
+impl<S, F> LayeredGraphProblem<S, F>
+where
+    S: Clone,
+    F: Fn(usize, &S) -> Vec<LayeredGraphEdge<S>>,
+{
+    /// Creates the longest-path problem over the layered DAG described by
+    /// the given root state and edge-enumeration closure
+    pub fn new(nb_layers: usize, root: S, edges: F) -> Self {
+        Self { nb_layers, root, edges }
+    }
+}
+
+impl<S, F> Problem for LayeredGraphProblem<S, F>
+where
+    S: Clone,
+    F: Fn(usize, &S) -> Vec<LayeredGraphEdge<S>>,
+{
+    type State = S;
+
+    fn nb_variables(&self) -> usize {
+        self.nb_layers
+    }
+    fn initial_state(&self) -> Self::State {
+        self.root.clone()
+    }
+    fn initial_value(&self) -> isize {
+        0
+    }
+    fn transition(&self, state: &Self::State, decision: Decision) -> Self::State {
+        let edges = (self.edges)(decision.variable.id(), state);
+        edges[decision.value as usize].to.clone()
+    }
+    fn transition_cost(&self, source: &Self::State, _dest: &Self::State, decision: Decision) -> isize {
+        let edges = (self.edges)(decision.variable.id(), source);
+        edges[decision.value as usize].weight
+    }
+    fn next_variable(&self, depth: usize, _next_layer: &mut dyn Iterator<Item = &Self::State>) -> Option<Variable> {
+        if depth < self.nb_layers {
+            Some(Variable(depth))
+        } else {
+            None
+        }
+    }
+    fn for_each_in_domain(&self, variable: Variable, state: &Self::State, f: &mut dyn DecisionCallback) {
+        let nb_edges = (self.edges)(variable.id(), state).len();
+        for value in 0..nb_edges {
+            f.apply(Decision { variable, value: value as isize });
+        }
+    }
+}
+
+/// The trivial relaxation which goes with a `LayeredGraphProblem`: it keeps
+/// the first state of each merge group and leaves the edge weights untouched.
+///
+/// # Warning
+/// Keeping an arbitrary representative does not over-approximate the merged
+/// nodes: the bounds of a relaxed DD compiled with this relaxation are only
+/// heuristic. On an explicit graph this is rarely a concern, though: the
+/// exact DD is the graph itself, so compiling with an unbounded maximum
+/// width is cheap and never invokes the relaxation at all.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LayeredGraphRelaxation<S> {
+    _phantom: std::marker::PhantomData<S>,
+}
+
+impl<S: Clone> Relaxation for LayeredGraphRelaxation<S> {
+    type State = S;
+
+    fn merge(&self, states: &mut dyn Iterator<Item = &Self::State>) -> Self::State {
+        states.next().unwrap().clone()
+    }
+    fn relax(&self, _source: &Self::State, _dest: &Self::State, _new: &Self::State, _decision: Decision, cost: isize) -> isize {
+        cost
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    /// A tiny 3-layer DAG whose nodes are identified by (layer, id). From
+    /// every node, one edge goes "straight" (same id, cheap) and one edge
+    /// goes "diagonal" (other id, dear). The longest path alternates the
+    /// diagonals: 5 + 5 + 5 = 15.
+    fn dag() -> LayeredGraphProblem<usize, impl Fn(usize, &usize) -> Vec<LayeredGraphEdge<usize>>> {
+        LayeredGraphProblem::new(3, 0, |_layer, node| vec![
+            LayeredGraphEdge { weight: 1, to: *node },
+            LayeredGraphEdge { weight: 5, to: 1 - *node },
+        ])
+    }
+
+    struct NodeRanking;
+    impl StateRanking for NodeRanking {
+        type State = usize;
+        fn compare(&self, a: &usize, b: &usize) -> std::cmp::Ordering {
+            a.cmp(b)
+        }
+    }
+
+    #[test]
+    fn the_domain_lists_one_decision_per_outgoing_edge() {
+        let problem = dag();
+        let mut domain = vec![];
+        problem.for_each_in_domain(Variable(0), &0, &mut |d: Decision| domain.push(d.value));
+        assert_eq!(vec![0, 1], domain);
+    }
+
+    #[test]
+    fn transitions_walk_the_explicit_edges() {
+        let problem = dag();
+        let straight = Decision { variable: Variable(0), value: 0 };
+        let diagonal = Decision { variable: Variable(0), value: 1 };
+        assert_eq!(0, problem.transition(&0, straight));
+        assert_eq!(1, problem.transition(&0, diagonal));
+        assert_eq!(1, problem.transition_cost(&0, &0, straight));
+        assert_eq!(5, problem.transition_cost(&0, &1, diagonal));
+    }
+
+    #[test]
+    fn the_solver_finds_the_longest_path() {
+        let problem = dag();
+        let relax = LayeredGraphRelaxation::default();
+        let ranking = NodeRanking;
+        let cutoff = NoCutoff;
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SeqNoCachingSolverLel::custom(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+
+        let maximized = solver.maximize();
+        assert!(maximized.is_exact);
+        assert_eq!(Some(15), maximized.best_value);
+    }
+}
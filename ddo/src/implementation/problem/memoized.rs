@@ -0,0 +1,278 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! This module provides the implementation of a `Problem` adapter which
+//! memoizes the outcome of the (potentially expensive) `transition` function
+//! of the decorated problem.
+
+use std::hash::{Hash, Hasher};
+
+use dashmap::DashMap;
+
+use crate::{Decision, DecisionCallback, Problem, Variable};
+
+/// This adapter memoizes the outcome of the `transition` function of the
+/// problem it decorates. It is useful for DP models whose transition function
+/// is expensive to evaluate and where the same `(state, decision)` pair recurs
+/// during the compilation of several DDs (which is typical of problems like
+/// jssp or srflp). On a cache miss, the call is simply delegated to the inner
+/// problem and the result is recorded for later reuse.
+///
+/// # Trade-off
+/// The memoization table keeps a copy of every distinct state that was ever
+/// expanded along with each of its successors. This can amount to a large
+/// quantity of memory for models with big state spaces; you should therefore
+/// only reach for this adapter when the transition function is costly enough
+/// for the recomputation time to outweigh the memory overhead.
+pub struct Memoized<P: Problem> {
+    /// The problem whose transition function is being memoized
+    problem: P,
+    /// The memoization table which maps a `(state, decision)` pair onto the
+    /// successor state the inner problem computed for that pair
+    table: DashMap<(P::State, Decision), P::State, fxhash::FxBuildHasher>,
+}
+
+impl<P> Memoized<P>
+where
+    P: Problem,
+    P::State: Eq + std::hash::Hash + Clone,
+{
+    /// Creates a new adapter memoizing the transitions of the given problem
+    pub fn new(problem: P) -> Self {
+        Self {
+            problem,
+            table: DashMap::default(),
+        }
+    }
+
+    /// Returns a reference to the decorated problem
+    pub fn inner(&self) -> &P {
+        &self.problem
+    }
+}
+
+impl<P> Problem for Memoized<P>
+where
+    P: Problem,
+    P::State: Eq + std::hash::Hash + Clone,
+{
+    type State = P::State;
+
+    fn nb_variables(&self) -> usize {
+        self.problem.nb_variables()
+    }
+
+    fn initial_state(&self) -> Self::State {
+        self.problem.initial_state()
+    }
+
+    fn initial_value(&self) -> isize {
+        self.problem.initial_value()
+    }
+
+    fn transition(&self, state: &Self::State, decision: Decision) -> Self::State {
+        let key = (state.clone(), decision);
+        if let Some(next) = self.table.get(&key) {
+            next.clone()
+        } else {
+            let next = self.problem.transition(state, decision);
+            self.table.insert(key, next.clone());
+            next
+        }
+    }
+
+    fn transition_checked(&self, state: &Self::State, decision: Decision) -> Option<Self::State> {
+        // this is the entry point the compilers actually use: accepted
+        // transitions are served from (and recorded into) the memoization
+        // table just like the plain ones, while rejections -- which are
+        // usually cheap to recompute -- are simply delegated
+        let key = (state.clone(), decision);
+        if let Some(next) = self.table.get(&key) {
+            Some(next.clone())
+        } else {
+            let next = self.problem.transition_checked(state, decision)?;
+            self.table.insert(key, next.clone());
+            Some(next)
+        }
+    }
+
+    fn transition_cost(&self, source: &Self::State, dest: &Self::State, decision: Decision) -> isize {
+        self.problem.transition_cost(source, dest, decision)
+    }
+
+    fn next_variable(&self, depth: usize, next_layer: &mut dyn Iterator<Item = &Self::State>) -> Option<Variable> {
+        self.problem.next_variable(depth, next_layer)
+    }
+
+    fn static_order(&self) -> Option<Vec<Variable>> {
+        self.problem.static_order()
+    }
+
+    fn for_each_in_domain(&self, var: Variable, state: &Self::State, f: &mut dyn DecisionCallback) {
+        self.problem.for_each_in_domain(var, state, f)
+    }
+
+    fn for_each_in_domain_with_path(&self, var: Variable, state: &Self::State, path: &[Decision], f: &mut dyn DecisionCallback) {
+        self.problem.for_each_in_domain_with_path(var, state, path, f)
+    }
+
+    fn has_path_dependent_domains(&self) -> bool {
+        self.problem.has_path_dependent_domains()
+    }
+
+    fn domain_iter<'a>(&'a self, var: Variable, state: &'a Self::State) -> Box<dyn Iterator<Item = isize> + 'a> {
+        self.problem.domain_iter(var, state)
+    }
+
+    fn has_lazy_domain_iter(&self) -> bool {
+        self.problem.has_lazy_domain_iter()
+    }
+
+    fn supports_caching(&self) -> bool {
+        self.problem.supports_caching()
+    }
+
+    fn state_fingerprint(&self, state: &Self::State) -> Option<u64> {
+        self.problem.state_fingerprint(state)
+    }
+
+    fn state_hash(&self, state: &Self::State, hasher: &mut dyn Hasher)
+    where Self::State: Hash {
+        self.problem.state_hash(state, hasher)
+    }
+
+    fn state_eq(&self, a: &Self::State, b: &Self::State) -> bool
+    where Self::State: Eq {
+        self.problem.state_eq(a, b)
+    }
+
+    fn is_impacted_by(&self, var: Variable, state: &Self::State) -> bool {
+        self.problem.is_impacted_by(var, state)
+    }
+
+    fn is_leaf(&self, state: &Self::State) -> bool {
+        self.problem.is_leaf(state)
+    }
+
+    fn always_feasible(&self) -> bool {
+        self.problem.always_feasible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::*;
+
+    /// A dummy problem counting how many times its transition fn was called
+    struct Counting {
+        nb_calls: AtomicUsize,
+    }
+    impl Problem for Counting {
+        type State = usize;
+
+        fn nb_variables(&self) -> usize {
+            3
+        }
+        fn initial_state(&self) -> Self::State {
+            0
+        }
+        fn initial_value(&self) -> isize {
+            0
+        }
+        fn transition(&self, state: &Self::State, d: Decision) -> Self::State {
+            self.nb_calls.fetch_add(1, Ordering::SeqCst);
+            state + d.value as usize
+        }
+        fn transition_cost(&self, _: &Self::State, _: &Self::State, d: Decision) -> isize {
+            d.value
+        }
+        fn next_variable(&self, depth: usize, _: &mut dyn Iterator<Item = &Self::State>) -> Option<Variable> {
+            if depth < self.nb_variables() {
+                Some(Variable(depth))
+            } else {
+                None
+            }
+        }
+        fn for_each_in_domain(&self, variable: Variable, _: &Self::State, f: &mut dyn DecisionCallback) {
+            f.apply(Decision { variable, value: 0 });
+            f.apply(Decision { variable, value: 1 });
+        }
+    }
+
+    #[test]
+    fn the_inner_problem_is_only_consulted_on_a_cache_miss() {
+        let memoized = Memoized::new(Counting {
+            nb_calls: AtomicUsize::new(0),
+        });
+        let decision = Decision {
+            variable: Variable(0),
+            value: 1,
+        };
+
+        assert_eq!(1, memoized.transition(&0, decision));
+        assert_eq!(1, memoized.inner().nb_calls.load(Ordering::SeqCst));
+
+        // the same (state, decision) pair is served from the memoization table
+        assert_eq!(1, memoized.transition(&0, decision));
+        assert_eq!(1, memoized.inner().nb_calls.load(Ordering::SeqCst));
+
+        // but any other pair is delegated to the inner problem
+        assert_eq!(2, memoized.transition(&1, decision));
+        assert_eq!(2, memoized.inner().nb_calls.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn checked_transitions_share_the_memoization_table() {
+        let memoized = Memoized::new(Counting {
+            nb_calls: AtomicUsize::new(0),
+        });
+        let decision = Decision {
+            variable: Variable(0),
+            value: 1,
+        };
+
+        assert_eq!(Some(1), memoized.transition_checked(&0, decision));
+        assert_eq!(1, memoized.inner().nb_calls.load(Ordering::SeqCst));
+
+        // the plain and the checked entry points hit the very same table
+        assert_eq!(1, memoized.transition(&0, decision));
+        assert_eq!(Some(1), memoized.transition_checked(&0, decision));
+        assert_eq!(1, memoized.inner().nb_calls.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn the_other_methods_delegate_to_the_inner_problem() {
+        let memoized = Memoized::new(Counting {
+            nb_calls: AtomicUsize::new(0),
+        });
+        let decision = Decision {
+            variable: Variable(0),
+            value: 1,
+        };
+
+        assert_eq!(3, memoized.nb_variables());
+        assert_eq!(0, memoized.initial_state());
+        assert_eq!(0, memoized.initial_value());
+        assert_eq!(1, memoized.transition_cost(&0, &1, decision));
+        assert_eq!(Some(Variable(1)), memoized.next_variable(1, &mut std::iter::empty()));
+        assert!(memoized.is_impacted_by(Variable(0), &0));
+    }
+}
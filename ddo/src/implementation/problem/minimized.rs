@@ -0,0 +1,346 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! This module provides the adapters used to solve minimization problems
+//! without negating every cost by hand. The engine only ever maximizes: to
+//! minimize, one writes the DP model and its relaxation in the natural
+//! (minimization) sense, wraps them in `Minimized` / `MinimizedRelaxation`,
+//! and wraps the solver in a `Minimizer` (see the solver module) so that all
+//! the reported values and bounds come back in the original sense.
+
+use std::hash::{Hash, Hasher};
+
+use crate::{CompilationType, Decision, DecisionCallback, Problem, Relaxation, Variable};
+
+/// This adapter turns a minimization DP model into the maximization model the
+/// engine expects, by negating the initial value and every transition cost.
+/// The decorated problem is written in the natural (minimization) sense, with
+/// `transition_cost` returning the actual cost of each transition.
+pub struct Minimized<P> {
+    /// The minimization problem being decorated
+    problem: P,
+}
+
+impl<P: Problem> Minimized<P> {
+    /// Creates the maximization view of the given minimization problem
+    pub fn new(problem: P) -> Self {
+        Self { problem }
+    }
+    /// Returns a reference to the decorated problem
+    pub fn inner(&self) -> &P {
+        &self.problem
+    }
+}
+
+impl<P: Problem> Problem for Minimized<P> {
+    type State = P::State;
+
+    fn nb_variables(&self) -> usize {
+        self.problem.nb_variables()
+    }
+    fn initial_state(&self) -> Self::State {
+        self.problem.initial_state()
+    }
+    fn initial_value(&self) -> isize {
+        self.problem.initial_value().saturating_neg()
+    }
+    fn transition(&self, state: &Self::State, decision: Decision) -> Self::State {
+        self.problem.transition(state, decision)
+    }
+    fn transition_checked(&self, state: &Self::State, decision: Decision) -> Option<Self::State> {
+        self.problem.transition_checked(state, decision)
+    }
+    fn transition_cost(&self, source: &Self::State, dest: &Self::State, decision: Decision) -> isize {
+        self.problem.transition_cost(source, dest, decision).saturating_neg()
+    }
+    fn next_variable(&self, depth: usize, next_layer: &mut dyn Iterator<Item = &Self::State>) -> Option<Variable> {
+        self.problem.next_variable(depth, next_layer)
+    }
+    fn static_order(&self) -> Option<Vec<Variable>> {
+        self.problem.static_order()
+    }
+    fn for_each_in_domain(&self, var: Variable, state: &Self::State, f: &mut dyn DecisionCallback) {
+        self.problem.for_each_in_domain(var, state, f)
+    }
+    fn for_each_in_domain_with_path(&self, var: Variable, state: &Self::State, path: &[Decision], f: &mut dyn DecisionCallback) {
+        self.problem.for_each_in_domain_with_path(var, state, path, f)
+    }
+    fn has_path_dependent_domains(&self) -> bool {
+        self.problem.has_path_dependent_domains()
+    }
+    fn domain_iter<'a>(&'a self, var: Variable, state: &'a Self::State) -> Box<dyn Iterator<Item = isize> + 'a> {
+        self.problem.domain_iter(var, state)
+    }
+    fn has_lazy_domain_iter(&self) -> bool {
+        self.problem.has_lazy_domain_iter()
+    }
+    fn supports_caching(&self) -> bool {
+        self.problem.supports_caching()
+    }
+    fn state_fingerprint(&self, state: &Self::State) -> Option<u64> {
+        self.problem.state_fingerprint(state)
+    }
+    fn state_hash(&self, state: &Self::State, hasher: &mut dyn Hasher)
+    where Self::State: Hash {
+        self.problem.state_hash(state, hasher)
+    }
+    fn state_eq(&self, a: &Self::State, b: &Self::State) -> bool
+    where Self::State: Eq {
+        self.problem.state_eq(a, b)
+    }
+    fn is_impacted_by(&self, var: Variable, state: &Self::State) -> bool {
+        self.problem.is_impacted_by(var, state)
+    }
+    fn is_leaf(&self, state: &Self::State) -> bool {
+        self.problem.is_leaf(state)
+    }
+    fn always_feasible(&self) -> bool {
+        self.problem.always_feasible()
+    }
+}
+
+/// The relaxation counterpart of `Minimized`: it lets you write the
+/// relaxation of a minimization problem in the natural sense and turns it
+/// into the relaxation of the negated (maximization) model.
+///
+/// # Note
+/// For the decorated relaxation, every method receives and returns actual
+/// (minimization-sense) costs and values: `relax` works on actual costs,
+/// `fast_upper_bound` (and its variants) must return an optimistic completion
+/// bound -- that is, a *lower bound on the cost* that remains to be paid from
+/// the given state (the lower, the weaker) -- and `fast_lower_bound` must
+/// return the cost of some feasible completion (a pessimistic, achievable
+/// bound). Returning `isize::MAX` (resp. `isize::MIN`) keeps the usual "no
+/// pruning information" meaning of each. Likewise, `select_representative`
+/// receives the actual accumulated cost of each node of the merged group.
+pub struct MinimizedRelaxation<R> {
+    /// The minimization-sense relaxation being decorated
+    relaxation: R,
+}
+
+impl<R: Relaxation> MinimizedRelaxation<R> {
+    /// Creates the maximization view of the given minimization-sense relaxation
+    pub fn new(relaxation: R) -> Self {
+        Self { relaxation }
+    }
+    /// Returns a reference to the decorated relaxation
+    pub fn inner(&self) -> &R {
+        &self.relaxation
+    }
+}
+
+impl<R: Relaxation> Relaxation for MinimizedRelaxation<R> {
+    type State = R::State;
+
+    fn merge(&self, states: &mut dyn Iterator<Item = &Self::State>) -> Self::State {
+        self.relaxation.merge(states)
+    }
+    fn merge_opt(&self, states: &mut dyn Iterator<Item = &Self::State>) -> Option<Self::State> {
+        self.relaxation.merge_opt(states)
+    }
+    fn merge_opt_with_type(&self, comp_type: CompilationType, states: &mut dyn Iterator<Item = &Self::State>) -> Option<Self::State> {
+        self.relaxation.merge_opt_with_type(comp_type, states)
+    }
+    fn relax(&self, source: &Self::State, dest: &Self::State, new: &Self::State, decision: Decision, cost: isize) -> isize {
+        self.relaxation
+            .relax(source, dest, new, decision, cost.saturating_neg())
+            .saturating_neg()
+    }
+    #[allow(clippy::too_many_arguments)]
+    fn relax_with_type(&self, comp_type: CompilationType, source: &Self::State, dest: &Self::State, new: &Self::State, decision: Decision, cost: isize) -> isize {
+        self.relaxation
+            .relax_with_type(comp_type, source, dest, new, decision, cost.saturating_neg())
+            .saturating_neg()
+    }
+    fn fast_upper_bound(&self, state: &Self::State) -> isize {
+        let bound = self.relaxation.fast_upper_bound(state);
+        if bound == isize::MAX {
+            isize::MAX // the default: no pruning information
+        } else {
+            bound.saturating_neg()
+        }
+    }
+    fn fast_upper_bound_with_value(&self, state: &Self::State, value: isize, depth: usize) -> isize {
+        // the engine hands out the negated accumulated value: the decorated
+        // relaxation receives the actual cost of the path instead
+        let bound = self.relaxation.fast_upper_bound_with_value(state, value.saturating_neg(), depth);
+        if bound == isize::MAX {
+            isize::MAX // the default: no pruning information
+        } else {
+            bound.saturating_neg()
+        }
+    }
+    fn fast_upper_bound_with_solution(&self, state: &Self::State) -> (isize, Option<Vec<Decision>>) {
+        let (bound, solution) = self.relaxation.fast_upper_bound_with_solution(state);
+        let bound = if bound == isize::MAX {
+            isize::MAX // the default: no pruning information
+        } else {
+            bound.saturating_neg()
+        };
+        // the suggested completion is a plain sequence of decisions: it is
+        // sign-agnostic and may be forwarded untouched
+        (bound, solution)
+    }
+    fn fast_lower_bound(&self, state: &Self::State) -> isize {
+        let bound = self.relaxation.fast_lower_bound(state);
+        if bound == isize::MIN {
+            isize::MIN // the default: no achievable completion is known
+        } else {
+            bound.saturating_neg()
+        }
+    }
+    fn select_merge_groups(&self, states: &[&Self::State], width: usize) -> Vec<Vec<usize>> {
+        self.relaxation.select_merge_groups(states, width)
+    }
+    fn select_representative(&self, states: &mut dyn Iterator<Item = (&Self::State, isize)>) -> usize {
+        self.relaxation
+            .select_representative(&mut states.map(|(state, value)| (state, value.saturating_neg())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    /// A dummy minimization problem: each of the three variables must pick
+    /// one of two modes, each with its own (positive) cost
+    struct ModeCosts;
+    const CHEAP: [isize; 3] = [1, 2, 3];
+    const DEAR : [isize; 3] = [4, 5, 6];
+
+    impl Problem for ModeCosts {
+        type State = usize;
+
+        fn nb_variables(&self) -> usize {
+            3
+        }
+        fn initial_state(&self) -> Self::State {
+            0
+        }
+        fn initial_value(&self) -> isize {
+            10
+        }
+        fn transition(&self, state: &Self::State, _: Decision) -> Self::State {
+            state + 1
+        }
+        fn transition_cost(&self, _: &Self::State, _: &Self::State, d: Decision) -> isize {
+            if d.value == 0 {
+                CHEAP[d.variable.id()]
+            } else {
+                DEAR[d.variable.id()]
+            }
+        }
+        fn next_variable(&self, depth: usize, _: &mut dyn Iterator<Item = &Self::State>) -> Option<Variable> {
+            if depth < self.nb_variables() {
+                Some(Variable(depth))
+            } else {
+                None
+            }
+        }
+        fn for_each_in_domain(&self, variable: Variable, _: &Self::State, f: &mut dyn DecisionCallback) {
+            f.apply(Decision { variable, value: 0 });
+            f.apply(Decision { variable, value: 1 });
+        }
+    }
+
+    /// The relaxation of the dummy problem, written in the minimization sense
+    struct ModeRelax;
+    impl Relaxation for ModeRelax {
+        type State = usize;
+
+        fn merge(&self, states: &mut dyn Iterator<Item = &Self::State>) -> Self::State {
+            states.copied().min().unwrap()
+        }
+        fn relax(&self, _: &Self::State, _: &Self::State, _: &Self::State, _: Decision, cost: isize) -> isize {
+            cost
+        }
+        fn fast_upper_bound(&self, state: &Self::State) -> isize {
+            // an optimistic bound: each remaining variable costs at least its
+            // cheapest mode
+            (*state..3).map(|i| CHEAP[i]).sum()
+        }
+        fn fast_upper_bound_with_solution(&self, state: &Self::State) -> (isize, Option<Vec<Decision>>) {
+            // the optimistic bound is achieved by picking the cheap mode of
+            // every remaining variable, which is a feasible completion
+            let solution = (*state..3)
+                .map(|i| Decision { variable: Variable(i), value: 0 })
+                .collect();
+            (self.fast_upper_bound(state), Some(solution))
+        }
+        fn fast_lower_bound(&self, state: &Self::State) -> isize {
+            // an achievable completion: pick the dear mode everywhere
+            (*state..3).map(|i| DEAR[i]).sum()
+        }
+    }
+
+    #[test]
+    fn the_costs_and_initial_value_are_negated() {
+        let minimized = Minimized::new(ModeCosts);
+
+        assert_eq!(-10, minimized.initial_value());
+        assert_eq!(-1, minimized.transition_cost(&0, &1, Decision { variable: Variable(0), value: 0 }));
+        assert_eq!(-4, minimized.transition_cost(&0, &1, Decision { variable: Variable(0), value: 1 }));
+    }
+
+    #[test]
+    fn the_relaxation_works_on_minimization_sense_costs() {
+        let relax = MinimizedRelaxation::new(ModeRelax);
+
+        // the engine hands out negated costs and expects a negated result
+        assert_eq!(-5, relax.relax(&0, &1, &1, Decision { variable: Variable(0), value: 1 }, -5));
+        // the optimistic completion bound becomes a valid rough upper bound
+        assert_eq!(-6, relax.fast_upper_bound(&0));
+        assert_eq!(-3, relax.fast_upper_bound(&2));
+    }
+
+    #[test]
+    fn the_suggested_completion_is_forwarded_with_a_negated_bound() {
+        let relax = MinimizedRelaxation::new(ModeRelax);
+
+        let (bound, solution) = relax.fast_upper_bound_with_solution(&2);
+        assert_eq!(-3, bound);
+        assert_eq!(Some(vec![Decision { variable: Variable(2), value: 0 }]), solution);
+    }
+
+    #[test]
+    fn the_achievable_completion_bound_is_negated_too() {
+        let relax = MinimizedRelaxation::new(ModeRelax);
+
+        // the dear-mode completion costs 15 from the root, 6 from depth 2
+        assert_eq!(-15, relax.fast_lower_bound(&0));
+        assert_eq!(-6, relax.fast_lower_bound(&2));
+    }
+
+    #[test]
+    fn the_default_fast_upper_bound_stays_uninformative() {
+        struct NoBoundRelax;
+        impl Relaxation for NoBoundRelax {
+            type State = usize;
+            fn merge(&self, states: &mut dyn Iterator<Item = &Self::State>) -> Self::State {
+                states.copied().min().unwrap()
+            }
+            fn relax(&self, _: &Self::State, _: &Self::State, _: &Self::State, _: Decision, cost: isize) -> isize {
+                cost
+            }
+        }
+        let relax = MinimizedRelaxation::new(NoBoundRelax);
+        assert_eq!(isize::MAX, relax.fast_upper_bound(&0));
+        assert_eq!(isize::MIN, relax.fast_lower_bound(&0));
+    }
+}
@@ -0,0 +1,176 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! This module provides a combinator to derive a rough upper bound from a
+//! per-variable optimistic cost estimate.
+
+use crate::{CompilationType, Decision, Relaxation, Variable};
+
+/// This trait is the user-facing half of the [`PerVariableBound`] combinator:
+/// it captures the one problem-specific insight from which a rough upper
+/// bound can be derived mechanically, namely the most any single remaining
+/// variable could still contribute to the objective.
+pub trait OptimisticCost {
+    /// The state type of the DP model this estimate applies to
+    type State;
+
+    /// Calls `f` once for every variable that has not been assigned yet in
+    /// the given state (for most models: the variables of the layers below
+    /// the state's)
+    fn for_each_remaining_variable(&self, state: &Self::State, f: &mut dyn FnMut(Variable));
+
+    /// Returns an optimistic estimate of the contribution of the given
+    /// (remaining) variable to the objective: no completion of the state may
+    /// collect more than this value from the decision on `var`
+    fn optimistic_cost(&self, var: Variable, state: &Self::State) -> isize;
+}
+
+/// This combinator derives the rough upper bound of a relaxation from a
+/// per-variable [`OptimisticCost`] estimate: the bound for a state is the sum
+/// of the optimistic costs of all its remaining variables. This is the very
+/// summation loop which most models (knapsack, misp, ...) hand-roll in their
+/// `fast_upper_bound`; supplying the per-variable estimate is all that is
+/// left to do. Everything else (the merging scheme, `relax` and the
+/// merge-group selection) is forwarded untouched to the decorated relaxation.
+///
+/// # Warning
+/// The resulting bound is admissible iff `optimistic_cost` truly
+/// over-estimates the contribution of each variable in *every* completion of
+/// the state: an under-estimate on any single variable may prune the optimum.
+pub struct PerVariableBound<R, B> {
+    /// The decorated relaxation: it drives the merging scheme
+    relax: R,
+    /// The per-variable estimate from which the bound is summed
+    bound: B,
+}
+
+impl<R, B> PerVariableBound<R, B>
+where
+    R: Relaxation,
+    B: OptimisticCost<State = R::State>,
+{
+    /// Creates a relaxation which merges like `relax` and prunes with the sum
+    /// of the per-variable estimates of `bound`
+    pub fn new(relax: R, bound: B) -> Self {
+        Self { relax, bound }
+    }
+}
+
+impl<R, B> Relaxation for PerVariableBound<R, B>
+where
+    R: Relaxation,
+    B: OptimisticCost<State = R::State>,
+{
+    type State = R::State;
+
+    fn merge(&self, states: &mut dyn Iterator<Item = &Self::State>) -> Self::State {
+        self.relax.merge(states)
+    }
+    fn merge_opt(&self, states: &mut dyn Iterator<Item = &Self::State>) -> Option<Self::State> {
+        self.relax.merge_opt(states)
+    }
+    fn merge_opt_with_type(&self, comp_type: CompilationType, states: &mut dyn Iterator<Item = &Self::State>) -> Option<Self::State> {
+        self.relax.merge_opt_with_type(comp_type, states)
+    }
+    fn relax(&self, source: &Self::State, dest: &Self::State, new: &Self::State, decision: Decision, cost: isize) -> isize {
+        self.relax.relax(source, dest, new, decision, cost)
+    }
+    #[allow(clippy::too_many_arguments)]
+    fn relax_with_type(&self, comp_type: CompilationType, source: &Self::State, dest: &Self::State, new: &Self::State, decision: Decision, cost: isize) -> isize {
+        self.relax.relax_with_type(comp_type, source, dest, new, decision, cost)
+    }
+    fn fast_lower_bound(&self, state: &Self::State) -> isize {
+        // only the *optimistic* bound is replaced by the summation: an
+        // achievable bound of the decorated relaxation remains achievable
+        self.relax.fast_lower_bound(state)
+    }
+    fn fast_upper_bound(&self, state: &Self::State) -> isize {
+        let mut rub = 0_isize;
+        self.bound.for_each_remaining_variable(state, &mut |var| {
+            rub = rub.saturating_add(self.bound.optimistic_cost(var, state));
+        });
+        rub
+    }
+    fn select_merge_groups(&self, states: &[&Self::State], width: usize) -> Vec<Vec<usize>> {
+        self.relax.select_merge_groups(states, width)
+    }
+    fn select_representative(&self, group: &mut dyn Iterator<Item = (&Self::State, isize)>) -> usize {
+        self.relax.select_representative(group)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    /// A relaxation with no bound of its own: the combinator must provide it.
+    /// The state is the depth (number of assigned variables).
+    struct DepthRelax;
+    impl Relaxation for DepthRelax {
+        type State = usize;
+
+        fn merge(&self, states: &mut dyn Iterator<Item = &Self::State>) -> Self::State {
+            states.copied().min().unwrap()
+        }
+        fn relax(&self, _: &Self::State, _: &Self::State, _: &Self::State, _: Decision, cost: isize) -> isize {
+            cost
+        }
+    }
+
+    /// A knapsack-like estimate: each remaining item could at best be taken,
+    /// collecting its full profit
+    struct TakeEverything {
+        profit: Vec<isize>,
+    }
+    impl OptimisticCost for TakeEverything {
+        type State = usize;
+
+        fn for_each_remaining_variable(&self, state: &Self::State, f: &mut dyn FnMut(Variable)) {
+            (*state..self.profit.len()).for_each(|i| f(Variable(i)));
+        }
+        fn optimistic_cost(&self, var: Variable, _: &Self::State) -> isize {
+            self.profit[var.id()]
+        }
+    }
+
+    #[test]
+    fn the_bound_is_the_sum_over_the_remaining_variables() {
+        let relax = PerVariableBound::new(DepthRelax, TakeEverything { profit: vec![5, 3, 2] });
+
+        assert_eq!(10, relax.fast_upper_bound(&0));
+        assert_eq!(5, relax.fast_upper_bound(&1));
+        assert_eq!(2, relax.fast_upper_bound(&2));
+    }
+
+    #[test]
+    fn the_bound_of_a_state_with_no_remaining_variable_is_zero() {
+        let relax = PerVariableBound::new(DepthRelax, TakeEverything { profit: vec![5, 3, 2] });
+
+        assert_eq!(0, relax.fast_upper_bound(&3));
+    }
+
+    #[test]
+    fn the_merging_scheme_is_that_of_the_decorated_relaxation() {
+        let relax = PerVariableBound::new(DepthRelax, TakeEverything { profit: vec![5, 3, 2] });
+        let decision = Decision { variable: Variable(0), value: 0 };
+
+        assert_eq!(3, relax.merge(&mut [3_usize, 7].iter()));
+        assert_eq!(2, relax.relax(&0, &1, &1, decision, 2));
+    }
+}
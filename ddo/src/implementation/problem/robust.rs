@@ -0,0 +1,262 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! This module provides the adapters used to solve problems whose transition
+//! costs are uncertain (see the `RobustProblem` trait). Maximizing the
+//! `WorstCase` view of such a problem yields the robust optimum -- the best
+//! objective value that is guaranteed regardless of how the uncertainty
+//! resolves -- while maximizing the `BestCase` view yields the optimistic
+//! bound on what could possibly be attained.
+
+use std::hash::{Hash, Hasher};
+
+use crate::{Decision, DecisionCallback, Problem, RobustProblem, Variable};
+
+/// This adapter turns a `RobustProblem` into a regular `Problem` by assuming
+/// that every uncertain cost takes the lowest value of its interval. Since we
+/// maximize, this is the pessimistic (adversarial) resolution of the
+/// uncertainty: the optimum of this problem is the robust optimum of the
+/// decorated one.
+pub struct WorstCase<P: RobustProblem> {
+    /// The robust problem being pessimistically resolved
+    problem: P,
+}
+
+/// This adapter turns a `RobustProblem` into a regular `Problem` by assuming
+/// that every uncertain cost takes the highest value of its interval. The
+/// optimum of this problem is an optimistic bound on the value attainable by
+/// any resolution of the uncertainty.
+pub struct BestCase<P: RobustProblem> {
+    /// The robust problem being optimistically resolved
+    problem: P,
+}
+
+impl<P: RobustProblem> WorstCase<P> {
+    /// Creates the pessimistic view of the given robust problem
+    pub fn new(problem: P) -> Self {
+        Self { problem }
+    }
+    /// Returns a reference to the decorated problem
+    pub fn inner(&self) -> &P {
+        &self.problem
+    }
+}
+
+impl<P: RobustProblem> BestCase<P> {
+    /// Creates the optimistic view of the given robust problem
+    pub fn new(problem: P) -> Self {
+        Self { problem }
+    }
+    /// Returns a reference to the decorated problem
+    pub fn inner(&self) -> &P {
+        &self.problem
+    }
+}
+
+/// The two adapters only differ by the end of the cost interval they pick:
+/// this macro factors out the boilerplate delegation of all other methods.
+macro_rules! delegate_problem {
+    ($adapter:ident, $pick:expr) => {
+        impl<P: RobustProblem> Problem for $adapter<P> {
+            type State = P::State;
+
+            fn nb_variables(&self) -> usize {
+                self.problem.nb_variables()
+            }
+            fn initial_state(&self) -> Self::State {
+                self.problem.initial_state()
+            }
+            fn initial_value(&self) -> isize {
+                self.problem.initial_value()
+            }
+            fn transition(&self, state: &Self::State, decision: Decision) -> Self::State {
+                self.problem.transition(state, decision)
+            }
+            fn transition_checked(&self, state: &Self::State, decision: Decision) -> Option<Self::State> {
+                self.problem.transition_checked(state, decision)
+            }
+            fn transition_cost(&self, source: &Self::State, dest: &Self::State, decision: Decision) -> isize {
+                let pick: fn((isize, isize)) -> isize = $pick;
+                pick(self.problem.transition_cost_interval(source, dest, decision))
+            }
+            fn next_variable(&self, depth: usize, next_layer: &mut dyn Iterator<Item = &Self::State>) -> Option<Variable> {
+                self.problem.next_variable(depth, next_layer)
+            }
+            fn static_order(&self) -> Option<Vec<Variable>> {
+                self.problem.static_order()
+            }
+            fn for_each_in_domain(&self, var: Variable, state: &Self::State, f: &mut dyn DecisionCallback) {
+                self.problem.for_each_in_domain(var, state, f)
+            }
+            fn for_each_in_domain_with_path(&self, var: Variable, state: &Self::State, path: &[Decision], f: &mut dyn DecisionCallback) {
+                self.problem.for_each_in_domain_with_path(var, state, path, f)
+            }
+            fn has_path_dependent_domains(&self) -> bool {
+                self.problem.has_path_dependent_domains()
+            }
+            fn domain_iter<'a>(&'a self, var: Variable, state: &'a Self::State) -> Box<dyn Iterator<Item = isize> + 'a> {
+                self.problem.domain_iter(var, state)
+            }
+            fn has_lazy_domain_iter(&self) -> bool {
+                self.problem.has_lazy_domain_iter()
+            }
+            fn supports_caching(&self) -> bool {
+                self.problem.supports_caching()
+            }
+            fn state_fingerprint(&self, state: &Self::State) -> Option<u64> {
+                self.problem.state_fingerprint(state)
+            }
+            fn state_hash(&self, state: &Self::State, hasher: &mut dyn Hasher)
+            where Self::State: Hash {
+                self.problem.state_hash(state, hasher)
+            }
+            fn state_eq(&self, a: &Self::State, b: &Self::State) -> bool
+            where Self::State: Eq {
+                self.problem.state_eq(a, b)
+            }
+            fn is_impacted_by(&self, var: Variable, state: &Self::State) -> bool {
+                self.problem.is_impacted_by(var, state)
+            }
+            fn is_leaf(&self, state: &Self::State) -> bool {
+                self.problem.is_leaf(state)
+            }
+            fn always_feasible(&self) -> bool {
+                self.problem.always_feasible()
+            }
+        }
+    };
+}
+
+delegate_problem!(WorstCase, |(min, _max)| min);
+delegate_problem!(BestCase, |(_min, max)| max);
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    /// A dummy robust problem with binary domains: taking an item yields a
+    /// profit which is only known to lie between 1 and 5
+    struct Uncertain;
+    impl Problem for Uncertain {
+        type State = usize;
+
+        fn nb_variables(&self) -> usize {
+            3
+        }
+        fn initial_state(&self) -> Self::State {
+            0
+        }
+        fn initial_value(&self) -> isize {
+            0
+        }
+        fn transition(&self, state: &Self::State, d: Decision) -> Self::State {
+            state + d.value as usize
+        }
+        fn transition_cost(&self, _: &Self::State, _: &Self::State, d: Decision) -> isize {
+            3 * d.value // the nominal cost
+        }
+        fn next_variable(&self, depth: usize, _: &mut dyn Iterator<Item = &Self::State>) -> Option<Variable> {
+            if depth < self.nb_variables() {
+                Some(Variable(depth))
+            } else {
+                None
+            }
+        }
+        fn for_each_in_domain(&self, variable: Variable, _: &Self::State, f: &mut dyn DecisionCallback) {
+            f.apply(Decision { variable, value: 1 });
+            f.apply(Decision { variable, value: 0 });
+        }
+    }
+    impl RobustProblem for Uncertain {
+        fn transition_cost_interval(&self, _: &Self::State, _: &Self::State, d: Decision) -> (isize, isize) {
+            (d.value, 5 * d.value)
+        }
+    }
+
+    struct UncertainRelax;
+    impl Relaxation for UncertainRelax {
+        type State = usize;
+
+        fn merge(&self, states: &mut dyn Iterator<Item = &Self::State>) -> Self::State {
+            states.copied().max().unwrap()
+        }
+        fn relax(&self, _: &Self::State, _: &Self::State, _: &Self::State, _: Decision, cost: isize) -> isize {
+            cost
+        }
+    }
+
+    struct UncertainRanking;
+    impl StateRanking for UncertainRanking {
+        type State = usize;
+
+        fn compare(&self, a: &Self::State, b: &Self::State) -> std::cmp::Ordering {
+            a.cmp(b)
+        }
+    }
+
+    #[test]
+    fn the_adapters_pick_the_matching_end_of_the_cost_interval() {
+        let decision = Decision { variable: Variable(0), value: 1 };
+
+        let pessimistic = WorstCase::new(Uncertain);
+        assert_eq!(1, pessimistic.transition_cost(&0, &1, decision));
+
+        let optimistic = BestCase::new(Uncertain);
+        assert_eq!(5, optimistic.transition_cost(&0, &1, decision));
+    }
+
+    #[test]
+    fn the_other_methods_delegate_to_the_inner_problem() {
+        let pessimistic = WorstCase::new(Uncertain);
+
+        assert_eq!(3, pessimistic.nb_variables());
+        assert_eq!(0, pessimistic.initial_state());
+        assert_eq!(0, pessimistic.initial_value());
+        assert_eq!(1, pessimistic.transition(&0, Decision { variable: Variable(0), value: 1 }));
+        assert_eq!(Some(Variable(1)), pessimistic.next_variable(1, &mut std::iter::empty()));
+        assert_eq!(3, pessimistic.inner().nb_variables());
+    }
+
+    #[test]
+    fn solving_both_views_brackets_the_robust_optimum() {
+        let pessimistic = WorstCase::new(Uncertain);
+        let optimistic = BestCase::new(Uncertain);
+        let relax = UncertainRelax;
+        let ranking = UncertainRanking;
+        let cutoff = NoCutoff;
+        let width = NbUnassignedWidth(pessimistic.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SeqNoCachingSolverLel::new(
+            &pessimistic, &relax, &ranking, &width, &dominance, &cutoff, &mut fringe,
+        );
+        let robust = solver.maximize();
+
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SeqNoCachingSolverLel::new(
+            &optimistic, &relax, &ranking, &width, &dominance, &cutoff, &mut fringe,
+        );
+        let upper = solver.maximize();
+
+        // taking the 3 items is worth at least 3 and at most 15
+        assert_eq!(Some(3), robust.best_value);
+        assert_eq!(Some(15), upper.best_value);
+    }
+}
@@ -0,0 +1,172 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! This module provides a combinator to prune with the tighter of two rough
+//! upper bounds.
+
+use crate::{CompilationType, Decision, Relaxation};
+
+/// This combinator assembles two relaxations over the same state type into
+/// one: the merging scheme (`merge`, `relax` and the merge-group selection)
+/// is entirely that of the primary relaxation, but the rough upper bound is
+/// the minimum -- hence the tighter -- of the bounds computed by the two.
+/// This is useful when several cheap upper-bound estimators exist, none of
+/// which dominates the other on every state.
+///
+/// # Warning
+/// Both bounds must be valid over-approximations on their own: taking the
+/// minimum of a valid and an invalid bound yields an invalid bound.
+pub struct TightestBound<R1, R2> {
+    /// The primary relaxation: it drives the merging scheme and contributes
+    /// its rough upper bound
+    primary: R1,
+    /// The secondary relaxation: it only contributes its rough upper bound
+    secondary: R2,
+}
+
+impl<R1, R2> TightestBound<R1, R2>
+where
+    R1: Relaxation,
+    R2: Relaxation<State = R1::State>,
+{
+    /// Creates a relaxation which merges like `primary` and prunes with the
+    /// tighter of the two bounds
+    pub fn new(primary: R1, secondary: R2) -> Self {
+        Self { primary, secondary }
+    }
+    /// Returns a reference to the primary relaxation
+    pub fn primary(&self) -> &R1 {
+        &self.primary
+    }
+    /// Returns a reference to the secondary relaxation
+    pub fn secondary(&self) -> &R2 {
+        &self.secondary
+    }
+}
+
+impl<R1, R2> Relaxation for TightestBound<R1, R2>
+where
+    R1: Relaxation,
+    R2: Relaxation<State = R1::State>,
+{
+    type State = R1::State;
+
+    fn merge(&self, states: &mut dyn Iterator<Item = &Self::State>) -> Self::State {
+        self.primary.merge(states)
+    }
+    fn merge_opt(&self, states: &mut dyn Iterator<Item = &Self::State>) -> Option<Self::State> {
+        self.primary.merge_opt(states)
+    }
+    fn merge_opt_with_type(&self, comp_type: CompilationType, states: &mut dyn Iterator<Item = &Self::State>) -> Option<Self::State> {
+        self.primary.merge_opt_with_type(comp_type, states)
+    }
+    fn relax(&self, source: &Self::State, dest: &Self::State, new: &Self::State, decision: Decision, cost: isize) -> isize {
+        self.primary.relax(source, dest, new, decision, cost)
+    }
+    #[allow(clippy::too_many_arguments)]
+    fn relax_with_type(&self, comp_type: CompilationType, source: &Self::State, dest: &Self::State, new: &Self::State, decision: Decision, cost: isize) -> isize {
+        self.primary.relax_with_type(comp_type, source, dest, new, decision, cost)
+    }
+    fn fast_upper_bound(&self, state: &Self::State) -> isize {
+        self.primary
+            .fast_upper_bound(state)
+            .min(self.secondary.fast_upper_bound(state))
+    }
+    fn fast_upper_bound_with_value(&self, state: &Self::State, value: isize, depth: usize) -> isize {
+        self.primary
+            .fast_upper_bound_with_value(state, value, depth)
+            .min(self.secondary.fast_upper_bound_with_value(state, value, depth))
+    }
+    fn fast_upper_bound_with_solution(&self, state: &Self::State) -> (isize, Option<Vec<Decision>>) {
+        let (primary, solution) = self.primary.fast_upper_bound_with_solution(state);
+        let (secondary, fallback) = self.secondary.fast_upper_bound_with_solution(state);
+        // the tighter of the two bounds remains valid no matter which of the
+        // relaxations suggested the feasible completion: the solver validates
+        // the suggestion before using it anyway
+        (primary.min(secondary), solution.or(fallback))
+    }
+    fn fast_lower_bound(&self, state: &Self::State) -> isize {
+        // both estimates are achievable, so the higher one is the tighter
+        self.primary
+            .fast_lower_bound(state)
+            .max(self.secondary.fast_lower_bound(state))
+    }
+    fn select_merge_groups(&self, states: &[&Self::State], width: usize) -> Vec<Vec<usize>> {
+        self.primary.select_merge_groups(states, width)
+    }
+    fn select_representative(&self, group: &mut dyn Iterator<Item = (&Self::State, isize)>) -> usize {
+        self.primary.select_representative(group)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    /// A relaxation whose bound only depends on the state: used to check
+    /// that the combinator picks the tighter of the two bounds
+    struct StateBound;
+    impl Relaxation for StateBound {
+        type State = usize;
+
+        fn merge(&self, states: &mut dyn Iterator<Item = &Self::State>) -> Self::State {
+            states.copied().max().unwrap()
+        }
+        fn relax(&self, _: &Self::State, _: &Self::State, _: &Self::State, _: Decision, cost: isize) -> isize {
+            cost
+        }
+        fn fast_upper_bound(&self, state: &Self::State) -> isize {
+            *state as isize
+        }
+    }
+
+    /// A second estimator, tighter on the large states, looser on the small
+    /// ones
+    struct FlatBound;
+    impl Relaxation for FlatBound {
+        type State = usize;
+
+        fn merge(&self, states: &mut dyn Iterator<Item = &Self::State>) -> Self::State {
+            states.copied().min().unwrap()
+        }
+        fn relax(&self, _: &Self::State, _: &Self::State, _: &Self::State, _: Decision, cost: isize) -> isize {
+            cost + 1
+        }
+        fn fast_upper_bound(&self, _: &Self::State) -> isize {
+            10
+        }
+    }
+
+    #[test]
+    fn the_bound_is_the_minimum_of_the_two_estimates() {
+        let relax = TightestBound::new(StateBound, FlatBound);
+
+        assert_eq!(5, relax.fast_upper_bound(&5)); // primary is tighter
+        assert_eq!(10, relax.fast_upper_bound(&42)); // secondary is tighter
+    }
+
+    #[test]
+    fn the_merging_scheme_is_that_of_the_primary_relaxation() {
+        let relax = TightestBound::new(StateBound, FlatBound);
+        let decision = Decision { variable: Variable(0), value: 0 };
+
+        assert_eq!(7, relax.merge(&mut [3_usize, 7].iter()));
+        assert_eq!(2, relax.relax(&0, &1, &1, decision, 2));
+    }
+}
@@ -0,0 +1,369 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! This module provides a solver which automatically decides between the
+//! caching and the non-caching default engines based on the
+//! `supports_caching` hint of the problem being solved.
+
+use std::hash::Hash;
+
+use crate::{
+    Completion, Cutoff, Decision, DefaultCachingSolver, DefaultSolver, DominanceChecker, Fringe,
+    Problem, Relaxation, Solution, Solver, StateRanking, Variable, WidthHeuristic,
+};
+
+/// A solver which spares its user the choice between `DefaultSolver` and
+/// `DefaultCachingSolver`: it consults the `supports_caching` hint of the
+/// problem and instantiates the caching engine when (and only when) the
+/// problem claims that the barrier-based caching of thresholds is sound for
+/// its DP formulation. Apart from that initial decision, it behaves exactly
+/// like the engine it delegates to.
+pub enum AutoSolver<'a, State>
+where
+    State: Eq + PartialEq + Hash + Clone + Send + Sync,
+{
+    /// The default engine without a cache: this is what gets instantiated
+    /// when the problem does not claim support for caching
+    NoCaching(DefaultSolver<'a, State>),
+    /// The default engine with the threshold cache: this is what gets
+    /// instantiated when the problem claims support for caching
+    Caching(DefaultCachingSolver<'a, State>),
+}
+
+impl<'a, State> AutoSolver<'a, State>
+where
+    State: Eq + PartialEq + Hash + Clone + Send + Sync,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        problem: &'a (dyn Problem<State = State> + Send + Sync),
+        relaxation: &'a (dyn Relaxation<State = State> + Send + Sync),
+        ranking: &'a (dyn StateRanking<State = State> + Send + Sync),
+        width: &'a (dyn WidthHeuristic<State> + Send + Sync),
+        dominance: &'a (dyn DominanceChecker<State = State> + Send + Sync),
+        cutoff: &'a (dyn Cutoff + Send + Sync),
+        fringe: &'a mut (dyn Fringe<State = State> + Send + Sync),
+    ) -> Self {
+        if problem.supports_caching() {
+            Self::Caching(DefaultCachingSolver::new(
+                problem, relaxation, ranking, width, dominance, cutoff, fringe,
+            ))
+        } else {
+            Self::NoCaching(DefaultSolver::new(
+                problem, relaxation, ranking, width, dominance, cutoff, fringe,
+            ))
+        }
+    }
+
+    /// Returns the best solution together with the `transition_cost` of each
+    /// of its decisions, as reported by the engine this solver delegates to
+    pub fn best_solution_with_costs(&self) -> Option<Vec<(Decision, isize)>> {
+        match self {
+            Self::NoCaching(solver) => solver.best_solution_with_costs(),
+            Self::Caching(solver) => solver.best_solution_with_costs(),
+        }
+    }
+
+    /// Returns true iff the current best solution was extracted by the search
+    /// itself from a fully exact portion of a compiled DD, as opposed to a
+    /// primal injected from the outside (or no solution at all)
+    pub fn best_path_was_exact(&self) -> bool {
+        match self {
+            Self::NoCaching(solver) => solver.best_path_was_exact(),
+            Self::Caching(solver) => solver.best_path_was_exact(),
+        }
+    }
+
+    /// Returns a dirt-cheap estimate of the root upper bound, obtained by
+    /// evaluating `Relaxation::fast_upper_bound` on the initial state without
+    /// compiling any DD
+    pub fn root_fast_upper_bound(&self) -> isize {
+        match self {
+            Self::NoCaching(solver) => solver.root_fast_upper_bound(),
+            Self::Caching(solver) => solver.root_fast_upper_bound(),
+        }
+    }
+
+    /// Asks the underlying engine to record the per-(variable, value)
+    /// histogram of the DD edges compiled during the search (see
+    /// `variable_value_histogram`); the recording is off by default
+    pub fn with_value_histogram(self) -> Self {
+        match self {
+            Self::NoCaching(solver) => Self::NoCaching(solver.with_value_histogram()),
+            Self::Caching(solver) => Self::Caching(solver.with_value_histogram()),
+        }
+    }
+
+    /// Returns, for every (variable, value) pair, the number of DD edges which
+    /// assigned that value to that variable over all the compilations of the
+    /// search so far. Beware that this histogram reflects the *explored* space,
+    /// not the feasible region; it stays empty unless the solver was
+    /// configured with `with_value_histogram`
+    pub fn variable_value_histogram(&self) -> std::collections::HashMap<(Variable, isize), u64> {
+        match self {
+            Self::NoCaching(solver) => solver.variable_value_histogram(),
+            Self::Caching(solver) => solver.variable_value_histogram(),
+        }
+    }
+
+    /// Resets the underlying engine so that the next `maximize` starts from
+    /// a clean slate while reusing the allocations of the previous search
+    pub fn reset(&mut self) {
+        match self {
+            Self::NoCaching(solver) => solver.reset(),
+            Self::Caching(solver) => solver.reset(),
+        }
+    }
+}
+
+impl<State> Solver for AutoSolver<'_, State>
+where
+    State: Eq + PartialEq + Hash + Clone + Send + Sync,
+{
+    fn maximize(&mut self) -> Completion {
+        match self {
+            Self::NoCaching(solver) => solver.maximize(),
+            Self::Caching(solver) => solver.maximize(),
+        }
+    }
+
+    fn best_value(&self) -> Option<isize> {
+        match self {
+            Self::NoCaching(solver) => solver.best_value(),
+            Self::Caching(solver) => solver.best_value(),
+        }
+    }
+
+    fn best_solution(&self) -> Option<Solution> {
+        match self {
+            Self::NoCaching(solver) => solver.best_solution(),
+            Self::Caching(solver) => solver.best_solution(),
+        }
+    }
+
+    fn best_lower_bound(&self) -> isize {
+        match self {
+            Self::NoCaching(solver) => solver.best_lower_bound(),
+            Self::Caching(solver) => solver.best_lower_bound(),
+        }
+    }
+
+    fn best_upper_bound(&self) -> isize {
+        match self {
+            Self::NoCaching(solver) => solver.best_upper_bound(),
+            Self::Caching(solver) => solver.best_upper_bound(),
+        }
+    }
+
+    fn set_primal(&mut self, value: isize, solution: Solution) {
+        match self {
+            Self::NoCaching(solver) => solver.set_primal(value, solution),
+            Self::Caching(solver) => solver.set_primal(value, solution),
+        }
+    }
+
+    fn explored(&self) -> usize {
+        match self {
+            Self::NoCaching(solver) => solver.explored(),
+            Self::Caching(solver) => solver.explored(),
+        }
+    }
+}
+
+// ############################################################################
+// #### TESTS #################################################################
+// ############################################################################
+
+/// These tests validate that the `supports_caching` hint drives the choice of
+/// the engine and that the chosen engine solves the same tiny knapsack
+/// instance as the other solvers.
+#[cfg(test)]
+mod test_solver {
+    use crate::*;
+
+    #[test]
+    fn by_default_the_non_caching_engine_is_picked() {
+        let problem = knapsack(false);
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let solver = AutoSolver::new(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+
+        assert!(matches!(solver, AutoSolver::NoCaching(_)));
+    }
+
+    #[test]
+    fn the_hint_selects_the_caching_engine() {
+        let problem = knapsack(true);
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let solver = AutoSolver::new(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+
+        assert!(matches!(solver, AutoSolver::Caching(_)));
+    }
+
+    #[test]
+    fn the_chosen_engine_finds_the_optimum() {
+        for hint in [false, true] {
+            let problem = knapsack(hint);
+            let relax = KPRelax {pb: &problem};
+            let ranking = KPRanking;
+            let cutoff = NoCutoff;
+            let width = NbUnassignedWidth(problem.nb_variables());
+            let dominance = EmptyDominanceChecker::default();
+            let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+            let mut solver = AutoSolver::new(
+                &problem,
+                &relax,
+                &ranking,
+                &width,
+                &dominance,
+                &cutoff,
+                &mut fringe,
+            );
+
+            let maximized = solver.maximize();
+            assert!(maximized.is_exact);
+            assert_eq!(maximized.best_value, Some(220));
+        }
+    }
+
+    fn knapsack(cache_hint: bool) -> Knapsack {
+        Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30],
+            cache_hint,
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+    struct KnapsackState {
+        depth: usize,
+        capacity: usize
+    }
+
+    struct Knapsack {
+        capacity: usize,
+        profit: Vec<usize>,
+        weight: Vec<usize>,
+        /// Whether this model claims that caching is sound for it (it is:
+        /// the remaining capacity and the depth fully determine the residual
+        /// subproblem)
+        cache_hint: bool,
+    }
+
+    const TAKE_IT: isize = 1;
+    const LEAVE_IT_OUT: isize = 0;
+
+    impl Problem for Knapsack {
+        type State = KnapsackState;
+
+        fn nb_variables(&self) -> usize {
+            self.profit.len()
+        }
+        fn initial_state(&self) -> Self::State {
+            KnapsackState{ depth: 0, capacity: self.capacity }
+        }
+        fn initial_value(&self) -> isize {
+            0
+        }
+        fn transition(&self, state: &Self::State, dec: Decision) -> Self::State {
+            let mut ret = *state;
+            ret.depth += 1;
+            if dec.value == TAKE_IT {
+                ret.capacity -= self.weight[dec.variable.id()]
+            }
+            ret
+        }
+        fn transition_cost(&self, _state: &Self::State, _next: &Self::State, dec: Decision) -> isize {
+            self.profit[dec.variable.id()] as isize * dec.value
+        }
+        fn next_variable(&self, depth: usize, _: &mut dyn Iterator<Item = &Self::State>) -> Option<Variable> {
+            let n = self.nb_variables();
+            if depth < n {
+                Some(Variable(depth))
+            } else {
+                None
+            }
+        }
+        fn for_each_in_domain(&self, variable: Variable, state: &Self::State, f: &mut dyn DecisionCallback) {
+            if state.capacity >= self.weight[variable.id()] {
+                f.apply(Decision { variable, value: TAKE_IT });
+            }
+            f.apply(Decision { variable, value: LEAVE_IT_OUT });
+        }
+        fn supports_caching(&self) -> bool {
+            self.cache_hint
+        }
+    }
+
+    struct KPRelax<'a> {
+        pb: &'a Knapsack,
+    }
+    impl Relaxation for KPRelax<'_> {
+        type State = KnapsackState;
+
+        fn merge(&self, states: &mut dyn Iterator<Item = &Self::State>) -> Self::State {
+            states.max_by_key(|node| node.capacity).copied().unwrap()
+        }
+        fn relax(&self, _source: &Self::State, _dest: &Self::State, _merged: &Self::State, _decision: Decision, cost: isize) -> isize {
+            cost
+        }
+        fn fast_upper_bound(&self, state: &Self::State) -> isize {
+            let mut tot = 0;
+            for var in state.depth..self.pb.nb_variables() {
+                tot += self.pb.profit[var];
+            }
+            tot as isize
+        }
+    }
+
+    struct KPRanking;
+    impl StateRanking for KPRanking {
+        type State = KnapsackState;
+
+        fn compare(&self, a: &Self::State, b: &Self::State) -> std::cmp::Ordering {
+            a.capacity.cmp(&b.capacity)
+        }
+    }
+}
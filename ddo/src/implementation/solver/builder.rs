@@ -0,0 +1,333 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! This module provides a builder which assembles a solver from named parts
+//! instead of a long list of positional constructor arguments.
+
+use std::hash::Hash;
+
+use crate::{
+    AutoSolver, Cutoff, DefaultCachingSolver, DefaultSolver, DominanceChecker, Fringe, Problem,
+    Relaxation, StateRanking, WidthHeuristic,
+};
+
+/// This builder assembles a solver from named parts, which spares the user
+/// the transposition hazards of the eight positional arguments of the
+/// `custom` constructors. Every part except the number of threads is
+/// required: `build` checks their presence and panics with an explicit
+/// message naming the missing part when the configuration is incomplete.
+/// The built solver is an `AutoSolver`: it instantiates the caching engine
+/// when (and only when) the problem claims support for caching.
+///
+/// # Example
+/// ```compile_fail
+/// let mut solver = SolverBuilder::new()
+///     .problem(&problem)
+///     .relaxation(&relaxation)
+///     .ranking(&heuristic)
+///     .width(&width)
+///     .dominance(&dominance)
+///     .cutoff(&cutoff)
+///     .fringe(&mut fringe)
+///     .build();
+/// ```
+pub struct SolverBuilder<'a, State>
+where
+    State: Eq + PartialEq + Hash + Clone + Send + Sync,
+{
+    problem: Option<&'a (dyn Problem<State = State> + Send + Sync)>,
+    relaxation: Option<&'a (dyn Relaxation<State = State> + Send + Sync)>,
+    ranking: Option<&'a (dyn StateRanking<State = State> + Send + Sync)>,
+    width: Option<&'a (dyn WidthHeuristic<State> + Send + Sync)>,
+    dominance: Option<&'a (dyn DominanceChecker<State = State> + Send + Sync)>,
+    cutoff: Option<&'a (dyn Cutoff + Send + Sync)>,
+    fringe: Option<&'a mut (dyn Fringe<State = State> + Send + Sync)>,
+    nb_threads: Option<usize>,
+}
+
+impl<'a, State> Default for SolverBuilder<'a, State>
+where
+    State: Eq + PartialEq + Hash + Clone + Send + Sync,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, State> SolverBuilder<'a, State>
+where
+    State: Eq + PartialEq + Hash + Clone + Send + Sync,
+{
+    /// Creates a new builder with no part configured yet
+    pub fn new() -> Self {
+        Self {
+            problem: None,
+            relaxation: None,
+            ranking: None,
+            width: None,
+            dominance: None,
+            cutoff: None,
+            fringe: None,
+            nb_threads: None,
+        }
+    }
+    /// Sets the problem which must be maximized
+    pub fn problem(mut self, problem: &'a (dyn Problem<State = State> + Send + Sync)) -> Self {
+        self.problem = Some(problem);
+        self
+    }
+    /// Sets the relaxation used when a DD layer grows too large
+    pub fn relaxation(mut self, relaxation: &'a (dyn Relaxation<State = State> + Send + Sync)) -> Self {
+        self.relaxation = Some(relaxation);
+        self
+    }
+    /// Sets the ranking heuristic discriminating the most promising states
+    pub fn ranking(mut self, ranking: &'a (dyn StateRanking<State = State> + Send + Sync)) -> Self {
+        self.ranking = Some(ranking);
+        self
+    }
+    /// Sets the maximum width heuristic of the compiled DDs
+    pub fn width(mut self, width: &'a (dyn WidthHeuristic<State> + Send + Sync)) -> Self {
+        self.width = Some(width);
+        self
+    }
+    /// Sets the dominance checker used to prune the search
+    pub fn dominance(mut self, dominance: &'a (dyn DominanceChecker<State = State> + Send + Sync)) -> Self {
+        self.dominance = Some(dominance);
+        self
+    }
+    /// Sets the cutoff deciding when to stop trying to solve the problem
+    pub fn cutoff(mut self, cutoff: &'a (dyn Cutoff + Send + Sync)) -> Self {
+        self.cutoff = Some(cutoff);
+        self
+    }
+    /// Sets the fringe holding the subproblems that remain to be explored
+    pub fn fringe(mut self, fringe: &'a mut (dyn Fringe<State = State> + Send + Sync)) -> Self {
+        self.fringe = Some(fringe);
+        self
+    }
+    /// Sets the number of threads used by the solver (one thread per
+    /// available core when left unconfigured)
+    pub fn threads(mut self, nb_threads: usize) -> Self {
+        self.nb_threads = Some(nb_threads);
+        self
+    }
+    /// Builds the solver from the configured parts.
+    ///
+    /// # Panics
+    /// This method panics -- with a message naming the missing part -- when
+    /// any of the required parts (everything but the number of threads) has
+    /// not been configured.
+    pub fn build(self) -> AutoSolver<'a, State> {
+        let problem = self.problem.expect("SolverBuilder: no problem was configured");
+        let relaxation = self.relaxation.expect("SolverBuilder: no relaxation was configured");
+        let ranking = self.ranking.expect("SolverBuilder: no ranking was configured");
+        let width = self.width.expect("SolverBuilder: no width heuristic was configured");
+        let dominance = self.dominance.expect("SolverBuilder: no dominance checker was configured");
+        let cutoff = self.cutoff.expect("SolverBuilder: no cutoff was configured");
+        let fringe = self.fringe.expect("SolverBuilder: no fringe was configured");
+        let nb_threads = self.nb_threads.unwrap_or_else(num_cpus::get);
+
+        if problem.supports_caching() {
+            AutoSolver::Caching(DefaultCachingSolver::custom(
+                problem, relaxation, ranking, width, dominance, cutoff, fringe, nb_threads,
+            ))
+        } else {
+            AutoSolver::NoCaching(DefaultSolver::custom(
+                problem, relaxation, ranking, width, dominance, cutoff, fringe, nb_threads,
+            ))
+        }
+    }
+}
+
+// ############################################################################
+// #### TESTS #################################################################
+// ############################################################################
+
+/// These tests validate that the builder assembles a working solver, that the
+/// `supports_caching` hint still drives the choice of the engine, and that an
+/// incomplete configuration is rejected with an explicit message.
+#[cfg(test)]
+mod test_builder {
+    use crate::*;
+
+    #[test]
+    fn the_built_solver_finds_the_optimum() {
+        let problem = knapsack(false);
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SolverBuilder::new()
+            .problem(&problem)
+            .relaxation(&relax)
+            .ranking(&ranking)
+            .width(&width)
+            .dominance(&dominance)
+            .cutoff(&cutoff)
+            .fringe(&mut fringe)
+            .threads(1)
+            .build();
+
+        let maximized = solver.maximize();
+        assert!(maximized.is_exact);
+        assert_eq!(maximized.best_value, Some(220));
+    }
+
+    #[test]
+    fn the_caching_hint_selects_the_caching_engine() {
+        let problem = knapsack(true);
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let solver = SolverBuilder::new()
+            .problem(&problem)
+            .relaxation(&relax)
+            .ranking(&ranking)
+            .width(&width)
+            .dominance(&dominance)
+            .cutoff(&cutoff)
+            .fringe(&mut fringe)
+            .build();
+
+        assert!(matches!(solver, AutoSolver::Caching(_)));
+    }
+
+    #[test]
+    #[should_panic(expected = "no cutoff was configured")]
+    fn an_incomplete_configuration_is_rejected() {
+        let problem = knapsack(false);
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let _solver = SolverBuilder::new()
+            .problem(&problem)
+            .relaxation(&relax)
+            .ranking(&ranking)
+            .width(&width)
+            .dominance(&dominance)
+            .fringe(&mut fringe)
+            .build();
+    }
+
+    fn knapsack(cache_hint: bool) -> Knapsack {
+        Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30],
+            cache_hint,
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+    struct KnapsackState {
+        depth: usize,
+        capacity: usize
+    }
+
+    struct Knapsack {
+        capacity: usize,
+        profit: Vec<usize>,
+        weight: Vec<usize>,
+        /// Whether this model claims that caching is sound for it
+        cache_hint: bool,
+    }
+
+    const TAKE_IT: isize = 1;
+    const LEAVE_IT_OUT: isize = 0;
+
+    impl Problem for Knapsack {
+        type State = KnapsackState;
+
+        fn nb_variables(&self) -> usize {
+            self.profit.len()
+        }
+        fn initial_state(&self) -> Self::State {
+            KnapsackState{ depth: 0, capacity: self.capacity }
+        }
+        fn initial_value(&self) -> isize {
+            0
+        }
+        fn transition(&self, state: &Self::State, dec: Decision) -> Self::State {
+            let mut ret = *state;
+            ret.depth += 1;
+            if dec.value == TAKE_IT {
+                ret.capacity -= self.weight[dec.variable.id()]
+            }
+            ret
+        }
+        fn transition_cost(&self, _state: &Self::State, _next: &Self::State, dec: Decision) -> isize {
+            self.profit[dec.variable.id()] as isize * dec.value
+        }
+        fn next_variable(&self, depth: usize, _: &mut dyn Iterator<Item = &Self::State>) -> Option<Variable> {
+            let n = self.nb_variables();
+            if depth < n {
+                Some(Variable(depth))
+            } else {
+                None
+            }
+        }
+        fn for_each_in_domain(&self, variable: Variable, state: &Self::State, f: &mut dyn DecisionCallback) {
+            if state.capacity >= self.weight[variable.id()] {
+                f.apply(Decision { variable, value: TAKE_IT });
+            }
+            f.apply(Decision { variable, value: LEAVE_IT_OUT });
+        }
+        fn supports_caching(&self) -> bool {
+            self.cache_hint
+        }
+    }
+
+    struct KPRelax<'a> {
+        pb: &'a Knapsack,
+    }
+    impl Relaxation for KPRelax<'_> {
+        type State = KnapsackState;
+
+        fn merge(&self, states: &mut dyn Iterator<Item = &Self::State>) -> Self::State {
+            states.max_by_key(|node| node.capacity).copied().unwrap()
+        }
+        fn relax(&self, _source: &Self::State, _dest: &Self::State, _merged: &Self::State, _decision: Decision, cost: isize) -> isize {
+            cost
+        }
+        fn fast_upper_bound(&self, state: &Self::State) -> isize {
+            let mut tot = 0;
+            for var in state.depth..self.pb.nb_variables() {
+                tot += self.pb.profit[var];
+            }
+            tot as isize
+        }
+    }
+
+    struct KPRanking;
+    impl StateRanking for KPRanking {
+        type State = KnapsackState;
+
+        fn compare(&self, a: &Self::State, b: &Self::State) -> std::cmp::Ordering {
+            a.capacity.cmp(&b.capacity)
+        }
+    }
+}
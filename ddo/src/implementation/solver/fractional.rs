@@ -0,0 +1,481 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! This module provides the branch-and-bound MDD solver for the models whose
+//! objective values are of a generic `Objective` type (see the
+//! `ObjectiveProblem` and `ObjectiveRelaxation` traits) rather than `isize`.
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use fxhash::FxHashMap;
+
+use crate::{Decision, Objective, ObjectiveProblem, ObjectiveRelaxation};
+
+/// The arcs entering one node of the layer under construction: the index of
+/// the parent in the previous layer, the decision labeling the arc and its
+/// cost
+type InArcs<O> = Vec<(usize, Decision, O)>;
+
+/// A node left open by the branch-and-bound: the root of a subproblem which
+/// remains to be explored
+struct OpenNode<State, O> {
+    /// The root state of the subproblem
+    state: Arc<State>,
+    /// The objective value accumulated along the path to this state
+    value: O,
+    /// The decisions taken along that path
+    path: Vec<Decision>,
+    /// An upper bound on the objective reachable in this subproblem
+    ub: O,
+    /// The number of variables assigned along the path
+    depth: usize,
+}
+
+impl<State, O: Objective> PartialEq for OpenNode<State, O> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+impl<State, O: Objective> Eq for OpenNode<State, O> {}
+impl<State, O: Objective> PartialOrd for OpenNode<State, O> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl<State, O: Objective> Ord for OpenNode<State, O> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // the fringe pops the subproblem with the highest upper bound first
+        self.ub.cmp(&other.ub)
+            .then_with(|| self.value.cmp(&other.value))
+            .then_with(|| self.depth.cmp(&other.depth))
+    }
+}
+
+/// A node of a DD being compiled
+struct Node<State, O> {
+    /// The state this node stands for
+    state: Arc<State>,
+    /// The value of the longest path from the root to this node
+    value: O,
+    /// The decisions along that longest path (from the root of the problem)
+    path: Vec<Decision>,
+    /// True iff every path to this node traverses exact nodes only: the
+    /// value of an exact terminal is the value of an actual solution
+    exact: bool,
+}
+
+/// The outcome of the compilation of one DD
+struct CompiledDd<State, O> {
+    /// The value of the best terminal node (an upper bound on the subproblem
+    /// optimum when the DD is relaxed), if any terminal was reached
+    best_value: Option<O>,
+    /// The value and path of the best *exact* terminal node: a feasible
+    /// solution which may be installed as the incumbent
+    best_exact: Option<(O, Vec<Decision>)>,
+    /// True iff no node was ever dropped or merged: the DD solves the
+    /// subproblem exactly
+    is_exact: bool,
+    /// The last exact layer of a non-exact relaxed DD: the open nodes which
+    /// must be enqueued to keep the search exhaustive
+    cutset: Vec<Node<State, O>>,
+    /// The number of variables assigned at the nodes of the cutset
+    cutset_depth: usize,
+}
+
+/// The outcome of a call to `ObjectiveSolver::maximize`: the counterpart of
+/// `Completion` for a generic objective type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ObjectiveCompletion<O> {
+    /// True iff the search was carried to the end without being cut off
+    pub is_exact: bool,
+    /// The best value found, if at all
+    pub best_value: Option<O>,
+}
+
+/// This is the branch-and-bound MDD solver for the models expressed over a
+/// generic `Objective` type (e.g. `OrderedFloat<f64>` for genuinely
+/// fractional costs, with the `fractional` cargo feature): the objective
+/// values are accumulated, bounded and reported in that very type, so no
+/// precision is ever lost to a fixed-point encoding. The algorithm is the
+/// classical one: subproblems are popped from a fringe ordered by upper
+/// bound, each one is dived into with a restricted DD (to find incumbents)
+/// then bounded with a relaxed DD whose last exact layer re-feeds the fringe.
+///
+/// This engine deliberately trades the bells and whistles of the `isize`
+/// solvers (caching, dominance, parallelism, custom heuristics, ...) for
+/// genericity: the maximum width is a plain constant and the nodes are
+/// ranked by their objective value.
+pub struct ObjectiveSolver<'a, P, R>
+where
+    P: ObjectiveProblem,
+    R: ObjectiveRelaxation<State = P::State, Objective = P::Objective>,
+    P::State: Eq + Hash,
+{
+    /// A reference to the problem being maximized
+    problem: &'a P,
+    /// The relaxation used when a DD layer grows too large
+    relaxation: &'a R,
+    /// The maximum number of nodes any DD layer may comprise
+    max_width: usize,
+    /// The value of the best solution found so far, if any
+    best_value: Option<P::Objective>,
+    /// The decisions of the best solution found so far, if any
+    best_sol: Option<Vec<Decision>>,
+    /// The subproblems which remain to be explored, ordered by upper bound
+    fringe: BinaryHeap<OpenNode<P::State, P::Objective>>,
+}
+
+impl<'a, P, R> ObjectiveSolver<'a, P, R>
+where
+    P: ObjectiveProblem,
+    R: ObjectiveRelaxation<State = P::State, Objective = P::Objective>,
+    P::State: Eq + Hash,
+{
+    /// Creates a solver maximizing the given problem with DDs of (at most)
+    /// the given maximum width
+    pub fn new(problem: &'a P, relaxation: &'a R, max_width: usize) -> Self {
+        Self {
+            problem,
+            relaxation,
+            max_width: max_width.max(1),
+            best_value: None,
+            best_sol: None,
+            fringe: BinaryHeap::new(),
+        }
+    }
+
+    /// Returns the value of the best solution found so far, if any
+    pub fn best_value(&self) -> Option<P::Objective> {
+        self.best_value
+    }
+
+    /// Returns the decisions of the best solution found so far, if any
+    pub fn best_solution(&self) -> Option<Vec<Decision>> {
+        self.best_sol.clone()
+    }
+
+    /// Runs the branch-and-bound to completion and returns the outcome
+    pub fn maximize(&mut self) -> ObjectiveCompletion<P::Objective> {
+        let state = self.problem.initial_state();
+        let value = self.problem.initial_value();
+        let ub = value.saturating_add(self.relaxation.fast_upper_bound(&state));
+        self.fringe.push(OpenNode {
+            state: Arc::new(state),
+            value,
+            path: vec![],
+            ub,
+            depth: 0,
+        });
+
+        while let Some(node) = self.fringe.pop() {
+            // the fringe is ordered by upper bound: once the most promising
+            // open subproblem cannot improve on the incumbent, none can
+            if node.ub <= self.best_lb() {
+                break;
+            }
+
+            // 1. RESTRICTION
+            let restricted = self.compile(false, &node);
+            self.maybe_update_best(restricted.best_exact);
+            if restricted.is_exact {
+                continue;
+            }
+
+            // 2. RELAXATION
+            let relaxed = self.compile(true, &node);
+            let bound = relaxed.best_value.unwrap_or_else(P::Objective::min_value);
+            self.maybe_update_best(relaxed.best_exact);
+            if relaxed.is_exact || bound <= self.best_lb() {
+                continue;
+            }
+            for cut in relaxed.cutset {
+                let rub = cut.value.saturating_add(self.relaxation.fast_upper_bound(&cut.state));
+                let ub = bound.min(rub);
+                if ub > self.best_lb() {
+                    self.fringe.push(OpenNode {
+                        state: cut.state,
+                        value: cut.value,
+                        path: cut.path,
+                        ub,
+                        depth: relaxed.cutset_depth,
+                    });
+                }
+            }
+        }
+        self.fringe.clear();
+
+        ObjectiveCompletion {
+            is_exact: true,
+            best_value: self.best_value,
+        }
+    }
+
+    /// The value any candidate solution must strictly improve upon
+    fn best_lb(&self) -> P::Objective {
+        self.best_value.unwrap_or_else(P::Objective::min_value)
+    }
+
+    /// Installs the given feasible solution as the incumbent if it improves
+    /// on the best known one
+    fn maybe_update_best(&mut self, candidate: Option<(P::Objective, Vec<Decision>)>) {
+        if let Some((value, path)) = candidate {
+            if value > self.best_lb() {
+                self.best_value = Some(value);
+                self.best_sol = Some(path);
+            }
+        }
+    }
+
+    /// Compiles one DD (restricted or relaxed) for the subproblem rooted in
+    /// the given open node
+    fn compile(&self, relaxed: bool, root: &OpenNode<P::State, P::Objective>) -> CompiledDd<P::State, P::Objective> {
+        let mut layer = vec![Node {
+            state: root.state.clone(),
+            value: root.value,
+            path: root.path.clone(),
+            exact: true,
+        }];
+        let mut cutset = vec![];
+        let mut cutset_depth = root.depth;
+        let mut is_exact = true;
+        let mut depth = root.depth;
+
+        while let Some(var) = self.problem.next_variable(depth, &mut layer.iter().map(|node| node.state.as_ref())) {
+            // 1. expansion: group the outgoing arcs by the state they reach
+            let mut groups: FxHashMap<Arc<P::State>, InArcs<P::Objective>> = Default::default();
+            for (parent, node) in layer.iter().enumerate() {
+                self.problem.for_each_in_domain(var, &node.state, &mut |decision: Decision| {
+                    let child = self.problem.transition(&node.state, decision);
+                    let cost = self.problem.transition_cost(&node.state, &child, decision);
+                    groups.entry(Arc::new(child)).or_default().push((parent, decision, cost));
+                });
+            }
+            if groups.is_empty() {
+                // every node of the layer is a dead end
+                return CompiledDd { best_value: None, best_exact: None, is_exact, cutset, cutset_depth };
+            }
+
+            // 2. deduplication: one node per distinct state, valued by its
+            //    longest incoming path
+            let mut next = groups.into_iter()
+                .map(|(state, arcs)| {
+                    let mut value = P::Objective::min_value();
+                    let mut best = 0;
+                    let mut exact = true;
+                    for (k, (parent, _, cost)) in arcs.iter().enumerate() {
+                        let total = layer[*parent].value.saturating_add(*cost);
+                        exact &= layer[*parent].exact;
+                        if total > value {
+                            value = total;
+                            best = k;
+                        }
+                    }
+                    let (parent, decision, _) = arcs[best];
+                    let mut path = layer[parent].path.clone();
+                    path.push(decision);
+                    (Node { state, value, path, exact }, arcs)
+                })
+                .collect::<Vec<_>>();
+
+            // 3. width enforcement: drop (restriction) or merge (relaxation)
+            //    the least promising surplus nodes
+            if next.len() > self.max_width {
+                next.sort_unstable_by_key(|node| std::cmp::Reverse(node.0.value));
+                if relaxed && is_exact {
+                    // the parents form the last exact layer of this DD
+                    cutset = layer.iter()
+                        .map(|node| Node {
+                            state: node.state.clone(),
+                            value: node.value,
+                            path: node.path.clone(),
+                            exact: node.exact,
+                        })
+                        .collect();
+                    cutset_depth = depth;
+                }
+                is_exact = false;
+                if relaxed {
+                    let kept = (self.max_width - 1).max(1);
+                    let surplus = next.split_off(kept);
+                    let merged = Arc::new(self.relaxation.merge(&mut surplus.iter().map(|(node, _)| node.state.as_ref())));
+                    // the merged node inherits the best of the relaxed arcs
+                    let mut value = P::Objective::min_value();
+                    let mut path = vec![];
+                    for (node, arcs) in surplus.iter() {
+                        for (parent, decision, cost) in arcs.iter() {
+                            let relaxed_cost = self.relaxation.relax(&layer[*parent].state, &node.state, &merged, *decision, *cost);
+                            let total = layer[*parent].value.saturating_add(relaxed_cost);
+                            if total > value || path.is_empty() {
+                                value = total;
+                                path = layer[*parent].path.clone();
+                                path.push(*decision);
+                            }
+                        }
+                    }
+                    next.push((Node { state: merged, value, path, exact: false }, vec![]));
+                } else {
+                    next.truncate(self.max_width);
+                }
+            }
+
+            layer = next.into_iter().map(|(node, _)| node).collect();
+            depth += 1;
+        }
+
+        // 4. the terminal layer yields the bound and the candidate incumbent
+        let best_value = layer.iter().map(|node| node.value).max();
+        let best_exact = layer.iter()
+            .filter(|node| node.exact)
+            .max_by(|a, b| a.value.cmp(&b.value))
+            .map(|node| (node.value, node.path.clone()));
+        CompiledDd { best_value, best_exact, is_exact, cutset, cutset_depth }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    /// The knapsack fixture used throughout the solver tests, this time
+    /// expressed over a generic objective type
+    struct Knapsack<O> {
+        capacity: usize,
+        profit: Vec<O>,
+        weight: Vec<usize>,
+    }
+    impl<O: Objective> ObjectiveProblem for Knapsack<O> {
+        type State = usize;
+        type Objective = O;
+
+        fn nb_variables(&self) -> usize {
+            self.profit.len()
+        }
+        fn initial_state(&self) -> Self::State {
+            self.capacity
+        }
+        fn initial_value(&self) -> Self::Objective {
+            O::zero()
+        }
+        fn transition(&self, state: &Self::State, decision: Decision) -> Self::State {
+            if decision.value == 1 {
+                state - self.weight[decision.variable.id()]
+            } else {
+                *state
+            }
+        }
+        fn transition_cost(&self, _: &Self::State, _: &Self::State, decision: Decision) -> Self::Objective {
+            if decision.value == 1 {
+                self.profit[decision.variable.id()]
+            } else {
+                O::zero()
+            }
+        }
+        fn next_variable(&self, depth: usize, _: &mut dyn Iterator<Item = &Self::State>) -> Option<Variable> {
+            if depth < self.nb_variables() {
+                Some(Variable(depth))
+            } else {
+                None
+            }
+        }
+        fn for_each_in_domain(&self, variable: Variable, state: &Self::State, f: &mut dyn DecisionCallback) {
+            if *state >= self.weight[variable.id()] {
+                f.apply(Decision { variable, value: 1 });
+            }
+            f.apply(Decision { variable, value: 0 });
+        }
+    }
+
+    /// The usual knapsack relaxation: merged sacks keep the largest
+    /// remaining capacity, and the rough bound takes every remaining item
+    struct KPRelax<'a, O> {
+        pb: &'a Knapsack<O>,
+    }
+    impl<O: Objective> ObjectiveRelaxation for KPRelax<'_, O> {
+        type State = usize;
+        type Objective = O;
+
+        fn merge(&self, states: &mut dyn Iterator<Item = &Self::State>) -> Self::State {
+            states.copied().max().unwrap()
+        }
+        fn relax(&self, _: &Self::State, _: &Self::State, _: &Self::State, _: Decision, cost: Self::Objective) -> Self::Objective {
+            cost
+        }
+        fn fast_upper_bound(&self, _state: &Self::State) -> Self::Objective {
+            self.pb.profit.iter().fold(O::zero(), |a, b| a.saturating_add(*b))
+        }
+    }
+
+    #[test]
+    fn the_generic_solver_reproduces_the_integral_optimum() {
+        let problem = Knapsack::<isize> {
+            capacity: 50,
+            profit: vec![60, 100, 120],
+            weight: vec![10, 20, 30],
+        };
+        let relax = KPRelax { pb: &problem };
+        // a width of 2 forces actual restrictions, relaxations and branching
+        let mut solver = ObjectiveSolver::new(&problem, &relax, 2);
+
+        let completion = solver.maximize();
+        assert!(completion.is_exact);
+        assert_eq!(Some(220), completion.best_value);
+
+        let mut solution = solver.best_solution().unwrap();
+        solution.sort_unstable_by_key(|d| d.variable.id());
+        assert_eq!(vec![0, 1, 1], solution.iter().map(|d| d.value).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn an_infeasible_problem_reports_no_value() {
+        let problem = Knapsack::<isize> {
+            capacity: 0,
+            profit: vec![60],
+            weight: vec![10],
+        };
+        let relax = KPRelax { pb: &problem };
+        let mut solver = ObjectiveSolver::new(&problem, &relax, 2);
+
+        // leaving the item out is always allowed: the optimum is 0, not None
+        let completion = solver.maximize();
+        assert_eq!(Some(0), completion.best_value);
+    }
+
+    #[cfg(feature = "fractional")]
+    #[test]
+    fn fractional_profits_are_maximized_without_any_precision_loss() {
+        use ordered_float::OrderedFloat;
+
+        // dyadic profits: their sums are exactly representable, so the
+        // optimum can be asserted with a strict equality
+        let problem = Knapsack::<OrderedFloat<f64>> {
+            capacity: 50,
+            profit: vec![OrderedFloat(60.5), OrderedFloat(100.25), OrderedFloat(120.125)],
+            weight: vec![10, 20, 30],
+        };
+        let relax = KPRelax { pb: &problem };
+        let mut solver = ObjectiveSolver::new(&problem, &relax, 2);
+
+        let completion = solver.maximize();
+        assert!(completion.is_exact);
+        assert_eq!(Some(OrderedFloat(220.375)), completion.best_value);
+    }
+}
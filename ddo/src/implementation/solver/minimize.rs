@@ -0,0 +1,240 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! This module provides the solver decorator used to solve minimization
+//! problems. The engine only ever maximizes: one models the problem in the
+//! natural sense, wraps the model in the `Minimized` / `MinimizedRelaxation`
+//! adapters, builds any solver over that wrapped model and finally decorates
+//! the solver with a `Minimizer`. The decorator un-negates every value and
+//! bound, so the results all come back in the original (minimization) sense.
+
+use crate::{Completion, Solution, Solver};
+
+/// This decorator presents the outcome of a maximization run over a
+/// `Minimized` problem in the original (minimization) sense: the best value
+/// is the least total cost, `best_lower_bound` is an optimistic bound (no
+/// solution can cost less) and `best_upper_bound` is the cost of the best
+/// solution found so far.
+pub struct Minimizer<S> {
+    /// The solver which maximizes the negated model
+    solver: S,
+}
+
+impl<S: Solver> Minimizer<S> {
+    /// Decorates the given solver, which must be solving a `Minimized` view
+    /// of the problem at hand
+    pub fn new(solver: S) -> Self {
+        Self { solver }
+    }
+
+    /// Returns a reference to the decorated solver
+    pub fn inner(&self) -> &S {
+        &self.solver
+    }
+
+    /// Solves the problem to (or as close as the cutoff allows to) the least
+    /// possible total cost. The returned completion carries the best value in
+    /// the original (minimization) sense.
+    pub fn minimize(&mut self) -> Completion {
+        let Completion { is_exact, best_value } = self.solver.maximize();
+        Completion {
+            is_exact,
+            best_value: best_value.map(isize::saturating_neg),
+        }
+    }
+
+    /// Returns the cost of the best (least cost) solution found so far, or
+    /// `None` when no solution has been found
+    pub fn best_value(&self) -> Option<isize> {
+        self.solver.best_value().map(isize::saturating_neg)
+    }
+
+    /// Returns the best (least cost) solution found so far, or `None` when no
+    /// solution has been found
+    pub fn best_solution(&self) -> Option<Solution> {
+        self.solver.best_solution()
+    }
+
+    /// Returns the tightest optimistic bound identified so far: no solution
+    /// can cost less than this
+    pub fn best_lower_bound(&self) -> isize {
+        self.solver.best_upper_bound().saturating_neg()
+    }
+
+    /// Returns the tightest pessimistic bound identified so far (the cost of
+    /// the incumbent, once one has been found)
+    pub fn best_upper_bound(&self) -> isize {
+        self.solver.best_lower_bound().saturating_neg()
+    }
+
+    /// Sets a primal (best known cost and solution) of the problem, expressed
+    /// in the original (minimization) sense
+    pub fn set_primal(&mut self, value: isize, solution: Solution) {
+        self.solver.set_primal(value.saturating_neg(), solution);
+    }
+
+    /// Computes the optimality gap
+    pub fn gap(&self) -> f32 {
+        self.solver.gap()
+    }
+
+    /// Returns the number of nodes that have been explored during the search
+    pub fn explored(&self) -> usize {
+        self.solver.explored()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    /// A dummy minimization problem: each of the three variables must pick
+    /// one of two modes, each with its own (positive) cost
+    struct ModeCosts;
+    const CHEAP: [isize; 3] = [1, 2, 3];
+    const DEAR : [isize; 3] = [4, 5, 6];
+
+    impl Problem for ModeCosts {
+        type State = usize;
+
+        fn nb_variables(&self) -> usize {
+            3
+        }
+        fn initial_state(&self) -> Self::State {
+            0
+        }
+        fn initial_value(&self) -> isize {
+            0
+        }
+        fn transition(&self, state: &Self::State, _: Decision) -> Self::State {
+            state + 1
+        }
+        fn transition_cost(&self, _: &Self::State, _: &Self::State, d: Decision) -> isize {
+            if d.value == 0 {
+                CHEAP[d.variable.id()]
+            } else {
+                DEAR[d.variable.id()]
+            }
+        }
+        fn next_variable(&self, depth: usize, _: &mut dyn Iterator<Item = &Self::State>) -> Option<Variable> {
+            if depth < self.nb_variables() {
+                Some(Variable(depth))
+            } else {
+                None
+            }
+        }
+        fn for_each_in_domain(&self, variable: Variable, _: &Self::State, f: &mut dyn DecisionCallback) {
+            f.apply(Decision { variable, value: 0 });
+            f.apply(Decision { variable, value: 1 });
+        }
+    }
+
+    /// The relaxation of the dummy problem, written in the minimization sense
+    struct ModeRelax;
+    impl Relaxation for ModeRelax {
+        type State = usize;
+
+        fn merge(&self, states: &mut dyn Iterator<Item = &Self::State>) -> Self::State {
+            states.copied().min().unwrap()
+        }
+        fn relax(&self, _: &Self::State, _: &Self::State, _: &Self::State, _: Decision, cost: isize) -> isize {
+            cost
+        }
+    }
+
+    struct ModeRanking;
+    impl StateRanking for ModeRanking {
+        type State = usize;
+
+        fn compare(&self, a: &Self::State, b: &Self::State) -> std::cmp::Ordering {
+            a.cmp(b)
+        }
+    }
+
+    #[test]
+    fn minimize_reports_the_least_cost_in_the_original_sense() {
+        let problem = Minimized::new(ModeCosts);
+        let relax = MinimizedRelaxation::new(ModeRelax);
+        let ranking = ModeRanking;
+        let cutoff = NoCutoff;
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+
+        let mut solver = Minimizer::new(SeqNoCachingSolverLel::new(
+            &problem, &relax, &ranking, &width, &dominance, &cutoff, &mut fringe,
+        ));
+
+        let Completion { is_exact, best_value } = solver.minimize();
+        // always picking the cheapest mode costs 1 + 2 + 3
+        assert!(is_exact);
+        assert_eq!(Some(6), best_value);
+        assert_eq!(Some(6), solver.best_value());
+        assert!(solver.best_solution().is_some());
+    }
+
+    #[test]
+    fn the_bounds_come_back_in_the_original_sense() {
+        let problem = Minimized::new(ModeCosts);
+        let relax = MinimizedRelaxation::new(ModeRelax);
+        let ranking = ModeRanking;
+        let cutoff = NoCutoff;
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+
+        let mut solver = Minimizer::new(SeqNoCachingSolverLel::new(
+            &problem, &relax, &ranking, &width, &dominance, &cutoff, &mut fringe,
+        ));
+
+        let _ = solver.minimize();
+        // once solved to optimality, both bounds coincide with the optimum
+        assert_eq!(6, solver.best_lower_bound());
+        assert_eq!(6, solver.best_upper_bound());
+        assert_eq!(0.0, solver.gap());
+    }
+
+    #[test]
+    fn a_primal_is_given_in_the_original_sense() {
+        let problem = Minimized::new(ModeCosts);
+        let relax = MinimizedRelaxation::new(ModeRelax);
+        let ranking = ModeRanking;
+        let cutoff = NoCutoff;
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+
+        let mut solver = Minimizer::new(SeqNoCachingSolverLel::new(
+            &problem, &relax, &ranking, &width, &dominance, &cutoff, &mut fringe,
+        ));
+
+        // an all-dear solution costing 4 + 5 + 6
+        solver.set_primal(15, Solution::new(vec![
+            Decision { variable: Variable(0), value: 1 },
+            Decision { variable: Variable(1), value: 1 },
+            Decision { variable: Variable(2), value: 1 },
+        ]));
+        assert_eq!(Some(15), solver.best_value());
+        assert_eq!(15, solver.best_upper_bound());
+
+        // solving improves on the primal
+        let Completion { best_value, .. } = solver.minimize();
+        assert_eq!(Some(6), best_value);
+    }
+}
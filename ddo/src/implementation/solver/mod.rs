@@ -20,10 +20,26 @@
 //! This module provide the solver implementation.
 mod parallel;
 mod sequential;
+mod restart;
+mod minimize;
+mod reporter;
+mod widening;
+mod peeling;
+mod auto;
+mod builder;
+mod fractional;
 pub use parallel::*;
 pub use sequential::*;
+pub use restart::*;
+pub use minimize::*;
+pub use reporter::*;
+pub use widening::*;
+pub use peeling::*;
+pub use auto::*;
+pub use builder::*;
+pub use fractional::*;
 
-use crate::{DefaultMDDLEL, EmptyCache, SimpleCache, DefaultMDDFC, Pooled};
+use crate::{DefaultMDDLEL, EmptyCache, SimpleCache, DefaultMDDFC, FlatMdd, Pooled};
 
 /// A type alias to emphasize that this is the solver that should be used by default.
 pub type DefaultSolver<'a, State>        = ParNoCachingSolverLel<'a, State>;
@@ -32,6 +48,8 @@ pub type DefaultCachingSolver<'a, State> = ParCachingSolverFc<'a, State>;
 pub type ParNoCachingSolverLel<'a, State>   = ParallelSolver<'a, State, DefaultMDDLEL<State>, EmptyCache<State>>;
 pub type ParNoCachingSolverFc<'a, State>    = ParallelSolver<'a, State, DefaultMDDFC<State>,  EmptyCache<State>>;
 pub type ParNoCachingSolverPooled<'a, State>= ParallelSolver<'a, State, Pooled<State>,        EmptyCache<State>>;
+// the flat mdd never exploits a cache, hence no caching alias is provided for it
+pub type ParNoCachingSolverFlat<'a, State>  = ParallelSolver<'a, State, FlatMdd<State>,       EmptyCache<State>>;
 
 pub type ParCachingSolverLel<'a, State>   = ParallelSolver<'a, State, DefaultMDDLEL<State>, SimpleCache<State>>;
 pub type ParCachingSolverFc<'a, State>    = ParallelSolver<'a, State, DefaultMDDFC<State>,  SimpleCache<State>>;
@@ -41,6 +59,7 @@ pub type ParCachingSolverPooled<'a, State>= ParallelSolver<'a, State, Pooled<Sta
 pub type SeqNoCachingSolverLel<'a, State>   = SequentialSolver<'a, State, DefaultMDDLEL<State>, EmptyCache<State>>;
 pub type SeqNoCachingSolverFc<'a, State>    = SequentialSolver<'a, State, DefaultMDDFC<State>,  EmptyCache<State>>;
 pub type SeqNoCachingSolverPooled<'a, State>= SequentialSolver<'a, State, Pooled<State>,        EmptyCache<State>>;
+pub type SeqNoCachingSolverFlat<'a, State>  = SequentialSolver<'a, State, FlatMdd<State>,       EmptyCache<State>>;
 
 pub type SeqCachingSolverLel<'a, State>   = SequentialSolver<'a, State, DefaultMDDLEL<State>, SimpleCache<State>>;
 pub type SeqCachingSolverFc<'a, State>    = SequentialSolver<'a, State, DefaultMDDFC<State>,  SimpleCache<State>>;
@@ -22,11 +22,87 @@
 //! By default, it uses as many threads as the number of hardware threads
 //! available on the machine.
 use std::clone::Clone;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
 use std::{marker::PhantomData, sync::Arc, hash::Hash};
 
 use parking_lot::{Condvar, Mutex};
 
-use crate::{Fringe, Decision, Problem, Relaxation, StateRanking, WidthHeuristic, Cutoff, SubProblem, DecisionDiagram, CompilationInput, CompilationType, Solver, Solution, Completion, Reason, Cache, DominanceChecker};
+use crate::{Fringe, Decision, Variable, Problem, Relaxation, StateRanking, WidthHeuristic, Cutoff, SubProblem, DecisionDiagram, CompilationInput, CompilationType, Solver, Solution, Completion, Reason, Cache, DominanceChecker, DominanceCheckResult, SolverStats, SearchReporter};
+
+/// A callback which is notified of every reported incumbent solution
+/// (see `with_on_incumbent`)
+type IncumbentCallback<'a> = Box<dyn FnMut(isize, &[Decision]) + Send + 'a>;
+
+/// The atomic counterpart of `SolverStats`: the workers update these counters
+/// without taking the critical lock, which keeps the accounting cheap even
+/// with many threads.
+#[derive(Default)]
+struct AtomicStats {
+    /// The number of restricted DDs which have been compiled
+    nb_restricted_dds: AtomicUsize,
+    /// The number of relaxed DDs which have been compiled
+    nb_relaxed_dds: AtomicUsize,
+    /// The total number of nodes developed over all the DD compilations
+    nb_nodes_expanded: AtomicUsize,
+    /// The number of subproblems which have been popped off the fringe
+    nb_fringe_pops: AtomicUsize,
+    /// The largest size the fringe has ever reached
+    peak_fringe_size: AtomicUsize,
+    /// The number of states discarded by a dominance check
+    nb_dominance_prunes: AtomicUsize,
+    /// The number of subproblems discarded because of a cache threshold
+    nb_cache_prunes: AtomicUsize,
+}
+
+/// A decorator which counts the nodes that the DDs report having developed
+/// during a compilation, while delegating the actual cutoff decision to the
+/// user-supplied criterion.
+struct CountingCutoff<'b> {
+    /// The criterion which effectively decides when to stop
+    inner: &'b (dyn Cutoff + Send + Sync),
+    /// The shared counter of developed nodes
+    expanded: &'b AtomicUsize,
+}
+impl Cutoff for CountingCutoff<'_> {
+    fn must_stop(&self) -> bool {
+        self.inner.must_stop()
+    }
+    fn add_expanded(&self, nb_nodes: usize) {
+        self.expanded.fetch_add(nb_nodes, Ordering::Relaxed);
+        self.inner.add_expanded(nb_nodes);
+    }
+}
+
+/// A decorator which counts the states pruned by the dominance checks, while
+/// delegating the actual checks to the user-supplied checker.
+struct CountingDominance<'b, State> {
+    /// The dominance checker which effectively performs the checks
+    inner: &'b (dyn DominanceChecker<State = State> + Send + Sync),
+    /// The shared counter of pruned states
+    pruned: &'b AtomicUsize,
+}
+impl<State> DominanceChecker for CountingDominance<'_, State> {
+    type State = State;
+
+    fn clear_layer(&self, depth: usize) {
+        self.inner.clear_layer(depth)
+    }
+    fn clear(&self) {
+        self.inner.clear()
+    }
+    fn is_dominated_or_insert(&self, state: Arc<Self::State>, depth: usize, value: isize) -> DominanceCheckResult {
+        let result = self.inner.is_dominated_or_insert(state, depth, value);
+        if result.dominated {
+            self.pruned.fetch_add(1, Ordering::Relaxed);
+        }
+        result
+    }
+    fn cmp(&self, a: &Self::State, val_a: isize, b: &Self::State, val_b: isize) -> std::cmp::Ordering {
+        self.inner.cmp(a, val_a, b, val_b)
+    }
+}
 
 /// The shared data that may only be manipulated within critical sections
 struct Critical<'a, State> {
@@ -60,13 +136,32 @@ struct Critical<'a, State> {
     ongoing_by_layer: Vec<usize>,
     /// This is the index of the first level above which there are no nodes in the fringe
     first_active_layer: usize,
+    /// This is the number of DDs which are currently being compiled; it is
+    /// only maintained when a limit on the concurrent compilations has been
+    /// configured (see `with_max_concurrent_dds`)
+    nb_compiling: usize,
     /// This is the value of the best known lower bound.
     best_lb: isize,
+    /// The best lower bound derived from the `fast_lower_bound` estimates of
+    /// the relaxation. It proves that a feasible completion of that value
+    /// exists but, unlike `best_lb`, it is not backed by a concrete solution:
+    /// it is only ever used for pruning (see `pruning_bound`) and never flows
+    /// into `best_value`.
+    rough_lb: isize,
     /// This is the value of the best known lower bound.
     /// *WARNING* This one only gets set when the interrupt condition is satisfied
     best_ub: isize,
+    /// This is the value of the last incumbent that has been reported; that is
+    /// the last incumbent which improved its predecessor by at least the
+    /// configured minimum improvement.
+    reported_lb: isize,
     /// If set, this keeps the info about the best solution so far.
-    best_sol: Option<Vec<Decision>>,
+    best_sol: Option<Solution>,
+    /// Whether the current incumbent was extracted by the search itself from
+    /// a fully exact (merge-free) portion of a compiled DD. This is false for
+    /// the solutions injected from the outside (via `set_primal` or a warm
+    /// start), whose provenance is unknown to the solver.
+    best_path_exact: bool,
     /// This vector is used to store the upper bound on the node which is
     /// currently processed by each thread.
     ///
@@ -78,6 +173,25 @@ struct Critical<'a, State> {
     /// If we decide not to go through a complete proof of optimality, this is
     /// the reason why we took that decision.
     abort_proof: Option<Reason>,
+    /// If set, a callback which gets notified of every reported incumbent.
+    /// It lives in the critical section so that the concurrent workers never
+    /// race when reporting their discoveries (see `with_on_incumbent`).
+    on_incumbent: Option<IncumbentCallback<'a>>,
+    /// If set, an observer which gets notified of the salient events of the
+    /// search. It lives in the critical section so that the concurrent
+    /// workers never race when reporting (see `with_reporter`).
+    reporter: Option<Box<dyn SearchReporter + Send + 'a>>,
+    /// If set, the time series of the incumbent bounds (see `with_bound_log`):
+    /// one `(elapsed, lower bound, upper bound)` sample per change of either
+    /// bound
+    bound_log: Option<Vec<(Duration, isize, isize)>>,
+    /// The instant against which the samples of the bound log are timed (the
+    /// start of the search)
+    bound_log_start: Instant,
+    /// The upper bound derived from the very first relaxed DD compiled for
+    /// the root subproblem. Unlike `best_ub`, it never gets tightened by the
+    /// branch-and-bound: it measures the quality of the relaxation alone.
+    root_ub: Option<isize>,
 }
 /// The state which is shared among the many running threads: it provides an
 /// access to the critical data (protected by a mutex) as well as a monitor
@@ -103,6 +217,27 @@ struct Shared<'a, State, C> where
     cache: C,
     dominance: &'a (dyn DominanceChecker<State = State> + Send + Sync),
 
+    /// The minimum improvement over the last reported incumbent which is
+    /// required before the solver acknowledges (reports) a new incumbent to
+    /// its observers. The true best value and solution are always tracked
+    /// regardless of this threshold: it only throttles the reporting cadence.
+    min_improvement: isize,
+    /// If set, the maximum number of incoming edges which may be retained on
+    /// a merged node of a relaxed dd (see `CompilationInput::max_in_degree`).
+    max_in_degree: Option<usize>,
+    /// When true, the workers process the subproblems one at a time, strictly
+    /// in the canonical fringe order (see `with_deterministic`).
+    deterministic: bool,
+    /// If set, the maximum number of DDs which may be compiled simultaneously
+    /// (see `with_max_concurrent_dds`).
+    max_concurrent_dds: Option<usize>,
+    /// If set, counts for every (variable, value) pair the number of DD edges
+    /// that assigned that value to that variable over all the compilations of
+    /// this search (see `with_value_histogram`). It lives behind its own lock
+    /// so that the workers never hold the critical section while merging
+    /// their locally-harvested counts.
+    value_histogram: Option<Mutex<HashMap<(Variable, isize), u64>>>,
+
     /// This is the shared state data which can only be accessed within critical
     /// sections. Therefore, it is protected by a mutex which prevents concurrent
     /// reads/writes.
@@ -111,6 +246,9 @@ struct Shared<'a, State, C> where
     /// The corollary, it that whenever a node has completed the processing of
     /// a subproblem, it must wake-up all parked threads waiting on this monitor.
     monitor: Condvar,
+    /// The work counters which have been accumulated so far (DDs compiled,
+    /// nodes expanded, fringe operations and prunings)
+    stats: AtomicStats,
 }
 /// The workload a thread can get from the shared state
 enum WorkLoad<T> {
@@ -294,6 +432,13 @@ where D: DecisionDiagram<State = State> + Default,
     /// will be spawned to solve the problem. By default, this number amounts
     /// to the number of hardware threads available on the machine.
     nb_threads: usize,
+    /// The number of threads (among `nb_threads`) that are dedicated to the
+    /// search for incumbents (restricted dives). The other threads are devoted
+    /// to the tightening of the dual bound and hence skip the compilation of
+    /// restricted DDs. When `None` (default), no splitting occurs and every
+    /// worker performs both phases; `Some(0)` genuinely devotes every worker
+    /// to the dual bound.
+    primal_threads: Option<usize>,
     /// This is just a marker that allows us to remember the exact type of the
     /// mdds to be instantiated.
     _phantom: PhantomData<D>, 
@@ -336,12 +481,21 @@ where
                 cutoff,
                 cache: C::default(),
                 dominance,
+                min_improvement: 0,
+                max_in_degree: None,
+                deterministic: false,
+                max_concurrent_dds: None,
+                value_histogram: None,
                 //
                 monitor: Condvar::new(),
+                stats: AtomicStats::default(),
                 critical: Mutex::new(Critical {
                     best_sol: None,
+                    best_path_exact: false,
                     best_lb: isize::MIN,
+                    rough_lb: isize::MIN,
                     best_ub: isize::MAX,
+                    reported_lb: isize::MIN,
                     upper_bounds: vec![isize::MAX; nb_threads],
                     fringe,
                     ongoing: 0,
@@ -349,10 +503,17 @@ where
                     open_by_layer: vec![0; problem.nb_variables() + 1],
                     ongoing_by_layer: vec![0; problem.nb_variables() + 1],
                     first_active_layer: 0,
+                    nb_compiling: 0,
                     abort_proof: None,
+                    on_incumbent: None,
+                    reporter: None,
+                    bound_log: None,
+                    bound_log_start: Instant::now(),
+                    root_ub: None,
                 }),
             },
             nb_threads,
+            primal_threads: None,
             _phantom: Default::default(),
         }
     }
@@ -362,6 +523,432 @@ where
         self
     }
 
+    /// Makes the resolution deterministic (or not). In deterministic mode,
+    /// the workers process the subproblems one at a time, strictly in the
+    /// canonical order imposed by the fringe: repeated runs then explore the
+    /// very same subproblems in the very same order and hence yield the exact
+    /// same `best_solution` tie-breaks and the exact same stats, regardless
+    /// of the number of threads. This obviously forfeits any parallel
+    /// speedup, so it is mostly useful to write reproducible (regression)
+    /// tests. The default is non-deterministic.
+    pub fn with_deterministic(mut self, deterministic: bool) -> Self {
+        self.shared.deterministic = deterministic;
+        self
+    }
+
+    /// Sets the minimum improvement over the last reported incumbent which is
+    /// required before the solver acknowledges a new incumbent to its
+    /// observers. This is useful in anytime settings where tiny improvements
+    /// would otherwise generate a lot of reporting churn. Note that this only
+    /// affects the reporting cadence: the solver keeps tracking the true best
+    /// value and solution internally so the search is left unchanged.
+    pub fn with_min_improvement(mut self, min_improvement: isize) -> Self {
+        self.shared.min_improvement = min_improvement;
+        self
+    }
+
+    /// Caps the number of incoming edges which may be retained on a merged
+    /// node of a relaxed dd (keeping the edges with the best value). This
+    /// keeps the relaxed DDs sparse on wide problems, but beware: dropping
+    /// incoming edges removes paths from the relaxed DD, so the bounds it
+    /// yields are no longer guaranteed to be valid upper bounds. Only use
+    /// this option when an approximate resolution is acceptable.
+    pub fn with_max_in_degree(mut self, max_in_degree: usize) -> Self {
+        self.shared.max_in_degree = Some(max_in_degree);
+        self
+    }
+
+    /// Bounds the number of DDs which may be compiled simultaneously: the
+    /// peak memory devoted to DDs then amounts to `max_concurrent_dds` times
+    /// the size of one DD rather than `nb_threads` times. A worker which
+    /// wants to compile a DD while the limit is reached parks until one of
+    /// the ongoing compilations completes, effectively trading parallelism
+    /// for a predictable memory ceiling.
+    ///
+    /// This option interacts with `with_nb_threads` as follows: it only ever
+    /// has an effect when the limit is smaller than the number of threads
+    /// (a limit of `nb_threads` or more changes nothing since no more than
+    /// one DD is ever compiled per thread). A limit of zero is treated as
+    /// one, as no DD could ever be compiled otherwise.
+    pub fn with_max_concurrent_dds(mut self, max_concurrent_dds: usize) -> Self {
+        self.shared.max_concurrent_dds = Some(max_concurrent_dds.max(1));
+        self
+    }
+
+    /// Resets this solver so that the next call to `maximize` starts from a
+    /// clean slate while reusing the allocations of the previous search. This
+    /// is meant for processes which solve many instances in a row: it empties
+    /// the fringe, the threshold cache and the dominance store, forgets the
+    /// incumbent solution and both bounds, and zeroes the exploration
+    /// counters and statistics. The configuration of the solver -- number of
+    /// threads, callbacks and heuristics -- is left untouched.
+    pub fn reset(&mut self) {
+        self.shared.cache.clear();
+        self.shared.dominance.clear();
+        self.shared.stats = AtomicStats::default();
+        if let Some(histogram) = self.shared.value_histogram.as_ref() { histogram.lock().clear(); }
+
+        let mut critical = self.shared.critical.lock();
+        critical.fringe.clear();
+        critical.ongoing = 0;
+        critical.explored = 0;
+        critical.open_by_layer.iter_mut().for_each(|x| *x = 0);
+        critical.ongoing_by_layer.iter_mut().for_each(|x| *x = 0);
+        critical.first_active_layer = 0;
+        critical.nb_compiling = 0;
+        critical.best_lb = isize::MIN;
+        critical.rough_lb = isize::MIN;
+        critical.best_ub = isize::MAX;
+        critical.reported_lb = isize::MIN;
+        critical.best_sol = None;
+        critical.best_path_exact = false;
+        if let Some(log) = critical.bound_log.as_mut() { log.clear(); }
+        critical.root_ub = None;
+        critical.upper_bounds.iter_mut().for_each(|x| *x = isize::MAX);
+        critical.abort_proof = None;
+    }
+
+    /// This method returns a dirt-cheap estimate of the root upper bound:
+    /// it merely evaluates `Relaxation::fast_upper_bound` on the initial
+    /// state (on top of the initial value), without compiling any DD. It is
+    /// a convenient way to probe the quality of a relaxation before
+    /// committing to a long solve.
+    pub fn root_fast_upper_bound(&self) -> isize {
+        let problem = self.shared.problem;
+        problem.initial_value()
+            .saturating_add(self.shared.relaxation.fast_upper_bound(&problem.initial_state()))
+    }
+
+    /// Returns true iff the current best solution was extracted by the search
+    /// itself from a fully exact portion of a compiled DD (no merged node on
+    /// its path). This is how the engine always admits its own incumbents, so
+    /// the only ways to get `false` with a solution installed are a primal
+    /// injected from the outside (`set_primal`, warm start) whose provenance
+    /// the solver cannot vouch for. When no solution is known at all, this
+    /// returns false.
+    pub fn best_path_was_exact(&self) -> bool {
+        let critical = self.shared.critical.lock();
+        critical.best_sol.is_some() && critical.best_path_exact
+    }
+
+    /// Returns, for every (variable, value) pair, the number of DD edges
+    /// which assigned that value to that variable over all the compilations
+    /// of the search so far -- a crude per-variable marginal. A variable
+    /// which only ever shows up with a single value is effectively fixed
+    /// over the explored space and could be preprocessed out of the model.
+    ///
+    /// # Warning
+    /// The histogram reflects the *explored* space, not the feasible region:
+    /// restriction and pruning skew the counts towards the promising
+    /// assignments, and the edges of a relaxed DD may even lie on no feasible
+    /// path at all. Treat it as a profiling aid, not as an exact count. The
+    /// histogram stays empty unless the solver was configured with
+    /// `with_value_histogram`.
+    pub fn variable_value_histogram(&self) -> HashMap<(Variable, isize), u64> {
+        self.shared.value_histogram.as_ref()
+            .map(|histogram| histogram.lock().clone())
+            .unwrap_or_default()
+    }
+
+    /// Returns the best solution along with a breakdown of its value: each
+    /// decision of the optimal path paired with the `transition_cost` of the
+    /// arc it labels. Together with the initial value of the problem, these
+    /// costs sum up to `best_value`. The costs are recovered by replaying
+    /// the transitions of the model along the best path; this is sound
+    /// because an incumbent is only ever extracted from an exact (merge-free)
+    /// path, whose arcs carry genuine transition costs.
+    pub fn best_solution_with_costs(&self) -> Option<Vec<(Decision, isize)>> {
+        let best_sol = self.shared.critical.lock().best_sol.clone();
+        best_sol.map(|sol| Self::replay_costs(self.shared.problem, &sol))
+    }
+
+    /// Validates the feasible completion which the relaxation suggested for
+    /// the given subproblem: the decisions are replayed from the subproblem
+    /// state, checking at each step that the decision assigns the variable
+    /// being branched on and belongs to its domain. When the completion
+    /// checks out, this returns the value of the full solution along with
+    /// its decisions (the path to the subproblem extended with the decisions
+    /// of the completion); otherwise it returns None and the suggestion is
+    /// simply ignored.
+    fn validate_completion(
+        problem: &(dyn Problem<State = State> + Send + Sync),
+        node: &SubProblem<State>,
+        completion: &[Decision],
+    ) -> Option<(isize, Vec<Decision>)> {
+        let mut state = node.state.as_ref().clone();
+        let mut value = node.value;
+        let mut depth = node.depth;
+        let mut decisions = node.path.clone();
+
+        while let Some(var) = problem.next_variable(depth, &mut std::iter::once(&state)) {
+            let decision = completion.iter().copied().find(|d| d.variable == var)?;
+            let mut feasible = false;
+            problem.for_each_in_domain(var, &state, &mut |d: Decision| feasible |= d == decision);
+            if !feasible {
+                return None;
+            }
+            let next = problem.transition_checked(&state, decision)?;
+            value = value.saturating_add(problem.transition_cost(&state, &next, decision));
+            state = next;
+            depth += 1;
+            decisions.push(decision);
+        }
+        Some((value, decisions))
+    }
+
+    /// Replays the transitions of the model along the given solution and
+    /// pairs each of its decisions with the corresponding transition cost
+    fn replay_costs(problem: &dyn Problem<State = State>, sol: &Solution) -> Vec<(Decision, isize)> {
+        let order = problem.static_order();
+        let mut costs = Vec::with_capacity(sol.len());
+        let mut state = problem.initial_state();
+        for depth in 0..sol.len() {
+            let variable = match order.as_ref() {
+                Some(order) => order.get(depth).copied(),
+                None => problem.next_variable(depth, &mut std::iter::once(&state)),
+            };
+            let decision = variable.and_then(|var| sol.iter().copied().find(|d| d.variable == var));
+            if let Some(decision) = decision {
+                let next = problem.transition(&state, decision);
+                costs.push((decision, problem.transition_cost(&state, &next, decision)));
+                state = next;
+            } else {
+                break;
+            }
+        }
+        costs
+    }
+
+    /// Registers a callback which gets invoked every time a new incumbent is
+    /// acknowledged -- that is, every time the best known lower bound
+    /// improves on the last reported one by at least the configured
+    /// `min_improvement` (any strict improvement, by default). The callback
+    /// receives the new objective value and the corresponding decision
+    /// vector, which lets you log progress, checkpoint, or feed the incumbent
+    /// into another system without waiting for `maximize` to return.
+    ///
+    /// # Note
+    /// The callback is always invoked while holding the critical lock of the
+    /// solver (so the concurrent workers never race), but it may be called
+    /// from any of the worker threads. Keep it short: the whole search stalls
+    /// for as long as it runs.
+    pub fn with_on_incumbent(mut self, callback: impl FnMut(isize, &[Decision]) + Send + 'a) -> Self {
+        self.shared.critical.get_mut().on_incumbent = Some(Box::new(callback));
+        self
+    }
+
+    /// Registers an observer which gets notified of the salient events of
+    /// the search: its start and end, the improvements of the incumbent
+    /// bounds, and the progress of the active layer. Use `PrintReporter` to
+    /// log the progress on stdout, or provide your own `SearchReporter` to
+    /// forward the events to a logging facade, a file, or a GUI. The
+    /// notifications are emitted from within the critical section, so the
+    /// reporter needs not be thread-safe itself but it should return quickly
+    /// lest it slows down all the workers.
+    pub fn with_reporter(mut self, reporter: impl SearchReporter + Send + 'a) -> Self {
+        self.shared.critical.get_mut().reporter = Some(Box::new(reporter));
+        self
+    }
+
+    /// Asks the solver to record the evolution of the incumbent bounds as a
+    /// time series: whenever either bound changes, an
+    /// `(elapsed, lower bound, upper bound)` sample is appended, timed from
+    /// the start of the search. On the contrary to a `SearchReporter`, this
+    /// yields a structured, queryable log (see `bound_history`) which comes
+    /// in handy to plot convergence curves in automated experiments.
+    pub fn with_bound_log(mut self) -> Self {
+        self.shared.critical.get_mut().bound_log = Some(vec![]);
+        self
+    }
+
+    /// Asks the solver to record the per-(variable, value) histogram of the
+    /// DD edges compiled during the search (see `variable_value_histogram`).
+    /// Harvesting the edges of every compiled DD is pure overhead when the
+    /// histogram is not consulted, so the recording is off by default.
+    pub fn with_value_histogram(mut self) -> Self {
+        self.shared.value_histogram = Some(Mutex::new(Default::default()));
+        self
+    }
+
+    /// Returns the time series of the incumbent bounds recorded so far: one
+    /// `(elapsed, lower bound, upper bound)` sample per change of either
+    /// bound, timed from the start of the search. The series stays empty
+    /// unless the solver was configured with `with_bound_log`.
+    pub fn bound_history(&self) -> Vec<(Duration, isize, isize)> {
+        self.shared.critical.lock().bound_log.clone().unwrap_or_default()
+    }
+
+    /// Returns the upper bound proved by the very first relaxed DD the solver
+    /// compiled for the root subproblem. Contrary to `best_upper_bound`, this
+    /// bound is never tightened afterwards: comparing the two tells how much
+    /// of the final bound is owed to the relaxation alone and how much to the
+    /// branch-and-bound. This returns `None` as long as no relaxed DD has
+    /// been compiled for the root (in particular when the restricted DD of
+    /// the root was already exact).
+    pub fn root_upper_bound(&self) -> Option<isize> {
+        self.shared.critical.lock().root_ub
+    }
+
+    /// Returns the work counters accumulated by this solver so far: the
+    /// number of DDs compiled, nodes expanded, fringe operations and
+    /// prunings. These make it easy to compare the effort spent by two solver
+    /// configurations on the same instance.
+    pub fn stats(&self) -> SolverStats {
+        let stats = &self.shared.stats;
+        SolverStats {
+            nb_restricted_dds: stats.nb_restricted_dds.load(Ordering::Relaxed),
+            nb_relaxed_dds: stats.nb_relaxed_dds.load(Ordering::Relaxed),
+            nb_nodes_expanded: stats.nb_nodes_expanded.load(Ordering::Relaxed),
+            nb_fringe_pops: stats.nb_fringe_pops.load(Ordering::Relaxed),
+            peak_fringe_size: stats.peak_fringe_size.load(Ordering::Relaxed),
+            nb_dominance_prunes: stats.nb_dominance_prunes.load(Ordering::Relaxed),
+            nb_cache_prunes: stats.nb_cache_prunes.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Seeds the search with an externally-known lower bound on the optimal
+    /// value (warm start). Subproblems whose rough upper bound does not exceed
+    /// this value are pruned right away, even before the first incumbent is
+    /// found. The bound is only taken when it improves on the current one, and
+    /// since no solution comes with it, `best_solution` stays `None` until the
+    /// search actually finds one.
+    pub fn with_initial_lower_bound(mut self, lower_bound: isize) -> Self {
+        let critical = self.shared.critical.get_mut();
+        critical.best_lb = critical.best_lb.max(lower_bound);
+        self
+    }
+
+    /// Seeds the search with a known feasible solution (warm start). The
+    /// decisions are validated by replaying them against the model from the
+    /// root: each one must assign the variable being branched on, belong to
+    /// its domain and be accepted by the (checked) transitions. The replay
+    /// computes the value of the solution, which is then installed as the
+    /// initial incumbent. If nothing better is found, `best_solution` returns
+    /// this very solution.
+    ///
+    /// # Panics
+    /// When the given solution does not assign every variable of the problem,
+    /// or when it is infeasible (some decision falls outside the domain of
+    /// its variable or is rejected by the transitions): installing it as the
+    /// incumbent would silently corrupt `best_value`.
+    pub fn with_initial_solution(mut self, solution: Vec<Decision>) -> Self {
+        assert_eq!(self.shared.problem.nb_variables(), solution.len(),
+            "the initial solution must assign every variable of the problem");
+
+        let root = SubProblem {
+            state: Arc::new(self.shared.problem.initial_state()),
+            value: self.shared.problem.initial_value(),
+            path: vec![],
+            ub: isize::MAX,
+            depth: 0,
+        };
+        let (value, solution) = Self::validate_completion(self.shared.problem, &root, &solution)
+            .expect("the initial solution must be feasible for the problem");
+        self.set_primal(value, Solution::new(solution));
+        self
+    }
+
+    /// Splits the workers in two dedicated pools: `bound_threads` workers are
+    /// devoted to the tightening of the dual bound (they pop the most promising
+    /// nodes and go straight to the compilation of relaxed DDs) while
+    /// `primal_threads` workers are devoted to the search for incumbents (they
+    /// start each node with a restricted dive). This lets users explicitly
+    /// allocate the effort between proving and finding, which is valuable when
+    /// one side is much cheaper than the other.
+    ///
+    /// # Note
+    /// Primal workers still complete the relaxation of the nodes they process
+    /// (after their restricted dive) so that no subproblem is ever lost and the
+    /// optimality proof remains valid. The incumbent and the bounds are shared
+    /// among all workers through the critical section of the solver: both
+    /// pools read and update them under the same mutex, so no stale bound is
+    /// ever used to prune. Passing `primal_threads == 0` is allowed and means
+    /// exactly what it says: every worker skips the restricted dives (the
+    /// incumbents then only come from the exact relaxed DDs).
+    ///
+    /// # Panics
+    /// When both pools are empty: a solver without any worker could never
+    /// terminate its search.
+    pub fn with_split_workers(mut self, bound_threads: usize, primal_threads: usize) -> Self {
+        assert!(bound_threads + primal_threads > 0,
+            "at least one worker is required to carry out the search");
+        self.nb_threads = bound_threads + primal_threads;
+        self.primal_threads = Some(primal_threads);
+        self.shared.critical.lock().upper_bounds = vec![isize::MAX; self.nb_threads];
+        self
+    }
+
+    /// Same as `maximize` except that the search is not started from the sole
+    /// initial state of the problem but from the given set of root
+    /// subproblems. This is useful to resume a search from a checkpoint or to
+    /// inject a problem-specific decomposition of the state space.
+    ///
+    /// # Warning
+    /// It is the caller's responsibility to ensure that the given roots
+    /// collectively cover the portion of the state space that must be
+    /// explored: any part of the space unreachable from the given roots is
+    /// simply never considered and the reported optimum only holds relative
+    /// to the covered space.
+    pub fn maximize_from(&mut self, roots: Vec<SubProblem<State>>) -> Completion {
+        self.shared.cache.initialize(self.shared.problem);
+        {
+            let mut critical = self.shared.critical.lock();
+            for root in roots {
+                critical.open_by_layer[root.depth] += 1;
+                critical.fringe.push(root);
+            }
+        }
+        self.solve()
+    }
+
+    /// This is the main solving loop: it spawns `nb_threads` workers; each of
+    /// which continually fetches a workload from the shared state and
+    /// processes it until the problem is solved (or the search is aborted).
+    fn solve(&mut self) -> Completion {
+        if let Some(reporter) = self.shared.critical.get_mut().reporter.as_mut() {
+            reporter.on_start();
+        }
+        std::thread::scope(|s| {
+            for i in 0..self.nb_threads {
+                let shared = &self.shared;
+                // when the workers are split in two pools, the first threads
+                // are devoted to the search for incumbents and the others skip
+                // the restriction phase to focus on the dual bound
+                let with_restriction = self.primal_threads.is_none_or(|primal| i < primal);
+                s.spawn(move || {
+                    let mut mdd = D::default();
+                    loop {
+                        match Self::get_workload(shared, i) {
+                            WorkLoad::Complete => break,
+                            WorkLoad::Aborted => break, // this one cannot occur
+                            WorkLoad::Starvation => continue,
+                            WorkLoad::WorkItem { node } => {
+                                let ub = node.ub;
+                                let depth = node.depth;
+                                let outcome = Self::process_one_node(&mut mdd, shared, node, with_restriction);
+                                if let Err(reason) = outcome {
+                                    Self::abort_search(shared, reason, ub);
+                                    Self::notify_node_finished(shared, i, depth);
+                                    break;
+                                } else {
+                                    Self::notify_node_finished(shared, i, depth);
+                                }
+                            }
+                        }
+                    }
+                });
+            }
+        });
+
+        let mut critical = self.shared.critical.lock();
+        if let Some(sol) = critical.best_sol.as_mut() { sol.sort_unstable_by_key(|d| d.variable.0) }
+        let completion = Completion { is_exact: critical.abort_proof.is_none(), best_value: critical.best_sol.as_ref().map(|_| critical.best_lb) };
+        if let Some(reporter) = critical.reporter.as_mut() {
+            reporter.on_finish(&completion);
+        }
+        completion
+    }
+
     /// This method initializes the problem resolution. Put more simply, this
     /// method posts the root node of the mdd onto the fringe so that a thread
     /// can pick it up and the processing can be bootstrapped.
@@ -369,8 +956,10 @@ where
         let root = self.root_node();
         self.shared.cache.initialize(self.shared.problem);
         let mut critical = self.shared.critical.lock();
+        critical.bound_log_start = Instant::now();
         critical.fringe.push(root);
         critical.open_by_layer[0] += 1;
+        self.shared.stats.peak_fringe_size.fetch_max(critical.fringe.len(), Ordering::Relaxed);
     }
 
     fn root_node(&self) -> SubProblem<State> {
@@ -392,43 +981,107 @@ where
         mdd: &mut D,
         shared: &Shared<'a, State, C>,
         node: SubProblem<State>,
+        with_restriction: bool,
     ) -> Result<(), Reason> {
         // 1. RESTRICTION
         let node_ub = node.ub;
-        let best_lb = Self::best_lb(shared);
+        // the rough lower bound is the value of a feasible completion of the
+        // subproblem: it proves the existence of a solution of that value but
+        // yields no actual solution, hence it only ever tightens the
+        // pruning-only bound (see `pruning_bound`), never the incumbent
+        let rough_lb = shared.relaxation.fast_lower_bound(node.state.as_ref());
+        // when the rough upper bound computation stumbled upon a feasible
+        // completion, validate it and use it as an incumbent right away
+        let rough_completion = shared.relaxation.fast_upper_bound_with_solution(node.state.as_ref()).1
+            .and_then(|completion| Self::validate_completion(shared.problem, &node, &completion));
+        let (best_lb, best_ub) = {
+            let mut critical = shared.critical.lock();
+            if rough_lb > isize::MIN {
+                critical.rough_lb = critical.rough_lb.max(node.value.saturating_add(rough_lb));
+            }
+            if let Some((value, solution)) = rough_completion {
+                if value > critical.best_lb {
+                    critical.best_lb = value;
+                    critical.best_sol = Some(Solution::new(solution));
+                    critical.best_path_exact = false;
+                    Self::maybe_report_incumbent(shared, &mut critical, value);
+                }
+            }
+            (Self::pruning_bound(&critical), critical.best_ub)
+        };
 
         if node_ub <= best_lb {
             return Ok(());
         }
 
-        let width = shared.width_heu.max_width(&node);
+        // the permit bounds the number of DDs being compiled simultaneously
+        // (see `with_max_concurrent_dds`); it is a no-op without a limit
+        Self::acquire_dd_permit(shared);
+        let outcome = Self::compile_dds(mdd, shared, &node, best_lb, best_ub, with_restriction);
+        Self::release_dd_permit(shared);
+        outcome
+    }
+
+    /// Compiles the restricted and possibly relaxed DD rooted in the given
+    /// node and harvests their outcome (incumbents, cutset nodes)
+    fn compile_dds(
+        mdd: &mut D,
+        shared: &Shared<'a, State, C>,
+        node: &SubProblem<State>,
+        best_lb: isize,
+        best_ub: isize,
+        with_restriction: bool,
+    ) -> Result<(), Reason> {
+        let node_ub = node.ub;
+        let width = shared.width_heu.max_width_with_bounds(node, best_lb, best_ub);
+        let counting_cutoff = CountingCutoff { inner: shared.cutoff, expanded: &shared.stats.nb_nodes_expanded };
+        let counting_dominance = CountingDominance { inner: shared.dominance, pruned: &shared.stats.nb_dominance_prunes };
         let mut compilation = CompilationInput {
             comp_type: CompilationType::Restricted,
+            max_in_degree: shared.max_in_degree,
+            max_out_degree: shared.width_heu.max_degree(node),
             max_width: width,
             problem: shared.problem,
             relaxation: shared.relaxation,
             ranking: shared.ranking,
-            cutoff: shared.cutoff,
-            residual: &node,
+            cutoff: &counting_cutoff,
+            residual: node,
             //
             best_lb,
             cache: &shared.cache,
-            dominance: shared.dominance,
+            dominance: &counting_dominance,
         };
 
-        let Completion{is_exact, ..} = mdd.compile(&compilation)?;
-        Self::maybe_update_best(mdd, shared);
-        if is_exact {
-            return Ok(());
+        if with_restriction {
+            let completion = mdd.compile(&compilation);
+            shared.stats.nb_restricted_dds.fetch_add(1, Ordering::Relaxed);
+            Self::harvest_value_histogram(mdd, shared);
+            let Completion{is_exact, ..} = completion?;
+            Self::maybe_update_best(mdd, shared);
+            if is_exact {
+                return Ok(());
+            }
         }
 
         // 2. RELAXATION
         let best_lb = Self::best_lb(shared);
         compilation.comp_type = CompilationType::Relaxed;
+        // the out-degree cap only applies to restricted compilations: keeping
+        // it for the relaxation would invalidate the derived bounds
+        compilation.max_out_degree = usize::MAX;
         compilation.best_lb = best_lb;
 
-        let Completion{is_exact, ..} = mdd.compile(&compilation)?;
+        let completion = mdd.compile(&compilation);
+        shared.stats.nb_relaxed_dds.fetch_add(1, Ordering::Relaxed);
+        Self::harvest_value_histogram(mdd, shared);
+        let Completion{is_exact, ..} = completion?;
         Self::maybe_update_best(mdd, shared);
+        if node.depth == 0 {
+            let mut critical = shared.critical.lock();
+            if critical.root_ub.is_none() {
+                critical.root_ub = Some(mdd.best_value().unwrap_or(isize::MIN));
+            }
+        }
         if !is_exact {
             Self::enqueue_cutset(mdd, shared, node_ub);
         }
@@ -436,26 +1089,109 @@ where
         Ok(())
     }
 
+    /// Parks the calling worker until fewer DDs than the configured limit are
+    /// being compiled, then takes a compilation slot. This is a no-op when no
+    /// limit was configured with `with_max_concurrent_dds`.
+    fn acquire_dd_permit(shared: &Shared<'a, State, C>) {
+        if let Some(limit) = shared.max_concurrent_dds {
+            let mut critical = shared.critical.lock();
+            while critical.nb_compiling >= limit {
+                shared.monitor.wait(&mut critical);
+            }
+            critical.nb_compiling += 1;
+        }
+    }
+
+    /// Releases a compilation slot taken with `acquire_dd_permit` and wakes
+    /// the workers possibly parked waiting for one.
+    fn release_dd_permit(shared: &Shared<'a, State, C>) {
+        if shared.max_concurrent_dds.is_some() {
+            shared.critical.lock().nb_compiling -= 1;
+            shared.monitor.notify_all();
+        }
+    }
+
+    /// Returns the threshold against which the subproblems and DD nodes are
+    /// pruned (anything whose upper bound does not exceed it is dropped). It
+    /// combines the incumbent-backed lower bound with the rough one, but the
+    /// latter only prunes *strictly* worse nodes: the rough bound carries no
+    /// solution, so a node whose upper bound ties with it may still be the
+    /// only one from which a solution of that value can be extracted.
+    fn pruning_bound(critical: &Critical<'_, State>) -> isize {
+        critical.best_lb.max(critical.rough_lb.saturating_sub(1))
+    }
+
     fn best_lb(shared: &Shared<'a, State, C>) -> isize {
-        shared.critical.lock().best_lb
+        Self::pruning_bound(&shared.critical.lock())
+    }
+
+    /// This private method adds the decision labeling each edge of the last
+    /// compiled DD to the shared per-(variable, value) histogram. It does
+    /// nothing unless the recording was requested with `with_value_histogram`.
+    /// The counts are first accumulated in a worker-local map, then merged
+    /// under the histogram's own lock: the critical section is never involved.
+    fn harvest_value_histogram(mdd: &D, shared: &Shared<'a, State, C>) {
+        if let Some(histogram) = shared.value_histogram.as_ref() {
+            let mut local: HashMap<(Variable, isize), u64> = Default::default();
+            mdd.for_each_edge_decision(&mut |decision| {
+                *local.entry((decision.variable, decision.value)).or_insert(0) += 1;
+            });
+            if !local.is_empty() {
+                let mut histogram = histogram.lock();
+                for (key, count) in local {
+                    *histogram.entry(key).or_insert(0) += count;
+                }
+            }
+        }
     }
 
     /// This private method updates the shared best known node and lower bound in
     /// case the best value of the current `mdd` expansion improves the current
     /// bounds.
     fn maybe_update_best(mdd: &D, shared: &Shared<'a, State, C>) {
-        let mut shared = shared.critical.lock();
+        let mut critical = shared.critical.lock();
         let dd_best_value = mdd.best_exact_value().unwrap_or(isize::MIN);
-        if dd_best_value > shared.best_lb {
-            shared.best_lb = dd_best_value;
-            shared.best_sol = mdd.best_exact_solution();
+        if dd_best_value > critical.best_lb {
+            critical.best_lb = dd_best_value;
+            critical.best_sol = mdd.best_exact_solution();
+            critical.best_path_exact = true;
+            Self::maybe_report_incumbent(shared, &mut critical, dd_best_value);
+            let (lb, ub) = (critical.best_lb, critical.best_ub);
+            if let Some(reporter) = critical.reporter.as_mut() {
+                reporter.on_new_bound(lb, ub);
+            }
+            Self::maybe_log_bounds(&mut critical, lb, ub);
+        }
+    }
+
+    /// This private method appends a sample to the bound log, provided that
+    /// recording was requested (`with_bound_log`) and that either bound
+    /// changed since the last recorded sample.
+    fn maybe_log_bounds(critical: &mut Critical<'a, State>, lb: isize, ub: isize) {
+        let elapsed = critical.bound_log_start.elapsed();
+        if let Some(log) = critical.bound_log.as_mut() {
+            if log.last().map(|(_, l, u)| (*l, *u)) != Some((lb, ub)) {
+                log.push((elapsed, lb, ub));
+            }
+        }
+    }
+
+    /// Acknowledges a new incumbent to the observers of this solver, unless
+    /// it fails to improve the last reported one by at least the configured
+    /// minimum improvement.
+    fn maybe_report_incumbent(shared: &Shared<'a, State, C>, critical: &mut Critical<'a, State>, value: isize) {
+        if value.saturating_sub(critical.reported_lb) >= shared.min_improvement {
+            critical.reported_lb = value;
+            if let (Some(callback), Some(solution)) = (critical.on_incumbent.as_mut(), critical.best_sol.as_ref()) {
+                callback(value, solution);
+            }
         }
     }
     /// If necessary, tightens the bound of nodes in the cut-set of `mdd` and
     /// then add the relevant nodes to the shared fringe.
     fn enqueue_cutset(mdd: &mut D, shared: &Shared<'a, State, C>, ub: isize) {
         let mut critical = shared.critical.lock();
-        let best_lb = critical.best_lb;
+        let best_lb = Self::pruning_bound(&critical);
         mdd.drain_cutset(|mut cutset_node| {
             cutset_node.ub = ub.min(cutset_node.ub);
             if cutset_node.ub > best_lb {
@@ -466,6 +1202,8 @@ where
                 critical.open_by_layer[depth] += after - before;
             }
         });
+        shared.stats.peak_fringe_size.fetch_max(critical.fringe.len(), Ordering::Relaxed);
+        shared.cutoff.set_fringe_size(critical.fringe.len());
     }
     /// Acknowledges that a thread finished processing its node.
     fn notify_node_finished(shared: &Shared<'a, State, C>, thread_id: usize, depth: usize) {
@@ -484,6 +1222,8 @@ where
         } else {
             critical.best_ub = current_ub.max(critical.best_ub);
         }
+        let (lb, ub) = (critical.best_lb, critical.best_ub);
+        Self::maybe_log_bounds(&mut critical, lb, ub);
         critical.fringe.clear();
         shared.cache.clear();
     }
@@ -506,11 +1246,17 @@ where
                 critical.open_by_layer[critical.first_active_layer] + critical.ongoing_by_layer[critical.first_active_layer] == 0 {
             shared.cache.clear_layer(critical.first_active_layer);
             critical.first_active_layer += 1;
+            let layer = critical.first_active_layer;
+            if let Some(reporter) = critical.reporter.as_mut() {
+                reporter.on_layer(layer);
+            }
         }
 
         // Are we done ?
         if critical.ongoing == 0 && critical.fringe.is_empty() {
             critical.best_ub = critical.best_lb;
+            let (lb, ub) = (critical.best_lb, critical.best_ub);
+            Self::maybe_log_bounds(&mut critical, lb, ub);
             return WorkLoad::Complete;
         }
 
@@ -519,6 +1265,23 @@ where
             return WorkLoad::Aborted;
         }
 
+        // Did the cutoff kick in ?
+        shared.cutoff.set_fringe_size(critical.fringe.len());
+        if shared.cutoff.must_stop() {
+            critical.abort_proof = Some(Reason::CutoffOccurred);
+            critical.fringe.clear();
+            shared.cache.clear();
+            return WorkLoad::Aborted;
+        }
+
+        // In deterministic mode, a single subproblem may be in expansion at
+        // any point in time: wait until the current one has been completely
+        // processed before popping the next one in canonical fringe order
+        if shared.deterministic && critical.ongoing > 0 {
+            shared.monitor.wait(&mut critical);
+            return WorkLoad::Starvation;
+        }
+
         // Nothing to do yet ? => Wait for someone to post jobs
         if critical.fringe.is_empty() {
             shared.monitor.wait(&mut critical);
@@ -526,18 +1289,20 @@ where
         }
 
         let mut nn = critical.fringe.pop().unwrap();
+        shared.stats.nb_fringe_pops.fetch_add(1, Ordering::Relaxed);
         loop {
             // Nothing relevant ? =>  Wait for someone to post jobs
-            if nn.ub <= critical.best_lb {
+            if nn.ub <= Self::pruning_bound(&critical) {
                 critical.fringe.clear();
                 critical.open_by_layer.iter_mut().for_each(|o| *o = 0);
                 return WorkLoad::Starvation;
             }
             
-            if shared.cache.must_explore(&nn) {
-                shared.cache.update_threshold(nn.state.clone(), nn.depth, nn.value, true);
+            if shared.cache.must_explore_with_problem(shared.problem, &nn) {
+                shared.cache.update_threshold_with_problem(shared.problem, nn.state.clone(), nn.depth, nn.value, true);
                 break;
             } else {
+                shared.stats.nb_cache_prunes.fetch_add(1, Ordering::Relaxed);
                 critical.open_by_layer[nn.depth] -= 1;
 
                 if critical.fringe.is_empty() {
@@ -545,6 +1310,7 @@ where
                 }
     
                 nn = critical.fringe.pop().unwrap();
+                shared.stats.nb_fringe_pops.fetch_add(1, Ordering::Relaxed);
             }
         }
 
@@ -555,6 +1321,18 @@ where
         critical.open_by_layer[nn.depth] -= 1;
         critical.ongoing_by_layer[nn.depth] += 1;
 
+        // at this very instant, the global upper bound is exactly the largest
+        // ub among the ongoing subproblems (the fringe is sorted by
+        // decreasing ub, so none of the queued subproblems does better than
+        // the one which was just popped)
+        let live_ub = critical.upper_bounds.iter().copied()
+            .filter(|ub| *ub != isize::MAX)
+            .max()
+            .unwrap_or(isize::MAX);
+        shared.cutoff.set_bounds(critical.best_lb.max(critical.rough_lb), live_ub);
+        let lb = critical.best_lb;
+        Self::maybe_log_bounds(&mut critical, lb, live_ub);
+
         WorkLoad::WorkItem { node: nn }
     }
 
@@ -572,42 +1350,11 @@ where
     /// and process it until the problem is solved.
     fn maximize(&mut self) -> Completion {
         self.initialize();
-
-        std::thread::scope(|s| {
-            for i in 0..self.nb_threads {
-                let shared = &self.shared;
-                s.spawn(move || {
-                    let mut mdd = D::default();
-                    loop {
-                        match Self::get_workload(shared, i) {
-                            WorkLoad::Complete => break,
-                            WorkLoad::Aborted => break, // this one cannot occur
-                            WorkLoad::Starvation => continue,
-                            WorkLoad::WorkItem { node } => {
-                                let ub = node.ub;
-                                let depth = node.depth;
-                                let outcome = Self::process_one_node(&mut mdd, shared, node);
-                                if let Err(reason) = outcome {
-                                    Self::abort_search(shared, reason, ub);
-                                    Self::notify_node_finished(shared, i, depth); 
-                                    break;
-                                } else {
-                                    Self::notify_node_finished(shared, i, depth);
-                                }
-                            }
-                        }
-                    }
-                });
-            }
-        });
-
-        let mut critical = self.shared.critical.lock();
-        if let Some(sol) = critical.best_sol.as_mut() { sol.sort_unstable_by_key(|d| d.variable.0) }
-        Completion { is_exact: critical.abort_proof.is_none(), best_value: critical.best_sol.as_ref().map(|_| critical.best_lb) }
+        self.solve()
     }
 
     /// Returns the best solution that has been identified for this problem.
-    fn best_solution(&self) -> Option<Vec<Decision>> {
+    fn best_solution(&self) -> Option<Solution> {
         self.shared.critical.lock().best_sol.clone()
     }
     /// Returns the value of the best solution that has been identified for
@@ -617,9 +1364,12 @@ where
         critical.best_sol.as_ref().map(|_sol| critical.best_lb)
     }
     /// Returns the value of the best lower bound that has been identified for
-    /// this problem.
+    /// this problem. This includes the bound derived from the rough lower
+    /// bound estimates of the relaxation, which proves that a solution of
+    /// that value exists even when no solution has been materialized yet.
     fn best_lower_bound(&self) -> isize {
-        self.shared.critical.lock().best_lb
+        let critical = self.shared.critical.lock();
+        critical.best_lb.max(critical.rough_lb)
     }
     /// Returns the value of the best upper bound that has been identified for
     /// this problem.
@@ -632,6 +1382,8 @@ where
         if value > critical.best_lb {
             critical.best_sol = Some(solution);
             critical.best_lb  = value;
+            critical.best_path_exact = false;
+            Self::maybe_report_incumbent(&self.shared, &mut critical, value);
         }
     }
     /// Returns the number of nodes that have been explored so far.
@@ -653,6 +1405,9 @@ where
 
 #[cfg(test)]
 mod test_solver {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
     use crate::*;
     
     type DdLel<'a, T> = ParallelSolver<'a, T, DefaultMDDLEL<T>, EmptyCache<T>>;
@@ -685,11 +1440,11 @@ mod test_solver {
         assert_eq!(isize::min_value(), solver.best_lower_bound());
     }
     #[test]
-    fn by_default_best_ub_is_plus_infinity() {
+    fn split_workers_yield_the_optimum() {
         let problem = Knapsack {
             capacity: 50,
-            profit  : vec![60, 100, 120],
-            weight  : vec![10,  20,  30]
+            profit  : vec![60, 210, 12, 5, 100, 120, 110],
+            weight  : vec![10,  45, 20, 4,  20,  30,  50]
         };
         let relax = KPRelax {pb: &problem};
         let ranking = KPRanking;
@@ -697,7 +1452,7 @@ mod test_solver {
         let width = NbUnassignedWidth(problem.nb_variables());
         let dominance = EmptyDominanceChecker::default();
         let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
-        let solver = DdLel::custom(
+        let mut solver = DdLel::custom(
             &problem,
             &relax,
             &ranking,
@@ -706,16 +1461,19 @@ mod test_solver {
             &cutoff,
             &mut fringe,
             1,
-        );
+        ).with_split_workers(1, 1);
 
-        assert_eq!(isize::max_value(), solver.best_upper_bound());
+        let maximized = solver.maximize();
+
+        assert!(maximized.is_exact);
+        assert_eq!(maximized.best_value, Some(220));
     }
     #[test]
-    fn when_the_problem_is_solved_best_lb_is_best_value() {
+    fn zero_primal_threads_means_no_restricted_dives_not_all_of_them() {
         let problem = Knapsack {
             capacity: 50,
-            profit  : vec![60, 100, 120],
-            weight  : vec![10,  20,  30]
+            profit  : vec![60, 210, 12, 5, 100, 120, 110],
+            weight  : vec![10,  45, 20, 4,  20,  30,  50]
         };
         let relax = KPRelax {pb: &problem};
         let ranking = KPRanking;
@@ -732,13 +1490,18 @@ mod test_solver {
             &cutoff,
             &mut fringe,
             1,
-        );
+        ).with_split_workers(2, 0);
 
-        let _ = solver.maximize();
-        assert_eq!(220, solver.best_lower_bound());
+        // the incumbents then only come from the exact relaxed DDs, which is
+        // enough to close the search on such a small instance
+        let maximized = solver.maximize();
+
+        assert!(maximized.is_exact);
+        assert_eq!(maximized.best_value, Some(220));
     }
     #[test]
-    fn when_the_problem_is_solved_best_ub_is_best_value() {
+    #[should_panic]
+    fn a_solver_without_any_worker_is_rejected() {
         let problem = Knapsack {
             capacity: 50,
             profit  : vec![60, 100, 120],
@@ -750,7 +1513,7 @@ mod test_solver {
         let width = NbUnassignedWidth(problem.nb_variables());
         let dominance = EmptyDominanceChecker::default();
         let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
-        let mut solver = DdLel::custom(
+        let _ = DdLel::custom(
             &problem,
             &relax,
             &ranking,
@@ -759,14 +1522,11 @@ mod test_solver {
             &cutoff,
             &mut fringe,
             1,
-        );
-
-        let _ = solver.maximize();
-        assert_eq!(220, solver.best_upper_bound());
+        ).with_split_workers(0, 0);
     }
-    
+
     #[test]
-    fn no_solution_before_solving() {
+    fn by_default_best_ub_is_plus_infinity() {
         let problem = Knapsack {
             capacity: 50,
             profit  : vec![60, 100, 120],
@@ -788,10 +1548,11 @@ mod test_solver {
             &mut fringe,
             1,
         );
-        assert!(solver.best_solution().is_none());
+
+        assert_eq!(isize::max_value(), solver.best_upper_bound());
     }
     #[test]
-    fn empty_fringe_before_solving() {
+    fn when_the_problem_is_solved_best_lb_is_best_value() {
         let problem = Knapsack {
             capacity: 50,
             profit  : vec![60, 100, 120],
@@ -803,7 +1564,7 @@ mod test_solver {
         let width = NbUnassignedWidth(problem.nb_variables());
         let dominance = EmptyDominanceChecker::default();
         let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
-        let solver = DdLel::custom(
+        let mut solver = DdLel::custom(
             &problem,
             &relax,
             &ranking,
@@ -814,7 +1575,88 @@ mod test_solver {
             1,
         );
 
-        assert!(solver.best_value().is_none());
+        let _ = solver.maximize();
+        assert_eq!(220, solver.best_lower_bound());
+    }
+    #[test]
+    fn when_the_problem_is_solved_best_ub_is_best_value() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = DdLel::custom(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+            1,
+        );
+
+        let _ = solver.maximize();
+        assert_eq!(220, solver.best_upper_bound());
+    }
+    
+    #[test]
+    fn no_solution_before_solving() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let solver = DdLel::custom(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+            1,
+        );
+        assert!(solver.best_solution().is_none());
+    }
+    #[test]
+    fn empty_fringe_before_solving() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let solver = DdLel::custom(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+            1,
+        );
+
+        assert!(solver.best_value().is_none());
+        drop(solver);
         assert!(fringe.is_empty());
     }
 
@@ -899,6 +1741,348 @@ mod test_solver {
         assert_eq!(isize::max_value(), solver.best_upper_bound());
     }
 
+    #[test]
+    fn the_incumbent_callback_is_notified_of_every_improvement() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = FixedWidth(2); // a tiny width forces actual branch-and-bound
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+
+        let incumbents = std::sync::Mutex::new(vec![]);
+        let mut solver = DdLel::custom(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+            1,
+        ).with_on_incumbent(|value, solution: &[Decision]| {
+            incumbents.lock().unwrap().push((value, solution.to_vec()));
+        });
+
+        let maximized = solver.maximize();
+        assert_eq!(Some(220), maximized.best_value);
+
+        let incumbents = incumbents.lock().unwrap();
+        // the values reported form a strictly increasing sequence ending at
+        // the optimum, and each one comes with its complete solution
+        assert!(!incumbents.is_empty());
+        assert!(incumbents.windows(2).all(|w| w[0].0 < w[1].0));
+        assert_eq!(220, incumbents.last().unwrap().0);
+        assert_eq!(3, incumbents.last().unwrap().1.len());
+    }
+
+    #[test]
+    fn the_stats_reflect_the_work_done_by_a_solve() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = FixedWidth(2); // a tiny width forces actual branch-and-bound
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = DdLel::custom(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+            1,
+        );
+
+        let maximized = solver.maximize();
+        assert_eq!(Some(220), maximized.best_value);
+
+        let stats = solver.stats();
+        // with a width of two, the root DD cannot be exact: at least one
+        // restricted and one relaxed DD have been compiled
+        assert!(stats.nb_restricted_dds >= 1);
+        assert!(stats.nb_relaxed_dds >= 1);
+        assert!(stats.nb_nodes_expanded > 0);
+        assert_eq!(solver.explored(), stats.nb_fringe_pops);
+        assert!(stats.peak_fringe_size >= 1);
+    }
+
+    #[test]
+    fn a_warm_start_seeds_the_incumbent_before_the_search_begins() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = DdLel::custom(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+            1,
+        ).with_initial_solution(vec![
+            // only taking the first item is feasible and worth 60
+            Decision{variable: Variable(0), value: 1},
+            Decision{variable: Variable(1), value: 0},
+            Decision{variable: Variable(2), value: 0},
+        ]);
+
+        // the value of the warm start solution was computed by replaying it
+        assert_eq!(Some(60), solver.best_value());
+        assert_eq!(60, solver.best_lower_bound());
+
+        // the search improves on the warm start
+        let maximized = solver.maximize();
+        assert!(maximized.is_exact);
+        assert_eq!(maximized.best_value, Some(220));
+    }
+
+    #[test]
+    fn the_reporter_is_notified_of_the_salient_events_of_the_search() {
+        #[derive(Default)]
+        struct CountingReporter {
+            started: Arc<AtomicUsize>,
+            bounds: Arc<AtomicUsize>,
+            finished: Arc<AtomicUsize>,
+        }
+        impl SearchReporter for CountingReporter {
+            fn on_start(&mut self) {
+                self.started.fetch_add(1, Ordering::Relaxed);
+            }
+            fn on_new_bound(&mut self, lb: isize, ub: isize) {
+                assert!(lb <= ub);
+                self.bounds.fetch_add(1, Ordering::Relaxed);
+            }
+            fn on_finish(&mut self, completion: &Completion) {
+                assert_eq!(Some(220), completion.best_value);
+                self.finished.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = FixedWidth(2); // a tiny width forces actual branch-and-bound
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+
+        let reporter = CountingReporter::default();
+        let started = reporter.started.clone();
+        let bounds = reporter.bounds.clone();
+        let finished = reporter.finished.clone();
+
+        let mut solver = DdLel::custom(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+            2,
+        ).with_reporter(reporter);
+
+        let _ = solver.maximize();
+        drop(solver);
+
+        assert_eq!(1, started.load(Ordering::Relaxed));
+        assert!(bounds.load(Ordering::Relaxed) >= 1);
+        assert_eq!(1, finished.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn bounding_the_concurrent_dds_does_not_compromise_the_search() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = FixedWidth(2); // a tiny width forces actual branch-and-bound
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = DdLel::custom(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+            4,
+        ).with_max_concurrent_dds(1);
+
+        // at most one of the four workers compiles a DD at any time: the
+        // others park until a slot is freed, but the outcome is unchanged
+        let maximized = solver.maximize();
+        assert!(maximized.is_exact);
+        assert_eq!(maximized.best_value, Some(220));
+    }
+
+    #[test]
+    fn the_best_solution_can_be_broken_down_by_decision() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = DdLel::custom(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+            1,
+        );
+        assert!(solver.best_solution_with_costs().is_none());
+
+        let maximized = solver.maximize();
+        assert!(maximized.is_exact);
+
+        let breakdown = solver.best_solution_with_costs().unwrap();
+        assert_eq!(breakdown, vec![
+            (Decision{variable: Variable(0), value: 0},   0),
+            (Decision{variable: Variable(1), value: 1}, 100),
+            (Decision{variable: Variable(2), value: 1}, 120),
+        ]);
+        // the per-decision costs (plus the initial value) sum to best_value
+        let total: isize = breakdown.iter().map(|(_, cost)| cost).sum();
+        assert_eq!(maximized.best_value, Some(total + problem.initial_value()));
+    }
+
+    #[test]
+    fn a_deterministic_run_is_reproducible_regardless_of_thread_count() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 210, 12, 5, 100, 120, 110],
+            weight  : vec![10,  45, 20, 4,  20,  30,  50]
+        };
+        let run = |nb_threads: usize| {
+            let relax = KPRelax {pb: &problem};
+            let ranking = KPRanking;
+            let cutoff = NoCutoff;
+            let width = FixedWidth(2);
+            let dominance = EmptyDominanceChecker::default();
+            let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+            let mut solver = DdLel::custom(
+                &problem,
+                &relax,
+                &ranking,
+                &width,
+                &dominance,
+                &cutoff,
+                &mut fringe,
+                nb_threads,
+            ).with_deterministic(true);
+
+            let maximized = solver.maximize();
+            let solution = solver.best_solution();
+            let stats = solver.stats();
+            drop(solver);
+            (maximized.best_value, solution, stats)
+        };
+
+        let single = run(1);
+        let multi_a = run(4);
+        let multi_b = run(4);
+
+        assert_eq!(single, multi_a);
+        assert_eq!(multi_a, multi_b);
+    }
+
+    #[test]
+    fn a_fast_lower_bound_does_not_compromise_the_search() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPGreedyRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = FixedWidth(2);
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = DdLel::custom(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+            1,
+        );
+
+        let maximized = solver.maximize();
+        assert!(maximized.is_exact);
+        assert_eq!(220, solver.best_lower_bound());
+        assert_eq!(220, solver.best_upper_bound());
+    }
+
+    #[test]
+    fn an_initial_lower_bound_does_not_fake_a_solution() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let solver = DdLel::custom(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+            1,
+        ).with_initial_lower_bound(100);
+
+        assert_eq!(100, solver.best_lower_bound());
+        assert_eq!(None, solver.best_value());
+    }
+
     #[test]
     fn maximizes_yields_the_optimum_1a() {
         let problem = Knapsack {
@@ -1174,7 +2358,7 @@ mod test_solver {
         );
 
         let d1  = Decision{variable: Variable(0), value: 10};
-        let sol = vec![d1];
+        let sol = Solution::new(vec![d1]);
 
         solver.set_primal(10, sol.clone());
         assert!(solver.best_solution().is_some());
@@ -1327,6 +2511,40 @@ mod test_solver {
             tot as isize
         }
     }
+    /// Same relaxation as `KPRelax`, except it also provides a rough lower
+    /// bound: the value of the greedy completion which packs the remaining
+    /// items in index order whenever they fit
+    struct KPGreedyRelax<'a>{pb: &'a Knapsack}
+    impl Relaxation for KPGreedyRelax<'_> {
+        type State = KnapsackState;
+
+        fn merge(&self, states: &mut dyn Iterator<Item = &Self::State>) -> Self::State {
+            states.max_by_key(|node| node.capacity).copied().unwrap()
+        }
+        fn relax(&self, _source: &Self::State, _dest: &Self::State, _merged: &Self::State, _decision: Decision, cost: isize) -> isize {
+            cost
+        }
+        fn fast_upper_bound(&self, state: &Self::State) -> isize {
+            let mut tot = 0;
+            for var in state.depth..self.pb.nb_variables() {
+                if self.pb.weight[var] <= state.capacity {
+                    tot += self.pb.profit[var];
+                }
+            }
+            tot as isize
+        }
+        fn fast_lower_bound(&self, state: &Self::State) -> isize {
+            let mut capacity = state.capacity;
+            let mut tot = 0;
+            for var in state.depth..self.pb.nb_variables() {
+                if self.pb.weight[var] <= capacity {
+                    capacity -= self.pb.weight[var];
+                    tot += self.pb.profit[var];
+                }
+            }
+            tot as isize
+        }
+    }
     struct KPRanking;
     impl StateRanking for KPRanking {
         type State = KnapsackState;
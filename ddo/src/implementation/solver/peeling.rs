@@ -0,0 +1,462 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! This module provides the implementation of a layer-synchronized ("peeling")
+//! solver which never splits the search into per-subproblem DDs: every sweep
+//! develops the union of all the live nodes of one global layer at a time.
+
+use std::cell::Cell;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use crate::*;
+
+/// A tiny adapter which counts the nodes expanded during the compilations
+/// on behalf of the solver, on top of forwarding them to the user cutoff
+struct CountingCutoff<'b> {
+    /// The criterion which effectively decides when to stop
+    inner: &'b dyn Cutoff,
+    /// The number of nodes developed so far
+    expanded: &'b Cell<usize>,
+}
+impl Cutoff for CountingCutoff<'_> {
+    fn must_stop(&self) -> bool {
+        self.inner.must_stop()
+    }
+    fn add_expanded(&self, nb_nodes: usize) {
+        self.expanded.set(self.expanded.get() + nb_nodes);
+        self.inner.add_expanded(nb_nodes);
+    }
+}
+
+/// This solver proceeds breadth-first over the whole problem: instead of
+/// popping one subproblem at a time and compiling a DD per subproblem, each
+/// of its sweeps starts from the root and develops one global layer at a
+/// time -- the union of all the live nodes at that depth -- respecting the
+/// width prescribed by the heuristic. One restricted sweep harvests a
+/// feasible incumbent, one relaxed sweep proves a global upper bound: both
+/// bounds are thus available after a single pass over the layers, which is
+/// where this "peeling" strategy shines compared to a branch-and-bound that
+/// must process many subproblems before its bounds become meaningful.
+///
+/// When the bounds do not meet, the solver starts over with a larger width
+/// (multiplied by `width_factor` after each round, 2 by default) and repeats
+/// until either a sweep is exact, the bounds meet, or the cutoff trips. As
+/// usual, the reported completion is only exact in the two former cases.
+pub struct LayerSynchronizedSolver<'a, State, D = DefaultMDDLEL<State>>
+where
+    D: DecisionDiagram<State = State> + Default,
+{
+    /// A reference to the problem being solved
+    problem: &'a dyn Problem<State = State>,
+    /// The relaxation used to merge the surplus nodes of a global layer
+    /// (and for the rough upper bounds pruning the hopeless branches)
+    relaxation: &'a dyn Relaxation<State = State>,
+    /// The ranking heuristic used to discriminate the most promising from
+    /// the least promising states when a layer must be trimmed down
+    ranking: &'a dyn StateRanking<State = State>,
+    /// The heuristic prescribing the width granted to the very first sweeps
+    width_heu: &'a dyn WidthHeuristic<State>,
+    /// The dominance checker used to prune the dominated states
+    dominance: &'a dyn DominanceChecker<State = State>,
+    /// The cutoff criterion which decides when to stop trying
+    cutoff: &'a dyn Cutoff,
+
+    /// The geometric factor by which the width is multiplied after each
+    /// round of one restricted and one relaxed sweep
+    width_factor: usize,
+
+    /// The DD which is recycled from one sweep to the next
+    mdd: D,
+    /// This is the value of the best known solution so far
+    best_lb: isize,
+    /// This is the best proven upper bound so far
+    best_ub: isize,
+    /// If set, this keeps the info about the best solution so far
+    best_sol: Option<Solution>,
+    /// A flag set to true when a sweep was exact or when the bounds met
+    /// (in which case the incumbent is optimal)
+    proved_optimal: bool,
+    /// This is a counter that tracks the total number of nodes that have
+    /// been explored, all sweeps taken together
+    explored: usize,
+}
+
+impl<'a, State, D> LayerSynchronizedSolver<'a, State, D>
+where
+    State: Eq + Hash + Clone,
+    D: DecisionDiagram<State = State> + Default,
+{
+    pub fn new(
+        problem: &'a dyn Problem<State = State>,
+        relaxation: &'a dyn Relaxation<State = State>,
+        ranking: &'a dyn StateRanking<State = State>,
+        width_heu: &'a dyn WidthHeuristic<State>,
+        dominance: &'a dyn DominanceChecker<State = State>,
+        cutoff: &'a dyn Cutoff,
+    ) -> Self {
+        Self {
+            problem,
+            relaxation,
+            ranking,
+            width_heu,
+            dominance,
+            cutoff,
+            //
+            width_factor: 2,
+            //
+            mdd: D::default(),
+            best_lb: isize::MIN,
+            best_ub: isize::MAX,
+            best_sol: None,
+            proved_optimal: false,
+            explored: 0,
+        }
+    }
+
+    /// Sets the geometric factor by which the width is multiplied after each
+    /// round of one restricted and one relaxed sweep
+    pub fn with_width_factor(mut self, width_factor: usize) -> Self {
+        self.width_factor = width_factor;
+        self
+    }
+
+    /// Performs one global sweep of the given compilation type at the given
+    /// width and returns its completion (unless the cutoff tripped mid-sweep)
+    fn sweep(
+        &mut self,
+        comp_type: CompilationType,
+        width: usize,
+        residual: &SubProblem<State>,
+        cache: &EmptyCache<State>,
+        expanded: &Cell<usize>,
+    ) -> Result<Completion, Reason> {
+        let counting_cutoff = CountingCutoff { inner: self.cutoff, expanded };
+        let input = CompilationInput {
+            comp_type,
+            max_in_degree: None,
+            max_out_degree: usize::MAX,
+            max_width: width,
+            problem: self.problem,
+            relaxation: self.relaxation,
+            ranking: self.ranking,
+            cutoff: &counting_cutoff,
+            cache,
+            dominance: self.dominance,
+            residual,
+            best_lb: self.best_lb,
+        };
+        let completion = self.mdd.compile(&input);
+        self.explored += expanded.replace(0);
+        completion
+    }
+
+    /// Harvests the best exact incumbent of the last sweep (every node of a
+    /// restricted sweep is exact; a relaxed sweep may still expose exact
+    /// paths below its cut)
+    fn maybe_update_best(&mut self) {
+        let dd_best_value = self.mdd.best_exact_value().unwrap_or(isize::MIN);
+        if dd_best_value > self.best_lb {
+            self.best_lb = dd_best_value;
+            self.best_sol = self.mdd.best_exact_solution();
+        }
+    }
+}
+
+impl<State, D> Solver for LayerSynchronizedSolver<'_, State, D>
+where
+    State: Eq + PartialEq + Hash + Clone,
+    D: DecisionDiagram<State = State> + Default,
+{
+    /// Alternates global restricted and relaxed sweeps at geometrically
+    /// increasing widths until the bounds meet, a sweep is exact, or the
+    /// cutoff trips.
+    fn maximize(&mut self) -> Completion {
+        let cache = EmptyCache::new();
+        let expanded = Cell::new(0);
+        let residual = SubProblem {
+            state: Arc::new(self.problem.initial_state()),
+            value: self.problem.initial_value(),
+            path: vec![],
+            ub: isize::MAX,
+            depth: 0,
+        };
+
+        let mut width = self.width_heu.max_width(&residual).max(1);
+        while !self.cutoff.must_stop() {
+            // one restricted sweep harvests a feasible incumbent
+            let Ok(restricted) = self.sweep(CompilationType::Restricted, width, &residual, &cache, &expanded) else {
+                break; // the cutoff kicked in mid-sweep
+            };
+            self.maybe_update_best();
+            self.cutoff.set_bounds(self.best_lb, self.best_ub);
+            if restricted.is_exact {
+                // the restriction dropped nothing: this was the exact DD of
+                // the problem and the incumbent is a proven optimum
+                self.best_ub = self.best_ub.min(self.best_lb);
+                self.proved_optimal = true;
+                break;
+            }
+
+            if self.cutoff.must_stop() {
+                break;
+            }
+            // one relaxed sweep proves a global upper bound
+            let Ok(relaxed) = self.sweep(CompilationType::Relaxed, width, &residual, &cache, &expanded) else {
+                break;
+            };
+            // the paths pruned from the relaxed DD on account of the current
+            // incumbent are all worth best_lb or less: the sweep hence proves
+            // that nothing beats the better of its own longest path and the
+            // incumbent
+            self.best_ub = self
+                .best_ub
+                .min(self.mdd.best_value().unwrap_or(isize::MIN).max(self.best_lb));
+            self.maybe_update_best();
+            self.cutoff.set_bounds(self.best_lb, self.best_ub);
+            if relaxed.is_exact || self.best_lb >= self.best_ub {
+                self.proved_optimal = true;
+                break;
+            }
+
+            width = width.saturating_mul(self.width_factor.max(2));
+        }
+
+        Completion {
+            is_exact: self.proved_optimal,
+            best_value: self.best_sol.as_ref().map(|_| self.best_lb),
+        }
+    }
+
+    /// Returns the best solution that has been identified for this problem.
+    fn best_solution(&self) -> Option<Solution> {
+        self.best_sol.clone()
+    }
+    /// Returns the value of the best solution that has been identified for
+    /// this problem.
+    fn best_value(&self) -> Option<isize> {
+        self.best_sol.as_ref().map(|_sol| self.best_lb)
+    }
+    /// Returns the value of the best lower bound that has been identified
+    /// for this problem.
+    fn best_lower_bound(&self) -> isize {
+        self.best_lb
+    }
+    /// Returns the value of the best upper bound that has been proved for
+    /// this problem (by the relaxed sweeps).
+    fn best_upper_bound(&self) -> isize {
+        self.best_ub
+    }
+    /// Sets a primal (best known value and solution) of the problem.
+    fn set_primal(&mut self, value: isize, solution: Solution) {
+        if value > self.best_lb {
+            self.best_sol = Some(solution);
+            self.best_lb = value;
+        }
+    }
+    /// Returns the number of nodes that have been explored so far, all
+    /// sweeps taken together.
+    fn explored(&self) -> usize {
+        self.explored
+    }
+}
+
+// ############################################################################
+// #### TESTS #################################################################
+// ############################################################################
+
+/// These tests validate the behavior of the layer-synchronized strategy on
+/// the same tiny knapsack instance as the other solvers.
+#[cfg(test)]
+mod test_solver {
+    use crate::*;
+
+    #[test]
+    fn the_sweeps_widen_until_the_bounds_meet() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        // start as narrow as possible: several rounds are needed
+        let width = FixedWidth(1);
+        let dominance = EmptyDominanceChecker::default();
+        let mut solver = LayerSynchronizedSolver::<KnapsackState>::new(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+        );
+
+        let maximized = solver.maximize();
+        assert!(maximized.is_exact);
+        assert_eq!(maximized.best_value, Some(220));
+        assert_eq!(220, solver.best_lower_bound());
+        assert_eq!(220, solver.best_upper_bound());
+    }
+
+    #[test]
+    fn one_round_of_sweeps_already_yields_both_bounds() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        // the budget suffices for one restricted and one relaxed sweep at
+        // width 1, but not for a second round at width 2
+        let cutoff = NodeBudget::new(7);
+        let width = FixedWidth(1);
+        let dominance = EmptyDominanceChecker::default();
+        let mut solver = LayerSynchronizedSolver::<KnapsackState>::new(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+        );
+
+        let maximized = solver.maximize();
+        assert!(!maximized.is_exact);
+        // a feasible incumbent and a proven global bound, after one round
+        assert!(solver.best_value().is_some());
+        assert!(solver.best_lower_bound() > isize::MIN);
+        assert!(solver.best_upper_bound() < isize::MAX);
+        assert!(solver.best_lower_bound() <= solver.best_upper_bound());
+    }
+
+    #[test]
+    fn the_primal_survives_when_nothing_better_is_found() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        // no sweep at all: the cutoff trips right away
+        let cutoff = NodeBudget::new(0);
+        let width = FixedWidth(1);
+        let dominance = EmptyDominanceChecker::default();
+        let mut solver = LayerSynchronizedSolver::<KnapsackState>::new(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+        );
+        solver.set_primal(10000, Solution::new(vec![]));
+
+        let maximized = solver.maximize();
+        assert!(!maximized.is_exact);
+        assert_eq!(maximized.best_value, Some(10000));
+    }
+
+    #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+    struct KnapsackState {
+        depth: usize,
+        capacity: usize
+    }
+
+    struct Knapsack {
+        capacity: usize,
+        profit: Vec<usize>,
+        weight: Vec<usize>,
+    }
+
+    const TAKE_IT: isize = 1;
+    const LEAVE_IT_OUT: isize = 0;
+
+    impl Problem for Knapsack {
+        type State = KnapsackState;
+
+        fn nb_variables(&self) -> usize {
+            self.profit.len()
+        }
+        fn initial_state(&self) -> Self::State {
+            KnapsackState{ depth: 0, capacity: self.capacity }
+        }
+        fn initial_value(&self) -> isize {
+            0
+        }
+        fn transition(&self, state: &Self::State, dec: Decision) -> Self::State {
+            let mut ret = *state;
+            ret.depth += 1;
+            if dec.value == TAKE_IT {
+                ret.capacity -= self.weight[dec.variable.id()]
+            }
+            ret
+        }
+        fn transition_cost(&self, _state: &Self::State, _next: &Self::State, dec: Decision) -> isize {
+            self.profit[dec.variable.id()] as isize * dec.value
+        }
+        fn next_variable(&self, depth: usize, _: &mut dyn Iterator<Item = &Self::State>) -> Option<Variable> {
+            let n = self.nb_variables();
+            if depth < n {
+                Some(Variable(depth))
+            } else {
+                None
+            }
+        }
+        fn for_each_in_domain(&self, variable: Variable, state: &Self::State, f: &mut dyn DecisionCallback) {
+            if state.capacity >= self.weight[variable.id()] {
+                f.apply(Decision { variable, value: TAKE_IT });
+            }
+            f.apply(Decision { variable, value: LEAVE_IT_OUT });
+        }
+    }
+
+    struct KPRelax<'a> {
+        pb: &'a Knapsack,
+    }
+    impl Relaxation for KPRelax<'_> {
+        type State = KnapsackState;
+
+        fn merge(&self, states: &mut dyn Iterator<Item = &Self::State>) -> Self::State {
+            states.max_by_key(|node| node.capacity).copied().unwrap()
+        }
+        fn relax(&self, _source: &Self::State, _dest: &Self::State, _merged: &Self::State, _decision: Decision, cost: isize) -> isize {
+            cost
+        }
+        fn fast_upper_bound(&self, state: &Self::State) -> isize {
+            let mut tot = 0;
+            for var in state.depth..self.pb.nb_variables() {
+                tot += self.pb.profit[var];
+            }
+            tot as isize
+        }
+    }
+
+    struct KPRanking;
+    impl StateRanking for KPRanking {
+        type State = KnapsackState;
+
+        fn compare(&self, a: &Self::State, b: &Self::State) -> std::cmp::Ordering {
+            a.capacity.cmp(&b.capacity)
+        }
+    }
+}
@@ -0,0 +1,78 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! This module provides a ready-made implementation of the `SearchReporter`
+//! trait which simply prints the progress of the search on stdout.
+
+use crate::{Completion, SearchReporter};
+
+/// A reporter that prints the progress of the search on stdout. The wrapped
+/// integer is a verbosity level which controls the amount of output:
+///
+/// * `0` prints nothing at all,
+/// * `1` prints the improvements of the incumbent bounds,
+/// * `2` additionally prints the start and the end of the resolution,
+/// * `3` additionally prints the progress of the active layer.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PrintReporter(pub u8);
+
+impl SearchReporter for PrintReporter {
+    fn on_start(&mut self) {
+        if self.0 >= 2 {
+            println!("search started");
+        }
+    }
+    fn on_new_bound(&mut self, lb: isize, ub: isize) {
+        if self.0 >= 1 {
+            println!("new bounds: lb = {lb}, ub = {ub}");
+        }
+    }
+    fn on_layer(&mut self, layer: usize) {
+        if self.0 >= 3 {
+            println!("all subproblems above layer {layer} are closed");
+        }
+    }
+    fn on_finish(&mut self, completion: &Completion) {
+        if self.0 >= 2 {
+            match completion.best_value {
+                Some(value) => println!("search ended: best value = {value}, exact = {}", completion.is_exact),
+                None => println!("search ended: no solution, exact = {}", completion.is_exact),
+            }
+        }
+    }
+}
+
+// ############################################################################
+// #### TESTS #################################################################
+// ############################################################################
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn a_silent_print_reporter_can_handle_all_the_events() {
+        // this is a smoke test: at verbosity zero, none of the notifications
+        // should print (or panic)
+        let mut reporter = PrintReporter(0);
+        reporter.on_start();
+        reporter.on_new_bound(10, 100);
+        reporter.on_layer(3);
+        reporter.on_finish(&Completion { is_exact: true, best_value: Some(42) });
+    }
+}
@@ -0,0 +1,463 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! This module provides the implementation of a restarting branch-and-bound
+//! MDD solver. Borrowing from the SAT and CP worlds, this solver runs the
+//! usual branch-and-bound with a small maximum width for a bounded number of
+//! nodes; and whenever the problem could not be solved within that budget, it
+//! discards the fringe and restarts the search from scratch with a
+//! geometrically larger width (and budget). Only the incumbent solution and
+//! the dominance front survive a restart, so no feasible progress is ever
+//! lost. Restarting with a growing width is an effective way to escape the
+//! bad merging decisions which narrow relaxed DDs tend to make early on.
+
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+use crate::*;
+
+/// This is a branch-and-bound solver which proceeds by restarts with a
+/// geometrically increasing maximum width. Each run delegates to a
+/// `SequentialSolver` compiling its DDs with a fixed width and exploring at
+/// most a given number of nodes. When the budget of a run is exhausted before
+/// the problem is solved, the fringe is discarded and a new run is started
+/// with a `width_factor` times larger width and a `budget_factor` times
+/// larger node budget. The incumbent of the previous runs is used to warm
+/// start each new run, and since the dominance checker is shared by all runs,
+/// the dominance front is retained across restarts as well.
+///
+/// The user-provided `Cutoff` is honored across runs: when it kicks in, the
+/// solver stops for good instead of restarting.
+pub struct RestartSolver<'a, State, D = DefaultMDDLEL<State>, C = EmptyCache<State>>
+where
+    D: DecisionDiagram<State = State> + Default,
+    C: Cache<State = State> + Default,
+{
+    /// A reference to the problem being solved with branch-and-bound MDD
+    problem: &'a (dyn Problem<State = State>),
+    /// The relaxation used when a DD layer grows too large
+    relaxation: &'a (dyn Relaxation<State = State>),
+    /// The ranking heuristic used to discriminate the most promising from
+    /// the least promising states
+    ranking: &'a (dyn StateRanking<State = State>),
+    /// The dominance checker which is shared by all the runs (this is what
+    /// allows the dominance front to be retained across restarts)
+    dominance: &'a (dyn DominanceChecker<State = State>),
+    /// A cutoff heuristic meant to decide when to stop the resolution of
+    /// the problem for good (that is, without restarting)
+    cutoff: &'a (dyn Cutoff),
+    /// The fringe which is lent to each run and discarded between two runs
+    fringe: &'a mut (dyn Fringe<State = State>),
+
+    /// The maximum width imposed on the DDs of the very first run
+    initial_width: usize,
+    /// The geometric factor by which the maximum width is multiplied upon
+    /// each restart
+    width_factor: usize,
+    /// The number of nodes which the very first run may explore
+    initial_budget: usize,
+    /// The geometric factor by which the node budget is multiplied upon each
+    /// restart
+    budget_factor: usize,
+
+    /// This is the value of the best known lower bound.
+    best_lb: isize,
+    /// This is the value of the best known upper bound.
+    best_ub: isize,
+    /// If set, this keeps the info about the best solution so far.
+    best_sol: Option<Solution>,
+    /// This is a counter that tracks the total number of nodes that have been
+    /// explored, all runs taken together.
+    explored: usize,
+    /// If we decide not to go through a complete proof of optimality, this is
+    /// the reason why we took that decision.
+    abort_proof: Option<Reason>,
+
+    /// This is just a marker that allows us to remember the exact type of the
+    /// mdds and cache which the delegate solvers must instantiate.
+    _phantom: PhantomData<(D, C)>,
+}
+
+impl<'a, State, D, C> RestartSolver<'a, State, D, C>
+where
+    State: Eq + Hash + Clone,
+    D: DecisionDiagram<State = State> + Default,
+    C: Cache<State = State> + Default,
+{
+    pub fn new(
+        problem: &'a (dyn Problem<State = State>),
+        relaxation: &'a (dyn Relaxation<State = State>),
+        ranking: &'a (dyn StateRanking<State = State>),
+        dominance: &'a (dyn DominanceChecker<State = State>),
+        cutoff: &'a (dyn Cutoff),
+        fringe: &'a mut (dyn Fringe<State = State>),
+    ) -> Self {
+        Self {
+            problem,
+            relaxation,
+            ranking,
+            dominance,
+            cutoff,
+            fringe,
+            //
+            initial_width: 2,
+            width_factor: 2,
+            initial_budget: 1000,
+            budget_factor: 2,
+            //
+            best_lb: isize::MIN,
+            best_ub: isize::MAX,
+            best_sol: None,
+            explored: 0,
+            abort_proof: None,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Sets the maximum width imposed on the DDs of the very first run
+    pub fn with_initial_width(mut self, initial_width: usize) -> Self {
+        self.initial_width = initial_width;
+        self
+    }
+    /// Sets the geometric factor by which the maximum width is multiplied
+    /// upon each restart
+    pub fn with_width_factor(mut self, width_factor: usize) -> Self {
+        self.width_factor = width_factor;
+        self
+    }
+    /// Sets the number of nodes which the very first run may explore
+    pub fn with_initial_budget(mut self, initial_budget: usize) -> Self {
+        self.initial_budget = initial_budget;
+        self
+    }
+    /// Sets the geometric factor by which the node budget is multiplied upon
+    /// each restart
+    pub fn with_budget_factor(mut self, budget_factor: usize) -> Self {
+        self.budget_factor = budget_factor;
+        self
+    }
+}
+
+impl<'a, State, D, C> Solver for RestartSolver<'a, State, D, C>
+where
+    State: Eq + PartialEq + Hash + Clone,
+    D: DecisionDiagram<State = State> + Default,
+    C: Cache<State = State> + Default,
+{
+    /// Repeatedly applies the sequential branch-and-bound algorithm with a
+    /// geometrically increasing maximum width (and node budget) until either
+    /// the problem is solved to optimality or the cutoff criterion is met.
+    fn maximize(&mut self) -> Completion {
+        let mut width = self.initial_width.max(1);
+        let mut budget = self.initial_budget.max(1);
+
+        loop {
+            let width_heu = FixedWidth(width);
+            let mut solver = SequentialSolver::<State, D, C>::custom(
+                self.problem,
+                self.relaxation,
+                self.ranking,
+                &width_heu,
+                self.dominance,
+                self.cutoff,
+                &mut *self.fringe,
+            )
+            .with_node_budget(budget);
+
+            // warm start the run with the incumbent of the previous ones
+            if let Some(incumbent) = self.best_sol.clone() {
+                solver.set_primal(self.best_lb, incumbent);
+            }
+
+            let completion = solver.maximize();
+            self.explored += solver.explored();
+            if solver.best_value().is_some() && solver.best_lower_bound() > self.best_lb {
+                self.best_lb = solver.best_lower_bound();
+                self.best_sol = solver.best_solution();
+            }
+            // each run is rooted in the original problem: its upper bound is
+            // globally valid and can only sharpen the one we already knew
+            self.best_ub = self.best_ub.min(solver.best_upper_bound()).max(self.best_lb);
+
+            if completion.is_exact {
+                self.abort_proof = None;
+                break;
+            }
+            if self.cutoff.must_stop() {
+                // the user-provided cutoff kicked in: stop for good
+                self.abort_proof = Some(Reason::CutoffOccurred);
+                break;
+            }
+            // release the borrow which the solver holds on the fringe
+            drop(solver);
+
+            // the node budget of this run was exhausted: discard the fringe
+            // and restart with a geometrically larger width and budget
+            self.fringe.clear();
+            width = width.saturating_mul(self.width_factor.max(2));
+            budget = budget.saturating_mul(self.budget_factor.max(2));
+        }
+
+        Completion {
+            is_exact: self.abort_proof.is_none(),
+            best_value: self.best_sol.as_ref().map(|_| self.best_lb),
+        }
+    }
+
+    /// Returns the best solution that has been identified for this problem.
+    fn best_solution(&self) -> Option<Solution> {
+        self.best_sol.clone()
+    }
+    /// Returns the value of the best solution that has been identified for
+    /// this problem.
+    fn best_value(&self) -> Option<isize> {
+        self.best_sol.as_ref().map(|_sol| self.best_lb)
+    }
+    /// Returns the value of the best lower bound that has been identified for
+    /// this problem.
+    fn best_lower_bound(&self) -> isize {
+        self.best_lb
+    }
+    /// Returns the value of the best upper bound that has been identified for
+    /// this problem.
+    fn best_upper_bound(&self) -> isize {
+        self.best_ub
+    }
+    /// Sets a primal (best known value and solution) of the problem.
+    fn set_primal(&mut self, value: isize, solution: Solution) {
+        if value > self.best_lb {
+            self.best_sol = Some(solution);
+            self.best_lb = value;
+        }
+    }
+    /// Returns the number of nodes that have been explored so far, all runs
+    /// taken together.
+    fn explored(&self) -> usize {
+        self.explored
+    }
+}
+
+// ############################################################################
+// #### TESTS #################################################################
+// ############################################################################
+
+/// As for the other solvers, these tests essentially validate the
+/// configuration capabilities of the restart solver and check the maximize
+/// function against a knapsack instance with a known optimum.
+
+#[cfg(test)]
+mod test_solver {
+    use crate::*;
+
+    type RestartLel<'a, T> = RestartSolver<'a, T, DefaultMDDLEL<T>, EmptyCache<T>>;
+
+    #[test]
+    fn by_default_best_lb_is_min_infinity() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let solver = RestartLel::new(
+            &problem,
+            &relax,
+            &ranking,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+
+        assert_eq!(isize::min_value(), solver.best_lower_bound());
+    }
+    #[test]
+    fn by_default_best_ub_is_plus_infinity() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let solver = RestartLel::new(
+            &problem,
+            &relax,
+            &ranking,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+
+        assert_eq!(isize::max_value(), solver.best_upper_bound());
+    }
+    #[test]
+    fn restarts_yield_the_optimum() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 210, 12, 5, 100, 120, 110],
+            weight  : vec![10,  45, 20, 4,  20,  30,  50]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        // a tiny width and budget force several restarts before the instance
+        // can be solved
+        let mut solver = RestartLel::new(
+            &problem,
+            &relax,
+            &ranking,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        )
+        .with_initial_width(1)
+        .with_width_factor(2)
+        .with_initial_budget(1)
+        .with_budget_factor(2);
+
+        let Completion{is_exact, best_value} = solver.maximize();
+        assert!(is_exact);
+        assert_eq!(Some(220), best_value);
+        assert!(solver.best_solution().is_some());
+    }
+    #[test]
+    fn the_primal_is_kept_across_restarts() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = RestartLel::new(
+            &problem,
+            &relax,
+            &ranking,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        )
+        .with_initial_budget(1);
+
+        let d1  = Decision{variable: Variable(0), value: 10};
+        let sol = Solution::new(vec![d1]);
+        solver.set_primal(10000, sol);
+
+        // the primal is better than any feasible solution: it survives every
+        // restart and ends up being the reported optimum
+        let maximized = solver.maximize();
+        assert!(maximized.is_exact);
+        assert_eq!(maximized.best_value, Some(10000));
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    struct KnapsackState {
+        depth: usize,
+        capacity: usize
+    }
+    struct Knapsack {
+        capacity: usize,
+        profit: Vec<usize>,
+        weight: Vec<usize>,
+    }
+
+    const TAKE_IT: isize = 1;
+    const LEAVE_IT_OUT: isize = 0;
+
+    impl Problem for Knapsack {
+        type State = KnapsackState;
+        fn nb_variables(&self) -> usize {
+            self.profit.len()
+        }
+        fn initial_state(&self) -> Self::State {
+            KnapsackState{ depth: 0, capacity: self.capacity }
+        }
+        fn initial_value(&self) -> isize {
+            0
+        }
+        fn transition(&self, state: &Self::State, dec: Decision) -> Self::State {
+            let mut ret = *state;
+            ret.depth  += 1;
+            if dec.value == TAKE_IT {
+                ret.capacity -= self.weight[dec.variable.id()]
+            }
+            ret
+        }
+        fn transition_cost(&self, _state: &Self::State, _: &Self::State, dec: Decision) -> isize {
+            self.profit[dec.variable.id()] as isize * dec.value
+        }
+        fn next_variable(&self, depth: usize, _: &mut dyn Iterator<Item = &Self::State>) -> Option<Variable> {
+            let n = self.nb_variables();
+            if depth < n {
+                Some(Variable(depth))
+            } else {
+                None
+            }
+        }
+        fn for_each_in_domain(&self, variable: Variable, state: &Self::State, f: &mut dyn DecisionCallback)
+        {
+            if state.capacity >= self.weight[variable.id()] {
+                f.apply(Decision { variable, value: TAKE_IT });
+                f.apply(Decision { variable, value: LEAVE_IT_OUT });
+            } else {
+                f.apply(Decision { variable, value: LEAVE_IT_OUT });
+            }
+        }
+    }
+    struct KPRelax<'a>{pb: &'a Knapsack}
+    impl Relaxation for KPRelax<'_> {
+        type State = KnapsackState;
+
+        fn merge(&self, states: &mut dyn Iterator<Item = &Self::State>) -> Self::State {
+            states.max_by_key(|node| node.capacity).copied().unwrap()
+        }
+        fn relax(&self, _source: &Self::State, _dest: &Self::State, _merged: &Self::State, _decision: Decision, cost: isize) -> isize {
+            cost
+        }
+        fn fast_upper_bound(&self, state: &Self::State) -> isize {
+            let mut tot = 0;
+            for var in state.depth..self.pb.nb_variables() {
+                if self.pb.weight[var] <= state.capacity {
+                    tot += self.pb.profit[var];
+                }
+            }
+            tot as isize
+        }
+    }
+    struct KPRanking;
+    impl StateRanking for KPRanking {
+        type State = KnapsackState;
+
+        fn compare(&self, a: &Self::State, b: &Self::State) -> std::cmp::Ordering {
+            a.capacity.cmp(&b.capacity)
+        }
+    }
+}
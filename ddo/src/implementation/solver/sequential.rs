@@ -25,10 +25,95 @@
 //! you would be using a constrained environment (e.g. a python interpreter)
 //! where multithreading is not an option; then you might want to use this 
 //! implementation instead.
+use std::cell::Cell;
 use std::clone::Clone;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
 use std::{sync::Arc, hash::Hash};
 
-use crate::{Fringe, Decision, Problem, Relaxation, StateRanking, WidthHeuristic, Cutoff, SubProblem, DecisionDiagram, CompilationInput, CompilationType, Solver, Solution, Completion, Reason, Cache, EmptyCache, DefaultMDDLEL, DominanceChecker};
+use crate::{Fringe, Decision, Variable, Problem, Relaxation, StateRanking, WidthHeuristic, Cutoff, SubProblem, SubProblemSummary, DecisionDiagram,CompilationInput, CompilationType, Solver, Solution, Completion, Reason, Cache, EmptyCache, EmptyDominanceChecker, DefaultMDDLEL, DominanceChecker, DominanceCheckResult, FixedDecisions, ProofEntry, PruningReason, TimeBreakdown, SolverStats, SearchTrace, TraceEntry, SearchReporter};
+
+/// Starts one of the profiling timers. This returns `None` (and the whole
+/// instrumentation boils down to nothing) when the `profiling` feature is
+/// disabled.
+#[cfg(feature = "profiling")]
+fn timer_start() -> Option<Instant> {
+    Some(Instant::now())
+}
+#[cfg(not(feature = "profiling"))]
+fn timer_start() -> Option<Instant> {
+    None
+}
+/// Returns the time elapsed since the given timer was started (zero when the
+/// `profiling` feature is disabled)
+fn timer_elapsed(start: Option<Instant>) -> Duration {
+    start.map(|start| start.elapsed()).unwrap_or_default()
+}
+
+/// A thin decorator which accumulates the time spent in the dominance checks
+/// performed during the compilation of one DD
+struct TimedDominance<'b, State> {
+    /// The dominance checker which effectively performs the checks
+    inner: &'b dyn DominanceChecker<State = State>,
+    /// The time spent in the decorated checker so far
+    elapsed: &'b Cell<Duration>,
+    /// The number of states which the decorated checker has pruned so far
+    pruned: &'b Cell<usize>,
+}
+impl<State> DominanceChecker for TimedDominance<'_, State> {
+    type State = State;
+
+    fn clear_layer(&self, depth: usize) {
+        let start = timer_start();
+        self.inner.clear_layer(depth);
+        self.elapsed.set(self.elapsed.get() + timer_elapsed(start));
+    }
+    fn clear(&self) {
+        let start = timer_start();
+        self.inner.clear();
+        self.elapsed.set(self.elapsed.get() + timer_elapsed(start));
+    }
+    fn is_dominated_or_insert(&self, state: Arc<Self::State>, depth: usize, value: isize) -> DominanceCheckResult {
+        let start = timer_start();
+        let result = self.inner.is_dominated_or_insert(state, depth, value);
+        self.elapsed.set(self.elapsed.get() + timer_elapsed(start));
+        if result.dominated {
+            self.pruned.set(self.pruned.get() + 1);
+        }
+        result
+    }
+    fn cmp(&self, a: &Self::State, val_a: isize, b: &Self::State, val_b: isize) -> std::cmp::Ordering {
+        self.inner.cmp(a, val_a, b, val_b)
+    }
+}
+
+/// A decorator which counts the nodes that the DDs report having developed
+/// during a compilation, while delegating the actual cutoff decision to the
+/// user-supplied criterion.
+struct CountingCutoff<'b> {
+    /// The criterion which effectively decides when to stop
+    inner: &'b dyn Cutoff,
+    /// The number of nodes developed so far
+    expanded: &'b Cell<usize>,
+}
+impl Cutoff for CountingCutoff<'_> {
+    fn must_stop(&self) -> bool {
+        self.inner.must_stop()
+    }
+    fn add_expanded(&self, nb_nodes: usize) {
+        self.expanded.set(self.expanded.get() + nb_nodes);
+        self.inner.add_expanded(nb_nodes);
+    }
+}
+
+/// A callback which receives the difficulty features of every subproblem the
+/// solver effectively explores (see `with_feature_callback`)
+type FeatureCallback<'a, State> = Box<dyn FnMut(&SubProblem<State>, &[f64]) + Send + 'a>;
+
+/// A callback which is notified of every reported incumbent solution
+/// (see `with_on_incumbent`)
+type IncumbentCallback<'a> = Box<dyn FnMut(isize, &[Decision]) + Send + 'a>;
 
 /// The workload a thread can get from the shared state
 enum WorkLoad<T> {
@@ -232,16 +317,100 @@ where D: DecisionDiagram<State = State> + Default,
     /// been explored. That is, the number of nodes that have been popped from
     /// the fringe, and for which a restricted and relaxed mdd have been developed.
     explored: usize,
+    /// If set, the maximum number of nodes this solver is allowed to explore
+    /// before it aborts the search (reporting a cutoff). This is mostly useful
+    /// to bound the effort of one run in a restart scheme.
+    node_budget: Option<usize>,
+    /// If set, the maximum number of incoming edges which may be retained on
+    /// a merged node of a relaxed dd (see `CompilationInput::max_in_degree`).
+    max_in_degree: Option<usize>,
+    /// If set, the decisions which are forced during the whole search: every
+    /// DD is compiled against a view of the problem where the domain of each
+    /// fixed variable is narrowed to its single fixed value (see
+    /// `with_fixed`).
+    fixed: Option<FixedDecisions<'a, dyn Problem<State = State> + 'a>>,
+    /// When this flag is turned on, the solver records one `ProofEntry` for
+    /// each subproblem it closes (see `with_proof_log`).
+    record_proof: bool,
+    /// The trace of all the subproblems which have been closed so far along
+    /// with the reason of their elimination. This only gets populated when
+    /// `record_proof` is turned on.
+    proof_log: Vec<ProofEntry<State>>,
+    /// If set, a callback which is fed, for each subproblem the solver
+    /// effectively explores, a vector of numeric features describing how hard
+    /// that subproblem looks (see `with_feature_callback`).
+    feature_callback: Option<FeatureCallback<'a, State>>,
+    /// When this flag is turned on, the solver records one `TraceEntry` for
+    /// each subproblem it pops off the fringe (see `with_search_trace`).
+    record_trace: bool,
+    /// The trace of all the subproblems which have been popped off the fringe
+    /// so far. This only gets populated when `record_trace` is turned on.
+    search_trace: SearchTrace<State>,
+    /// When this flag is turned on, the solver retains, once the search ends,
+    /// the frontier subproblems justifying the final upper bound (see
+    /// `with_upper_bound_certificate`).
+    record_certificate: bool,
+    /// The path and relaxed bound of every subproblem which was still open
+    /// when the search ended. This only gets populated when
+    /// `record_certificate` is turned on.
+    certificate: Vec<(Vec<Decision>, isize)>,
+    /// When this flag is turned on, the solver retains, whenever the search
+    /// gets aborted, the subproblems which were still open at that point so
+    /// that the search state can be saved and resumed later (see
+    /// `with_checkpointing`).
+    record_checkpoint: bool,
+    /// The subproblems which were still open when the search was aborted.
+    /// This only gets populated when `record_checkpoint` is turned on.
+    interrupted: Vec<SubProblem<State>>,
+    /// A breakdown of where the solving time has been spent so far (only
+    /// populated when the `profiling` feature is enabled)
+    time: TimeBreakdown,
+    /// The work counters which have been accumulated so far (DDs compiled,
+    /// nodes expanded, fringe operations and prunings)
+    stats: SolverStats,
+    /// If set, a callback which gets notified of every reported incumbent
+    /// (see `with_on_incumbent`)
+    on_incumbent: Option<IncumbentCallback<'a>>,
+    /// If set, an observer which gets notified of the salient events of the
+    /// search (see `with_reporter`)
+    reporter: Option<Box<dyn SearchReporter + 'a>>,
+    /// If set, the time series of the incumbent bounds (see `with_bound_log`):
+    /// one `(elapsed, lower bound, upper bound)` sample per change of either
+    /// bound
+    bound_log: Option<Vec<(Duration, isize, isize)>>,
+    /// The instant against which the samples of the bound log are timed (the
+    /// start of the search)
+    bound_log_start: Instant,
     /// This is a counter of the number of nodes in the fringe, for each level of the model
     open_by_layer: Vec<usize>,
     /// This is the index of the first level above which there are no nodes in the fringe
     first_active_layer: usize,
     /// This is the value of the best known lower bound.
     best_lb: isize,
+    /// The best lower bound derived from the `fast_lower_bound` estimates of
+    /// the relaxation. It proves that a feasible completion of that value
+    /// exists but, unlike `best_lb`, it is not backed by a concrete solution:
+    /// it is only ever used for pruning (see `pruning_bound`) and never flows
+    /// into `best_value`.
+    rough_lb: isize,
     /// This is the value of the best known upper bound.
     best_ub: isize,
+    /// The minimum improvement over the last reported incumbent which is
+    /// required before the solver acknowledges (reports) a new incumbent to
+    /// its observers. The true best value and solution are always tracked
+    /// regardless of this threshold: it only throttles the reporting cadence.
+    min_improvement: isize,
+    /// This is the value of the last incumbent that has been reported; that is
+    /// the last incumbent which improved its predecessor by at least
+    /// `min_improvement`.
+    reported_lb: isize,
     /// If set, this keeps the info about the best solution so far.
-    best_sol: Option<Vec<Decision>>,
+    best_sol: Option<Solution>,
+    /// Whether the current incumbent was extracted by the search itself from
+    /// a fully exact (merge-free) portion of a compiled DD. This is false for
+    /// the solutions injected from the outside (via `set_primal` or a warm
+    /// start), whose provenance is unknown to the solver.
+    best_path_exact: bool,
     /// If we decide not to go through a complete proof of optimality, this is
     /// the reason why we took that decision.
     abort_proof: Option<Reason>,
@@ -249,11 +418,46 @@ where D: DecisionDiagram<State = State> + Default,
     /// This is just a marker that allows us to remember the exact type of the
     /// mdds to be instantiated.
     mdd: D,
+    /// When this flag is turned on, the solver keeps the relaxed DD it compiled
+    /// for the root subproblem so that it can later be inspected (typically to
+    /// visualize the very first bound and cut-set of the search).
+    capture_root_dd: bool,
+    /// The relaxed DD compiled for the root subproblem, if its capture has been
+    /// requested with `with_capture_root_dd`.
+    root_dd: Option<D>,
+    /// The upper bound derived from the very first relaxed DD compiled for the
+    /// root subproblem. Unlike `best_ub`, it never gets tightened by the
+    /// branch-and-bound: it measures the quality of the relaxation alone.
+    root_ub: Option<isize>,
+    /// If set, counts for every (variable, value) pair the number of DD edges
+    /// that assigned that value to that variable over all the compilations of
+    /// this search (see `with_value_histogram`). This is a crude marginal
+    /// over the *explored* space.
+    value_histogram: Option<HashMap<(Variable, isize), u64>>,
     /// Data structure containing info about past compilations used to prune the search
     cache: C,
     dominance: &'a (dyn DominanceChecker<State = State>),
 }
 
+/// A snapshot of an interrupted search: the entire fringe of open subproblems
+/// along with the best bounds and solution found so far. This is what gets
+/// (de)serialized by `save_state` and `resume`, e.g. to stop a long run and
+/// continue it later on a different machine.
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Checkpoint<State> {
+    /// The subproblems which were still open when the search was interrupted
+    pub fringe: Vec<SubProblem<State>>,
+    /// The best known lower bound at the time of the interruption
+    pub best_lb: isize,
+    /// The best known upper bound at the time of the interruption
+    pub best_ub: isize,
+    /// The best solution found so far, if any
+    pub best_sol: Option<Solution>,
+    /// The number of nodes which had been explored so far
+    pub explored: usize,
+}
+
 impl<'a, State, D, C>  SequentialSolver<'a, State, D, C>
 where 
     State: Eq + Hash + Clone,
@@ -289,119 +493,1278 @@ where
             cutoff,
             //
             best_sol: None,
+            best_path_exact: false,
             best_lb: isize::MIN,
+            rough_lb: isize::MIN,
             best_ub: isize::MAX,
+            min_improvement: 0,
+            reported_lb: isize::MIN,
             fringe,
             explored: 0,
+            node_budget: None,
+            max_in_degree: None,
+            fixed: None,
+            record_proof: false,
+            proof_log: vec![],
+            feature_callback: None,
+            record_trace: false,
+            search_trace: SearchTrace::default(),
+            record_certificate: false,
+            certificate: vec![],
+            record_checkpoint: false,
+            interrupted: vec![],
+            time: TimeBreakdown::default(),
+            stats: SolverStats::default(),
+            on_incumbent: None,
+            reporter: None,
+            bound_log: None,
+            bound_log_start: Instant::now(),
             open_by_layer: vec![0; problem.nb_variables() + 1],
             first_active_layer: 0,
             abort_proof: None,
             mdd: D::default(),
+            capture_root_dd: false,
+            root_dd: None,
+            root_ub: None,
+            value_histogram: None,
             cache: C::default(),
             dominance,
         }
     }
 
-    /// This method initializes the problem resolution. Put more simply, this
-    /// method posts the root node of the mdd onto the fringe so that a thread
-    /// can pick it up and the processing can be bootstrapped.
-    fn initialize(&mut self) {
-        let root = self.root_node();
-        self.cache.initialize(self.problem);
-        self.fringe.push(root);
-        self.open_by_layer[0] += 1;
+    /// Requests that the solver keeps the relaxed DD it compiles for the root
+    /// subproblem (the very first relaxed DD of the search). Only the root DD
+    /// is captured so as to bound the memory consumption. This is mostly
+    /// useful for debugging and teaching purposes: the root DD is the diagram
+    /// that yields the initial upper bound and the first cut-set, and it can
+    /// be fed to the visualization tooling (`as_graphviz`).
+    pub fn with_capture_root_dd(mut self) -> Self {
+        self.capture_root_dd = true;
+        self
     }
 
-    fn root_node(&self) -> SubProblem<State> {
-        SubProblem {
+    /// Returns the relaxed DD that was compiled for the root subproblem if its
+    /// capture was requested with `with_capture_root_dd` prior to solving.
+    /// This returns `None` either when capture was not requested, or when the
+    /// solver never needed to compile a relaxed DD for the root (that is, when
+    /// the restricted DD of the root was already exact).
+    pub fn root_dd(&self) -> Option<&D> {
+        self.root_dd.as_ref()
+    }
+
+    /// Returns the upper bound proved by the very first relaxed DD the solver
+    /// compiled for the root subproblem. Contrary to `best_upper_bound`, this
+    /// bound is never tightened afterwards: comparing the two tells how much
+    /// of the final bound is owed to the relaxation alone and how much to the
+    /// branch-and-bound. This returns `None` as long as no relaxed DD has
+    /// been compiled for the root (in particular when the restricted DD of
+    /// the root was already exact).
+    pub fn root_upper_bound(&self) -> Option<isize> {
+        self.root_ub
+    }
+
+    /// Sets the minimum improvement over the last reported incumbent which is
+    /// required before the solver acknowledges a new incumbent to its
+    /// observers. This is useful in anytime settings where tiny improvements
+    /// would otherwise generate a lot of reporting churn. Note that this only
+    /// affects the reporting cadence: the solver keeps tracking the true best
+    /// value and solution internally so the search is left unchanged.
+    pub fn with_min_improvement(mut self, min_improvement: isize) -> Self {
+        self.min_improvement = min_improvement;
+        self
+    }
+
+    /// Imposes a maximum number of nodes which this solver may explore before
+    /// it aborts the search. When the budget is exhausted, the search stops
+    /// with a cutoff (exactly as if a `Cutoff` heuristic had kicked in). This
+    /// is mostly useful to bound the effort of one run in a restart scheme.
+    pub fn with_node_budget(mut self, node_budget: usize) -> Self {
+        self.node_budget = Some(node_budget);
+        self
+    }
+
+    /// Caps the number of incoming edges which may be retained on a merged
+    /// node of a relaxed dd (keeping the edges with the best value). This
+    /// keeps the relaxed DDs sparse on wide problems, but beware: dropping
+    /// incoming edges removes paths from the relaxed DD, so the bounds it
+    /// yields are no longer guaranteed to be valid upper bounds. Only use
+    /// this option when an approximate resolution is acceptable.
+    pub fn with_max_in_degree(mut self, max_in_degree: usize) -> Self {
+        self.max_in_degree = Some(max_in_degree);
+        self
+    }
+
+    /// Requests that the solver records, for each fringe subproblem it
+    /// closes, the reason why that subproblem was eliminated (bound-pruned,
+    /// cache-pruned, or exactly solved with some value). The resulting trace
+    /// is retrievable with `proof_log` and provides an auditable account of
+    /// why each region of the search space was discarded. Beware that the log
+    /// keeps a copy of the state of every closed subproblem, which can amount
+    /// to a lot of memory on large instances; this is why the recording is
+    /// opt-in.
+    pub fn with_proof_log(mut self) -> Self {
+        self.record_proof = true;
+        self
+    }
+
+    /// Returns the trace of all the subproblems which have been closed so
+    /// far, along with the reason of their elimination. This is empty unless
+    /// recording was requested with `with_proof_log`.
+    pub fn proof_log(&self) -> &[ProofEntry<State>] {
+        &self.proof_log
+    }
+
+    /// Requests that the solver records one `TraceEntry` for every subproblem
+    /// it pops off the fringe (its bounds at the time of the pop and the
+    /// decision path leading to it). The resulting trace is retrievable with
+    /// `search_trace` and can be rendered with `SearchTrace::as_graphviz` to
+    /// visualize the overall branch-and-bound exploration tree -- a precious
+    /// complement to the single-DD visualization when trying to understand
+    /// why the solver dives where it does. Beware that the trace keeps a copy
+    /// of the state of every popped subproblem, which can amount to a lot of
+    /// memory on large instances; this is why the recording is opt-in.
+    pub fn with_search_trace(mut self) -> Self {
+        self.record_trace = true;
+        self
+    }
+
+    /// Returns the trace of all the subproblems which have been popped off
+    /// the fringe so far, in exploration order. This is empty unless
+    /// recording was requested with `with_search_trace`.
+    pub fn search_trace(&self) -> &SearchTrace<State> {
+        &self.search_trace
+    }
+
+    /// Requests that the solver retains, once the search ends, a certificate
+    /// justifying the final `best_upper_bound`: the decision path of every
+    /// subproblem which was still open when the search stopped, along with
+    /// the relaxed bound that was attached to it when it was enqueued. The
+    /// final upper bound is the maximum of the best known lower bound and of
+    /// the bounds listed in the certificate; when the search completes (the
+    /// frontier is exhausted), the certificate is therefore empty and the
+    /// bound is justified by the incumbent alone.
+    pub fn with_upper_bound_certificate(mut self) -> Self {
+        self.record_certificate = true;
+        self
+    }
+
+    /// Returns the certificate justifying the final upper bound: the path
+    /// and relaxed bound of every subproblem which was still open when the
+    /// search ended. This is empty unless retention was requested with
+    /// `with_upper_bound_certificate` (and empty as well when the search
+    /// completed, since an exhausted frontier leaves nothing to justify).
+    pub fn upper_bound_certificate(&self) -> &[(Vec<Decision>, isize)] {
+        &self.certificate
+    }
+
+    /// Returns the best solution together with the cost contributed by each
+    /// of its decisions, i.e. the `transition_cost` of the corresponding arc
+    /// of the optimal path. Summing these costs and the initial value of the
+    /// problem yields `best_value`. The breakdown is obtained by replaying
+    /// the transitions of the model along the best path: incumbent solutions
+    /// always stem from exact (merge-free) paths, so the replayed costs are
+    /// exactly the ones the solver accounted for -- no relaxed-edge cost
+    /// offset can sneak in.
+    pub fn best_solution_with_costs(&self) -> Option<Vec<(Decision, isize)>> {
+        self.best_sol.as_ref().map(|sol| Self::replay_costs(self.problem, sol))
+    }
+
+    /// Returns true iff the current best solution was extracted by the search
+    /// itself from a fully exact portion of a compiled DD (no merged node on
+    /// its path). This is how the engine always admits its own incumbents, so
+    /// the only ways to get `false` with a solution installed are a primal
+    /// injected from the outside (`set_primal`, warm start) whose provenance
+    /// the solver cannot vouch for. When no solution is known at all, this
+    /// returns false.
+    pub fn best_path_was_exact(&self) -> bool {
+        self.best_sol.is_some() && self.best_path_exact
+    }
+
+    /// Returns, for every (variable, value) pair, the number of DD edges
+    /// which assigned that value to that variable over all the compilations
+    /// of the search so far -- a crude per-variable marginal. A variable
+    /// which only ever shows up with a single value is effectively fixed
+    /// over the explored space and could be preprocessed out of the model.
+    ///
+    /// # Warning
+    /// The histogram reflects the *explored* space, not the feasible region:
+    /// restriction and pruning skew the counts towards the promising
+    /// assignments, and the edges of a relaxed DD may even lie on no feasible
+    /// path at all. Treat it as a profiling aid, not as an exact count. The
+    /// histogram stays empty unless the solver was configured with
+    /// `with_value_histogram`.
+    pub fn variable_value_histogram(&self) -> HashMap<(Variable, isize), u64> {
+        self.value_histogram.clone().unwrap_or_default()
+    }
+
+    /// Returns the time series of the incumbent bounds recorded so far: one
+    /// `(elapsed, lower bound, upper bound)` sample per change of either
+    /// bound, timed from the start of the search. The series stays empty
+    /// unless the solver was configured with `with_bound_log`.
+    pub fn bound_history(&self) -> Vec<(Duration, isize, isize)> {
+        self.bound_log.clone().unwrap_or_default()
+    }
+
+    /// Validates the feasible completion which the relaxation suggested for
+    /// the given subproblem: the decisions are replayed from the subproblem
+    /// state, checking at each step that the decision assigns the variable
+    /// being branched on and belongs to its domain. When the completion
+    /// checks out, this returns the value of the full solution along with
+    /// its decisions (the path to the subproblem extended with the decisions
+    /// of the completion); otherwise it returns None and the suggestion is
+    /// simply ignored.
+    fn validate_completion(
+        problem: &dyn Problem<State = State>,
+        node: &SubProblem<State>,
+        completion: &[Decision],
+    ) -> Option<(isize, Vec<Decision>)> {
+        let mut state = node.state.as_ref().clone();
+        let mut value = node.value;
+        let mut depth = node.depth;
+        let mut decisions = node.path.clone();
+
+        while let Some(var) = problem.next_variable(depth, &mut std::iter::once(&state)) {
+            let decision = completion.iter().copied().find(|d| d.variable == var)?;
+            let mut feasible = false;
+            problem.for_each_in_domain(var, &state, &mut |d: Decision| feasible |= d == decision);
+            if !feasible {
+                return None;
+            }
+            let next = problem.transition_checked(&state, decision)?;
+            value = value.saturating_add(problem.transition_cost(&state, &next, decision));
+            state = next;
+            depth += 1;
+            decisions.push(decision);
+        }
+        Some((value, decisions))
+    }
+
+    /// Replays the transitions of the model along the given solution and
+    /// returns each decision paired with its transition cost
+    fn replay_costs(problem: &dyn Problem<State = State>, sol: &Solution) -> Vec<(Decision, isize)> {
+        let order = problem.static_order();
+        let mut costs = Vec::with_capacity(sol.len());
+        let mut state = problem.initial_state();
+        for depth in 0..sol.len() {
+            let variable = match order.as_ref() {
+                Some(order) => order.get(depth).copied(),
+                None => problem.next_variable(depth, &mut std::iter::once(&state)),
+            };
+            let decision = variable.and_then(|var| sol.iter().copied().find(|d| d.variable == var));
+            if let Some(decision) = decision {
+                let next = problem.transition(&state, decision);
+                costs.push((decision, problem.transition_cost(&state, &next, decision)));
+                state = next;
+            } else {
+                break;
+            }
+        }
+        costs
+    }
+
+    /// Requests that the solver retains, whenever the search gets aborted
+    /// (a cutoff occurred or the node budget was exhausted), the subproblems
+    /// which were still open at that point -- including the one whose
+    /// processing was interrupted. `save_state` can then serialize them along
+    /// with the best bounds and solution so that the search may later be
+    /// resumed exactly where it left off.
+    pub fn with_checkpointing(mut self) -> Self {
+        self.record_checkpoint = true;
+        self
+    }
+
+    /// Replaces the decision diagram used by this solver with the given one.
+    /// By default, the solver compiles its diagrams with `D::default()`; this
+    /// lets you select a diagram that carries some configuration of its own,
+    /// e.g. `HybridMdd::new(threshold)` to restrict the shallow layers and
+    /// relax the deep ones within the relaxed compilations.
+    pub fn with_mdd(mut self, mdd: D) -> Self {
+        self.mdd = mdd;
+        self
+    }
+
+    /// Registers a callback which gets invoked every time a new incumbent is
+    /// acknowledged -- that is, every time the best known lower bound
+    /// improves on the last reported one by at least the configured
+    /// `min_improvement` (any strict improvement, by default). The callback
+    /// receives the new objective value and the corresponding decision
+    /// vector, which lets you log progress, checkpoint, or feed the incumbent
+    /// into another system without waiting for `maximize` to return.
+    pub fn with_on_incumbent(mut self, callback: impl FnMut(isize, &[Decision]) + Send + 'a) -> Self {
+        self.on_incumbent = Some(Box::new(callback));
+        self
+    }
+
+    /// Registers an observer which gets notified of the salient events of
+    /// the search: its start and end, the improvements of the incumbent
+    /// bounds, and the progress of the active layer. Use `PrintReporter` to
+    /// log the progress on stdout, or provide your own `SearchReporter` to
+    /// forward the events to a logging facade, a file, or a GUI.
+    pub fn with_reporter(mut self, reporter: impl SearchReporter + 'a) -> Self {
+        self.reporter = Some(Box::new(reporter));
+        self
+    }
+
+    /// Asks the solver to record the evolution of the incumbent bounds as a
+    /// time series: whenever either bound changes, an
+    /// `(elapsed, lower bound, upper bound)` sample is appended, timed from
+    /// the start of the search. On the contrary to a `SearchReporter`, this
+    /// yields a structured, queryable log (see `bound_history`) which comes
+    /// in handy to plot convergence curves in automated experiments.
+    pub fn with_bound_log(mut self) -> Self {
+        self.bound_log = Some(vec![]);
+        self
+    }
+
+    /// Asks the solver to record the per-(variable, value) histogram of the
+    /// DD edges compiled during the search (see `variable_value_histogram`).
+    /// Harvesting the edges of every compiled DD is pure overhead when the
+    /// histogram is not consulted, so the recording is off by default.
+    pub fn with_value_histogram(mut self) -> Self {
+        self.value_histogram = Some(Default::default());
+        self
+    }
+
+    /// Seeds the search with an externally-known lower bound on the optimal
+    /// value (warm start). Subproblems whose rough upper bound does not exceed
+    /// this value are pruned right away, even before the first incumbent is
+    /// found. The bound is only taken when it improves on the current one, and
+    /// since no solution comes with it, `best_solution` stays `None` until the
+    /// search actually finds one.
+    pub fn with_initial_lower_bound(mut self, lower_bound: isize) -> Self {
+        self.best_lb = self.best_lb.max(lower_bound);
+        self
+    }
+
+    /// Seeds the search with a known feasible solution (warm start). The
+    /// decisions are validated by replaying them against the model from the
+    /// root: each one must assign the variable being branched on, belong to
+    /// its domain and be accepted by the (checked) transitions. The replay
+    /// computes the value of the solution, which is then installed as the
+    /// initial incumbent. If nothing better is found, `best_solution` returns
+    /// this very solution.
+    ///
+    /// # Panics
+    /// When the given solution does not assign every variable of the problem,
+    /// or when it is infeasible (some decision falls outside the domain of
+    /// its variable or is rejected by the transitions): installing it as the
+    /// incumbent would silently corrupt `best_value`.
+    pub fn with_initial_solution(mut self, solution: Vec<Decision>) -> Self {
+        assert_eq!(self.problem.nb_variables(), solution.len(),
+            "the initial solution must assign every variable of the problem");
+
+        let root = SubProblem {
             state: Arc::new(self.problem.initial_state()),
             value: self.problem.initial_value(),
             path: vec![],
             ub: isize::MAX,
             depth: 0,
+        };
+        let (value, solution) = Self::validate_completion(self.problem, &root, &solution)
+            .expect("the initial solution must be feasible for the problem");
+        self.set_primal(value, Solution::new(solution));
+        self
+    }
+
+    /// Forces the given decisions during the whole search (partial
+    /// assignment): every DD is compiled against a view of the problem where
+    /// the domain of each fixed variable is narrowed to its single fixed
+    /// value, while the other variables are branched on as usual. The search
+    /// thus explores only the subspace consistent with the given decisions,
+    /// which is handy for scenario analysis ("what is the best solution with
+    /// $x_3 = 1$ ?") and for user-guided, interactive solving. The fixed
+    /// values are validated against the domains of the reachable states: a
+    /// fixed value is only ever branched on from the states whose domain
+    /// contains it, so fixing a value which no reachable state admits makes
+    /// the subspace infeasible and the solver reports that no solution
+    /// exists.
+    ///
+    /// # Panics
+    /// When a decision bears on a variable which does not belong to the
+    /// problem, or when two decisions fix the same variable to different
+    /// values.
+    pub fn with_fixed(mut self, fixed: Vec<Decision>) -> Self {
+        self.fixed = Some(FixedDecisions::new(self.problem, fixed));
+        self
+    }
+
+    /// Returns a breakdown of where the solving time has been spent so far:
+    /// compiling restricted DDs, compiling relaxed DDs, operating the fringe
+    /// and performing dominance checks. This tells you whether to invest in a
+    /// faster relaxation, cheaper dominance keys, or a better fringe. The
+    /// underlying instrumentation is compiled away (and the breakdown stays
+    /// zero) when the `profiling` feature is disabled.
+    pub fn time_breakdown(&self) -> TimeBreakdown {
+        self.time
+    }
+
+    /// Returns the work counters accumulated by this solver so far: the
+    /// number of DDs compiled, nodes expanded, fringe operations and
+    /// prunings. These make it easy to compare the effort spent by two solver
+    /// configurations on the same instance.
+    pub fn stats(&self) -> SolverStats {
+        self.stats
+    }
+
+    /// Returns the average out-degree (total edges / total non-leaf nodes)
+    /// which has effectively been realized across all the DDs this solver has
+    /// compiled so far. A high branching factor explains wide DDs and gives a
+    /// quantitative hint as to whether domain reduction (or capping the
+    /// in-degree of merged nodes) would help.
+    pub fn avg_branching_factor(&self) -> f64 {
+        self.mdd.avg_branching_factor()
+    }
+
+    /// Registers a callback which gets invoked for each subproblem the solver
+    /// effectively explores (that is, the subproblems that survive the bound
+    /// and cache pruning checks) with a vector of numeric features describing
+    /// that subproblem. In order, the features are: the depth of the
+    /// subproblem, its root value, its upper bound, the residual gap between
+    /// those two, the maximum layer width allotted to compile its DDs, and
+    /// the number of values in the domain of the variable to branch on. This
+    /// is the raw material one needs to train (or to drive) a learned
+    /// branching or restriction policy without forking the engine.
+    pub fn with_feature_callback(mut self, callback: impl FnMut(&SubProblem<State>, &[f64]) + Send + 'a) -> Self {
+        self.feature_callback = Some(Box::new(callback));
+        self
+    }
+
+    /// Computes the difficulty features of the given subproblem which get fed
+    /// to the feature callback (see `with_feature_callback`)
+    fn difficulty_features(&self, node: &SubProblem<State>, width: usize) -> Vec<f64> {
+        let mut nb_domain_values = 0_usize;
+        if let Some(var) = self.problem.next_variable(node.depth, &mut std::iter::once(node.state.as_ref())) {
+            self.problem.for_each_in_domain(var, node.state.as_ref(), &mut |_: Decision| nb_domain_values += 1);
         }
+        vec![
+            node.depth as f64,
+            node.value as f64,
+            node.ub as f64,
+            node.ub.saturating_sub(node.value) as f64,
+            width as f64,
+            nb_domain_values as f64,
+        ]
     }
 
-    /// This method processes the given `node`. To do so, it reads the current
-    /// best lower bound from the critical data. Then it expands a restricted
-    /// and possibly a relaxed mdd rooted in `node`. If that is necessary,
-    /// it stores cut-set nodes onto the fringe for further parallel processing.
-    fn process_one_node(
+    /// Pushes one entry onto the proof log (when recording is enabled)
+    fn maybe_log_proof(&mut self, node: &SubProblem<State>, reason: PruningReason) {
+        if self.record_proof {
+            self.proof_log.push(ProofEntry {
+                state: node.state.clone(),
+                value: node.value,
+                ub: node.ub,
+                depth: node.depth,
+                reason,
+            });
+        }
+    }
+
+    /// Resets this solver so that the next call to `maximize` starts from a
+    /// clean slate while reusing the allocations of the previous search. This
+    /// is meant for processes which solve many instances in a row: it empties
+    /// the fringe, the threshold cache and the dominance store, forgets the
+    /// incumbent solution and both bounds, and zeroes the exploration
+    /// counters, statistics and time breakdown (along with the proof log,
+    /// certificate, search trace and checkpoint data when those are
+    /// recorded). The configuration of the solver -- budgets, recording
+    /// flags, callbacks and heuristics -- is left untouched.
+    pub fn reset(&mut self) {
+        self.fringe.clear();
+        self.cache.clear();
+        self.dominance.clear();
+        self.best_sol = None;
+        self.best_path_exact = false;
+        self.best_lb = isize::MIN;
+        self.rough_lb = isize::MIN;
+        self.best_ub = isize::MAX;
+        self.reported_lb = isize::MIN;
+        if let Some(log) = self.bound_log.as_mut() { log.clear(); }
+        self.root_ub = None;
+        self.explored = 0;
+        self.open_by_layer.iter_mut().for_each(|x| *x = 0);
+        self.first_active_layer = 0;
+        self.abort_proof = None;
+        self.interrupted.clear();
+        self.proof_log.clear();
+        self.certificate.clear();
+        self.search_trace.entries.clear();
+        if let Some(histogram) = self.value_histogram.as_mut() { histogram.clear(); }
+        self.time = TimeBreakdown::default();
+        self.stats = SolverStats::default();
+    }
+
+    /// Summarizes the subproblems which are still open: the queued fringe
+    /// itself plus the subproblems retained from an interrupted run. Each
+    /// summary reports the depth, value, upper bound and decision path of
+    /// one open subproblem -- enough to decide, after `maximize` returned
+    /// with `is_exact == false`, whether resuming the search (or changing
+    /// its parameters) is worthwhile.
+    ///
+    /// # Warning
+    /// An aborted search clears its fringe: for the snapshot of an
+    /// interrupted run to show anything, retention of the open subproblems
+    /// must have been requested upfront with `with_checkpointing`.
+    pub fn fringe_snapshot(&mut self) -> Vec<SubProblemSummary> {
+        let mut open = vec![];
+        while let Some(subproblem) = self.fringe.pop() {
+            open.push(subproblem);
+        }
+        let mut summaries = open.iter().map(SubProblemSummary::from).collect::<Vec<_>>();
+        for subproblem in open {
+            self.fringe.push(subproblem);
+        }
+        summaries.extend(self.interrupted.iter().map(SubProblemSummary::from));
+        summaries
+    }
+
+    /// Same as `maximize` except that the search is not started from the sole
+    /// initial state of the problem but from the given set of root
+    /// subproblems. This is useful to resume a search from a checkpoint or to
+    /// inject a problem-specific decomposition of the state space.
+    ///
+    /// # Warning
+    /// It is the caller's responsibility to ensure that the given roots
+    /// collectively cover the portion of the state space that must be
+    /// explored: any part of the space unreachable from the given roots is
+    /// simply never considered and the reported optimum only holds relative
+    /// to the covered space.
+    pub fn maximize_from(&mut self, roots: Vec<SubProblem<State>>) -> Completion {
+        self.cache.initialize(self.problem);
+        for root in roots {
+            self.open_by_layer[root.depth] += 1;
+            self.fringe.push(root);
+        }
+        self.solve()
+    }
+
+    /// Serializes the current state of the search (the entire fringe of open
+    /// subproblems, the best bounds and the best solution found so far) with
+    /// the given serializer. The solver is left unchanged: the search can
+    /// either carry on in this process or be resumed later from the snapshot
+    /// with `resume` -- possibly on a different machine.
+    ///
+    /// # Note
+    /// The problem, relaxation and heuristics are *not* part of the snapshot:
+    /// they must be re-provided (identical) to the solver which resumes the
+    /// search. The threshold cache is not saved either: resuming only
+    /// forfeits the prunings it would have allowed, not the correctness of
+    /// the search.
+    ///
+    /// # Warning
+    /// An aborted search clears its fringe: for the snapshot of an
+    /// interrupted run to be of any use, retention of the open subproblems
+    /// must have been requested upfront with `with_checkpointing`.
+    #[cfg(feature = "serde")]
+    pub fn save_state<S>(&mut self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+        State: serde::Serialize,
+    {
+        let mut fringe = vec![];
+        while let Some(open) = self.fringe.pop() {
+            fringe.push(open);
+        }
+        for open in fringe.iter() {
+            self.fringe.push(open.clone());
+        }
+        fringe.extend_from_slice(&self.interrupted);
+        let checkpoint = Checkpoint {
+            fringe,
+            best_lb: self.best_lb,
+            best_ub: self.best_ub,
+            best_sol: self.best_sol.clone(),
+            explored: self.explored,
+        };
+        serde::Serialize::serialize(&checkpoint, serializer)
+    }
+
+    /// Resumes a search which has been interrupted and saved with
+    /// `save_state`: the best bounds and solution are restored and the
+    /// exploration carries on from the saved fringe, exactly where it left
+    /// off. This method must be called on a freshly created solver which has
+    /// been handed the same problem, relaxation and heuristics as the one
+    /// whose state was saved (none of these are serialized).
+    #[cfg(feature = "serde")]
+    pub fn resume<'de, De>(&mut self, deserializer: De) -> Result<Completion, De::Error>
+    where
+        De: serde::Deserializer<'de>,
+        State: serde::Deserialize<'de>,
+    {
+        let checkpoint: Checkpoint<State> = serde::Deserialize::deserialize(deserializer)?;
+        self.best_lb = checkpoint.best_lb;
+        self.best_ub = checkpoint.best_ub;
+        self.best_sol = checkpoint.best_sol;
+        self.reported_lb = checkpoint.best_lb;
+        self.explored = checkpoint.explored;
+        Ok(self.maximize_from(checkpoint.fringe))
+    }
+
+    /// Enumerates the `k` best distinct solutions of the problem, sorted by
+    /// decreasing objective value. The usual branch-and-bound is run, except
+    /// that the pruning threshold is the value of the k-th best solution
+    /// found so far (rather than the single best one) and that every
+    /// merge-free DD contributes its k best paths instead of one.
+    ///
+    /// When fewer than `k` feasible solutions exist, all of them are
+    /// returned. When several solutions are tied at the k-th value, the
+    /// enumeration is truncated: at most `k` solutions are returned and the
+    /// tied ones which did not make it into the result are dropped
+    /// arbitrarily.
+    ///
+    /// # Note
+    /// The dominance and caching prunings are suppressed during this
+    /// enumeration: both may discard provably suboptimal -- yet top-k --
+    /// solutions. When this method returns, `best_value` and `best_solution`
+    /// report the overall optimum, exactly as after a call to `maximize`.
+    pub fn maximize_k(&mut self, k: usize) -> Vec<(isize, Solution)> {
+        let mut k_best = vec![];
+        if k == 0 {
+            return k_best;
+        }
+        self.initialize();
+        loop {
+            match self.get_workload() {
+                WorkLoad::Complete => break,
+                WorkLoad::Aborted => break, // the node budget has been exhausted
+                WorkLoad::WorkItem { node } => {
+                    let outcome = self.process_one_node_k(node, k, &mut k_best);
+                    if let Err(reason) = outcome {
+                        self.abort_search(reason);
+                        break;
+                    }
+                }
+            }
+        }
+        if let Some((value, solution)) = k_best.first() {
+            if *value > self.best_lb {
+                self.best_lb = *value;
+                self.best_sol = Some(solution.clone());
+            }
+        }
+        k_best
+    }
+
+    /// Solves the problem with one single exact DD compilation of unbounded
+    /// width, short-circuiting the whole branch-and-bound machinery (no
+    /// restriction, no relaxation, no fringe). This is the method of choice
+    /// for instances small enough that their complete state space fits in
+    /// memory. When it returns, `best_value` and `best_solution` report the
+    /// optimum, exactly as after a call to `maximize`.
+    ///
+    /// # Note
+    /// An exact DD can grow exponentially large. To guard against the cases
+    /// where the instance turns out bigger than expected, configure the
+    /// solver with a node-counting cutoff (e.g. `NodeBudget`): the
+    /// compilation is then aborted gracefully once the budget is exhausted
+    /// and the returned completion is flagged inexact.
+    pub fn solve_exact(&mut self) -> Completion {
+        let root = self.root_node();
+        let cache = EmptyCache::<State>::default();
+        let dominance = EmptyDominanceChecker::<State>::default();
+        let expanded = Cell::new(0);
+        let counting_cutoff = CountingCutoff { inner: self.cutoff, expanded: &expanded };
+
+        let compilation = CompilationInput {
+            comp_type: CompilationType::Exact,
+            max_in_degree: self.max_in_degree,
+            max_out_degree: usize::MAX,
+            max_width: usize::MAX,
+            problem: self.fixed.as_ref().map_or(self.problem, |fixed| fixed as &dyn Problem<State = State>),
+            relaxation: self.relaxation,
+            ranking: self.ranking,
+            cutoff: &counting_cutoff,
+            cache: &cache,
+            dominance: &dominance,
+            residual: &root,
+            //
+            best_lb: self.best_lb,
+        };
+
+        let completion = self.mdd.compile(&compilation);
+        self.stats.nb_nodes_expanded += expanded.replace(0);
+        match completion {
+            Ok(Completion { is_exact, .. }) => {
+                self.maybe_update_best();
+                if is_exact {
+                    self.best_ub = self.best_lb;
+                    self.maybe_log_bounds();
+                }
+                Completion { is_exact, best_value: self.best_value() }
+            }
+            Err(reason) => {
+                self.abort_search(reason);
+                Completion { is_exact: false, best_value: self.best_value() }
+            }
+        }
+    }
+
+    /// Counts the number of distinct decision paths achieving the optimal
+    /// value of the problem, saturating at `u128::MAX`. To that end, it
+    /// compiles one single exact DD of unbounded width (just like
+    /// `solve_exact`) but with the primal-bound pruning disabled: pruning a
+    /// tied-optimal path away would falsify the count. It returns `None`
+    /// when the compilation gets cut off (the count is only meaningful when
+    /// the final compile is exact) and `Some(0)` when the problem admits no
+    /// feasible solution.
+    ///
+    /// # Warning
+    /// Like `solve_exact`, this method materializes the complete state space
+    /// of the problem and may thus require an exponential amount of memory.
+    pub fn count_optimal_solutions(&mut self) -> Option<u128> {
+        let root = self.root_node();
+        let cache = EmptyCache::<State>::default();
+        let dominance = EmptyDominanceChecker::<State>::default();
+        let expanded = Cell::new(0);
+        let counting_cutoff = CountingCutoff { inner: self.cutoff, expanded: &expanded };
+
+        let compilation = CompilationInput {
+            comp_type: CompilationType::Exact,
+            max_in_degree: self.max_in_degree,
+            max_out_degree: usize::MAX,
+            max_width: usize::MAX,
+            problem: self.fixed.as_ref().map_or(self.problem, |fixed| fixed as &dyn Problem<State = State>),
+            relaxation: self.relaxation,
+            ranking: self.ranking,
+            cutoff: &counting_cutoff,
+            cache: &cache,
+            dominance: &dominance,
+            residual: &root,
+            // never prune on the primal bound: every tied-optimal path must
+            // survive the compilation for the count to be right
+            best_lb: isize::MIN,
+        };
+
+        let completion = self.mdd.compile(&compilation);
+        self.stats.nb_nodes_expanded += expanded.replace(0);
+        match completion {
+            Ok(_) => Some(self.mdd.count_best_paths()),
+            Err(_) => None,
+        }
+    }
+
+    /// Enumerates all the feasible solutions whose value lies within
+    /// `epsilon` of the optimum, along with their values, sorted by
+    /// decreasing value. To that end, it compiles one single exact DD of
+    /// unbounded width with every pruning disabled (pruning against the
+    /// primal bound or a dominance relation could delete near-optimal
+    /// solutions) and then collects all the qualifying paths. It returns an
+    /// empty vector when the compilation gets cut off or when the problem
+    /// admits no feasible solution.
+    ///
+    /// # Warning
+    /// Like `solve_exact`, this method materializes the complete state space
+    /// of the problem and may thus require an exponential amount of memory.
+    /// On top of that, the number of qualifying solutions may blow up
+    /// combinatorially as `epsilon` grows: consider pairing this call with a
+    /// node budget or a time cutoff.
+    pub fn solutions_within(&mut self, epsilon: isize) -> Vec<(isize, Vec<Decision>)> {
+        let root = self.root_node();
+        let cache = EmptyCache::<State>::default();
+        let dominance = EmptyDominanceChecker::<State>::default();
+        let expanded = Cell::new(0);
+        let counting_cutoff = CountingCutoff { inner: self.cutoff, expanded: &expanded };
+
+        let compilation = CompilationInput {
+            comp_type: CompilationType::Exact,
+            max_in_degree: self.max_in_degree,
+            max_out_degree: usize::MAX,
+            max_width: usize::MAX,
+            problem: self.fixed.as_ref().map_or(self.problem, |fixed| fixed as &dyn Problem<State = State>),
+            relaxation: self.relaxation,
+            ranking: self.ranking,
+            cutoff: &counting_cutoff,
+            cache: &cache,
+            dominance: &dominance,
+            residual: &root,
+            // never prune on the primal bound: the near-optimal solutions
+            // must all survive the compilation
+            best_lb: isize::MIN,
+        };
+
+        let completion = self.mdd.compile(&compilation);
+        self.stats.nb_nodes_expanded += expanded.replace(0);
+        match completion.ok().and_then(|_| self.mdd.best_value()) {
+            Some(optimum) => {
+                let threshold = optimum.saturating_sub(epsilon);
+                self.mdd.solutions_above(threshold).into_iter()
+                    .map(|(value, sol)| (value, sol.into()))
+                    .collect()
+            }
+            None => vec![],
+        }
+    }
+
+    /// This method first compiles one relaxed DD rooted at the initial state
+    /// (at the width which the heuristic configures for the root subproblem)
+    /// to obtain the root relaxation bound, then runs the complete branch and
+    /// bound. It returns both the root bound and the outcome of the search,
+    /// which tells at a glance how tight the relaxation is: the closer the
+    /// root bound to the proven optimum, the better the relaxation. The root
+    /// bound is `isize::MAX` when the relaxed compilation gets cut off before
+    /// yielding a bound, and `isize::MIN` when the problem is infeasible.
+    pub fn solve_with_root_bound(&mut self) -> (isize, Completion) {
+        let root = self.root_node();
+        let cache = EmptyCache::<State>::default();
+        let dominance = EmptyDominanceChecker::<State>::default();
+        let expanded = Cell::new(0);
+        let counting_cutoff = CountingCutoff { inner: self.cutoff, expanded: &expanded };
+        let width = self.width_heu.max_width_with_bounds(&root, self.best_lb, self.best_ub);
+
+        let compilation = CompilationInput {
+            comp_type: CompilationType::Relaxed,
+            max_in_degree: self.max_in_degree,
+            max_out_degree: usize::MAX,
+            max_width: width,
+            problem: self.fixed.as_ref().map_or(self.problem, |fixed| fixed as &dyn Problem<State = State>),
+            relaxation: self.relaxation,
+            ranking: self.ranking,
+            cutoff: &counting_cutoff,
+            cache: &cache,
+            dominance: &dominance,
+            residual: &root,
+            //
+            best_lb: self.best_lb,
+        };
+
+        let start = timer_start();
+        let completion = self.mdd.compile(&compilation);
+        self.time.relaxed += timer_elapsed(start);
+        self.stats.nb_relaxed_dds += 1;
+        self.stats.nb_nodes_expanded += expanded.replace(0);
+
+        let root_bound = match completion {
+            Ok(_) => {
+                self.maybe_update_best();
+                let bound = self.mdd.best_value().unwrap_or(isize::MIN);
+                self.root_ub.get_or_insert(bound);
+                bound
+            }
+            Err(_) => isize::MAX,
+        };
+        (root_bound, self.maximize())
+    }
+
+    /// This method returns a dirt-cheap estimate of the root upper bound:
+    /// it merely evaluates `Relaxation::fast_upper_bound` on the initial
+    /// state (on top of the initial value), without compiling any DD. It is
+    /// a convenient way to probe the quality of a relaxation before
+    /// committing to a long solve.
+    pub fn root_fast_upper_bound(&self) -> isize {
+        self.problem.initial_value()
+            .saturating_add(self.relaxation.fast_upper_bound(&self.problem.initial_state()))
+    }
+
+    /// Returns the value of the k-th best solution found so far, which is the
+    /// pruning threshold of a k-best enumeration (`isize::MIN` as long as
+    /// fewer than k solutions have been found).
+    fn kth_best_value(k: usize, k_best: &[(isize, Solution)]) -> isize {
+        if k_best.len() < k {
+            isize::MIN
+        } else {
+            k_best[k - 1].0
+        }
+    }
+
+    /// Merges the solutions harvested from one DD into the k best found so
+    /// far, weeding out the duplicates (the same solution may be reachable
+    /// from several subproblems of the fringe).
+    fn merge_k_best(k: usize, k_best: &mut Vec<(isize, Solution)>, harvest: Vec<(isize, Solution)>) {
+        for (value, mut solution) in harvest {
+            solution.sort_unstable_by_key(|d| d.variable.0);
+            if !k_best.iter().any(|(v, s)| *v == value && *s == solution) {
+                k_best.push((value, solution));
+            }
+        }
+        k_best.sort_by_key(|entry| Reverse(entry.0));
+        k_best.truncate(k);
+    }
+
+    /// The k-best counterpart of `process_one_node`: it expands a restricted
+    /// and possibly a relaxed mdd rooted in `node`, harvesting the k best
+    /// paths of every merge-free DD along the way. The dominance and caching
+    /// prunings are replaced by their empty (no-op) counterparts.
+    fn process_one_node_k(
         &mut self,
         node: SubProblem<State>,
+        k: usize,
+        k_best: &mut Vec<(isize, Solution)>,
     ) -> Result<(), Reason> {
         // 1. RESTRICTION
         let node_ub = node.ub;
-        let best_lb = self.best_lb;
+        let best_lb = Self::kth_best_value(k, k_best);
 
         if node_ub <= best_lb {
             return Ok(());
         }
 
-        if !self.cache.must_explore(&node) {
-            return Ok(());
-        }
+        let width = self.width_heu.max_width_with_bounds(&node, best_lb, self.best_ub);
+        let dominance = EmptyDominanceChecker::<State>::default();
+        let cache = EmptyCache::<State>::default();
+        let expanded = Cell::new(0);
+        let counting_cutoff = CountingCutoff { inner: self.cutoff, expanded: &expanded };
 
-        let width = self.width_heu.max_width(&node);
         let compilation = CompilationInput {
             comp_type: CompilationType::Restricted,
+            max_in_degree: self.max_in_degree,
+            max_out_degree: self.width_heu.max_degree(&node),
             max_width: width,
-            problem: self.problem,
+            problem: self.fixed.as_ref().map_or(self.problem, |fixed| fixed as &dyn Problem<State = State>),
             relaxation: self.relaxation,
             ranking: self.ranking,
-            cutoff: self.cutoff,
-            cache: &self.cache,
-            dominance: self.dominance,
+            cutoff: &counting_cutoff,
+            cache: &cache,
+            dominance: &dominance,
             residual: &node,
             //
             best_lb,
         };
 
-        let Completion{is_exact, ..} = self.mdd.compile(&compilation)?;
-        self.maybe_update_best();
+        let start = timer_start();
+        let completion = self.mdd.compile(&compilation);
+        self.time.restricted += timer_elapsed(start);
+        self.stats.nb_restricted_dds += 1;
+        self.stats.nb_nodes_expanded += expanded.replace(0);
+        let Completion{is_exact, ..} = completion?;
+        Self::merge_k_best(k, k_best, self.mdd.best_k_solutions(k));
         if is_exact {
             return Ok(());
         }
 
         // 2. RELAXATION
-        let best_lb = self.best_lb;
+        let best_lb = Self::kth_best_value(k, k_best);
         let compilation = CompilationInput {
             comp_type: CompilationType::Relaxed,
+            max_in_degree: self.max_in_degree,
+            max_out_degree: usize::MAX,
             max_width: width,
-            problem: self.problem,
+            problem: self.fixed.as_ref().map_or(self.problem, |fixed| fixed as &dyn Problem<State = State>),
             relaxation: self.relaxation,
             ranking: self.ranking,
-            cutoff: self.cutoff,
-            cache: &self.cache,
-            dominance: self.dominance,
+            cutoff: &counting_cutoff,
+            cache: &cache,
+            dominance: &dominance,
             residual: &node,
             //
             best_lb,
         };
 
-        let Completion{is_exact, ..} = self.mdd.compile(&compilation)?;
-        self.maybe_update_best();
-        if !is_exact {
-            self.enqueue_cutset(node_ub);
+        let start = timer_start();
+        let completion = self.mdd.compile(&compilation);
+        self.time.relaxed += timer_elapsed(start);
+        self.stats.nb_relaxed_dds += 1;
+        self.stats.nb_nodes_expanded += expanded.replace(0);
+        let Completion{is_exact, ..} = completion?;
+        if is_exact {
+            // an exact relaxed dd comprises no merged node: its paths are all
+            // feasible solutions
+            Self::merge_k_best(k, k_best, self.mdd.best_k_solutions(k));
+        } else {
+            self.enqueue_cutset(node_ub, best_lb);
         }
 
         Ok(())
     }
 
-    /// This private method updates the shared best known node and lower bound in
-    /// case the best value of the current `mdd` expansion improves the current
-    /// bounds.
-    fn maybe_update_best(&mut self) {
-        let dd_best_value = self.mdd.best_exact_value().unwrap_or(isize::MIN);
-        if dd_best_value > self.best_lb {
-            self.best_lb = dd_best_value;
-            self.best_sol = self.mdd.best_exact_solution();
+    /// This is the main solving loop: it repeatedly fetches one subproblem
+    /// from the fringe and processes it until the fringe is exhausted (or the
+    /// search is aborted).
+    fn solve(&mut self) -> Completion {
+        self.bound_log_start = Instant::now();
+        if let Some(reporter) = self.reporter.as_mut() {
+            reporter.on_start();
+        }
+        loop {
+            match self.get_workload() {
+                WorkLoad::Complete => break,
+                WorkLoad::Aborted => break, // the node budget has been exhausted
+                WorkLoad::WorkItem { node } => {
+                    // the node has been popped off the fringe: should its
+                    // processing be aborted, it belongs to the open frontier
+                    // justifying the final upper bound
+                    let justification = if self.record_certificate {
+                        Some((node.path.clone(), node.ub))
+                    } else {
+                        None
+                    };
+                    let in_flight = if self.record_checkpoint {
+                        Some(node.clone())
+                    } else {
+                        None
+                    };
+                    let outcome = self.process_one_node(node);
+                    if let Err(reason) = outcome {
+                        if let Some(justification) = justification {
+                            self.certificate.push(justification);
+                        }
+                        self.abort_search(reason);
+                        if let Some(in_flight) = in_flight {
+                            // the node whose processing was interrupted is
+                            // still open: it belongs to the checkpoint too
+                            self.interrupted.push(in_flight);
+                        }
+                        break;
+                    }
+                }
+            }
         }
+        if self.record_certificate {
+            // drain the subproblems left open on the fringe: together with
+            // the incumbent, their relaxed bounds justify the final ub
+            while let Some(open) = self.fringe.pop() {
+                self.open_by_layer[open.depth] -= 1;
+                self.certificate.push((open.path, open.ub));
+            }
+        }
+
+        if let Some(sol) = self.best_sol.as_mut() { sol.sort_unstable_by_key(|d| d.variable.0) }
+        let completion = Completion { is_exact: self.abort_proof.is_none(), best_value: self.best_sol.as_ref().map(|_| self.best_lb) };
+        if let Some(reporter) = self.reporter.as_mut() {
+            reporter.on_finish(&completion);
+        }
+        completion
     }
-    /// If necessary, tightens the bound of nodes in the cut-set of `mdd` and
-    /// then add the relevant nodes to the shared fringe.
-    fn enqueue_cutset(&mut self, ub: isize) {
-        let best_lb = self.best_lb;
+
+    /// This method initializes the problem resolution. Put more simply, this
+    /// method posts the root node of the mdd onto the fringe so that a thread
+    /// can pick it up and the processing can be bootstrapped.
+    fn initialize(&mut self) {
+        let root = self.root_node();
+        self.cache.initialize(self.problem);
+        self.fringe.push(root);
+        self.open_by_layer[0] += 1;
+        self.stats.peak_fringe_size = self.stats.peak_fringe_size.max(self.fringe.len());
+    }
+
+    fn root_node(&self) -> SubProblem<State> {
+        SubProblem {
+            state: Arc::new(self.problem.initial_state()),
+            value: self.problem.initial_value(),
+            path: vec![],
+            ub: isize::MAX,
+            depth: 0,
+        }
+    }
+
+    /// This method processes the given `node`. To do so, it reads the current
+    /// best lower bound from the critical data. Then it expands a restricted
+    /// and possibly a relaxed mdd rooted in `node`. If that is necessary,
+    /// it stores cut-set nodes onto the fringe for further parallel processing.
+    fn process_one_node(
+        &mut self,
+        node: SubProblem<State>,
+    ) -> Result<(), Reason> {
+        // 1. RESTRICTION
+        let node_ub = node.ub;
+        let node_depth = node.depth;
+        let best_lb = self.best_lb;
+
+        if self.record_trace {
+            self.search_trace.entries.push(TraceEntry {
+                state: node.state.clone(),
+                value: node.value,
+                ub: node.ub,
+                lb: best_lb,
+                depth: node.depth,
+                path: node.path.clone(),
+            });
+        }
+
+        if node_ub <= self.pruning_bound() {
+            self.maybe_log_proof(&node, PruningReason::BoundPruned);
+            return Ok(());
+        }
+
+        if !self.cache.must_explore_with_problem(self.problem, &node) {
+            self.stats.nb_cache_prunes += 1;
+            self.maybe_log_proof(&node, PruningReason::CachePruned);
+            return Ok(());
+        }
+
+        // the rough lower bound is the value of a feasible completion of the
+        // subproblem: it proves the existence of a solution of that value but
+        // yields no actual solution, hence it only ever tightens the
+        // pruning-only bound (see `pruning_bound`), never the incumbent
+        let rough_lb = self.relaxation.fast_lower_bound(node.state.as_ref());
+        if rough_lb > isize::MIN {
+            self.rough_lb = self.rough_lb.max(node.value.saturating_add(rough_lb));
+        }
+
+        // when the rough upper bound computation stumbled upon a feasible
+        // completion, validate it and use it as an incumbent right away
+        let (rough_ub, rough_completion) = self.relaxation.fast_upper_bound_with_solution(node.state.as_ref());
+        if let Some(completion) = rough_completion {
+            if let Some((value, solution)) = Self::validate_completion(self.problem, &node, &completion) {
+                if value > self.best_lb {
+                    self.best_lb = value;
+                    self.best_sol = Some(Solution::new(solution));
+                    self.best_path_exact = false;
+                    self.maybe_report_incumbent(value);
+                }
+            }
+        }
+        // the bound itself is not discarded either: it may be tighter than
+        // the upper bound the node was enqueued with, in which case it can
+        // prune the node before any DD is compiled
+        if rough_ub < isize::MAX && node.value.saturating_add(rough_ub) <= self.pruning_bound() {
+            self.maybe_log_proof(&node, PruningReason::BoundPruned);
+            return Ok(());
+        }
+        let best_lb = self.pruning_bound();
+
+        let width = self.width_heu.max_width_with_bounds(&node, best_lb, self.best_ub);
+        if self.feature_callback.is_some() {
+            let features = self.difficulty_features(&node, width);
+            if let Some(callback) = self.feature_callback.as_mut() {
+                callback(&node, &features);
+            }
+        }
+        let dominance_time = Cell::new(Duration::ZERO);
+        let dominance_prunes = Cell::new(0);
+        let timed_dominance = TimedDominance { inner: self.dominance, elapsed: &dominance_time, pruned: &dominance_prunes };
+        let expanded = Cell::new(0);
+        let counting_cutoff = CountingCutoff { inner: self.cutoff, expanded: &expanded };
+
+        let compilation = CompilationInput {
+            comp_type: CompilationType::Restricted,
+            max_in_degree: self.max_in_degree,
+            max_out_degree: self.width_heu.max_degree(&node),
+            max_width: width,
+            problem: self.fixed.as_ref().map_or(self.problem, |fixed| fixed as &dyn Problem<State = State>),
+            relaxation: self.relaxation,
+            ranking: self.ranking,
+            cutoff: &counting_cutoff,
+            cache: &self.cache,
+            dominance: &timed_dominance,
+            residual: &node,
+            //
+            best_lb,
+        };
+
+        let start = timer_start();
+        let completion = self.mdd.compile(&compilation);
+        self.time.restricted += timer_elapsed(start);
+        self.time.dominance += dominance_time.replace(Duration::ZERO);
+        self.stats.nb_restricted_dds += 1;
+        self.stats.nb_nodes_expanded += expanded.replace(0);
+        self.stats.nb_dominance_prunes += dominance_prunes.replace(0);
+        self.harvest_value_histogram();
+        let Completion{is_exact, ..} = completion?;
+        self.maybe_update_best();
+        if is_exact {
+            let value = self.mdd.best_value();
+            self.maybe_log_proof(&node, PruningReason::ExactlySolved(value));
+            return Ok(());
+        }
+
+        // 2. RELAXATION
+        let best_lb = self.pruning_bound();
+        let compilation = CompilationInput {
+            comp_type: CompilationType::Relaxed,
+            max_in_degree: self.max_in_degree,
+            max_out_degree: usize::MAX,
+            max_width: width,
+            problem: self.fixed.as_ref().map_or(self.problem, |fixed| fixed as &dyn Problem<State = State>),
+            relaxation: self.relaxation,
+            ranking: self.ranking,
+            cutoff: &counting_cutoff,
+            cache: &self.cache,
+            dominance: &timed_dominance,
+            residual: &node,
+            //
+            best_lb,
+        };
+
+        let start = timer_start();
+        let completion = self.mdd.compile(&compilation);
+        self.time.relaxed += timer_elapsed(start);
+        self.time.dominance += dominance_time.replace(Duration::ZERO);
+        self.stats.nb_relaxed_dds += 1;
+        self.stats.nb_nodes_expanded += expanded.replace(0);
+        self.stats.nb_dominance_prunes += dominance_prunes.replace(0);
+        self.harvest_value_histogram();
+        let Completion{is_exact, ..} = completion?;
+        self.maybe_update_best();
+        if !is_exact {
+            self.enqueue_cutset(node_ub, self.pruning_bound());
+        } else {
+            let value = self.mdd.best_value();
+            self.maybe_log_proof(&node, PruningReason::ExactlySolved(value));
+        }
+
+        if node_depth == 0 && self.root_ub.is_none() {
+            self.root_ub = Some(self.mdd.best_value().unwrap_or(isize::MIN));
+        }
+        if self.capture_root_dd && node_depth == 0 && self.root_dd.is_none() {
+            self.root_dd = Some(std::mem::take(&mut self.mdd));
+        }
+
+        Ok(())
+    }
+
+    /// This private method adds the decision labeling each edge of the last
+    /// compiled DD to the per-(variable, value) histogram of the search. It
+    /// does nothing unless the recording was requested with
+    /// `with_value_histogram`.
+    fn harvest_value_histogram(&mut self) {
+        if let Some(histogram) = self.value_histogram.as_mut() {
+            self.mdd.for_each_edge_decision(&mut |decision| {
+                *histogram.entry((decision.variable, decision.value)).or_insert(0) += 1;
+            });
+        }
+    }
+
+    /// Returns the threshold against which the subproblems and DD nodes are
+    /// pruned (anything whose upper bound does not exceed it is dropped). It
+    /// combines the incumbent-backed lower bound with the rough one, but the
+    /// latter only prunes *strictly* worse nodes: the rough bound carries no
+    /// solution, so a node whose upper bound ties with it may still be the
+    /// only one from which a solution of that value can be extracted.
+    fn pruning_bound(&self) -> isize {
+        self.best_lb.max(self.rough_lb.saturating_sub(1))
+    }
+
+    /// This private method updates the shared best known node and lower bound in
+    /// case the best value of the current `mdd` expansion improves the current
+    /// bounds.
+    fn maybe_update_best(&mut self) {
+        let dd_best_value = self.mdd.best_exact_value().unwrap_or(isize::MIN);
+        if dd_best_value > self.best_lb {
+            self.best_lb = dd_best_value;
+            self.best_sol = self.mdd.best_exact_solution();
+            self.best_path_exact = true;
+            self.maybe_report_incumbent(dd_best_value);
+            if let Some(reporter) = self.reporter.as_mut() {
+                reporter.on_new_bound(self.best_lb, self.best_ub);
+            }
+            self.maybe_log_bounds();
+        }
+    }
+
+    /// This private method appends a sample to the bound log, provided that
+    /// recording was requested (`with_bound_log`) and that either bound
+    /// changed since the last recorded sample.
+    fn maybe_log_bounds(&mut self) {
+        if let Some(log) = self.bound_log.as_mut() {
+            if log.last().map(|(_, lb, ub)| (*lb, *ub)) != Some((self.best_lb, self.best_ub)) {
+                log.push((self.bound_log_start.elapsed(), self.best_lb, self.best_ub));
+            }
+        }
+    }
+
+    /// Acknowledges a new incumbent to the observers of this solver, unless
+    /// it fails to improve the last reported one by at least `min_improvement`.
+    fn maybe_report_incumbent(&mut self, value: isize) {
+        if value.saturating_sub(self.reported_lb) >= self.min_improvement {
+            self.reported_lb = value;
+            if let (Some(callback), Some(solution)) = (self.on_incumbent.as_mut(), self.best_sol.as_ref()) {
+                callback(value, solution);
+            }
+        }
+    }
+    /// If necessary, tightens the bound of nodes in the cut-set of `mdd` and
+    /// then add the relevant nodes to the shared fringe.
+    fn enqueue_cutset(&mut self, ub: isize, best_lb: isize) {
+        let start = timer_start();
         let fringe = &mut self.fringe;
         self.mdd.drain_cutset(|mut cutset_node| {
             cutset_node.ub = ub.min(cutset_node.ub);
@@ -413,10 +1776,21 @@ where
                 self.open_by_layer[depth] += after - before;
             }
         });
+        self.time.fringe += timer_elapsed(start);
+        self.stats.peak_fringe_size = self.stats.peak_fringe_size.max(self.fringe.len());
+        self.cutoff.set_fringe_size(self.fringe.len());
     }
 
     fn abort_search(&mut self, reason: Reason) {
         self.abort_proof = Some(reason);
+        if self.record_checkpoint {
+            // keep the open subproblems around: they are the fringe which a
+            // resumed search will pick up from
+            while let Some(open) = self.fringe.pop() {
+                self.open_by_layer[open.depth] -= 1;
+                self.interrupted.push(open);
+            }
+        }
         self.fringe.clear();
         self.cache.clear();
     }
@@ -437,11 +1811,15 @@ where
                 self.open_by_layer[self.first_active_layer] == 0 {
             self.cache.clear_layer(self.first_active_layer);
             self.first_active_layer += 1;
+            if let Some(reporter) = self.reporter.as_mut() {
+                reporter.on_layer(self.first_active_layer);
+            }
         }
 
         // Are we done ?
         if self.fringe.is_empty() {
             self.best_ub = self.best_lb;
+            self.maybe_log_bounds();
             return WorkLoad::Complete;
         }
 
@@ -450,12 +1828,30 @@ where
             return WorkLoad::Aborted;
         }
 
+        // Did the cutoff kick in ?
+        self.cutoff.set_fringe_size(self.fringe.len());
+        self.cutoff.set_bounds(self.best_lb.max(self.rough_lb), self.best_ub);
+        if self.cutoff.must_stop() {
+            self.abort_search(Reason::CutoffOccurred);
+            return WorkLoad::Aborted;
+        }
+
+        // Did we exhaust the node budget of this run ?
+        if self.node_budget.is_some_and(|budget| self.explored >= budget) {
+            self.abort_search(Reason::CutoffOccurred);
+            return WorkLoad::Aborted;
+        }
+
+        let start = timer_start();
         let nn = self.fringe.pop().unwrap();
+        self.time.fringe += timer_elapsed(start);
 
         // Consume the current node and process it
+        self.stats.nb_fringe_pops += 1;
         self.explored += 1;
         self.open_by_layer[nn.depth] -= 1;
         self.best_ub   = nn.ub;
+        self.maybe_log_bounds();
 
         WorkLoad::WorkItem { node: nn }
     }
@@ -474,27 +1870,11 @@ where
     /// and process it until the problem is solved.
     fn maximize(&mut self) -> Completion {
         self.initialize();
-
-        loop {
-            match self.get_workload() {
-                WorkLoad::Complete => break,
-                WorkLoad::Aborted => break, // this one cannot occur
-                WorkLoad::WorkItem { node } => {
-                    let outcome = self.process_one_node(node);
-                    if let Err(reason) = outcome {
-                        self.abort_search(reason); 
-                        break;
-                    }
-                }
-            }
-        }
-
-        if let Some(sol) = self.best_sol.as_mut() { sol.sort_unstable_by_key(|d| d.variable.0) }
-        Completion { is_exact: self.abort_proof.is_none(), best_value: self.best_sol.as_ref().map(|_| self.best_lb) }
+        self.solve()
     }
 
     /// Returns the best solution that has been identified for this problem.
-    fn best_solution(&self) -> Option<Vec<Decision>> {
+    fn best_solution(&self) -> Option<Solution> {
         self.best_sol.clone()
     }
     /// Returns the value of the best solution that has been identified for
@@ -503,9 +1883,11 @@ where
         self.best_sol.as_ref().map(|_sol| self.best_lb)
     }
     /// Returns the value of the best lower bound that has been identified for
-    /// this problem.
+    /// this problem. This includes the bound derived from the rough lower
+    /// bound estimates of the relaxation, which proves that a solution of
+    /// that value exists even when no solution has been materialized yet.
     fn best_lower_bound(&self) -> isize {
-        self.best_lb
+        self.best_lb.max(self.rough_lb)
     }
     /// Returns the value of the best upper bound that has been identified for
     /// this problem.
@@ -517,6 +1899,8 @@ where
         if value > self.best_lb {
             self.best_sol = Some(solution);
             self.best_lb  = value;
+            self.best_path_exact = false;
+            self.maybe_report_incumbent(value);
         }
     }
     /// Returns the number of nodes that have been explored so far.
@@ -538,25 +1922,1793 @@ where
 
 #[cfg(test)]
 mod test_solver {
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use std::sync::Arc;
+
     use crate::*;
 
-    type SeqSolver<'a, T> = SequentialSolver<'a, T, DefaultMDDLEL<T>, EmptyCache<T>>;
-    type SeqCachingSolver<'a, T> = SequentialSolver<'a, T, DefaultMDDFC<T>, SimpleCache<T>>;
-    
+    type SeqSolver<'a, T> = SequentialSolver<'a, T, DefaultMDDLEL<T>, EmptyCache<T>>;
+    type SeqCachingSolver<'a, T> = SequentialSolver<'a, T, DefaultMDDFC<T>, SimpleCache<T>>;
+    
+    #[test]
+    fn by_default_best_lb_is_min_infinity() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let solver = SeqSolver::new(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+
+        assert_eq!(isize::min_value(), solver.best_lower_bound());
+    }
+    #[test]
+    fn by_default_best_ub_is_plus_infinity() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let solver = SeqSolver::new(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+
+        assert_eq!(isize::max_value(), solver.best_upper_bound());
+    }
+    #[test]
+    fn when_the_problem_is_solved_best_lb_is_best_value() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SeqSolver::new(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+
+        let _ = solver.maximize();
+        assert_eq!(220, solver.best_lower_bound());
+    }
+    #[test]
+    fn when_the_problem_is_solved_best_ub_is_best_value() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SeqSolver::new(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+
+        let _ = solver.maximize();
+        assert_eq!(220, solver.best_upper_bound());
+    }
+    
+    #[test]
+    fn no_solution_before_solving() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let solver = SeqSolver::new(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+        assert!(solver.best_sol.is_none());
+    }
+    #[test]
+    fn empty_fringe_before_solving() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let solver = SeqSolver::new(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+
+        assert!(solver.fringe.is_empty());
+    }
+    #[test]
+    fn default_best_lb_is_neg_infinity() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let solver = SeqSolver::new(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+
+        assert_eq!(isize::min_value(), solver.best_lb);
+    }
+    #[test]
+    fn default_best_ub_is_pos_infinity() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let solver = SeqSolver::new(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+
+        assert_eq!(isize::max_value(), solver.best_ub);
+    }
+
+    #[test]
+    fn a_node_budget_cutoff_aborts_the_search() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        // the root layer alone exhausts the budget: the very next compilation
+        // must be aborted and the search reported inexact
+        let cutoff = NodeBudget::new(1);
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SeqSolver::custom(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+
+        let maximized = solver.maximize();
+        assert!(!maximized.is_exact);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn a_saved_search_resumes_where_it_left_off() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let dominance = EmptyDominanceChecker::default();
+        let width = FixedWidth(2);
+
+        // run the search until the (tiny) node budget aborts it, then save
+        // its state into a json snapshot
+        let cutoff = NodeBudget::new(1);
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SeqSolver::custom(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        ).with_checkpointing();
+        let interrupted = solver.maximize();
+        assert!(!interrupted.is_exact);
+
+        let mut snapshot = vec![];
+        solver.save_state(&mut serde_json::Serializer::new(&mut snapshot)).unwrap();
+
+        // resume the search on a fresh solver (with no cutoff this time): it
+        // must complete the proof and find the optimum
+        let cutoff = NoCutoff;
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut resumed = SeqSolver::custom(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+        let completion = resumed
+            .resume(&mut serde_json::Deserializer::from_slice(&snapshot))
+            .unwrap();
+
+        assert!(completion.is_exact);
+        assert_eq!(Some(220), completion.best_value);
+        assert_eq!(Some(220), resumed.best_value());
+    }
+
+    #[test]
+    fn the_best_solution_can_be_broken_down_by_decision() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SeqSolver::custom(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+        assert!(solver.best_solution_with_costs().is_none());
+
+        let maximized = solver.maximize();
+        assert!(maximized.is_exact);
+
+        let breakdown = solver.best_solution_with_costs().unwrap();
+        assert_eq!(breakdown, vec![
+            (Decision{variable: Variable(0), value: 0},   0),
+            (Decision{variable: Variable(1), value: 1}, 100),
+            (Decision{variable: Variable(2), value: 1}, 120),
+        ]);
+        // the per-decision costs (plus the initial value) sum to best_value
+        let total: isize = breakdown.iter().map(|(_, cost)| cost).sum();
+        assert_eq!(maximized.best_value, Some(total + problem.initial_value()));
+    }
+
+    #[test]
+    fn tied_optimal_solutions_are_all_counted() {
+        // either one of the two items fills the sack for a profit of 50:
+        // the optimum is reached by exactly two distinct decision paths
+        let problem = Knapsack {
+            capacity: 20,
+            profit  : vec![50, 50],
+            weight  : vec![20, 20]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SeqSolver::custom(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+
+        let maximized = solver.maximize();
+        assert!(maximized.is_exact);
+        assert_eq!(maximized.best_value, Some(50));
+        assert_eq!(solver.count_optimal_solutions(), Some(2));
+    }
+
+    #[test]
+    fn a_fast_lower_bound_tightens_the_reported_bound_before_any_dd_is_compiled() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPGreedyRelax {pb: &problem};
+        let ranking = KPRanking;
+        // the budget only suffices to develop the root layer: the very first
+        // compilation is aborted, but the greedy completion from the root
+        // (items 0 and 1, worth 160) has already been proved feasible
+        let cutoff = NodeBudget::new(1);
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SeqSolver::custom(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+
+        let maximized = solver.maximize();
+        assert!(!maximized.is_exact);
+        assert_eq!(160, solver.best_lower_bound());
+        // the bound was proved without ever constructing an actual solution
+        assert!(solver.best_solution().is_none());
+    }
+
+    #[test]
+    fn a_suggested_completion_installs_an_incumbent_solution_right_away() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPSuggestingRelax {pb: &problem};
+        let ranking = KPRanking;
+        // the budget only suffices to develop the root layer: the very first
+        // compilation is aborted, but the greedy completion suggested from
+        // the root (items 0 and 1, worth 160) has been validated and
+        // installed as the incumbent -- solution included
+        let cutoff = NodeBudget::new(1);
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SeqSolver::custom(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+
+        let maximized = solver.maximize();
+        assert!(!maximized.is_exact);
+        assert_eq!(160, solver.best_lower_bound());
+        let solution = solver.best_solution().unwrap().into_sorted_by_variable();
+        assert_eq!(vec![1, 1, 0], solution.iter().map(|d| d.value).collect::<Vec<_>>());
+        // the incumbent was suggested by the relaxation, not lifted from a DD
+        assert!(!solver.best_path_was_exact());
+    }
+
+    #[test]
+    fn an_infeasible_suggested_completion_is_ignored() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPLyingRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NodeBudget::new(1);
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SeqSolver::custom(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+
+        let maximized = solver.maximize();
+        assert!(!maximized.is_exact);
+        // the suggestion takes every item and busts the capacity: it must
+        // not have been turned into an incumbent
+        assert!(solver.best_solution().is_none());
+        assert_eq!(isize::MIN, solver.best_lower_bound());
+    }
+
+    #[test]
+    fn a_suggested_completion_does_not_compromise_the_search() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPSuggestingRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = FixedWidth(2);
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SeqSolver::custom(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+
+        let maximized = solver.maximize();
+        assert!(maximized.is_exact);
+        assert_eq!(220, solver.best_lower_bound());
+        assert_eq!(220, solver.best_upper_bound());
+    }
+
+    #[test]
+    fn a_fast_lower_bound_does_not_compromise_the_search() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPGreedyRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = FixedWidth(2);
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SeqSolver::custom(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+
+        let maximized = solver.maximize();
+        assert!(maximized.is_exact);
+        assert_eq!(220, solver.best_lower_bound());
+        assert_eq!(220, solver.best_upper_bound());
+    }
+
+    #[test]
+    fn solve_exact_yields_the_optimum_with_one_single_dd() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = FixedWidth(1); // the width heuristic is irrelevant here
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SeqSolver::custom(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+
+        let solved = solver.solve_exact();
+        assert!(solved.is_exact);
+        assert_eq!(Some(220), solved.best_value);
+        assert_eq!(220, solver.best_lower_bound());
+        assert_eq!(220, solver.best_upper_bound());
+
+        let mut sln = solver.best_solution().unwrap();
+        sln.sort_unstable_by_key(|d| d.variable.id());
+        assert_eq!(sln, vec![
+            Decision{variable: Variable(0), value: 0},
+            Decision{variable: Variable(1), value: 1},
+            Decision{variable: Variable(2), value: 1},
+        ]);
+
+        // the branch-and-bound machinery was never involved
+        let stats = solver.stats();
+        assert_eq!(0, stats.nb_restricted_dds);
+        assert_eq!(0, stats.nb_relaxed_dds);
+        assert_eq!(0, stats.nb_fringe_pops);
+        assert!(stats.nb_nodes_expanded > 0);
+    }
+
+    #[test]
+    fn solve_exact_aborts_gracefully_when_the_node_budget_is_exhausted() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        // the root layer alone exhausts the budget
+        let cutoff = NodeBudget::new(1);
+        let width = FixedWidth(1);
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SeqSolver::custom(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+
+        let solved = solver.solve_exact();
+        assert!(!solved.is_exact);
+        assert_eq!(None, solved.best_value);
+    }
+
+    #[test]
+    fn solutions_within_enumerates_the_near_optimal_solutions() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = FixedWidth(1); // the width heuristic is irrelevant here
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SeqSolver::custom(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+
+        // with no slack, only the optimum itself qualifies
+        let solutions = solver.solutions_within(0);
+        assert_eq!(1, solutions.len());
+        assert_eq!(220, solutions[0].0);
+
+        // the feasible values are 0, 60, 100, 120, 160, 180 and 220: a slack
+        // of 40 spares the solutions worth 180 (items 0 and 2) and 220
+        let solutions = solver.solutions_within(40);
+        assert_eq!(vec![220, 180], solutions.iter().map(|s| s.0).collect::<Vec<_>>());
+
+        let mut best = solutions[0].1.clone();
+        best.sort_unstable_by_key(|d| d.variable.id());
+        assert_eq!(best, vec![
+            Decision{variable: Variable(0), value: 0},
+            Decision{variable: Variable(1), value: 1},
+            Decision{variable: Variable(2), value: 1},
+        ]);
+    }
+
+    #[test]
+    fn solve_with_root_bound_reports_the_root_relaxation_bound() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = FixedWidth(2);
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SeqSolver::custom(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+
+        let (root_bound, completion) = solver.solve_with_root_bound();
+        assert!(completion.is_exact);
+        assert_eq!(Some(220), completion.best_value);
+        // the root relaxation is a valid upper bound on the proven optimum
+        assert!(root_bound >= 220);
+        assert!(root_bound < isize::MAX);
+    }
+
+    #[test]
+    fn root_fast_upper_bound_probes_the_relaxation_without_compiling() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let solver = SeqSolver::custom(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+
+        // the sum of all profits, as computed by KPRelax::fast_upper_bound
+        assert_eq!(280, solver.root_fast_upper_bound());
+        // no dd was compiled in the process
+        assert_eq!(0, solver.stats.nb_restricted_dds + solver.stats.nb_relaxed_dds);
+    }
+
+    #[test]
+    fn reset_lets_a_solver_be_reused_for_a_second_search() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = FixedWidth(2);
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SeqCachingSolver::custom(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+
+        let completion = solver.maximize();
+        assert!(completion.is_exact);
+        assert_eq!(Some(220), completion.best_value);
+
+        solver.reset();
+        assert_eq!(None, solver.best_solution());
+        assert_eq!(isize::MIN, solver.best_lower_bound());
+        assert_eq!(isize::MAX, solver.best_upper_bound());
+        assert_eq!(0, solver.explored());
+
+        let completion = solver.maximize();
+        assert!(completion.is_exact);
+        assert_eq!(Some(220), completion.best_value);
+    }
+
+    #[test]
+    fn maximizes_yields_the_optimum_1a() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SeqSolver::custom(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+
+        let maximized = solver.maximize();
+
+        assert!(maximized.is_exact);
+        assert_eq!(maximized.best_value, Some(220));
+        assert!(solver.best_solution().is_some());
+
+        let mut sln = solver.best_solution().unwrap();
+        sln.sort_unstable_by_key(|d| d.variable.id());
+        assert_eq!(sln, vec![
+            Decision{variable: Variable(0), value: 0},
+            Decision{variable: Variable(1), value: 1},
+            Decision{variable: Variable(2), value: 1},
+        ]);
+    }
+
+    #[test]
+    fn maximizes_yields_the_optimum_1b() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SeqCachingSolver::custom(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+
+        let maximized = solver.maximize();
+
+        assert!(maximized.is_exact);
+        assert_eq!(maximized.best_value, Some(220));
+        assert!(solver.best_solution().is_some());
+
+        let mut sln = solver.best_solution().unwrap();
+        sln.sort_unstable_by_key(|d| d.variable.id());
+        assert_eq!(sln, vec![
+            Decision{variable: Variable(0), value: 0},
+            Decision{variable: Variable(1), value: 1},
+            Decision{variable: Variable(2), value: 1},
+        ]);
+    }
+
+    #[test]
+    fn maximizes_yields_the_optimum_2a() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 210, 12, 5, 100, 120, 110],
+            weight  : vec![10,  45, 20, 4,  20,  30,  50]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SeqSolver::new(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+
+        let maximized = solver.maximize();
+
+        assert!(maximized.is_exact);
+        assert_eq!(maximized.best_value, Some(220));
+        assert!(solver.best_solution().is_some());
+
+        let mut sln = solver.best_solution().unwrap();
+        sln.sort_unstable_by_key(|d| d.variable.id());
+        assert_eq!(sln, vec![
+            Decision { variable: Variable(0), value: 0 },
+            Decision { variable: Variable(1), value: 0 },
+            Decision { variable: Variable(2), value: 0 },
+            Decision { variable: Variable(3), value: 0 },
+            Decision { variable: Variable(4), value: 1 },
+            Decision { variable: Variable(5), value: 1 },
+            Decision { variable: Variable(6), value: 0 }
+        ]);
+    }
+
+    #[test]
+    fn maximizes_yields_the_optimum_2b() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 210, 12, 5, 100, 120, 110],
+            weight  : vec![10,  45, 20, 4,  20,  30,  50]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SeqCachingSolver::new(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+
+        let maximized = solver.maximize();
+
+        assert!(maximized.is_exact);
+        assert_eq!(maximized.best_value, Some(220));
+        assert!(solver.best_solution().is_some());
+
+        let mut sln = solver.best_solution().unwrap();
+        sln.sort_unstable_by_key(|d| d.variable.id());
+        assert_eq!(sln, vec![
+            Decision { variable: Variable(0), value: 0 },
+            Decision { variable: Variable(1), value: 0 },
+            Decision { variable: Variable(2), value: 0 },
+            Decision { variable: Variable(3), value: 0 },
+            Decision { variable: Variable(4), value: 1 },
+            Decision { variable: Variable(5), value: 1 },
+            Decision { variable: Variable(6), value: 0 }
+        ]);
+    }
+
+    #[test]
+    fn best_path_was_exact_distinguishes_found_from_injected_incumbents() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SeqSolver::new(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+
+        // no solution is known yet
+        assert!(!solver.best_path_was_exact());
+
+        // the solutions found by the search are lifted from exact paths
+        let maximized = solver.maximize();
+        assert_eq!(Some(220), maximized.best_value);
+        assert!(solver.best_path_was_exact());
+
+        // an injected primal has an unknown provenance
+        let d1  = Decision{variable: Variable(0), value: 10};
+        solver.set_primal(10000, Solution::new(vec![d1]));
+        assert!(!solver.best_path_was_exact());
+    }
+
+    #[test]
+    fn the_fringe_snapshot_describes_the_open_subproblems() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        // the budget suffices to process the root subproblem (and enqueue
+        // its cutset) but not to explore any further
+        let cutoff = NodeBudget::new(7);
+        let width = FixedWidth(1);
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SeqSolver::custom(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        ).with_checkpointing();
+
+        assert!(solver.fringe_snapshot().is_empty());
+
+        let maximized = solver.maximize();
+        assert!(!maximized.is_exact);
+
+        let snapshot = solver.fringe_snapshot();
+        assert!(!snapshot.is_empty());
+        for summary in snapshot.iter() {
+            assert!(summary.depth > 0);
+            assert_eq!(summary.depth, summary.path.len());
+            assert!(summary.ub >= summary.value);
+        }
+        // the snapshot is read-only: taking it twice yields the same result
+        assert_eq!(snapshot, solver.fringe_snapshot());
+    }
+
+    #[test]
+    fn the_value_histogram_reveals_effectively_fixed_variables() {
+        // the first item is too heavy to ever fit in the sack: the search
+        // can only ever explore the assignment (Variable(0), 0)
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![60,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SeqSolver::new(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        ).with_value_histogram();
+
+        assert!(solver.variable_value_histogram().is_empty());
+
+        let maximized = solver.maximize();
+        assert_eq!(Some(220), maximized.best_value);
+
+        let histogram = solver.variable_value_histogram();
+        // variable 0 is effectively fixed to 0 over the explored space
+        assert!(!histogram.contains_key(&(Variable(0), 1)));
+        assert!(histogram[&(Variable(0), 0)] > 0);
+        // the other variables were explored with both of their values
+        assert!(histogram[&(Variable(1), 0)] > 0);
+        assert!(histogram[&(Variable(1), 1)] > 0);
+        assert!(histogram[&(Variable(2), 0)] > 0);
+        assert!(histogram[&(Variable(2), 1)] > 0);
+    }
+
+    #[test]
+    fn without_the_flag_the_value_histogram_stays_empty() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SeqSolver::new(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+
+        let maximized = solver.maximize();
+        assert_eq!(Some(220), maximized.best_value);
+        assert!(solver.variable_value_histogram().is_empty());
+    }
+
+    #[test]
+    fn set_primal_overwrites_best_value_and_sol_if_it_improves() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SeqSolver::new(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+
+        let d1  = Decision{variable: Variable(0), value: 10};
+        let sol = Solution::new(vec![d1]);
+
+        solver.set_primal(10, sol.clone());
+        assert!(solver.best_sol.is_some());
+        assert_eq!(10, solver.best_lb);
+
+        // in this case, it wont update because there is no improvement
+        solver.set_primal(5, sol.clone());
+        assert!(solver.best_sol.is_some());
+        assert_eq!(10, solver.best_lb);
+
+        // but here, it will update as it improves the best known sol
+        solver.set_primal(10000, sol);
+        assert!(solver.best_sol.is_some());
+        assert_eq!(10000, solver.best_lb);
+
+        // it wont do much as the primal is better than the actual feasible solution
+        let maximized = solver.maximize();
+        assert!(maximized.is_exact);
+        assert_eq!(maximized.best_value, Some(10000));
+        assert!(solver.best_solution().is_some());
+    }
+
+    #[test]
+    fn a_fringe_size_limit_aborts_the_search_gracefully() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 210, 12, 5, 100, 120, 110],
+            weight  : vec![10,  45, 20, 4,  20,  30,  50]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        // the fringe may never hold more than one open subproblem: the
+        // cut-sets enqueued during the search quickly exceed that
+        let cutoff = FringeSizeLimit::new(1);
+        let width = FixedWidth(2); // a tiny width forces actual branch-and-bound
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SeqSolver::custom(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+
+        let maximized = solver.maximize();
+        assert!(!maximized.is_exact);
+    }
+
+    #[test]
+    fn a_gap_limit_aborts_the_search_once_the_gap_is_small_enough() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 210, 12, 5, 100, 120, 110],
+            weight  : vec![10,  45, 20, 4,  20,  30,  50]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        // any finite gap is good enough: the search must stop as soon as
+        // both bounds are known rather than close the gap completely
+        let cutoff = GapLimit::new(1.0);
+        let width = FixedWidth(2); // a tiny width forces actual branch-and-bound
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SeqSolver::custom(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+
+        let maximized = solver.maximize();
+        assert!(!maximized.is_exact);
+        assert!(solver.best_solution().is_some());
+        assert!(solver.gap() < 1.0);
+    }
+
+    #[test]
+    fn the_certificate_lists_the_open_frontier_when_the_search_is_aborted() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 210, 12, 5, 100, 120, 110],
+            weight  : vec![10,  45, 20, 4,  20,  30,  50]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NodeBudget::new(30);
+        let width = FixedWidth(2); // a tiny width forces actual branch-and-bound
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SeqSolver::custom(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        ).with_upper_bound_certificate();
+
+        let maximized = solver.maximize();
+        assert!(!maximized.is_exact);
+
+        // the largest bound among the open subproblems is exactly the final
+        // upper bound reported by the solver
+        let certificate = solver.upper_bound_certificate();
+        assert!(!certificate.is_empty());
+        let largest = certificate.iter().map(|(_, ub)| *ub).max().unwrap();
+        assert_eq!(largest, solver.best_upper_bound());
+    }
+
+    #[test]
+    fn the_certificate_is_empty_when_the_search_completes() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = FixedWidth(2); // a tiny width forces actual branch-and-bound
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SeqSolver::custom(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        ).with_upper_bound_certificate();
+
+        let maximized = solver.maximize();
+        assert!(maximized.is_exact);
+        // an exhausted frontier leaves nothing to justify: the bound is
+        // warranted by the incumbent alone
+        assert!(solver.upper_bound_certificate().is_empty());
+    }
+
+    #[test]
+    fn a_hybrid_mdd_still_drives_the_search_to_the_optimum() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = FixedWidth(2); // a tiny width forces actual branch-and-bound
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SequentialSolver::<KnapsackState, HybridMdd<KnapsackState>>::custom(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        ).with_mdd(HybridMdd::new(2));
+
+        // the shallow layers of the relaxed compilations are restricted, so
+        // their cutset nodes only come with the rough upper bounds: the
+        // search does more work but must reach the very same optimum
+        let maximized = solver.maximize();
+        assert!(maximized.is_exact);
+        assert_eq!(maximized.best_value, Some(220));
+    }
+
+    /// A width heuristic which additionally caps the number of decisions
+    /// enumerated from every node of the restricted dds
+    struct CappedDegree;
+    impl WidthHeuristic<KnapsackState> for CappedDegree {
+        fn max_width(&self, _: &SubProblem<KnapsackState>) -> usize {
+            2
+        }
+        fn max_degree(&self, _: &SubProblem<KnapsackState>) -> usize {
+            1
+        }
+    }
+
+    #[test]
+    fn capping_the_out_degree_still_drives_the_search_to_the_optimum() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = CappedDegree;
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SeqSolver::custom(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+
+        // the restricted dds only ever follow one decision out of each node,
+        // but the cap does not apply to the relaxed compilations: the bounds
+        // remain valid and branch-and-bound still proves the optimum
+        let maximized = solver.maximize();
+        assert!(maximized.is_exact);
+        assert_eq!(maximized.best_value, Some(220));
+    }
+
+    #[test]
+    fn the_stats_reflect_the_work_done_by_a_solve() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = FixedWidth(2); // a tiny width forces actual branch-and-bound
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SeqSolver::custom(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+
+        // before solving, all the counters are zero
+        let stats = solver.stats();
+        assert_eq!(0, stats.nb_restricted_dds);
+        assert_eq!(0, stats.nb_fringe_pops);
+
+        let maximized = solver.maximize();
+        assert_eq!(Some(220), maximized.best_value);
+
+        let stats = solver.stats();
+        // with a width of two, the root DD cannot be exact: at least one
+        // restricted and one relaxed DD have been compiled
+        assert!(stats.nb_restricted_dds >= 1);
+        assert!(stats.nb_relaxed_dds >= 1);
+        assert!(stats.nb_nodes_expanded > 0);
+        assert_eq!(solver.explored(), stats.nb_fringe_pops);
+        assert!(stats.peak_fringe_size >= 1);
+    }
+
+    #[test]
+    fn caching_the_proven_thresholds_reduces_the_number_of_expanded_nodes() {
+        let problem = Knapsack {
+            capacity: 30,
+            profit  : vec![2, 3, 6, 10, 4, 8, 5, 7, 9, 1],
+            weight  : vec![1, 2, 3,  5, 4, 6, 3, 5, 7, 2]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = FixedWidth(2); // a tiny width forces actual branch-and-bound
+        let dominance = EmptyDominanceChecker::default();
+
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut uncached = SequentialSolver::<KnapsackState, DefaultMDDFC<KnapsackState>, EmptyCache<KnapsackState>>::custom(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+        let without = uncached.maximize();
+
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut cached = SeqCachingSolver::custom(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+        let with = cached.maximize();
+
+        // the cache only ever prunes subproblems which provably cannot
+        // improve on what has been explored already: the optimum is the same
+        assert!(without.is_exact && with.is_exact);
+        assert_eq!(without.best_value, with.best_value);
+        // but the thresholds recorded from the solved subtrees save work
+        assert!(cached.stats().nb_nodes_expanded < uncached.stats().nb_nodes_expanded);
+    }
+
+    #[test]
+    fn maximize_k_enumerates_the_top_k_distinct_solutions() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = FixedWidth(2); // a tiny width forces actual branch-and-bound
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SeqSolver::custom(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+
+        let k_best = solver.maximize_k(3);
+
+        // the three most profitable feasible subsets are {1,2}, {0,2}, {0,1}
+        let values = k_best.iter().map(|entry| entry.0).collect::<Vec<_>>();
+        assert_eq!(vec![220, 180, 160], values);
+        for (i, (_, a)) in k_best.iter().enumerate() {
+            for (_, b) in k_best.iter().skip(i + 1) {
+                assert_ne!(a, b);
+            }
+        }
+        // the single-best machinery reports the optimum as usual
+        assert_eq!(Some(220), solver.best_value());
+    }
+
+    #[test]
+    fn maximize_k_returns_every_solution_when_fewer_than_k_exist() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = FixedWidth(2);
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SeqSolver::custom(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+
+        // of the 8 subsets, only {0,1,2} exceeds the capacity
+        let k_best = solver.maximize_k(10);
+        let values = k_best.iter().map(|entry| entry.0).collect::<Vec<_>>();
+        assert_eq!(vec![220, 180, 160, 120, 100, 60, 0], values);
+    }
+
+    #[test]
+    fn an_initial_lower_bound_seeds_the_search_without_faking_a_solution() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SeqSolver::new(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        ).with_initial_lower_bound(100);
+
+        // the bound is installed but no solution is made up to back it
+        assert_eq!(100, solver.best_lower_bound());
+        assert_eq!(None, solver.best_value());
+
+        let maximized = solver.maximize();
+        assert!(maximized.is_exact);
+        assert_eq!(maximized.best_value, Some(220));
+    }
+
+    #[test]
+    fn an_initial_solution_is_replayed_and_installed_as_the_incumbent() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SeqSolver::new(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        ).with_initial_solution(vec![
+            // only taking the first item is feasible and worth 60
+            Decision{variable: Variable(0), value: TAKE_IT},
+            Decision{variable: Variable(1), value: LEAVE_IT_OUT},
+            Decision{variable: Variable(2), value: LEAVE_IT_OUT},
+        ]);
+
+        // the value of the warm start solution was computed by replaying it
+        assert_eq!(Some(60), solver.best_value());
+        assert_eq!(60, solver.best_lower_bound());
+
+        // the search improves on the warm start
+        let maximized = solver.maximize();
+        assert!(maximized.is_exact);
+        assert_eq!(maximized.best_value, Some(220));
+    }
+
+    #[test]
+    fn an_optimal_initial_solution_is_returned_when_nothing_better_is_found() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let optimal = vec![
+            Decision{variable: Variable(0), value: LEAVE_IT_OUT},
+            Decision{variable: Variable(1), value: TAKE_IT},
+            Decision{variable: Variable(2), value: TAKE_IT},
+        ];
+        let mut solver = SeqSolver::new(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        ).with_initial_solution(optimal.clone());
+
+        let maximized = solver.maximize();
+        assert!(maximized.is_exact);
+        assert_eq!(maximized.best_value, Some(220));
+
+        let mut sln = solver.best_solution().unwrap();
+        sln.sort_unstable_by_key(|d| d.variable.id());
+        assert_eq!(optimal, sln);
+    }
+
+    #[test]
+    #[should_panic]
+    fn an_infeasible_initial_solution_is_rejected() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let _ = SeqSolver::new(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        ).with_initial_solution(vec![
+            // taking every item weighs 60, which exceeds the capacity: the
+            // solution must not be installed as the incumbent
+            Decision{variable: Variable(0), value: TAKE_IT},
+            Decision{variable: Variable(1), value: TAKE_IT},
+            Decision{variable: Variable(2), value: TAKE_IT},
+        ]);
+    }
+
+    #[test]
+    fn fixing_a_variable_restricts_the_search_to_the_consistent_subspace() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SeqSolver::new(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        ).with_fixed(vec![
+            // leaving the second item out forbids the unconstrained optimum
+            // (items 1 and 2, worth 220): the best consistent solution packs
+            // items 0 and 2 instead
+            Decision{variable: Variable(1), value: LEAVE_IT_OUT},
+        ]);
+
+        let maximized = solver.maximize();
+        assert!(maximized.is_exact);
+        assert_eq!(maximized.best_value, Some(180));
+
+        let solution = solver.best_solution().unwrap();
+        assert!(solution.contains(&Decision{variable: Variable(1), value: LEAVE_IT_OUT}));
+    }
+
+    #[test]
+    fn fixing_a_variable_to_its_optimal_value_preserves_the_optimum() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SeqSolver::new(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        ).with_fixed(vec![
+            Decision{variable: Variable(2), value: TAKE_IT},
+        ]);
+
+        let maximized = solver.maximize();
+        assert!(maximized.is_exact);
+        assert_eq!(maximized.best_value, Some(220));
+    }
+
+    #[test]
+    fn fixing_an_out_of_domain_value_makes_the_subspace_infeasible() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SeqSolver::new(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        ).with_fixed(vec![
+            // no reachable state admits value 2 for the first item
+            Decision{variable: Variable(0), value: 2},
+        ]);
+
+        let maximized = solver.maximize();
+        assert!(maximized.is_exact);
+        assert_eq!(maximized.best_value, None);
+    }
+
+    #[test]
+    fn maximize_from_seeds_the_fringe_with_the_given_roots() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = NbUnassignedWidth(problem.nb_variables());
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SeqSolver::new(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+
+        // the two roots collectively cover the whole state space: taking or
+        // leaving the first item
+        let take_it = SubProblem {
+            state: Arc::new(KnapsackState{depth: 1, capacity: 40}),
+            value: 60,
+            path : vec![Decision{variable: Variable(0), value: TAKE_IT}],
+            ub   : isize::MAX,
+            depth: 1,
+        };
+        let leave_it = SubProblem {
+            state: Arc::new(KnapsackState{depth: 1, capacity: 50}),
+            value: 0,
+            path : vec![Decision{variable: Variable(0), value: LEAVE_IT_OUT}],
+            ub   : isize::MAX,
+            depth: 1,
+        };
+
+        let Completion{is_exact, best_value} = solver.maximize_from(vec![take_it, leave_it]);
+        assert!(is_exact);
+        assert_eq!(Some(220), best_value);
+    }
+
+    #[test]
+    fn root_dd_is_captured_when_requested() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 210, 12, 5, 100, 120, 110],
+            weight  : vec![10,  45, 20, 4,  20,  30,  50]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = FixedWidth(2); // a tiny width forces the compilation of a relaxed dd
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SeqSolver::new(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        ).with_capture_root_dd();
+
+        let _ = solver.maximize();
+        assert!(solver.root_dd().is_some());
+    }
+    #[test]
+    fn root_dd_is_not_captured_unless_requested() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 210, 12, 5, 100, 120, 110],
+            weight  : vec![10,  45, 20, 4,  20,  30,  50]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let width = FixedWidth(2);
+        let dominance = EmptyDominanceChecker::default();
+        let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
+        let mut solver = SeqSolver::new(
+            &problem,
+            &relax,
+            &ranking,
+            &width,
+            &dominance,
+            &cutoff,
+            &mut fringe,
+        );
+
+        let _ = solver.maximize();
+        assert!(solver.root_dd().is_none());
+    }
+
     #[test]
-    fn by_default_best_lb_is_min_infinity() {
+    fn the_root_upper_bound_is_retained_and_never_tightened() {
         let problem = Knapsack {
             capacity: 50,
-            profit  : vec![60, 100, 120],
-            weight  : vec![10,  20,  30]
+            profit  : vec![60, 210, 12, 5, 100, 120, 110],
+            weight  : vec![10,  45, 20, 4,  20,  30,  50]
         };
         let relax = KPRelax {pb: &problem};
         let ranking = KPRanking;
         let cutoff = NoCutoff;
-        let width = NbUnassignedWidth(problem.nb_variables());
+        let width = FixedWidth(2); // a tiny width forces the compilation of a relaxed dd
         let dominance = EmptyDominanceChecker::default();
         let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
-        let solver = SeqSolver::new(
+        let mut solver = SeqSolver::new(
             &problem,
             &relax,
             &ranking,
@@ -566,22 +3718,30 @@ mod test_solver {
             &mut fringe,
         );
 
-        assert_eq!(isize::min_value(), solver.best_lower_bound());
+        assert_eq!(None, solver.root_upper_bound());
+        let _ = solver.maximize();
+        // the root relaxation is looser than the proven optimum: the bound of
+        // its first relaxed dd is kept untouched while best_upper_bound gets
+        // tightened all the way down by the branch-and-bound
+        let root_ub = solver.root_upper_bound().unwrap();
+        assert!(root_ub >= solver.best_upper_bound());
+        assert_eq!(220, solver.best_upper_bound());
     }
+
     #[test]
-    fn by_default_best_ub_is_plus_infinity() {
+    fn the_proof_log_traces_every_closed_subproblem_when_requested() {
         let problem = Knapsack {
             capacity: 50,
-            profit  : vec![60, 100, 120],
-            weight  : vec![10,  20,  30]
+            profit  : vec![60, 210, 12, 5, 100, 120, 110],
+            weight  : vec![10,  45, 20, 4,  20,  30,  50]
         };
         let relax = KPRelax {pb: &problem};
         let ranking = KPRanking;
         let cutoff = NoCutoff;
-        let width = NbUnassignedWidth(problem.nb_variables());
+        let width = FixedWidth(2); // a tiny width forces actual branch-and-bound
         let dominance = EmptyDominanceChecker::default();
         let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
-        let solver = SeqSolver::new(
+        let mut solver = SeqSolver::new(
             &problem,
             &relax,
             &ranking,
@@ -589,21 +3749,27 @@ mod test_solver {
             &dominance,
             &cutoff,
             &mut fringe,
-        );
+        ).with_proof_log();
 
-        assert_eq!(isize::max_value(), solver.best_upper_bound());
+        let Completion{is_exact, ..} = solver.maximize();
+        assert!(is_exact);
+        // every single explored subproblem which did not spawn new ones must
+        // leave a trace in the log
+        assert!(!solver.proof_log().is_empty());
+        assert!(solver.proof_log().iter().any(|entry|
+            matches!(entry.reason, PruningReason::ExactlySolved(_) | PruningReason::BoundPruned)));
     }
     #[test]
-    fn when_the_problem_is_solved_best_lb_is_best_value() {
+    fn the_proof_log_remains_empty_unless_requested() {
         let problem = Knapsack {
             capacity: 50,
-            profit  : vec![60, 100, 120],
-            weight  : vec![10,  20,  30]
+            profit  : vec![60, 210, 12, 5, 100, 120, 110],
+            weight  : vec![10,  45, 20, 4,  20,  30,  50]
         };
         let relax = KPRelax {pb: &problem};
         let ranking = KPRanking;
         let cutoff = NoCutoff;
-        let width = NbUnassignedWidth(problem.nb_variables());
+        let width = FixedWidth(2);
         let dominance = EmptyDominanceChecker::default();
         let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
         let mut solver = SeqSolver::new(
@@ -617,19 +3783,20 @@ mod test_solver {
         );
 
         let _ = solver.maximize();
-        assert_eq!(220, solver.best_lower_bound());
+        assert!(solver.proof_log().is_empty());
     }
+
     #[test]
-    fn when_the_problem_is_solved_best_ub_is_best_value() {
+    fn the_search_trace_records_every_popped_subproblem_when_requested() {
         let problem = Knapsack {
             capacity: 50,
-            profit  : vec![60, 100, 120],
-            weight  : vec![10,  20,  30]
+            profit  : vec![60, 210, 12, 5, 100, 120, 110],
+            weight  : vec![10,  45, 20, 4,  20,  30,  50]
         };
         let relax = KPRelax {pb: &problem};
         let ranking = KPRanking;
         let cutoff = NoCutoff;
-        let width = NbUnassignedWidth(problem.nb_variables());
+        let width = FixedWidth(2); // a tiny width forces actual branch-and-bound
         let dominance = EmptyDominanceChecker::default();
         let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
         let mut solver = SeqSolver::new(
@@ -640,14 +3807,21 @@ mod test_solver {
             &dominance,
             &cutoff,
             &mut fringe,
-        );
+        ).with_search_trace();
 
-        let _ = solver.maximize();
-        assert_eq!(220, solver.best_upper_bound());
+        let Completion{is_exact, ..} = solver.maximize();
+        assert!(is_exact);
+
+        let trace = solver.search_trace();
+        // the root subproblem is popped first; the subsequent pops descend
+        // from the exact cutsets of the relaxed DDs
+        assert!(trace.entries.len() > 1);
+        assert_eq!(0, trace.entries[0].depth);
+        assert!(trace.entries[0].path.is_empty());
+        assert!(trace.entries[1..].iter().all(|e| e.depth > 0 && !e.path.is_empty()));
     }
-    
     #[test]
-    fn no_solution_before_solving() {
+    fn the_search_trace_remains_empty_unless_requested() {
         let problem = Knapsack {
             capacity: 50,
             profit  : vec![60, 100, 120],
@@ -656,10 +3830,10 @@ mod test_solver {
         let relax = KPRelax {pb: &problem};
         let ranking = KPRanking;
         let cutoff = NoCutoff;
-        let width = NbUnassignedWidth(problem.nb_variables());
+        let width = FixedWidth(2);
         let dominance = EmptyDominanceChecker::default();
         let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
-        let solver = SeqSolver::new(
+        let mut solver = SeqSolver::new(
             &problem,
             &relax,
             &ranking,
@@ -668,10 +3842,75 @@ mod test_solver {
             &cutoff,
             &mut fringe,
         );
-        assert!(solver.best_sol.is_none());
+
+        let _ = solver.maximize();
+        assert!(solver.search_trace().entries.is_empty());
     }
     #[test]
-    fn empty_fringe_before_solving() {
+    fn the_search_trace_renders_the_exploration_tree_in_dot() {
+        let trace = SearchTrace {
+            entries: vec![
+                TraceEntry {
+                    state: Arc::new('r'),
+                    value: 0, ub: 100, lb: isize::MIN, depth: 0,
+                    path: vec![],
+                },
+                TraceEntry {
+                    state: Arc::new('a'),
+                    value: 10, ub: 90, lb: 50, depth: 2,
+                    path: vec![
+                        Decision{variable: Variable(0), value: 1},
+                        Decision{variable: Variable(1), value: 0},
+                    ],
+                },
+                TraceEntry {
+                    state: Arc::new('b'),
+                    value: 20, ub: 80, lb: 60, depth: 3,
+                    path: vec![
+                        Decision{variable: Variable(0), value: 1},
+                        Decision{variable: Variable(1), value: 0},
+                        Decision{variable: Variable(2), value: 1},
+                    ],
+                },
+            ]
+        };
+
+        let dot = trace.as_graphviz();
+        // the second entry branches off the root, the third one off the second
+        assert!(dot.contains("0 -> 1"));
+        assert!(dot.contains("1 -> 2"));
+        assert!(!dot.contains("0 -> 2"));
+        // the edges are labeled with the decisions separating the subproblems
+        assert!(dot.contains("x0 = 1\\nx1 = 0"));
+        assert!(dot.contains("x2 = 1"));
+    }
+
+    #[test]
+    fn the_reporter_is_notified_of_the_salient_events_of_the_search() {
+        #[derive(Default)]
+        struct CountingReporter {
+            started: Rc<Cell<usize>>,
+            bounds: Rc<Cell<usize>>,
+            layers: Rc<Cell<usize>>,
+            finished: Rc<Cell<usize>>,
+        }
+        impl SearchReporter for CountingReporter {
+            fn on_start(&mut self) {
+                self.started.set(self.started.get() + 1);
+            }
+            fn on_new_bound(&mut self, lb: isize, ub: isize) {
+                assert!(lb <= ub);
+                self.bounds.set(self.bounds.get() + 1);
+            }
+            fn on_layer(&mut self, _layer: usize) {
+                self.layers.set(self.layers.get() + 1);
+            }
+            fn on_finish(&mut self, completion: &Completion) {
+                assert_eq!(Some(220), completion.best_value);
+                self.finished.set(self.finished.get() + 1);
+            }
+        }
+
         let problem = Knapsack {
             capacity: 50,
             profit  : vec![60, 100, 120],
@@ -680,10 +3919,16 @@ mod test_solver {
         let relax = KPRelax {pb: &problem};
         let ranking = KPRanking;
         let cutoff = NoCutoff;
-        let width = NbUnassignedWidth(problem.nb_variables());
+        let width = FixedWidth(2); // a tiny width forces actual branch-and-bound
         let dominance = EmptyDominanceChecker::default();
         let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
-        let solver = SeqSolver::new(
+
+        let reporter = CountingReporter::default();
+        let started = reporter.started.clone();
+        let bounds = reporter.bounds.clone();
+        let finished = reporter.finished.clone();
+
+        let mut solver = SeqSolver::custom(
             &problem,
             &relax,
             &ranking,
@@ -691,12 +3936,18 @@ mod test_solver {
             &dominance,
             &cutoff,
             &mut fringe,
-        );
+        ).with_reporter(reporter);
 
-        assert!(solver.fringe.is_empty());
+        let _ = solver.maximize();
+        drop(solver);
+
+        assert_eq!(1, started.get());
+        assert!(bounds.get() >= 1);
+        assert_eq!(1, finished.get());
     }
+
     #[test]
-    fn default_best_lb_is_neg_infinity() {
+    fn the_bound_log_records_the_convergence_of_the_bounds() {
         let problem = Knapsack {
             capacity: 50,
             profit  : vec![60, 100, 120],
@@ -705,10 +3956,11 @@ mod test_solver {
         let relax = KPRelax {pb: &problem};
         let ranking = KPRanking;
         let cutoff = NoCutoff;
-        let width = NbUnassignedWidth(problem.nb_variables());
+        let width = FixedWidth(2); // a tiny width forces actual branch-and-bound
         let dominance = EmptyDominanceChecker::default();
         let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
-        let solver = SeqSolver::new(
+
+        let mut solver = SeqSolver::custom(
             &problem,
             &relax,
             &ranking,
@@ -716,12 +3968,25 @@ mod test_solver {
             &dominance,
             &cutoff,
             &mut fringe,
-        );
+        ).with_bound_log();
 
-        assert_eq!(isize::min_value(), solver.best_lb);
+        assert!(solver.bound_history().is_empty());
+        let _ = solver.maximize();
+
+        let history = solver.bound_history();
+        assert!(!history.is_empty());
+        // the elapsed times and the lower bounds are non-decreasing
+        for window in history.windows(2) {
+            assert!(window[0].0 <= window[1].0);
+            assert!(window[0].1 <= window[1].1);
+        }
+        // the search converged: the last sample has both bounds at the optimum
+        let (_, lb, ub) = history.last().copied().unwrap();
+        assert_eq!((220, 220), (lb, ub));
     }
+
     #[test]
-    fn default_best_ub_is_pos_infinity() {
+    fn without_the_bound_log_the_history_stays_empty() {
         let problem = Knapsack {
             capacity: 50,
             profit  : vec![60, 100, 120],
@@ -730,10 +3995,11 @@ mod test_solver {
         let relax = KPRelax {pb: &problem};
         let ranking = KPRanking;
         let cutoff = NoCutoff;
-        let width = NbUnassignedWidth(problem.nb_variables());
+        let width = FixedWidth(2);
         let dominance = EmptyDominanceChecker::default();
         let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
-        let solver = SeqSolver::new(
+
+        let mut solver = SeqSolver::custom(
             &problem,
             &relax,
             &ranking,
@@ -743,11 +4009,12 @@ mod test_solver {
             &mut fringe,
         );
 
-        assert_eq!(isize::max_value(), solver.best_ub);
+        let _ = solver.maximize();
+        assert!(solver.bound_history().is_empty());
     }
 
     #[test]
-    fn maximizes_yields_the_optimum_1a() {
+    fn the_incumbent_callback_is_notified_of_every_improvement() {
         let problem = Knapsack {
             capacity: 50,
             profit  : vec![60, 100, 120],
@@ -756,10 +4023,12 @@ mod test_solver {
         let relax = KPRelax {pb: &problem};
         let ranking = KPRanking;
         let cutoff = NoCutoff;
-        let width = NbUnassignedWidth(problem.nb_variables());
+        let width = FixedWidth(2); // a tiny width forces actual branch-and-bound
         let dominance = EmptyDominanceChecker::default();
         let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
-        let mut solver = SeqSolver::custom(
+
+        let incumbents = std::sync::Mutex::new(vec![]);
+        let mut solver = SeqSolver::new(
             &problem,
             &relax,
             &ranking,
@@ -767,37 +4036,37 @@ mod test_solver {
             &dominance,
             &cutoff,
             &mut fringe,
-        );
+        ).with_on_incumbent(|value, solution: &[Decision]| {
+            incumbents.lock().unwrap().push((value, solution.to_vec()));
+        });
 
         let maximized = solver.maximize();
+        assert_eq!(Some(220), maximized.best_value);
+        drop(solver);
 
-        assert!(maximized.is_exact);
-        assert_eq!(maximized.best_value, Some(220));
-        assert!(solver.best_solution().is_some());
-
-        let mut sln = solver.best_solution().unwrap();
-        sln.sort_unstable_by_key(|d| d.variable.id());
-        assert_eq!(sln, vec![
-            Decision{variable: Variable(0), value: 0},
-            Decision{variable: Variable(1), value: 1},
-            Decision{variable: Variable(2), value: 1},
-        ]);
+        let incumbents = incumbents.into_inner().unwrap();
+        // the values reported form a strictly increasing sequence ending at
+        // the optimum, and each one comes with its complete solution
+        assert!(!incumbents.is_empty());
+        assert!(incumbents.windows(2).all(|w| w[0].0 < w[1].0));
+        assert_eq!(220, incumbents.last().unwrap().0);
+        assert_eq!(3, incumbents.last().unwrap().1.len());
     }
 
     #[test]
-    fn maximizes_yields_the_optimum_1b() {
+    fn the_time_breakdown_is_populated_by_a_solve() {
         let problem = Knapsack {
             capacity: 50,
-            profit  : vec![60, 100, 120],
-            weight  : vec![10,  20,  30]
+            profit  : vec![60, 210, 12, 5, 100, 120, 110],
+            weight  : vec![10,  45, 20, 4,  20,  30,  50]
         };
         let relax = KPRelax {pb: &problem};
         let ranking = KPRanking;
         let cutoff = NoCutoff;
-        let width = NbUnassignedWidth(problem.nb_variables());
+        let width = FixedWidth(2); // a tiny width forces actual branch-and-bound
         let dominance = EmptyDominanceChecker::default();
         let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
-        let mut solver = SeqCachingSolver::custom(
+        let mut solver = SeqSolver::new(
             &problem,
             &relax,
             &ranking,
@@ -807,27 +4076,23 @@ mod test_solver {
             &mut fringe,
         );
 
-        let maximized = solver.maximize();
-
-        assert!(maximized.is_exact);
-        assert_eq!(maximized.best_value, Some(220));
-        assert!(solver.best_solution().is_some());
-
-        let mut sln = solver.best_solution().unwrap();
-        sln.sort_unstable_by_key(|d| d.variable.id());
-        assert_eq!(sln, vec![
-            Decision{variable: Variable(0), value: 0},
-            Decision{variable: Variable(1), value: 1},
-            Decision{variable: Variable(2), value: 1},
-        ]);
+        let _ = solver.maximize();
+        let breakdown = solver.time_breakdown();
+        if cfg!(feature = "profiling") {
+            assert!(breakdown.restricted > std::time::Duration::ZERO);
+            assert!(breakdown.relaxed > std::time::Duration::ZERO);
+        } else {
+            assert_eq!(std::time::Duration::ZERO, breakdown.restricted);
+            assert_eq!(std::time::Duration::ZERO, breakdown.relaxed);
+        }
     }
 
     #[test]
-    fn maximizes_yields_the_optimum_2a() {
+    fn the_avg_branching_factor_reflects_the_compiled_dds() {
         let problem = Knapsack {
             capacity: 50,
-            profit  : vec![60, 210, 12, 5, 100, 120, 110],
-            weight  : vec![10,  45, 20, 4,  20,  30,  50]
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
         };
         let relax = KPRelax {pb: &problem};
         let ranking = KPRanking;
@@ -845,27 +4110,15 @@ mod test_solver {
             &mut fringe,
         );
 
-        let maximized = solver.maximize();
-
-        assert!(maximized.is_exact);
-        assert_eq!(maximized.best_value, Some(220));
-        assert!(solver.best_solution().is_some());
-
-        let mut sln = solver.best_solution().unwrap();
-        sln.sort_unstable_by_key(|d| d.variable.id());
-        assert_eq!(sln, vec![
-            Decision { variable: Variable(0), value: 0 },
-            Decision { variable: Variable(1), value: 0 },
-            Decision { variable: Variable(2), value: 0 },
-            Decision { variable: Variable(3), value: 0 },
-            Decision { variable: Variable(4), value: 1 },
-            Decision { variable: Variable(5), value: 1 },
-            Decision { variable: Variable(6), value: 0 }
-        ]);
+        let _ = solver.maximize();
+        // the domains are binary: the average out-degree must lie in [1, 2]
+        let factor = solver.avg_branching_factor();
+        assert!(factor >= 1.0);
+        assert!(factor <= 2.0);
     }
 
     #[test]
-    fn maximizes_yields_the_optimum_2b() {
+    fn the_feature_callback_is_fed_one_vector_per_explored_subproblem() {
         let problem = Knapsack {
             capacity: 50,
             profit  : vec![60, 210, 12, 5, 100, 120, 110],
@@ -874,10 +4127,12 @@ mod test_solver {
         let relax = KPRelax {pb: &problem};
         let ranking = KPRanking;
         let cutoff = NoCutoff;
-        let width = NbUnassignedWidth(problem.nb_variables());
+        let width = FixedWidth(2); // a tiny width forces actual branch-and-bound
         let dominance = EmptyDominanceChecker::default();
         let mut fringe = SimpleFringe::new(MaxUB::new(&ranking));
-        let mut solver = SeqCachingSolver::new(
+
+        let features = std::sync::Mutex::new(vec![]);
+        let mut solver = SeqSolver::new(
             &problem,
             &relax,
             &ranking,
@@ -885,29 +4140,25 @@ mod test_solver {
             &dominance,
             &cutoff,
             &mut fringe,
-        );
-
-        let maximized = solver.maximize();
-
-        assert!(maximized.is_exact);
-        assert_eq!(maximized.best_value, Some(220));
-        assert!(solver.best_solution().is_some());
+        ).with_feature_callback(|node: &SubProblem<KnapsackState>, feats: &[f64]| {
+            features.lock().unwrap().push((node.depth, feats.to_vec()));
+        });
 
-        let mut sln = solver.best_solution().unwrap();
-        sln.sort_unstable_by_key(|d| d.variable.id());
-        assert_eq!(sln, vec![
-            Decision { variable: Variable(0), value: 0 },
-            Decision { variable: Variable(1), value: 0 },
-            Decision { variable: Variable(2), value: 0 },
-            Decision { variable: Variable(3), value: 0 },
-            Decision { variable: Variable(4), value: 1 },
-            Decision { variable: Variable(5), value: 1 },
-            Decision { variable: Variable(6), value: 0 }
-        ]);
+        let _ = solver.maximize();
+        drop(solver);
+        let features = features.into_inner().unwrap();
+        assert!(!features.is_empty());
+        for (depth, feats) in features.iter() {
+            // depth, value, ub, gap, allotted width, nb domain values
+            assert_eq!(6, feats.len());
+            assert_eq!(*depth as f64, feats[0]);
+            assert_eq!(2.0, feats[4]);
+            assert!(feats[5] <= 2.0);
+        }
     }
 
     #[test]
-    fn set_primal_overwrites_best_value_and_sol_if_it_improves() {
+    fn min_improvement_throttles_the_reported_incumbents() {
         let problem = Knapsack {
             capacity: 50,
             profit  : vec![60, 100, 120],
@@ -927,30 +4178,25 @@ mod test_solver {
             &dominance,
             &cutoff,
             &mut fringe,
-        );
+        ).with_min_improvement(50);
 
-        let d1  = Decision{variable: Variable(0), value: 10};
-        let sol = vec![d1];
+        let d1  = Decision{variable: Variable(0), value: 1};
+        let sol = Solution::new(vec![d1]);
 
+        // the first incumbent is always reported
         solver.set_primal(10, sol.clone());
-        assert!(solver.best_sol.is_some());
-        assert_eq!(10, solver.best_lb);
-
-        // in this case, it wont update because there is no improvement
-        solver.set_primal(5, sol.clone());
-        assert!(solver.best_sol.is_some());
         assert_eq!(10, solver.best_lb);
+        assert_eq!(10, solver.reported_lb);
 
-        // but here, it will update as it improves the best known sol
-        solver.set_primal(10000, sol);
-        assert!(solver.best_sol.is_some());
-        assert_eq!(10000, solver.best_lb);
+        // a tiny improvement updates the true best but is not reported
+        solver.set_primal(20, sol.clone());
+        assert_eq!(20, solver.best_lb);
+        assert_eq!(10, solver.reported_lb);
 
-        // it wont do much as the primal is better than the actual feasible solution
-        let maximized = solver.maximize();
-        assert!(maximized.is_exact);
-        assert_eq!(maximized.best_value, Some(10000));
-        assert!(solver.best_solution().is_some());
+        // a sufficiently large improvement is reported
+        solver.set_primal(60, sol);
+        assert_eq!(60, solver.best_lb);
+        assert_eq!(60, solver.reported_lb);
     }
 
     #[test]
@@ -1008,6 +4254,7 @@ mod test_solver {
     }
 
     #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     struct KnapsackState {
         depth: usize,
         capacity: usize
@@ -1081,6 +4328,91 @@ mod test_solver {
             tot as isize
         }
     }
+    /// Same relaxation as `KPRelax`, except it also provides a rough lower
+    /// bound: the value of the greedy completion which packs the remaining
+    /// items in index order whenever they fit
+    struct KPGreedyRelax<'a>{pb: &'a Knapsack}
+    impl Relaxation for KPGreedyRelax<'_> {
+        type State = KnapsackState;
+
+        fn merge(&self, states: &mut dyn Iterator<Item = &Self::State>) -> Self::State {
+            states.max_by_key(|node| node.capacity).copied().unwrap()
+        }
+        fn relax(&self, _source: &Self::State, _dest: &Self::State, _merged: &Self::State, _decision: Decision, cost: isize) -> isize {
+            cost
+        }
+        fn fast_upper_bound(&self, state: &Self::State) -> isize {
+            let mut tot = 0;
+            for var in state.depth..self.pb.nb_variables() {
+                if self.pb.weight[var] <= state.capacity {
+                    tot += self.pb.profit[var];
+                }
+            }
+            tot as isize
+        }
+        fn fast_lower_bound(&self, state: &Self::State) -> isize {
+            let mut capacity = state.capacity;
+            let mut tot = 0;
+            for var in state.depth..self.pb.nb_variables() {
+                if self.pb.weight[var] <= capacity {
+                    capacity -= self.pb.weight[var];
+                    tot += self.pb.profit[var];
+                }
+            }
+            tot as isize
+        }
+    }
+    /// A relaxation whose rough upper bound also suggests the greedy
+    /// completion it stumbled upon (take each remaining item that fits)
+    struct KPSuggestingRelax<'a> {
+        pb: &'a Knapsack,
+    }
+    impl Relaxation for KPSuggestingRelax<'_> {
+        type State = KnapsackState;
+
+        fn merge(&self, states: &mut dyn Iterator<Item = &Self::State>) -> Self::State {
+            states.max_by_key(|node| node.capacity).copied().unwrap()
+        }
+        fn relax(&self, _source: &Self::State, _dest: &Self::State, _merged: &Self::State, _decision: Decision, cost: isize) -> isize {
+            cost
+        }
+        fn fast_upper_bound_with_solution(&self, state: &Self::State) -> (isize, Option<Vec<Decision>>) {
+            let mut capacity = state.capacity;
+            let mut tot = 0;
+            let mut completion = vec![];
+            for var in state.depth..self.pb.nb_variables() {
+                if self.pb.weight[var] <= capacity {
+                    capacity -= self.pb.weight[var];
+                    tot += self.pb.profit[var] as isize;
+                    completion.push(Decision { variable: Variable(var), value: 1 });
+                } else {
+                    completion.push(Decision { variable: Variable(var), value: 0 });
+                }
+            }
+            (self.fast_upper_bound(state).max(tot), Some(completion))
+        }
+    }
+    /// A relaxation which suggests an infeasible completion (take every
+    /// remaining item, whether it fits or not)
+    struct KPLyingRelax<'a> {
+        pb: &'a Knapsack,
+    }
+    impl Relaxation for KPLyingRelax<'_> {
+        type State = KnapsackState;
+
+        fn merge(&self, states: &mut dyn Iterator<Item = &Self::State>) -> Self::State {
+            states.max_by_key(|node| node.capacity).copied().unwrap()
+        }
+        fn relax(&self, _source: &Self::State, _dest: &Self::State, _merged: &Self::State, _decision: Decision, cost: isize) -> isize {
+            cost
+        }
+        fn fast_upper_bound_with_solution(&self, state: &Self::State) -> (isize, Option<Vec<Decision>>) {
+            let completion = (state.depth..self.pb.nb_variables())
+                .map(|var| Decision { variable: Variable(var), value: 1 })
+                .collect();
+            (self.fast_upper_bound(state), Some(completion))
+        }
+    }
     struct KPRanking;
     impl StateRanking for KPRanking {
         type State = KnapsackState;
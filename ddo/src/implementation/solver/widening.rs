@@ -0,0 +1,430 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! This module provides the implementation of a relaxation-free anytime
+//! solver which proceeds by iterative widening: it repeatedly compiles
+//! restricted DDs of geometrically increasing width from the root of the
+//! problem and keeps the best solution it stumbles upon.
+
+use std::cell::Cell;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use crate::*;
+
+/// A tiny adapter which counts the nodes expanded during the compilations
+/// on behalf of the solver, on top of forwarding them to the user cutoff
+struct CountingCutoff<'b> {
+    /// The criterion which effectively decides when to stop
+    inner: &'b dyn Cutoff,
+    /// The number of nodes developed so far
+    expanded: &'b Cell<usize>,
+}
+impl Cutoff for CountingCutoff<'_> {
+    fn must_stop(&self) -> bool {
+        self.inner.must_stop()
+    }
+    fn add_expanded(&self, nb_nodes: usize) {
+        self.expanded.set(self.expanded.get() + nb_nodes);
+        self.inner.add_expanded(nb_nodes);
+    }
+}
+
+/// This solver never tries to prove optimality: it repeatedly compiles
+/// restricted DDs rooted in the original problem with a geometrically
+/// increasing maximum width (1, 2, 4, 8, ... by default) and keeps the best
+/// solution found along the way. No relaxed DD is ever compiled and no
+/// fringe is maintained: each iteration is one single top-down restricted
+/// compilation, which makes this strategy well suited to the very large
+/// instances where good feasible solutions are all that is wanted.
+///
+/// Every path of a restricted DD is feasible, so each iteration either
+/// improves the incumbent or leaves it untouched; the incumbent is also fed
+/// back into the next compilation as its lower bound, which lets the rough
+/// upper bounds prune the branches that cannot improve on it. The search
+/// stops when the cutoff criterion trips (this is the normal way out: by
+/// default the solver widens forever). The reported completion is inexact
+/// -- with one exception: when a restricted DD happens to compile without
+/// dropping any node, it *is* the exact DD of the problem and the solver
+/// stops right away with an optimality proof in hand.
+pub struct IterativeWideningSolver<'a, State, D = DefaultMDDLEL<State>>
+where
+    D: DecisionDiagram<State = State> + Default,
+{
+    /// A reference to the problem being solved
+    problem: &'a (dyn Problem<State = State>),
+    /// The relaxation is only consulted for its rough upper bounds (which
+    /// prune the branches that cannot improve the incumbent); its merge and
+    /// relax operators are never invoked
+    relaxation: &'a (dyn Relaxation<State = State>),
+    /// The ranking heuristic used to discriminate the most promising from
+    /// the least promising states when a layer must be trimmed down
+    ranking: &'a (dyn StateRanking<State = State>),
+    /// The dominance checker used to prune the dominated states
+    dominance: &'a (dyn DominanceChecker<State = State>),
+    /// The cutoff criterion which decides when to stop widening
+    cutoff: &'a (dyn Cutoff),
+
+    /// The maximum width imposed on the very first restricted DD
+    initial_width: usize,
+    /// The geometric factor by which the maximum width is multiplied after
+    /// each compilation
+    width_factor: usize,
+
+    /// The DD which is recycled from one compilation to the next
+    mdd: D,
+    /// This is the value of the best known solution so far
+    best_lb: isize,
+    /// If set, this keeps the info about the best solution so far
+    best_sol: Option<Solution>,
+    /// A flag set to true when one of the restricted DDs turned out to be
+    /// the exact DD of the problem (in which case the incumbent is optimal)
+    proved_optimal: bool,
+    /// This is a counter that tracks the total number of nodes that have
+    /// been explored, all compilations taken together
+    explored: usize,
+}
+
+impl<'a, State, D> IterativeWideningSolver<'a, State, D>
+where
+    State: Eq + Hash + Clone,
+    D: DecisionDiagram<State = State> + Default,
+{
+    pub fn new(
+        problem: &'a (dyn Problem<State = State>),
+        relaxation: &'a (dyn Relaxation<State = State>),
+        ranking: &'a (dyn StateRanking<State = State>),
+        dominance: &'a (dyn DominanceChecker<State = State>),
+        cutoff: &'a (dyn Cutoff),
+    ) -> Self {
+        Self {
+            problem,
+            relaxation,
+            ranking,
+            dominance,
+            cutoff,
+            //
+            initial_width: 1,
+            width_factor: 2,
+            //
+            mdd: D::default(),
+            best_lb: isize::MIN,
+            best_sol: None,
+            proved_optimal: false,
+            explored: 0,
+        }
+    }
+
+    /// Sets the maximum width imposed on the very first restricted DD
+    pub fn with_initial_width(mut self, initial_width: usize) -> Self {
+        self.initial_width = initial_width;
+        self
+    }
+    /// Sets the geometric factor by which the maximum width is multiplied
+    /// after each compilation
+    pub fn with_width_factor(mut self, width_factor: usize) -> Self {
+        self.width_factor = width_factor;
+        self
+    }
+}
+
+impl<State, D> Solver for IterativeWideningSolver<'_, State, D>
+where
+    State: Eq + PartialEq + Hash + Clone,
+    D: DecisionDiagram<State = State> + Default,
+{
+    /// Compiles restricted DDs of geometrically increasing width until the
+    /// cutoff criterion trips (or one of them turns out to be exact).
+    fn maximize(&mut self) -> Completion {
+        let cache = EmptyCache::new();
+        let expanded = Cell::new(0);
+        let counting_cutoff = CountingCutoff { inner: self.cutoff, expanded: &expanded };
+        let residual = SubProblem {
+            state: Arc::new(self.problem.initial_state()),
+            value: self.problem.initial_value(),
+            path: vec![],
+            ub: isize::MAX,
+            depth: 0,
+        };
+
+        let mut width = self.initial_width.max(1);
+        while !self.cutoff.must_stop() {
+            let input = CompilationInput {
+                comp_type: CompilationType::Restricted,
+                max_in_degree: None,
+                max_out_degree: usize::MAX,
+                max_width: width,
+                problem: self.problem,
+                relaxation: self.relaxation,
+                ranking: self.ranking,
+                cutoff: &counting_cutoff,
+                cache: &cache,
+                dominance: self.dominance,
+                residual: &residual,
+                best_lb: self.best_lb,
+            };
+
+            let completion = self.mdd.compile(&input);
+            self.explored += expanded.replace(0);
+            let Ok(completion) = completion else {
+                break; // the cutoff kicked in mid-compilation
+            };
+
+            // every path of a restricted DD is feasible: harvest the best one
+            if completion.best_value.is_some_and(|value| value > self.best_lb) {
+                self.best_lb = completion.best_value.unwrap();
+                self.best_sol = self.mdd.best_solution();
+                self.cutoff.set_bounds(self.best_lb, isize::MAX);
+            }
+
+            if completion.is_exact {
+                // the restriction dropped nothing: this was the exact DD of
+                // the problem and the incumbent is a proven optimum
+                self.proved_optimal = true;
+                break;
+            }
+            width = width.saturating_mul(self.width_factor.max(2));
+        }
+
+        Completion {
+            is_exact: self.proved_optimal,
+            best_value: self.best_sol.as_ref().map(|_| self.best_lb),
+        }
+    }
+
+    /// Returns the best solution that has been identified for this problem.
+    fn best_solution(&self) -> Option<Solution> {
+        self.best_sol.clone()
+    }
+    /// Returns the value of the best solution that has been identified for
+    /// this problem.
+    fn best_value(&self) -> Option<isize> {
+        self.best_sol.as_ref().map(|_sol| self.best_lb)
+    }
+    /// Returns the value of the best lower bound that has been identified
+    /// for this problem.
+    fn best_lower_bound(&self) -> isize {
+        self.best_lb
+    }
+    /// Returns the value of the best upper bound that can be guaranteed for
+    /// this problem. Since no relaxed DD is ever compiled, this solver only
+    /// ever proves an upper bound when one of its restricted DDs was exact.
+    fn best_upper_bound(&self) -> isize {
+        if self.proved_optimal {
+            self.best_lb
+        } else {
+            isize::MAX
+        }
+    }
+    /// Sets a primal (best known value and solution) of the problem.
+    fn set_primal(&mut self, value: isize, solution: Solution) {
+        if value > self.best_lb {
+            self.best_sol = Some(solution);
+            self.best_lb = value;
+        }
+    }
+    /// Returns the number of nodes that have been explored so far, all
+    /// compilations taken together.
+    fn explored(&self) -> usize {
+        self.explored
+    }
+}
+
+// ############################################################################
+// #### TESTS #################################################################
+// ############################################################################
+
+/// These tests validate the behavior of the iterative widening strategy on
+/// the same tiny knapsack instance as the other solvers.
+#[cfg(test)]
+mod test_solver {
+    use crate::*;
+
+    #[test]
+    fn the_incumbent_improves_as_the_widths_increase() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        let cutoff = NoCutoff;
+        let dominance = EmptyDominanceChecker::default();
+        let mut solver = IterativeWideningSolver::<KnapsackState>::new(
+            &problem,
+            &relax,
+            &ranking,
+            &dominance,
+            &cutoff,
+        );
+
+        // with no cutoff, the solver widens until a restricted DD is exact:
+        // at that point the incumbent is the actual optimum
+        let maximized = solver.maximize();
+        assert!(maximized.is_exact);
+        assert_eq!(maximized.best_value, Some(220));
+        assert_eq!(solver.best_upper_bound(), 220);
+
+        let mut sln = solver.best_solution().unwrap();
+        sln.sort_unstable_by_key(|d| d.variable.id());
+        assert_eq!(sln, vec![
+            Decision{variable: Variable(0), value: 0},
+            Decision{variable: Variable(1), value: 1},
+            Decision{variable: Variable(2), value: 1},
+        ]);
+    }
+
+    #[test]
+    fn the_search_is_anytime_when_cut_off() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        // a budget so tight that only the narrowest dives can complete
+        let cutoff = NodeBudget::new(3);
+        let dominance = EmptyDominanceChecker::default();
+        let mut solver = IterativeWideningSolver::<KnapsackState>::new(
+            &problem,
+            &relax,
+            &ranking,
+            &dominance,
+            &cutoff,
+        );
+
+        let maximized = solver.maximize();
+        assert!(!maximized.is_exact);
+        // whatever was found is feasible, and no upper bound was proved
+        assert!(solver.best_value().is_some());
+        assert_eq!(solver.best_upper_bound(), isize::MAX);
+    }
+
+    #[test]
+    fn the_primal_survives_when_nothing_better_is_found() {
+        let problem = Knapsack {
+            capacity: 50,
+            profit  : vec![60, 100, 120],
+            weight  : vec![10,  20,  30]
+        };
+        let relax = KPRelax {pb: &problem};
+        let ranking = KPRanking;
+        // no compilation at all: the cutoff trips right away
+        let cutoff = NodeBudget::new(0);
+        let dominance = EmptyDominanceChecker::default();
+        let mut solver = IterativeWideningSolver::<KnapsackState>::new(
+            &problem,
+            &relax,
+            &ranking,
+            &dominance,
+            &cutoff,
+        );
+        solver.set_primal(10000, Solution::new(vec![]));
+
+        let maximized = solver.maximize();
+        assert!(!maximized.is_exact);
+        assert_eq!(maximized.best_value, Some(10000));
+    }
+
+    #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+    struct KnapsackState {
+        depth: usize,
+        capacity: usize
+    }
+
+    struct Knapsack {
+        capacity: usize,
+        profit: Vec<usize>,
+        weight: Vec<usize>,
+    }
+
+    const TAKE_IT: isize = 1;
+    const LEAVE_IT_OUT: isize = 0;
+
+    impl Problem for Knapsack {
+        type State = KnapsackState;
+
+        fn nb_variables(&self) -> usize {
+            self.profit.len()
+        }
+        fn initial_state(&self) -> Self::State {
+            KnapsackState{ depth: 0, capacity: self.capacity }
+        }
+        fn initial_value(&self) -> isize {
+            0
+        }
+        fn transition(&self, state: &Self::State, dec: Decision) -> Self::State {
+            let mut ret = *state;
+            ret.depth += 1;
+            if dec.value == TAKE_IT {
+                ret.capacity -= self.weight[dec.variable.id()]
+            }
+            ret
+        }
+        fn transition_cost(&self, _state: &Self::State, _next: &Self::State, dec: Decision) -> isize {
+            self.profit[dec.variable.id()] as isize * dec.value
+        }
+        fn next_variable(&self, depth: usize, _: &mut dyn Iterator<Item = &Self::State>) -> Option<Variable> {
+            let n = self.nb_variables();
+            if depth < n {
+                Some(Variable(depth))
+            } else {
+                None
+            }
+        }
+        fn for_each_in_domain(&self, variable: Variable, state: &Self::State, f: &mut dyn DecisionCallback) {
+            if state.capacity >= self.weight[variable.id()] {
+                f.apply(Decision { variable, value: TAKE_IT });
+            }
+            f.apply(Decision { variable, value: LEAVE_IT_OUT });
+        }
+    }
+
+    struct KPRelax<'a> {
+        pb: &'a Knapsack,
+    }
+    impl Relaxation for KPRelax<'_> {
+        type State = KnapsackState;
+
+        fn merge(&self, states: &mut dyn Iterator<Item = &Self::State>) -> Self::State {
+            states.max_by_key(|node| node.capacity).copied().unwrap()
+        }
+        fn relax(&self, _source: &Self::State, _dest: &Self::State, _merged: &Self::State, _decision: Decision, cost: isize) -> isize {
+            cost
+        }
+        fn fast_upper_bound(&self, state: &Self::State) -> isize {
+            let mut tot = 0;
+            for var in state.depth..self.pb.nb_variables() {
+                tot += self.pb.profit[var];
+            }
+            tot as isize
+        }
+    }
+
+    struct KPRanking;
+    impl StateRanking for KPRanking {
+        type State = KnapsackState;
+
+        fn compare(&self, a: &Self::State, b: &Self::State) -> std::cmp::Ordering {
+            a.capacity.cmp(&b.capacity)
+        }
+    }
+}
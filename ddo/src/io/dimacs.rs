@@ -0,0 +1,321 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! This module provides parsers for the standard graph file formats in which
+//! the classic combinatorial benchmarks are distributed; the very formats
+//! which the `mcp` and `misp` examples consume. All the parsers return the
+//! same plain [`Graph`] type, from which one easily derives the structure a
+//! model actually needs (adjacency matrix, neighbor bitsets, ...).
+//!
+//! # Formats
+//! Three formats are supported:
+//!  - the *edge list* format (`read_edge_list`): a header line `V E` giving
+//!    the number of vertices and edges, followed by one `src dst [weight]`
+//!    line per edge. This is the format of the max-cut (`mcp`) benchmarks.
+//!  - the *DIMACS clq/col* format (`read_weighted_clq`): a problem line
+//!    `p edge V E`, optional vertex weight lines `n vertex weight`, and one
+//!    `e src dst` line per edge. This is the format of the independent set
+//!    (`misp`) and coloring benchmarks.
+//!  - the *gra* format (`read_gra`): the number of vertices, then the degree
+//!    of each vertex, then the concatenated adjacency lists -- all as one
+//!    whitespace-separated token stream, regardless of the line breaks. This
+//!    is the format of the minimum linear arrangement (minla) benchmarks.
+//!
+//! In the first two formats, the vertices are numbered from 1 in the files
+//! (the `gra` files number them from 0) but always from 0 in the returned
+//! `Graph`; blank lines and comments (lines starting with a `c`) are
+//! ignored; and malformed lines yield an error of the `InvalidData` kind.
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, Error, ErrorKind, Result};
+use std::path::Path;
+
+/// A plain graph, as read from one of the standard benchmark file formats.
+/// The vertices are numbered from 0 (the parsers shift the 1-based numbering
+/// of the files); the weights default to 1 whenever the format does not
+/// specify them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Graph {
+    /// The number of vertices of the graph
+    pub nb_vertices: usize,
+    /// The weight of each vertex (all 1 unless the file specifies them)
+    pub vertex_weights: Vec<isize>,
+    /// The edges, as `(src, dst, weight)` triples (weight 1 when the format
+    /// carries no edge weight). The edges are listed exactly as they appear
+    /// in the file: an undirected edge occurs once only.
+    pub edges: Vec<(usize, usize, isize)>,
+}
+impl Graph {
+    /// Creates an edgeless graph with the given number of vertices, all of
+    /// weight 1
+    pub fn new(nb_vertices: usize) -> Self {
+        Graph {
+            nb_vertices,
+            vertex_weights: vec![1; nb_vertices],
+            edges: vec![],
+        }
+    }
+}
+
+/// Reads a graph in the *edge list* format from the file located at `path`:
+/// a `V E` header followed by one `src dst [weight]` line per edge (see the
+/// module documentation). This is the format of the max-cut benchmarks used
+/// by the `mcp` example.
+pub fn read_edge_list<P: AsRef<Path>>(path: P) -> Result<Graph> {
+    parse_edge_list(BufReader::new(File::open(path)?))
+}
+
+/// Parses a graph in the *edge list* format from any buffered reader; this
+/// is the same parser as `read_edge_list`, usable on in-memory data too.
+pub fn parse_edge_list<B: BufRead>(input: B) -> Result<Graph> {
+    let mut graph: Option<Graph> = None;
+    for line in input.lines() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('c') {
+            continue;
+        }
+        let mut tokens = line.split_whitespace();
+        match graph.as_mut() {
+            None => {
+                let nb_vertices = parse_field(tokens.next(), line, "number of vertices")?;
+                graph = Some(Graph::new(nb_vertices));
+            },
+            Some(graph) => {
+                let src: usize = parse_field(tokens.next(), line, "edge source")?;
+                let dst: usize = parse_field(tokens.next(), line, "edge destination")?;
+                let weight = match tokens.next() {
+                    Some(tok) => parse_field(Some(tok), line, "edge weight")?,
+                    None => 1,
+                };
+                let (src, dst) = shifted(src, dst, graph.nb_vertices, line)?;
+                graph.edges.push((src, dst, weight));
+            },
+        }
+    }
+    graph.ok_or_else(|| invalid("the input holds no graph at all", "<eof>"))
+}
+
+/// Reads a graph in the *DIMACS clq/col* format from the file located at
+/// `path`: a `p edge V E` problem line, optional `n vertex weight` lines and
+/// one `e src dst` line per edge (see the module documentation). This is the
+/// format of the independent set benchmarks used by the `misp` example.
+pub fn read_weighted_clq<P: AsRef<Path>>(path: P) -> Result<Graph> {
+    parse_weighted_clq(BufReader::new(File::open(path)?))
+}
+
+/// Parses a graph in the *DIMACS clq/col* format from any buffered reader;
+/// this is the same parser as `read_weighted_clq`, usable on in-memory data
+/// too.
+pub fn parse_weighted_clq<B: BufRead>(input: B) -> Result<Graph> {
+    let mut graph: Option<Graph> = None;
+    for line in input.lines() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('c') {
+            continue;
+        }
+        let mut tokens = line.split_whitespace();
+        match (tokens.next(), graph.as_mut()) {
+            (Some("p"), None) => {
+                match tokens.next() {
+                    Some("edge") | Some("col") => (),
+                    _ => return Err(invalid("expected 'p edge V E'", line)),
+                }
+                let nb_vertices = parse_field(tokens.next(), line, "number of vertices")?;
+                graph = Some(Graph::new(nb_vertices));
+            },
+            (Some("n"), Some(graph)) => {
+                let vertex: usize = parse_field(tokens.next(), line, "vertex")?;
+                let weight = parse_field(tokens.next(), line, "vertex weight")?;
+                if vertex == 0 || vertex > graph.nb_vertices {
+                    return Err(invalid("vertex out of range", line));
+                }
+                graph.vertex_weights[vertex - 1] = weight;
+            },
+            (Some("e"), Some(graph)) => {
+                let src: usize = parse_field(tokens.next(), line, "edge source")?;
+                let dst: usize = parse_field(tokens.next(), line, "edge destination")?;
+                let (src, dst) = shifted(src, dst, graph.nb_vertices, line)?;
+                graph.edges.push((src, dst, 1));
+            },
+            (_, None) => return Err(invalid("expected the 'p edge V E' problem line first", line)),
+            (_, _)    => return Err(invalid("unrecognized line", line)),
+        }
+    }
+    graph.ok_or_else(|| invalid("the input holds no graph at all", "<eof>"))
+}
+
+/// Reads a graph in the *gra* format from the file located at `path`: the
+/// number of vertices, then the degree of each vertex, then the concatenated
+/// 0-based adjacency lists (see the module documentation). This is the format
+/// of the minimum linear arrangement benchmarks.
+pub fn read_gra<P: AsRef<Path>>(path: P) -> Result<Graph> {
+    parse_gra(BufReader::new(File::open(path)?))
+}
+
+/// Parses a graph in the *gra* format from any buffered reader; this is the
+/// same parser as `read_gra`, usable on in-memory data too.
+pub fn parse_gra<B: BufRead>(input: B) -> Result<Graph> {
+    // in this format the line breaks carry no meaning: the file is one long
+    // stream of whitespace-separated numbers (only the comment lines must be
+    // weeded out before the tokens are flattened)
+    let mut tokens = vec![];
+    for line in input.lines() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('c') {
+            continue;
+        }
+        tokens.extend(line.split_whitespace().map(String::from));
+    }
+    let mut tokens = tokens.iter().map(String::as_str);
+
+    let nb_vertices: usize = parse_field(tokens.next(), "<header>", "number of vertices")?;
+    let mut graph = Graph::new(nb_vertices);
+    let mut degrees = vec![];
+    for vertex in 0..nb_vertices {
+        let degree: usize = parse_field(tokens.next(), "<degrees>", &format!("degree of vertex {vertex}"))?;
+        degrees.push(degree);
+    }
+    for (src, degree) in degrees.into_iter().enumerate() {
+        for _ in 0..degree {
+            let dst: usize = parse_field(tokens.next(), "<adjacency>", &format!("neighbor of vertex {src}"))?;
+            if dst >= nb_vertices {
+                return Err(invalid("edge endpoint out of range", &format!("{src} -> {dst}")));
+            }
+            // each undirected edge occurs in the adjacency lists of both its
+            // endpoints: it is retained once only
+            if src <= dst {
+                graph.edges.push((src, dst, 1));
+            }
+        }
+    }
+    if tokens.next().is_some() {
+        return Err(invalid("trailing tokens after the last adjacency list", "<eof>"));
+    }
+    Ok(graph)
+}
+
+/// Parses one field of a line, reporting which field was being parsed when
+/// the line turns out to be malformed
+fn parse_field<T: std::str::FromStr>(token: Option<&str>, line: &str, what: &str) -> Result<T> {
+    token.and_then(|tok| tok.parse::<T>().ok())
+        .ok_or_else(|| invalid(&format!("invalid {what}"), line))
+}
+
+/// Shifts the 1-based endpoints of one edge to the 0-based numbering of the
+/// returned graph, validating that both lie within range
+fn shifted(src: usize, dst: usize, nb_vertices: usize, line: &str) -> Result<(usize, usize)> {
+    if src == 0 || dst == 0 || src > nb_vertices || dst > nb_vertices {
+        Err(invalid("edge endpoint out of range", line))
+    } else {
+        Ok((src - 1, dst - 1))
+    }
+}
+
+fn invalid(what: &str, line: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, format!("{what} in line '{line}'"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_edge_list, parse_gra, parse_weighted_clq, Graph};
+
+    #[test]
+    fn an_edge_list_is_parsed_with_its_weights() {
+        let input = "c this is a comment\n3 2\n1 2 10\n2 3 -4\n";
+        let graph = parse_edge_list(input.as_bytes()).unwrap();
+
+        assert_eq!(Graph {
+            nb_vertices: 3,
+            vertex_weights: vec![1, 1, 1],
+            edges: vec![(0, 1, 10), (1, 2, -4)],
+        }, graph);
+    }
+
+    #[test]
+    fn edge_list_weights_default_to_one() {
+        let graph = parse_edge_list("2 1\n1 2\n".as_bytes()).unwrap();
+        assert_eq!(vec![(0, 1, 1)], graph.edges);
+    }
+
+    #[test]
+    fn a_weighted_clq_is_parsed_with_its_vertex_weights() {
+        let input = "c comment\np edge 3 2\nn 1 5\nn 3 7\ne 1 2\ne 2 3\n";
+        let graph = parse_weighted_clq(input.as_bytes()).unwrap();
+
+        assert_eq!(Graph {
+            nb_vertices: 3,
+            vertex_weights: vec![5, 1, 7],
+            edges: vec![(0, 1, 1), (1, 2, 1)],
+        }, graph);
+    }
+
+    #[test]
+    fn clq_vertex_weights_default_to_one() {
+        let graph = parse_weighted_clq("p edge 2 1\ne 1 2\n".as_bytes()).unwrap();
+        assert_eq!(vec![1, 1], graph.vertex_weights);
+    }
+
+    #[test]
+    fn a_gra_is_parsed_with_each_edge_once() {
+        // a triangle: each edge occurs in the adjacency lists of both its
+        // endpoints but must be retained once only
+        let input = "c comment\n3\n2 2 2\n1 2\n0 2\n0 1\n";
+        let graph = parse_gra(input.as_bytes()).unwrap();
+
+        assert_eq!(Graph {
+            nb_vertices: 3,
+            vertex_weights: vec![1, 1, 1],
+            edges: vec![(0, 1, 1), (0, 2, 1), (1, 2, 1)],
+        }, graph);
+    }
+
+    #[test]
+    fn gra_line_breaks_carry_no_meaning() {
+        let wrapped  = parse_gra("3 2 2\n2 1\n2 0 2 0 1\n".as_bytes()).unwrap();
+        let straight = parse_gra("3\n2 2 2\n1 2\n0 2\n0 1\n".as_bytes()).unwrap();
+        assert_eq!(straight, wrapped);
+    }
+
+    #[test]
+    fn malformed_lines_yield_an_error() {
+        assert!(parse_edge_list("3 2\n1 oops 10\n".as_bytes()).is_err());
+        assert!(parse_weighted_clq("p edge 3 2\nz 1 2\n".as_bytes()).is_err());
+        assert!(parse_weighted_clq("e 1 2\n".as_bytes()).is_err());
+        // a truncated adjacency list and trailing garbage, respectively
+        assert!(parse_gra("2\n1 1\n1\n".as_bytes()).is_err());
+        assert!(parse_gra("2\n1 1\n1 0 7\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn out_of_range_endpoints_yield_an_error() {
+        assert!(parse_edge_list("3 2\n0 2\n".as_bytes()).is_err());
+        assert!(parse_weighted_clq("p edge 3 1\ne 1 4\n".as_bytes()).is_err());
+        assert!(parse_gra("2\n1 1\n1 2\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn an_empty_input_yields_an_error() {
+        assert!(parse_edge_list("c nothing here\n".as_bytes()).is_err());
+        assert!(parse_weighted_clq("".as_bytes()).is_err());
+        assert!(parse_gra("".as_bytes()).is_err());
+    }
+}
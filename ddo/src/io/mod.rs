@@ -0,0 +1,135 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! This module provides the means to read and write solutions using a simple
+//! standard text format. The format is intentionally minimalistic so that it
+//! can easily be post-processed by scripts and shared between runs (e.g. to
+//! warm start a solver with the incumbent of a previous run, or to compare an
+//! outcome against some reference solution).
+//!
+//! # Format
+//! The format comprises one line per decision, where each line is made of the
+//! variable identifier followed by the assigned value, separated by a single
+//! space. Empty lines and lines starting with a `#` (comments) are ignored
+//! when reading. For instance, the following denotes the solution assigning
+//! value 1 to variables 0 and 2 and value 0 to variable 1:
+//!
+//! ```plain
+//! 0 1
+//! 1 0
+//! 2 1
+//! ```
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Error, ErrorKind, Result, Write};
+use std::path::Path;
+
+use crate::{Decision, Variable};
+
+pub mod dimacs;
+
+/// Writes the given solution to the file located at `path`, using the standard
+/// one `variable value` pair per line format described in the module
+/// documentation. The file is created if it does not exist and truncated if
+/// it does.
+pub fn write_solution<P: AsRef<Path>>(path: P, solution: &[Decision]) -> Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+    for decision in solution {
+        writeln!(out, "{} {}", decision.variable.id(), decision.value)?;
+    }
+    out.flush()
+}
+
+/// Reads a solution from the file located at `path`, expecting the standard
+/// one `variable value` pair per line format described in the module
+/// documentation. Malformed lines yield an error of the `InvalidData` kind.
+pub fn read_solution<P: AsRef<Path>>(path: P) -> Result<Vec<Decision>> {
+    let file = BufReader::new(File::open(path)?);
+    let mut solution = vec![];
+    for line in file.lines() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut tokens = line.split_whitespace();
+        let variable = tokens.next()
+            .and_then(|tok| tok.parse::<usize>().ok())
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, format!("invalid variable in line '{line}'")))?;
+        let value = tokens.next()
+            .and_then(|tok| tok.parse::<isize>().ok())
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, format!("invalid value in line '{line}'")))?;
+        solution.push(Decision { variable: Variable(variable), value });
+    }
+    Ok(solution)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::{Decision, Variable};
+
+    use super::{read_solution, write_solution};
+
+    fn temp_file(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("ddo_io_{}_{name}", std::process::id()))
+    }
+
+    #[test]
+    fn solutions_round_trip() {
+        let path = temp_file("round_trip");
+        let solution = vec![
+            Decision { variable: Variable(0), value: 1 },
+            Decision { variable: Variable(1), value: 0 },
+            Decision { variable: Variable(2), value: -4 },
+        ];
+
+        write_solution(&path, &solution).unwrap();
+        let read = read_solution(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(solution, read);
+    }
+
+    #[test]
+    fn comments_and_blank_lines_are_ignored() {
+        let path = temp_file("comments");
+        fs::write(&path, "# a comment\n\n0 1\n\n1 0\n").unwrap();
+
+        let read = read_solution(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(read, vec![
+            Decision { variable: Variable(0), value: 1 },
+            Decision { variable: Variable(1), value: 0 },
+        ]);
+    }
+
+    #[test]
+    fn malformed_lines_yield_an_error() {
+        let path = temp_file("malformed");
+        fs::write(&path, "0 not_a_value\n").unwrap();
+
+        let read = read_solution(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert!(read.is_err());
+    }
+}
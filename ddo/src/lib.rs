@@ -497,6 +497,9 @@
 mod common;
 mod abstraction;
 mod implementation;
+#[cfg(feature = "io")]
+pub mod io;
+pub mod report;
 
 pub use common::*;
 pub use abstraction::*;
@@ -0,0 +1,128 @@
+// Copyright 2020 Xavier Gillard
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! This module provides a standardized, machine parseable reporting of the
+//! outcome of a resolution. Where the examples traditionally print a
+//! multi-line block, batch runs are better served with one tab separated
+//! line per instance which benchmark scripts can readily consume. The fields
+//! of that line are, in order: the instance name, the resolution status
+//! (`optimal` or `open`), the best lower bound, the best upper bound, the
+//! optimality gap, the duration of the resolution (in seconds) and the value
+//! of the best solution found (or `-` when none was found).
+
+use std::time::Duration;
+
+use crate::Solver;
+
+/// Returns the header line matching the fields produced by `tsv_line`. You
+/// will typically want to print it once at the top of your report file.
+pub fn tsv_header() -> String {
+    "instance\tstatus\tlb\tub\tgap\ttime\tobjective".to_string()
+}
+
+/// Formats the outcome of a resolution as a single tab separated line
+/// comprising the instance name, the resolution status, the best bounds, the
+/// optimality gap, the duration of the resolution and the objective value.
+/// The status is `optimal` when the bounds have met (the gap is closed) and
+/// `open` otherwise.
+pub fn tsv_line(name: &str, solver: &dyn Solver, duration: Duration) -> String {
+    let lb = solver.best_lower_bound();
+    let ub = solver.best_upper_bound();
+    let status = if lb >= ub { "optimal" } else { "open" };
+    let objective = solver.best_value()
+        .map(|value| value.to_string())
+        .unwrap_or_else(|| "-".to_string());
+
+    format!("{}\t{}\t{}\t{}\t{:.3}\t{:.3}\t{}",
+        name,
+        status,
+        lb,
+        ub,
+        solver.gap(),
+        duration.as_secs_f32(),
+        objective)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::*;
+
+    use super::{tsv_header, tsv_line};
+
+    /// A dummy solver reporting a fixed set of bounds
+    struct DummySolver {
+        lb: isize,
+        ub: isize,
+        sol: Option<Solution>,
+    }
+    impl Solver for DummySolver {
+        fn maximize(&mut self) -> Completion {
+            Completion { is_exact: true, best_value: self.best_value() }
+        }
+        fn best_value(&self) -> Option<isize> {
+            self.sol.as_ref().map(|_| self.lb)
+        }
+        fn best_solution(&self) -> Option<Solution> {
+            self.sol.clone()
+        }
+        fn best_lower_bound(&self) -> isize {
+            self.lb
+        }
+        fn best_upper_bound(&self) -> isize {
+            self.ub
+        }
+        fn set_primal(&mut self, value: isize, solution: Solution) {
+            self.lb = value;
+            self.sol = Some(solution);
+        }
+        fn explored(&self) -> usize {
+            0
+        }
+    }
+
+    #[test]
+    fn the_header_and_the_line_have_the_same_number_of_fields() {
+        let solver = DummySolver { lb: 10, ub: 20, sol: Some(Solution::default()) };
+        let line = tsv_line("foo", &solver, Duration::from_secs(1));
+        assert_eq!(tsv_header().split('\t').count(), line.split('\t').count());
+    }
+
+    #[test]
+    fn a_closed_gap_is_reported_as_optimal() {
+        let solver = DummySolver { lb: 42, ub: 42, sol: Some(Solution::default()) };
+        let line = tsv_line("foo", &solver, Duration::from_secs(2));
+        assert_eq!("foo\toptimal\t42\t42\t0.000\t2.000\t42", line);
+    }
+
+    #[test]
+    fn an_open_gap_is_reported_as_open() {
+        let solver = DummySolver { lb: 10, ub: 20, sol: Some(Solution::default()) };
+        let line = tsv_line("foo", &solver, Duration::from_secs(1));
+        assert!(line.starts_with("foo\topen\t10\t20\t"));
+    }
+
+    #[test]
+    fn the_lack_of_solution_is_reported_with_a_dash() {
+        let solver = DummySolver { lb: isize::MIN, ub: isize::MAX, sol: None };
+        let line = tsv_line("foo", &solver, Duration::from_secs(1));
+        assert!(line.ends_with("\t-"));
+    }
+}
@@ -1,6 +1,6 @@
-use std::{time::{Duration, Instant}, hash::Hash, collections::HashMap};
+use std::{time::{Duration, Instant}, hash::Hash, collections::HashMap, sync::Arc};
 
-use ::ddo::{Problem, Cutoff, TimeBudget, NoCutoff, Fringe, NoDupFringe, StateRanking, MaxUB, SimpleFringe, WidthHeuristic, FixedWidth, NbUnassignedWidth, Variable, Decision, Relaxation, Solver, Completion, SeqNoCachingSolverLel, SeqCachingSolverLel, SeqCachingSolverFc, SeqNoCachingSolverFc};
+use ::ddo::{Problem, Cutoff, TimeBudget, NoCutoff, Fringe, NoDupFringe, StateRanking, MaxUB, SimpleFringe, WidthHeuristic, FixedWidth, NbUnassignedWidth, Variable, Decision, Relaxation, Solver, Completion, SeqNoCachingSolverLel, SeqCachingSolverLel, SeqCachingSolverFc, SeqNoCachingSolverFc, Dominance, DominanceChecker, SimpleDominanceChecker, EmptyDominanceChecker};
 
 use pyo3::{prelude::*, types::{PyBool}};
 
@@ -37,10 +37,22 @@ pub struct Solution {
     /// The best known lower bound on the objective value
     pub lower_bound: isize,
     #[pyo3(get)]
-    /// What are the assignments leading to the best solution ? 
+    /// What are the assignments leading to the best solution ?
     /// `assignment[x] = y` means value `y` was assigned to variable `x`.
     /// -> If no solution was found, then the assignment value will be None
     pub assignment: Option<Vec<isize>>,
+    #[pyo3(get)]
+    /// The decisions of the best path as `(variable, value)` pairs, keeping
+    /// only the variables which were actually assigned along that path.
+    /// Contrary to `assignment`, this does not zero-fill the variables that
+    /// do not occur on the best path.
+    /// -> If no solution was found, then this value will be None
+    pub assignment_pairs: Option<Vec<(usize, isize)>>,
+    #[pyo3(get)]
+    /// Has the search completed and proved the optimality of its outcome ?
+    /// (this is the exact negation of `aborted`, kept to mirror the
+    /// `Completion` returned on the rust side)
+    pub is_exact: bool,
 }
 
 #[pyfunction]
@@ -54,21 +66,39 @@ fn maximize(
     dedup      : bool,
     width      : Option<usize>,
     timeout    : Option<u64>,
+    dominance  : Option<PyObject>,
+    threads    : Option<usize>,
 ) -> Solution {
     Python::with_gil(|gil| {
+        // Every `PyState` carries the GIL token (`Python<'a>`), hence the
+        // states may never be shared across threads: each of the callbacks
+        // of the model would have to re-acquire the GIL, which serializes
+        // the workers anyway. For that reason, asking for more than one
+        // thread keeps the sequential engine and merely emits a warning
+        // rather than risking an unsound cross-thread use of the token.
+        if threads.unwrap_or(1) > 1 {
+            let _ = PyErr::warn(
+                gil,
+                gil.get_type::<pyo3::exceptions::PyRuntimeWarning>(),
+                "the python binding is bound to the GIL: the requested thread count is ignored and a single thread is used",
+                1,
+            );
+        }
         let problem = PyProblem {gil, obj: pb};
         let relax = PyRelax {gil, obj: relax};
         let ranking = PyRanking {gil, obj: ranking};
         let max_width = max_width(problem.nb_variables(), width);
         let cutoff = cutoff(timeout);
+        let dominance = dominance_checker(gil, dominance, problem.nb_variables());
         let mut fringe = fringe(dedup, &ranking);
 
         let mut solver = solver(
-            &problem, 
-            &relax, 
-            &ranking, 
-            max_width.as_ref(), 
-            cutoff.as_ref(), 
+            &problem,
+            &relax,
+            &ranking,
+            max_width.as_ref(),
+            dominance.as_ref(),
+            cutoff.as_ref(),
             fringe.as_mut(),
             lel,
             use_cache
@@ -83,13 +113,19 @@ fn maximize(
             decisions.sort_unstable_by_key(|d| d.variable.id());
             decisions.iter().map(|d| d.value).collect()
         });
-        
+        let assignment_pairs = solver.best_solution().map(|mut decisions| {
+            decisions.sort_unstable_by_key(|d| d.variable.id());
+            decisions.iter().map(|d| (d.variable.id(), d.value)).collect()
+        });
+
         Solution {
             aborted:     !is_exact,
+            is_exact,
             objective:   best_value,
             upper_bound: solver.best_upper_bound(),
             lower_bound: solver.best_lower_bound(),
             assignment,
+            assignment_pairs,
             gap,
             duration
         }
@@ -98,24 +134,34 @@ fn maximize(
 
 #[allow(clippy::too_many_arguments)]
 fn solver<'a, 'b>(
-    problem    : &'a PyProblem<'b>, 
-    relaxation : &'a PyRelax<'b>, 
-    ranking    : &'a PyRanking<'b>, 
-    width_heu  : &'a dyn WidthHeuristic<PyState<'b>>, 
-    cutoff     : &'a dyn Cutoff, 
-    fringe     : &'a mut dyn Fringe<State = PyState<'b>>, 
+    problem    : &'a PyProblem<'b>,
+    relaxation : &'a PyRelax<'b>,
+    ranking    : &'a PyRanking<'b>,
+    width_heu  : &'a dyn WidthHeuristic<PyState<'b>>,
+    dominance  : &'a dyn DominanceChecker<State = PyState<'b>>,
+    cutoff     : &'a dyn Cutoff,
+    fringe     : &'a mut dyn Fringe<State = PyState<'b>>,
     lel        : bool,
     use_cache  : bool,
 ) -> Box<dyn Solver + 'a> {
     match (lel, use_cache) {
-        (true, true) => 
-            Box::new(SeqCachingSolverLel::custom(problem, relaxation, ranking, width_heu, cutoff, fringe)),
-        (true, false) => 
-            Box::new(SeqNoCachingSolverLel::custom(problem, relaxation, ranking, width_heu, cutoff, fringe)),
-        (false, true) => 
-            Box::new(SeqCachingSolverFc::custom(problem, relaxation, ranking, width_heu, cutoff, fringe)),
-        (false, false) => 
-            Box::new(SeqNoCachingSolverFc::custom(problem, relaxation, ranking, width_heu, cutoff, fringe)),
+        (true, true) =>
+            Box::new(SeqCachingSolverLel::custom(problem, relaxation, ranking, width_heu, dominance, cutoff, fringe)),
+        (true, false) =>
+            Box::new(SeqNoCachingSolverLel::custom(problem, relaxation, ranking, width_heu, dominance, cutoff, fringe)),
+        (false, true) =>
+            Box::new(SeqCachingSolverFc::custom(problem, relaxation, ranking, width_heu, dominance, cutoff, fringe)),
+        (false, false) =>
+            Box::new(SeqNoCachingSolverFc::custom(problem, relaxation, ranking, width_heu, dominance, cutoff, fringe)),
+    }
+}
+
+fn dominance_checker<'a>(gil: Python<'a>, dominance: Option<PyObject>, nb_variables: usize)
+    -> Box<dyn DominanceChecker<State = PyState<'a>> + 'a> {
+    if let Some(dominance) = dominance {
+        Box::new(SimpleDominanceChecker::new(PyDominance {gil, obj: dominance}, nb_variables))
+    } else {
+        Box::new(EmptyDominanceChecker::default())
     }
 }
 
@@ -295,6 +341,49 @@ impl <'a> Relaxation for PyRelax<'a> {
     }
 }
 
+pub struct PyDominance<'a> {
+    gil: Python<'a>,
+    obj: PyObject
+}
+unsafe impl Send for PyDominance<'_> {}
+impl <'a> Dominance for PyDominance<'a> {
+    type State = PyState<'a>;
+    type Key = PyState<'a>;
+
+    fn get_key(&self, state: Arc<Self::State>) -> Option<Self::Key> {
+        let res = self.obj.call_method(self.gil, "get_key", (&state.obj,), None)
+            .unwrap();
+        if res.is_none(self.gil) {
+            None
+        } else {
+            Some(PyState { gil: self.gil, obj: res })
+        }
+    }
+
+    fn nb_dimensions(&self, state: &Self::State) -> usize {
+        let res = self.obj.call_method(self.gil, "nb_dimensions", (&state.obj,), None)
+            .unwrap();
+        res.extract::<usize>(self.gil)
+            .unwrap()
+    }
+
+    fn get_coordinate(&self, state: &Self::State, i: usize) -> isize {
+        let res = self.obj.call_method(self.gil, "get_coordinate", (&state.obj, i), None)
+            .unwrap();
+        res.extract::<isize>(self.gil)
+            .unwrap()
+    }
+
+    fn use_value(&self) -> bool {
+        let res = self.obj.call_method(self.gil, "use_value", (), None);
+        if let Ok(res) = res {
+            res.extract(self.gil).unwrap()
+        } else {
+            false
+        }
+    }
+}
+
 pub struct PyRanking<'a> {
     gil: Python<'a>,
     obj: PyObject